@@ -113,7 +113,10 @@ pub mod trace {
                             Ok(parsed) => trace_id = parsed,
                         }
                     }
-                    HEADER_PARENT_KEY => parent_segment_id = SpanId::from_hex(value),
+                    HEADER_PARENT_KEY => {
+                        parent_segment_id =
+                            SpanId::from_hex(value).unwrap_or_else(|_| SpanId::invalid())
+                    }
                     HEADER_SAMPLED_KEY => {
                         sampling_decision = match value {
                             NOT_SAMPLED => TRACE_FLAG_NOT_SAMPLED,
@@ -126,9 +129,9 @@ pub mod trace {
                 }
             }
 
-            let trace_state: TraceState = TraceState::from_key_value(kv_vec)?;
+            let trace_state: TraceState = TraceState::from_key_value(kv_vec).map_err(|_| ())?;
 
-            if trace_id.to_u128() == 0 {
+            if !trace_id.is_valid() {
                 return Err(());
             }
 
@@ -228,9 +231,10 @@ pub mod trace {
                 return Err(());
             }
 
-            let trace_id: TraceId = TraceId::from_hex(format!("{}{}", parts[1], parts[2]).as_str());
+            let trace_id =
+                TraceId::from_hex(format!("{}{}", parts[1], parts[2]).as_str()).map_err(|_| ())?;
 
-            if trace_id.to_u128() == 0 {
+            if !trace_id.is_valid() {
                 Err(())
             } else {
                 Ok(trace_id)
@@ -290,14 +294,14 @@ pub mod trace {
                 ("Sampled=1;Self=foo", SpanContext::empty_context()),
                 ("Root=1-bogus-bad", SpanContext::empty_context()),
                 ("Root=1-too-many-parts", SpanContext::empty_context()),
-                ("Root=1-58406520-a006649127e371903a2de979;Parent=garbage", SpanContext::new(TraceId::from_hex("58406520a006649127e371903a2de979"), SpanId::invalid(), TRACE_FLAG_DEFERRED, true, TraceState::default())),
-                ("Root=1-58406520-a006649127e371903a2de979;Sampled=1", SpanContext::new(TraceId::from_hex("58406520a006649127e371903a2de979"), SpanId::invalid(), TRACE_FLAG_SAMPLED, true, TraceState::default())),
-                ("Root=1-58406520-a006649127e371903a2de979;Parent=4c721bf33e3caf8f;Sampled=0", SpanContext::new(TraceId::from_hex("58406520a006649127e371903a2de979"), SpanId::from_hex("4c721bf33e3caf8f"), TRACE_FLAG_NOT_SAMPLED, true, TraceState::default())),
-                ("Root=1-58406520-a006649127e371903a2de979;Parent=4c721bf33e3caf8f;Sampled=1", SpanContext::new(TraceId::from_hex("58406520a006649127e371903a2de979"), SpanId::from_hex("4c721bf33e3caf8f"), TRACE_FLAG_SAMPLED, true, TraceState::default())),
-                ("Root=1-58406520-a006649127e371903a2de979;Parent=4c721bf33e3caf8f", SpanContext::new(TraceId::from_hex("58406520a006649127e371903a2de979"), SpanId::from_hex("4c721bf33e3caf8f"), TRACE_FLAG_DEFERRED, true, TraceState::default())),
-                ("Root=1-58406520-a006649127e371903a2de979;Parent=4c721bf33e3caf8f;Sampled=?", SpanContext::new(TraceId::from_hex("58406520a006649127e371903a2de979"), SpanId::from_hex("4c721bf33e3caf8f"), TRACE_FLAG_DEFERRED, true, TraceState::default())),
-                ("Root=1-58406520-a006649127e371903a2de979;Self=1-58406520-bf42676c05e20ba4a90e448e;Parent=4c721bf33e3caf8f;Sampled=1", SpanContext::new(TraceId::from_hex("58406520a006649127e371903a2de979"), SpanId::from_hex("4c721bf33e3caf8f"), TRACE_FLAG_SAMPLED, true, TraceState::from_str("self=1-58406520-bf42676c05e20ba4a90e448e").unwrap())),
-                ("Root=1-58406520-a006649127e371903a2de979;Self=1-58406520-bf42676c05e20ba4a90e448e;Parent=4c721bf33e3caf8f;Sampled=1;RandomKey=RandomValue", SpanContext::new(TraceId::from_hex("58406520a006649127e371903a2de979"), SpanId::from_hex("4c721bf33e3caf8f"), TRACE_FLAG_SAMPLED, true, TraceState::from_str("self=1-58406520-bf42676c05e20ba4a90e448e,randomkey=RandomValue").unwrap())),
+                ("Root=1-58406520-a006649127e371903a2de979;Parent=garbage", SpanContext::new(TraceId::from_u128(0x5840_6520_a006_6491_27e3_7190_3a2d_e979), SpanId::invalid(), TRACE_FLAG_DEFERRED, true, TraceState::default())),
+                ("Root=1-58406520-a006649127e371903a2de979;Sampled=1", SpanContext::new(TraceId::from_u128(0x5840_6520_a006_6491_27e3_7190_3a2d_e979), SpanId::invalid(), TRACE_FLAG_SAMPLED, true, TraceState::default())),
+                ("Root=1-58406520-a006649127e371903a2de979;Parent=4c721bf33e3caf8f;Sampled=0", SpanContext::new(TraceId::from_u128(0x5840_6520_a006_6491_27e3_7190_3a2d_e979), SpanId::from_u64(0x4c72_1bf3_3e3c_af8f), TRACE_FLAG_NOT_SAMPLED, true, TraceState::default())),
+                ("Root=1-58406520-a006649127e371903a2de979;Parent=4c721bf33e3caf8f;Sampled=1", SpanContext::new(TraceId::from_u128(0x5840_6520_a006_6491_27e3_7190_3a2d_e979), SpanId::from_u64(0x4c72_1bf3_3e3c_af8f), TRACE_FLAG_SAMPLED, true, TraceState::default())),
+                ("Root=1-58406520-a006649127e371903a2de979;Parent=4c721bf33e3caf8f", SpanContext::new(TraceId::from_u128(0x5840_6520_a006_6491_27e3_7190_3a2d_e979), SpanId::from_u64(0x4c72_1bf3_3e3c_af8f), TRACE_FLAG_DEFERRED, true, TraceState::default())),
+                ("Root=1-58406520-a006649127e371903a2de979;Parent=4c721bf33e3caf8f;Sampled=?", SpanContext::new(TraceId::from_u128(0x5840_6520_a006_6491_27e3_7190_3a2d_e979), SpanId::from_u64(0x4c72_1bf3_3e3c_af8f), TRACE_FLAG_DEFERRED, true, TraceState::default())),
+                ("Root=1-58406520-a006649127e371903a2de979;Self=1-58406520-bf42676c05e20ba4a90e448e;Parent=4c721bf33e3caf8f;Sampled=1", SpanContext::new(TraceId::from_u128(0x5840_6520_a006_6491_27e3_7190_3a2d_e979), SpanId::from_u64(0x4c72_1bf3_3e3c_af8f), TRACE_FLAG_SAMPLED, true, TraceState::from_str("self=1-58406520-bf42676c05e20ba4a90e448e").unwrap())),
+                ("Root=1-58406520-a006649127e371903a2de979;Self=1-58406520-bf42676c05e20ba4a90e448e;Parent=4c721bf33e3caf8f;Sampled=1;RandomKey=RandomValue", SpanContext::new(TraceId::from_u128(0x5840_6520_a006_6491_27e3_7190_3a2d_e979), SpanId::from_u64(0x4c72_1bf3_3e3c_af8f), TRACE_FLAG_SAMPLED, true, TraceState::from_str("self=1-58406520-bf42676c05e20ba4a90e448e,randomkey=RandomValue").unwrap())),
             ]
         }
 
@@ -305,12 +309,12 @@ pub mod trace {
         fn inject_test_data() -> Vec<(&'static str, SpanContext)> {
             vec![
                 ("", SpanContext::empty_context()),
-                ("", SpanContext::new(TraceId::from_hex("garbage"), SpanId::invalid(), TRACE_FLAG_DEFERRED, true, TraceState::default())),
-                ("", SpanContext::new(TraceId::from_hex("58406520a006649127e371903a2de979"), SpanId::invalid(), TRACE_FLAG_DEFERRED, true, TraceState::default())),
-                ("", SpanContext::new(TraceId::from_hex("58406520a006649127e371903a2de979"), SpanId::invalid(), TRACE_FLAG_SAMPLED, true, TraceState::default())),
-                ("Root=1-58406520-a006649127e371903a2de979;Parent=4c721bf33e3caf8f;Sampled=0", SpanContext::new(TraceId::from_hex("58406520a006649127e371903a2de979"), SpanId::from_hex("4c721bf33e3caf8f"), TRACE_FLAG_NOT_SAMPLED, true, TraceState::default())),
-                ("Root=1-58406520-a006649127e371903a2de979;Parent=4c721bf33e3caf8f;Sampled=1", SpanContext::new(TraceId::from_hex("58406520a006649127e371903a2de979"), SpanId::from_hex("4c721bf33e3caf8f"), TRACE_FLAG_SAMPLED, true, TraceState::default())),
-                ("Root=1-58406520-a006649127e371903a2de979;Parent=4c721bf33e3caf8f;Sampled=?;Self=1-58406520-bf42676c05e20ba4a90e448e;Randomkey=RandomValue", SpanContext::new(TraceId::from_hex("58406520a006649127e371903a2de979"), SpanId::from_hex("4c721bf33e3caf8f"), TRACE_FLAG_DEFERRED, true, TraceState::from_str("self=1-58406520-bf42676c05e20ba4a90e448e,randomkey=RandomValue").unwrap())),
+                ("", SpanContext::new(TraceId::invalid(), SpanId::invalid(), TRACE_FLAG_DEFERRED, true, TraceState::default())),
+                ("", SpanContext::new(TraceId::from_u128(0x5840_6520_a006_6491_27e3_7190_3a2d_e979), SpanId::invalid(), TRACE_FLAG_DEFERRED, true, TraceState::default())),
+                ("", SpanContext::new(TraceId::from_u128(0x5840_6520_a006_6491_27e3_7190_3a2d_e979), SpanId::invalid(), TRACE_FLAG_SAMPLED, true, TraceState::default())),
+                ("Root=1-58406520-a006649127e371903a2de979;Parent=4c721bf33e3caf8f;Sampled=0", SpanContext::new(TraceId::from_u128(0x5840_6520_a006_6491_27e3_7190_3a2d_e979), SpanId::from_u64(0x4c72_1bf3_3e3c_af8f), TRACE_FLAG_NOT_SAMPLED, true, TraceState::default())),
+                ("Root=1-58406520-a006649127e371903a2de979;Parent=4c721bf33e3caf8f;Sampled=1", SpanContext::new(TraceId::from_u128(0x5840_6520_a006_6491_27e3_7190_3a2d_e979), SpanId::from_u64(0x4c72_1bf3_3e3c_af8f), TRACE_FLAG_SAMPLED, true, TraceState::default())),
+                ("Root=1-58406520-a006649127e371903a2de979;Parent=4c721bf33e3caf8f;Sampled=?;Self=1-58406520-bf42676c05e20ba4a90e448e;Randomkey=RandomValue", SpanContext::new(TraceId::from_u128(0x5840_6520_a006_6491_27e3_7190_3a2d_e979), SpanId::from_u64(0x4c72_1bf3_3e3c_af8f), TRACE_FLAG_DEFERRED, true, TraceState::from_str("self=1-58406520-bf42676c05e20ba4a90e448e,randomkey=RandomValue").unwrap())),
             ]
         }
 
@@ -1,4 +1,5 @@
 use opentelemetry::sdk::export::{trace, ExportError};
+use opentelemetry::trace::Status;
 
 mod v03;
 mod v05;
@@ -71,13 +72,21 @@ impl ApiVersion {
     }
 }
 
+/// Datadog marks spans as failed with a non-zero error field.
+pub(crate) fn error_flag(status: &Status) -> i32 {
+    match status {
+        Status::Error { .. } => 1,
+        _ => 0,
+    }
+}
+
 #[cfg(test)]
 pub(crate) mod tests {
     use super::*;
     use opentelemetry::sdk;
     use opentelemetry::sdk::InstrumentationLibrary;
     use opentelemetry::{
-        trace::{SpanContext, SpanId, SpanKind, StatusCode, TraceId, TraceState},
+        trace::{SpanContext, SpanId, SpanKind, TraceId, TraceState},
         Key,
     };
     use std::time::{Duration, SystemTime};
@@ -115,10 +124,9 @@ pub(crate) mod tests {
             attributes,
             events,
             links,
-            status_code: StatusCode::Ok,
-            status_message: "".into(),
+            status: Status::Ok,
             resource: None,
-            instrumentation_lib: InstrumentationLibrary::new("component", None),
+            instrumentation_lib: InstrumentationLibrary::new("component", None, None),
         }
     }
 
@@ -127,7 +135,7 @@ pub(crate) mod tests {
         let traces = get_traces();
         let encoded = base64::encode(ApiVersion::Version03.encode("service_name", traces)?);
 
-        assert_eq!(encoded.as_str(), "kZGLpHR5cGWjd2Vip3NlcnZpY2Wsc2VydmljZV9uYW1lpG5hbWWpY29tcG9uZW50qHJlc291cmNlqHJlc291cmNlqHRyYWNlX2lkzwAAAAAAAAAHp3NwYW5faWTPAAAAAAAAAGOpcGFyZW50X2lkzwAAAAAAAAABpXN0YXJ00wAAAAAAAAAAqGR1cmF0aW9u0wAAAAA7msoApWVycm9y0gAAAAGkbWV0YYGpc3Bhbi50eXBlo3dlYg==");
+        assert_eq!(encoded.as_str(), "kZGLpHR5cGWjd2Vip3NlcnZpY2Wsc2VydmljZV9uYW1lpG5hbWWpY29tcG9uZW50qHJlc291cmNlqHJlc291cmNlqHRyYWNlX2lkzwAAAAAAAAAHp3NwYW5faWTPAAAAAAAAAGOpcGFyZW50X2lkzwAAAAAAAAABpXN0YXJ00wAAAAAAAAAAqGR1cmF0aW9u0wAAAAA7msoApWVycm9y0gAAAACkbWV0YYGpc3Bhbi50eXBlo3dlYg==");
 
         Ok(())
     }
@@ -137,7 +145,7 @@ pub(crate) mod tests {
         let traces = get_traces();
         let encoded = base64::encode(ApiVersion::Version05.encode("service_name", traces)?);
 
-        assert_eq!(encoded.as_str(), "kpWsc2VydmljZV9uYW1lo3dlYqljb21wb25lbnSocmVzb3VyY2Wpc3Bhbi50eXBlkZGczgAAAADOAAAAAs4AAAADzwAAAAAAAAAHzwAAAAAAAABjzwAAAAAAAAAB0wAAAAAAAAAA0wAAAAA7msoA0gAAAAGBzgAAAATOAAAAAYDOAAAAAQ==");
+        assert_eq!(encoded.as_str(), "kpWsc2VydmljZV9uYW1lo3dlYqljb21wb25lbnSocmVzb3VyY2Wpc3Bhbi50eXBlkZGczgAAAADOAAAAAs4AAAADzwAAAAAAAAAHzwAAAAAAAABjzwAAAAAAAAAB0wAAAAAAAAAA0wAAAAA7msoA0gAAAACBzgAAAATOAAAAAYDOAAAAAQ==");
 
         Ok(())
     }
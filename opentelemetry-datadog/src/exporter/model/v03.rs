@@ -1,4 +1,4 @@
-use crate::exporter::model::Error;
+use crate::exporter::model::{error_flag, Error};
 use opentelemetry::sdk::export::trace;
 use opentelemetry::{Key, Value};
 use std::time::SystemTime;
@@ -61,7 +61,7 @@ pub(crate) fn encode(
             rmp::encode::write_i64(&mut encoded, duration)?;
 
             rmp::encode::write_str(&mut encoded, "error")?;
-            rmp::encode::write_i32(&mut encoded, span.status_code as i32)?;
+            rmp::encode::write_i32(&mut encoded, error_flag(&span.status))?;
 
             rmp::encode::write_str(&mut encoded, "meta")?;
             rmp::encode::write_map_len(&mut encoded, span.attributes.len() as u32)?;
@@ -1,4 +1,5 @@
 use crate::exporter::intern::StringInterner;
+use crate::exporter::model::error_flag;
 use crate::exporter::Error;
 use opentelemetry::sdk::export::trace;
 use opentelemetry::{Key, Value};
@@ -111,7 +112,7 @@ fn encode_traces(
             rmp::encode::write_u64(&mut encoded, span.parent_span_id.to_u64())?;
             rmp::encode::write_i64(&mut encoded, start)?;
             rmp::encode::write_i64(&mut encoded, duration)?;
-            rmp::encode::write_i32(&mut encoded, span.status_code as i32)?;
+            rmp::encode::write_i32(&mut encoded, error_flag(&span.status))?;
             rmp::encode::write_map_len(&mut encoded, span.attributes.len() as u32)?;
             for (key, value) in span.attributes.iter() {
                 rmp::encode::write_u32(&mut encoded, interner.intern(key.as_str()))?;
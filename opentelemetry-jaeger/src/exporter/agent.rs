@@ -75,27 +75,69 @@ impl AgentAsyncClientUdp {
     }
 
     /// Emit standard Jaeger batch
+    ///
+    /// Batches that do not fit in a single UDP packet are split and sent as
+    /// multiple packets.
     pub(crate) async fn emit_batch(&mut self, batch: jaeger::Batch) -> thrift::Result<()> {
-        // Write payload to buffer
-        self.buffer_client.client.emit_batch(batch)?;
-        let payload = self.buffer_client.buffer.take_bytes();
-
-        if payload.len() > self.max_packet_size {
-            return Err(thrift::ProtocolError::new(
-                thrift::ProtocolErrorKind::SizeLimit,
-                format!(
-                    "jaeger exporter payload size of {} bytes over max UDP packet size of {} bytes. Try setting a smaller batch size.",
-                    payload.len(),
-                    self.max_packet_size,
-                ),
-            )
-            .into());
-        }
+        // Write payloads to buffer
+        let (payloads, oversized) = self
+            .buffer_client
+            .serialize_batch(batch, self.max_packet_size)?;
 
         // Write async to socket, reading from buffer
-        write_to_socket(self, payload).await?;
+        for payload in payloads {
+            write_to_socket(self, payload).await?;
+        }
+
+        match oversized {
+            Some(err) => Err(err),
+            None => Ok(()),
+        }
+    }
+}
 
-        Ok(())
+impl BufferClient {
+    /// Serialize a batch into payloads of at most `max_packet_size` bytes.
+    ///
+    /// Oversized batches are halved until they fit. Spans that do not fit in a
+    /// packet on their own are dropped and reported in the returned error.
+    fn serialize_batch(
+        &mut self,
+        batch: jaeger::Batch,
+        max_packet_size: usize,
+    ) -> thrift::Result<(Vec<Vec<u8>>, Option<thrift::Error>)> {
+        let mut payloads = Vec::new();
+        let mut oversized = None;
+        let mut pending = vec![batch];
+
+        while let Some(batch) = pending.pop() {
+            self.client.emit_batch(batch.clone())?;
+            let payload = self.buffer.take_bytes();
+
+            if payload.len() <= max_packet_size {
+                payloads.push(payload);
+            } else if batch.spans.len() > 1 {
+                let jaeger::Batch { process, mut spans } = batch;
+                let tail = spans.split_off(spans.len() / 2);
+                // pushed in reverse so spans are sent in their original order
+                pending.push(jaeger::Batch::new(process.clone(), tail));
+                pending.push(jaeger::Batch::new(process, spans));
+            } else {
+                oversized = Some(
+                    thrift::ProtocolError::new(
+                        thrift::ProtocolErrorKind::SizeLimit,
+                        format!(
+                            "jaeger exporter span payload size of {} bytes over max UDP packet size of {} bytes",
+                            payload.len(),
+                            max_packet_size,
+                        ),
+                    )
+                    .into(),
+                );
+            }
+        }
+
+        Ok((payloads, oversized))
     }
 }
 
@@ -119,3 +161,69 @@ async fn write_to_socket(client: &mut AgentAsyncClientUdp, payload: Vec<u8>) ->
 
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn buffer_client() -> BufferClient {
+        let (buffer, write) = TBufferChannel::with_capacity(UDP_PACKET_MAX_LENGTH)
+            .split()
+            .unwrap();
+        let client = agent::AgentSyncClient::new(
+            TCompactInputProtocol::new(TNoopChannel),
+            TCompactOutputProtocol::new(write),
+        );
+        BufferClient { buffer, client }
+    }
+
+    fn batch(span_count: usize, name_len: usize) -> jaeger::Batch {
+        let spans = (0..span_count)
+            .map(|i| {
+                jaeger::Span::new(
+                    0,
+                    1,
+                    i as i64,
+                    0,
+                    "a".repeat(name_len),
+                    None,
+                    1,
+                    0,
+                    0,
+                    None,
+                    None,
+                )
+            })
+            .collect();
+        jaeger::Batch::new(jaeger::Process::new("service".to_string(), None), spans)
+    }
+
+    #[test]
+    fn small_batches_are_sent_in_one_packet() {
+        let (payloads, oversized) = buffer_client()
+            .serialize_batch(batch(10, 10), UDP_PACKET_MAX_LENGTH)
+            .unwrap();
+        assert_eq!(payloads.len(), 1);
+        assert!(oversized.is_none());
+    }
+
+    #[test]
+    fn oversized_batches_are_split() {
+        let max_packet_size = 1_000;
+        let (payloads, oversized) = buffer_client()
+            .serialize_batch(batch(40, 100), max_packet_size)
+            .unwrap();
+        assert!(payloads.len() > 1);
+        assert!(payloads.iter().all(|p| p.len() <= max_packet_size));
+        assert!(oversized.is_none());
+    }
+
+    #[test]
+    fn oversized_spans_are_reported() {
+        let (payloads, oversized) = buffer_client()
+            .serialize_batch(batch(2, 2_000), 1_000)
+            .unwrap();
+        assert!(payloads.is_empty());
+        assert!(oversized.is_some());
+    }
+}
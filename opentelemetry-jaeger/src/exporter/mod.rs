@@ -27,7 +27,7 @@ use opentelemetry::{
     runtime::Runtime,
     sdk,
     sdk::export::trace,
-    trace::{Event, Link, SpanKind, StatusCode, TracerProvider},
+    trace::{Event, Link, SpanKind, Status, TracerProvider},
     Key, KeyValue,
 };
 #[cfg(feature = "collector_client")]
@@ -48,9 +48,6 @@ use uploader::BatchUploader;
 ))]
 use headers::authorization::Credentials;
 
-/// Default service name if no service is configured.
-const DEFAULT_SERVICE_NAME: &str = "OpenTelemetry";
-
 /// Default agent endpoint if none is provided
 const DEFAULT_AGENT_ENDPOINT: &str = "127.0.0.1:6831";
 
@@ -83,6 +80,9 @@ pub struct Process {
     pub tags: Vec<KeyValue>,
 }
 
+// `set_export_timeout` keeps its default, neither the UDP agent client nor
+// `HttpClient` has a per request timeout and the span processors already
+// abandon exports running too long.
 #[async_trait]
 impl trace::SpanExporter for Exporter {
     /// Export spans to Jaeger
@@ -145,7 +145,7 @@ impl Default for PipelineBuilder {
             client: None,
             export_instrument_library: true,
             process: Process {
-                service_name: DEFAULT_SERVICE_NAME.to_string(),
+                service_name: sdk::resource::default_service_name(),
                 tags: Vec::new(),
             },
             max_packet_size: None,
@@ -226,6 +226,9 @@ impl PipelineBuilder {
     }
 
     /// Assign the process service name.
+    ///
+    /// Defaults to the `OTEL_SERVICE_NAME` environment variable, or
+    /// `unknown_service:<process executable name>` if it is not set.
     pub fn with_service_name<T: Into<String>>(mut self, service_name: T) -> Self {
         self.process.service_name = service_name.into();
         self
@@ -238,6 +241,9 @@ impl PipelineBuilder {
     }
 
     /// Assign the max packet size in bytes. Jaeger defaults is 65000.
+    ///
+    /// Batches exported to the agent that exceed this size are split across
+    /// multiple packets.
     pub fn with_max_packet_size(mut self, max_packet_size: usize) -> Self {
         self.max_packet_size = Some(max_packet_size);
         self
@@ -460,6 +466,10 @@ impl surf::middleware::Middleware for BasicAuthMiddleware {
     }
 }
 
+/// Convert links to `FollowsFrom` references.
+///
+/// The thrift span references have no room for attributes, see
+/// [`links_to_logs`] for how link attributes are exported.
 fn links_to_references(links: sdk::trace::EvictedQueue<Link>) -> Option<Vec<jaeger::SpanRef>> {
     if !links.is_empty() {
         let refs = links
@@ -484,6 +494,43 @@ fn links_to_references(links: sdk::trace::EvictedQueue<Link>) -> Option<Vec<jaeg
     }
 }
 
+/// Convert the attributes of links to logs at `timestamp`, the start time of
+/// the span, as Jaeger span references cannot carry attributes.
+///
+/// Each log identifies the linked span through the `otel.link.trace_id` and
+/// `otel.link.span_id` fields, links without attributes are only exported as
+/// references.
+fn links_to_logs(
+    links: &sdk::trace::EvictedQueue<Link>,
+    timestamp: i64,
+) -> impl Iterator<Item = jaeger::Log> + '_ {
+    links
+        .iter()
+        .filter(|link| !link.attributes().is_empty() || link.dropped_attributes_count() > 0)
+        .map(move |link| {
+            let span_context = link.span_context();
+            let mut fields = vec![
+                Key::new("event").string("link").into(),
+                Key::new(OTEL_LINK_TRACE_ID)
+                    .string(span_context.trace_id().to_hex())
+                    .into(),
+                Key::new(OTEL_LINK_SPAN_ID)
+                    .string(span_context.span_id().to_hex())
+                    .into(),
+            ];
+            fields.extend(link.attributes().iter().cloned().map(Into::into));
+            if link.dropped_attributes_count() > 0 {
+                fields.push(
+                    Key::new(OTEL_LINK_DROPPED_ATTRIBUTES_COUNT)
+                        .i64(i64::from(link.dropped_attributes_count()))
+                        .into(),
+                );
+            }
+
+            jaeger::Log::new(timestamp, fields)
+        })
+}
+
 /// Convert spans to jaeger thrift span for exporting.
 fn convert_otel_span_into_jaeger_span(
     span: trace::SpanData,
@@ -492,6 +539,32 @@ fn convert_otel_span_into_jaeger_span(
     let trace_id = span.span_context.trace_id().to_u128();
     let trace_id_high = (trace_id >> 64) as i64;
     let trace_id_low = trace_id as i64;
+    let dropped_counts = [
+        (
+            OTEL_DROPPED_ATTRIBUTES_COUNT,
+            span.attributes.dropped_count(),
+        ),
+        (OTEL_DROPPED_EVENTS_COUNT, span.events.dropped_count()),
+        (OTEL_DROPPED_LINKS_COUNT, span.links.dropped_count()),
+    ];
+    let mut tags = build_span_tags(
+        span.attributes,
+        if export_instrument_lib {
+            Some(span.instrumentation_lib)
+        } else {
+            None
+        },
+        span.status,
+        span.span_kind,
+    );
+    tags.extend(dropped_count_tags(&dropped_counts));
+    let start_time = span
+        .start_time
+        .duration_since(SystemTime::UNIX_EPOCH)
+        .unwrap_or_else(|_| Duration::from_secs(0))
+        .as_micros() as i64;
+    let mut logs = events_to_logs(span.events).unwrap_or_default();
+    logs.extend(links_to_logs(&span.links, start_time));
     jaeger::Span {
         trace_id_low,
         trace_id_high,
@@ -500,28 +573,14 @@ fn convert_otel_span_into_jaeger_span(
         operation_name: span.name.into_owned(),
         references: links_to_references(span.links),
         flags: span.span_context.trace_flags() as i32,
-        start_time: span
-            .start_time
-            .duration_since(SystemTime::UNIX_EPOCH)
-            .unwrap_or_else(|_| Duration::from_secs(0))
-            .as_micros() as i64,
+        start_time,
         duration: span
             .end_time
             .duration_since(span.start_time)
             .unwrap_or_else(|_| Duration::from_secs(0))
             .as_micros() as i64,
-        tags: Some(build_span_tags(
-            span.attributes,
-            if export_instrument_lib {
-                Some(span.instrumentation_lib)
-            } else {
-                None
-            },
-            span.status_code,
-            span.status_message.into_owned(),
-            span.span_kind,
-        )),
-        logs: events_to_logs(span.events),
+        tags: Some(tags),
+        logs: if logs.is_empty() { None } else { Some(logs) },
     }
 }
 
@@ -542,8 +601,7 @@ fn build_process_tags(
 fn build_span_tags(
     attrs: sdk::trace::EvictedHashMap,
     instrumentation_lib: Option<sdk::InstrumentationLibrary>,
-    status_code: StatusCode,
-    status_description: String,
+    status: Status,
     kind: SpanKind,
 ) -> Vec<jaeger::Tag> {
     let mut user_overrides = UserOverrides::default();
@@ -568,35 +626,50 @@ fn build_span_tags(
         tags.push(Key::new(SPAN_KIND).string(kind.to_string()).into());
     }
 
-    if status_code != StatusCode::Unset {
+    if status != Status::Unset {
         // Ensure error status is set unless user has already overrided it
-        if status_code == StatusCode::Error && !user_overrides.error {
+        if matches!(status, Status::Error { .. }) && !user_overrides.error {
             tags.push(Key::new(ERROR).bool(true).into());
         }
         if !user_overrides.status_code {
             tags.push(
                 Key::new(OTEL_STATUS_CODE)
-                    .string::<&'static str>(status_code.as_str())
+                    .string::<&'static str>(status.as_str())
                     .into(),
             );
         }
         // set status message if there is one
-        if !status_description.is_empty() && !user_overrides.status_description {
-            tags.push(
-                Key::new(OTEL_STATUS_DESCRIPTION)
-                    .string(status_description)
-                    .into(),
-            );
+        if let Status::Error { description } = status {
+            if !description.is_empty() && !user_overrides.status_description {
+                tags.push(Key::new(OTEL_STATUS_DESCRIPTION).string(description).into());
+            }
         }
     }
 
     tags
 }
 
+/// Jaeger has no dedicated fields for the data dropped by the span limits, so
+/// non-zero dropped counts are reported as tags.
+fn dropped_count_tags<'a>(
+    dropped_counts: &'a [(&'static str, u32)],
+) -> impl Iterator<Item = jaeger::Tag> + 'a {
+    dropped_counts
+        .iter()
+        .filter(|(_, count)| *count > 0)
+        .map(|(key, count)| Key::new(*key).i64(i64::from(*count)).into())
+}
+
 const ERROR: &str = "error";
 const SPAN_KIND: &str = "span.kind";
 const OTEL_STATUS_CODE: &str = "otel.status_code";
 const OTEL_STATUS_DESCRIPTION: &str = "otel.status_description";
+const OTEL_DROPPED_ATTRIBUTES_COUNT: &str = "otel.dropped_attributes_count";
+const OTEL_DROPPED_EVENTS_COUNT: &str = "otel.dropped_events_count";
+const OTEL_DROPPED_LINKS_COUNT: &str = "otel.dropped_links_count";
+const OTEL_LINK_TRACE_ID: &str = "otel.link.trace_id";
+const OTEL_LINK_SPAN_ID: &str = "otel.link.span_id";
+const OTEL_LINK_DROPPED_ATTRIBUTES_COUNT: &str = "otel.link.dropped_attributes_count";
 
 #[derive(Default)]
 struct UserOverrides {
@@ -653,6 +726,16 @@ pub enum Error {
     #[error("collector uri is invalid, {0}")]
     #[cfg(any(feature = "collector_client", feature = "wasm_collector_client"))]
     InvalidUri(#[from] http::uri::InvalidUri),
+
+    /// invalid sampling endpoint is provided.
+    #[error("sampling endpoint is invalid, {0}")]
+    #[cfg(feature = "remote_sampler")]
+    InvalidSamplingEndpoint(http::uri::InvalidUri),
+
+    /// the sampling endpoint responded with an unsuccessful status code.
+    #[error("sampling endpoint responded with status code {0}")]
+    #[cfg(feature = "remote_sampler")]
+    SamplingEndpointFailed(http::StatusCode),
 }
 
 impl ExportError for Error {
@@ -739,9 +822,13 @@ mod collector_client_tests {
 mod tests {
     use super::SPAN_KIND;
     use crate::exporter::thrift::jaeger::Tag;
-    use crate::exporter::{build_span_tags, OTEL_STATUS_CODE, OTEL_STATUS_DESCRIPTION};
-    use opentelemetry::sdk::trace::EvictedHashMap;
-    use opentelemetry::trace::{SpanKind, StatusCode};
+    use crate::exporter::{
+        build_span_tags, dropped_count_tags, links_to_logs, OTEL_DROPPED_ATTRIBUTES_COUNT,
+        OTEL_DROPPED_LINKS_COUNT, OTEL_LINK_SPAN_ID, OTEL_LINK_TRACE_ID, OTEL_STATUS_CODE,
+        OTEL_STATUS_DESCRIPTION,
+    };
+    use opentelemetry::sdk::trace::{EvictedHashMap, EvictedQueue};
+    use opentelemetry::trace::{Link, SpanContext, SpanId, SpanKind, Status, TraceId, TraceState};
     use opentelemetry::KeyValue;
 
     fn assert_tag_contains(tags: Vec<Tag>, key: &'static str, expect_val: &'static str) {
@@ -768,38 +855,24 @@ mod tests {
         );
     }
 
-    fn get_error_tag_test_data() -> Vec<(
-        StatusCode,
-        String,
-        Option<&'static str>,
-        Option<&'static str>,
-    )> {
-        // StatusCode, error message, OTEL_STATUS_CODE tag value, OTEL_STATUS_DESCRIPTION tag value
+    fn get_error_tag_test_data() -> Vec<(Status, Option<&'static str>, Option<&'static str>)> {
+        // Status, OTEL_STATUS_CODE tag value, OTEL_STATUS_DESCRIPTION tag value
         vec![
-            (StatusCode::Error, "".into(), Some("ERROR"), None),
-            (StatusCode::Unset, "".into(), None, None),
-            // When status is ok, no description should be in span data. This should be ensured by Otel API
-            (StatusCode::Ok, "".into(), Some("OK"), None),
+            (Status::error(""), Some("ERROR"), None),
+            (Status::Unset, None, None),
+            (Status::Ok, Some("OK"), None),
             (
-                StatusCode::Error,
-                "have message".into(),
+                Status::error("have message"),
                 Some("ERROR"),
                 Some("have message"),
             ),
-            (StatusCode::Unset, "have message".into(), None, None),
         ]
     }
 
     #[test]
     fn test_set_status() {
-        for (status_code, error_msg, status_tag_val, msg_tag_val) in get_error_tag_test_data() {
-            let tags = build_span_tags(
-                EvictedHashMap::new(20, 20),
-                None,
-                status_code,
-                error_msg,
-                SpanKind::Client,
-            );
+        for (status, status_tag_val, msg_tag_val) in get_error_tag_test_data() {
+            let tags = build_span_tags(EvictedHashMap::new(20, 20), None, status, SpanKind::Client);
             if let Some(val) = status_tag_val {
                 assert_tag_contains(tags.clone(), OTEL_STATUS_CODE, val);
             } else {
@@ -819,29 +892,88 @@ mod tests {
         let mut attributes = EvictedHashMap::new(20, 20);
         let user_error = true;
         let user_kind = "server";
-        let user_status_code = StatusCode::Error;
+        let user_status = Status::error("Something bad happened");
         let user_status_description = "Something bad happened";
         attributes.insert(KeyValue::new("error", user_error));
         attributes.insert(KeyValue::new(SPAN_KIND, user_kind));
-        attributes.insert(KeyValue::new(OTEL_STATUS_CODE, user_status_code.as_str()));
+        attributes.insert(KeyValue::new(OTEL_STATUS_CODE, user_status.as_str()));
         attributes.insert(KeyValue::new(
             OTEL_STATUS_DESCRIPTION,
             user_status_description,
         ));
-        let tags = build_span_tags(
-            attributes,
-            None,
-            user_status_code,
-            user_status_description.to_string(),
-            SpanKind::Client,
-        );
+        let tags = build_span_tags(attributes, None, user_status.clone(), SpanKind::Client);
 
         assert!(tags
             .iter()
             .filter(|tag| tag.key.as_str() == "error")
             .all(|tag| tag.v_bool.unwrap()));
         assert_tag_contains(tags.clone(), SPAN_KIND, user_kind);
-        assert_tag_contains(tags.clone(), OTEL_STATUS_CODE, user_status_code.as_str());
+        assert_tag_contains(tags.clone(), OTEL_STATUS_CODE, user_status.as_str());
         assert_tag_contains(tags, OTEL_STATUS_DESCRIPTION, user_status_description);
     }
+
+    #[test]
+    fn test_span_kind() {
+        let kinds = vec![
+            (SpanKind::Client, Some("client")),
+            (SpanKind::Server, Some("server")),
+            (SpanKind::Producer, Some("producer")),
+            (SpanKind::Consumer, Some("consumer")),
+            (SpanKind::Internal, None),
+        ];
+
+        for (kind, expected) in kinds {
+            let tags = build_span_tags(EvictedHashMap::new(20, 20), None, Status::Unset, kind);
+            match expected {
+                Some(val) => assert_tag_contains(tags, SPAN_KIND, val),
+                None => assert_tag_not_contains(tags, SPAN_KIND),
+            }
+        }
+    }
+
+    #[test]
+    fn dropped_counts_as_tags() {
+        let dropped_counts = [
+            (OTEL_DROPPED_ATTRIBUTES_COUNT, 2),
+            (OTEL_DROPPED_LINKS_COUNT, 0),
+        ];
+        let tags = dropped_count_tags(&dropped_counts).collect::<Vec<_>>();
+
+        assert_eq!(tags.len(), 1);
+        assert_eq!(tags[0].key, OTEL_DROPPED_ATTRIBUTES_COUNT);
+        assert_eq!(tags[0].v_long, Some(2));
+    }
+
+    #[test]
+    fn link_attributes_as_logs() {
+        let span_context = SpanContext::new(
+            TraceId::from_u128(1),
+            SpanId::from_u64(2),
+            0,
+            false,
+            TraceState::default(),
+        );
+        let mut links = EvictedQueue::new(10);
+        links.extend(vec![
+            Link::new(span_context.clone(), Vec::new()),
+            Link::new(span_context, vec![KeyValue::new("relationship", "batch")]),
+        ]);
+
+        let logs = links_to_logs(&links, 42).collect::<Vec<_>>();
+
+        assert_eq!(logs.len(), 1);
+        assert_eq!(logs[0].timestamp, 42);
+        assert_tag_contains(logs[0].fields.clone(), "event", "link");
+        assert_tag_contains(
+            logs[0].fields.clone(),
+            OTEL_LINK_TRACE_ID,
+            "00000000000000000000000000000001",
+        );
+        assert_tag_contains(
+            logs[0].fields.clone(),
+            OTEL_LINK_SPAN_ID,
+            "0000000000000002",
+        );
+        assert_tag_contains(logs[0].fields.clone(), "relationship", "batch");
+    }
 }
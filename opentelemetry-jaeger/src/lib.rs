@@ -115,7 +115,7 @@
 //!
 //! ```no_run
 //! use opentelemetry::{KeyValue, trace::{Tracer, TraceError}};
-//! use opentelemetry::sdk::{trace::{self, IdGenerator, Sampler}, Resource};
+//! use opentelemetry::sdk::{trace::{self, RandomIdGenerator, Sampler}, Resource};
 //! use opentelemetry::global;
 //!
 //! fn main() -> Result<(), TraceError> {
@@ -128,7 +128,7 @@
 //!         .with_trace_config(
 //!             trace::config()
 //!                 .with_sampler(Sampler::AlwaysOn)
-//!                 .with_id_generator(IdGenerator::default())
+//!                 .with_id_generator(RandomIdGenerator::default())
 //!                 .with_max_events_per_span(64)
 //!                 .with_max_attributes_per_span(16)
 //!                 .with_max_events_per_span(16)
@@ -162,6 +162,8 @@
 //!
 //! * `wasm_collector_client`: Enable collector in wasm.
 //!
+//! * `remote_sampler`: Sample spans with the strategies configured in Jaeger, see [`JaegerRemoteSampler`]. User MUST provide the http client.
+//!
 //! ## Supported Rust Versions
 //!
 //! OpenTelemetry is built against the latest stable release. The minimum
@@ -191,6 +193,8 @@
 #![cfg_attr(test, deny(warnings))]
 
 mod exporter;
+#[cfg(feature = "remote_sampler")]
+mod sampler;
 mod propagator {
     //! # Jaeger Propagator
     //!
@@ -242,6 +246,26 @@ mod propagator {
             Propagator::default()
         }
 
+        /// Create the propagator for a name of the `OTEL_PROPAGATORS`
+        /// environment variable, which is `jaeger` for this propagator.
+        ///
+        /// # Examples
+        ///
+        /// ```
+        /// use opentelemetry::global;
+        ///
+        /// global::set_text_map_propagator_from_env(|name| {
+        ///     opentelemetry_jaeger::Propagator::from_env_name(name).map(|p| Box::new(p) as _)
+        /// });
+        /// ```
+        pub fn from_env_name(name: &str) -> Option<Self> {
+            if name == "jaeger" {
+                Some(Propagator::new())
+            } else {
+                None
+            }
+        }
+
         /// Extract span context from header value
         fn extract_span_context(&self, extractor: &dyn Extractor) -> Result<SpanContext, ()> {
             let mut header_value = Cow::from(extractor.get(JAEGER_HEADER).unwrap_or(""));
@@ -325,7 +349,7 @@ mod propagator {
                         .map(|value| (key.to_string(), value.to_string()))
                 });
 
-            TraceState::from_key_value(uber_context_keys)
+            TraceState::from_key_value(uber_context_keys).map_err(|_| ())
         }
     }
 
@@ -576,3 +600,5 @@ mod propagator {
 
 pub use exporter::{new_pipeline, Error, Exporter, PipelineBuilder, Process};
 pub use propagator::Propagator;
+#[cfg(feature = "remote_sampler")]
+pub use sampler::{JaegerRemoteSampler, JaegerRemoteSamplerBuilder};
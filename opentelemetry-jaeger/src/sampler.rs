@@ -0,0 +1,579 @@
+//! # Jaeger Remote Sampler
+//!
+//! Periodically fetches the sampling strategy of a service from the sampling
+//! endpoint of a Jaeger agent, e.g. `http://localhost:5778/sampling`, and
+//! samples new spans according to it.
+//!
+//! The following strategies are supported:
+//!
+//! * `probabilistic`: sample a fixed ratio of traces.
+//! * `rateLimiting`: sample at most a fixed number of traces per second.
+//! * `perOperation`: sample a fixed ratio of traces per span name, with a
+//!   default ratio for span names without a dedicated strategy.
+//!
+//! See the [Jaeger sampling documentation] for details.
+//!
+//! [Jaeger sampling documentation]: https://www.jaegertracing.io/docs/latest/sampling/
+use crate::exporter::Error;
+use futures_util::StreamExt;
+use http::{Request, Uri};
+use opentelemetry::{
+    global,
+    runtime::Runtime,
+    sdk::trace::{RateLimitingSampler, Sampler, SamplingResult, ShouldSample},
+    trace::{Link, SpanKind, TraceError, TraceId},
+    Context, KeyValue,
+};
+use opentelemetry_http::HttpClient;
+use percent_encoding::{utf8_percent_encode, AsciiSet, NON_ALPHANUMERIC};
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::fmt;
+use std::sync::{Arc, RwLock};
+use std::time::Duration;
+
+/// Default sampling endpoint of a Jaeger agent.
+const DEFAULT_SAMPLING_ENDPOINT: &str = "http://localhost:5778/sampling";
+/// Default interval between two consecutive strategy fetches.
+const DEFAULT_UPDATE_INTERVAL: Duration = Duration::from_secs(60);
+/// Default ratio of sampled traces until the first strategy was fetched.
+const DEFAULT_FALLBACK_SAMPLING_RATE: f64 = 0.001;
+/// Characters of the service name escaped in the query string, all but the
+/// unreserved characters of RFC 3986.
+const QUERY_VALUE: &AsciiSet = &NON_ALPHANUMERIC
+    .remove(b'-')
+    .remove(b'.')
+    .remove(b'_')
+    .remove(b'~');
+
+/// A [`ShouldSample`] implementation that follows the sampling strategy
+/// configured for a service in Jaeger.
+///
+/// Spans are sampled by the fallback sampler until the first strategy has
+/// been fetched, and whenever the endpoint returns a strategy that cannot be
+/// understood.
+///
+/// # Examples
+///
+/// ```ignore
+/// // Note that this requires the `remote_sampler` feature and an http client
+/// // implementation, here reqwest's async client is used.
+/// use opentelemetry::{runtime, sdk::trace::{config, Sampler}};
+/// use opentelemetry_jaeger::JaegerRemoteSampler;
+/// use std::time::Duration;
+///
+/// let sampler = JaegerRemoteSampler::builder(runtime::Tokio, reqwest::Client::new(), "my-service")
+///     .with_endpoint("http://jaeger-agent:5778/sampling")
+///     .with_update_interval(Duration::from_secs(30))
+///     .with_fallback_sampler(Sampler::AlwaysOn)
+///     .build()
+///     .expect("valid sampling endpoint");
+///
+/// let config = config().with_sampler(sampler);
+/// ```
+#[derive(Debug)]
+pub struct JaegerRemoteSampler {
+    inner: Arc<Inner>,
+}
+
+impl JaegerRemoteSampler {
+    /// Create a new builder fetching the strategy of `service_name` with the
+    /// given HTTP client and polling on the given runtime.
+    pub fn builder<R, C, S>(
+        runtime: R,
+        http_client: C,
+        service_name: S,
+    ) -> JaegerRemoteSamplerBuilder<R>
+    where
+        R: Runtime,
+        C: HttpClient + 'static,
+        S: Into<String>,
+    {
+        JaegerRemoteSamplerBuilder {
+            runtime,
+            http_client: Box::new(http_client),
+            service_name: service_name.into(),
+            endpoint: DEFAULT_SAMPLING_ENDPOINT.to_string(),
+            update_interval: DEFAULT_UPDATE_INTERVAL,
+            fallback: Box::new(Sampler::TraceIdRatioBased(DEFAULT_FALLBACK_SAMPLING_RATE)),
+        }
+    }
+}
+
+impl ShouldSample for JaegerRemoteSampler {
+    fn should_sample(
+        &self,
+        parent_context: Option<&Context>,
+        trace_id: TraceId,
+        name: &str,
+        span_kind: &SpanKind,
+        attributes: &[KeyValue],
+        links: &[Link],
+    ) -> SamplingResult {
+        self.inner
+            .should_sample(parent_context, trace_id, name, span_kind, attributes, links)
+    }
+
+    fn description(&self) -> String {
+        match self.inner.strategy.read() {
+            Ok(strategy) => match strategy.as_ref() {
+                Some((_, strategy)) => format!("JaegerRemoteSampler{{{}}}", strategy.description()),
+                None => format!(
+                    "JaegerRemoteSampler{{fallback={}}}",
+                    self.inner.fallback.description()
+                ),
+            },
+            Err(_) => "JaegerRemoteSampler".to_string(),
+        }
+    }
+}
+
+/// A builder for creating [`JaegerRemoteSampler`] instances.
+pub struct JaegerRemoteSamplerBuilder<R> {
+    runtime: R,
+    http_client: Box<dyn HttpClient>,
+    service_name: String,
+    endpoint: String,
+    update_interval: Duration,
+    fallback: Box<dyn ShouldSample>,
+}
+
+impl<R> fmt::Debug for JaegerRemoteSamplerBuilder<R> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("JaegerRemoteSamplerBuilder")
+            .field("http_client", &self.http_client)
+            .field("service_name", &self.service_name)
+            .field("endpoint", &self.endpoint)
+            .field("update_interval", &self.update_interval)
+            .field("fallback", &self.fallback)
+            .finish()
+    }
+}
+
+impl<R: Runtime> JaegerRemoteSamplerBuilder<R> {
+    /// Set the sampling endpoint of the Jaeger agent. The service name is
+    /// appended as query parameter.
+    ///
+    /// Defaults to `http://localhost:5778/sampling`.
+    pub fn with_endpoint<T: Into<String>>(self, endpoint: T) -> Self {
+        JaegerRemoteSamplerBuilder {
+            endpoint: endpoint.into(),
+            ..self
+        }
+    }
+
+    /// Set the interval between two consecutive strategy fetches.
+    ///
+    /// Defaults to 60 seconds.
+    pub fn with_update_interval(self, update_interval: Duration) -> Self {
+        JaegerRemoteSamplerBuilder {
+            update_interval,
+            ..self
+        }
+    }
+
+    /// Set the sampler used until the first strategy has been fetched.
+    ///
+    /// Defaults to sampling 0.1% of traces.
+    pub fn with_fallback_sampler<T: ShouldSample + 'static>(self, fallback: T) -> Self {
+        JaegerRemoteSamplerBuilder {
+            fallback: Box::new(fallback),
+            ..self
+        }
+    }
+
+    /// Build the sampler and start polling the sampling endpoint in the
+    /// background. Polling stops once the sampler is dropped.
+    pub fn build(self) -> Result<JaegerRemoteSampler, TraceError> {
+        let uri = sampling_uri(&self.endpoint, &self.service_name)?;
+
+        let inner = Arc::new(Inner {
+            fallback: self.fallback,
+            strategy: RwLock::new(None),
+        });
+
+        let weak_inner = Arc::downgrade(&inner);
+        let http_client = self.http_client;
+        let mut interval = Box::pin(self.runtime.interval(self.update_interval));
+        self.runtime.spawn(Box::pin(async move {
+            loop {
+                match weak_inner.upgrade() {
+                    Some(inner) => {
+                        if let Err(err) = inner.update(http_client.as_ref(), &uri).await {
+                            global::handle_error(err);
+                        }
+                    }
+                    // the sampler has been dropped
+                    None => break,
+                }
+
+                if interval.next().await.is_none() {
+                    break;
+                }
+            }
+        }));
+
+        Ok(JaegerRemoteSampler { inner })
+    }
+}
+
+/// The uri fetching the strategy of `service_name` from `endpoint`.
+fn sampling_uri(endpoint: &str, service_name: &str) -> Result<Uri, Error> {
+    format!(
+        "{}?service={}",
+        endpoint,
+        utf8_percent_encode(service_name, QUERY_VALUE)
+    )
+    .parse()
+    .map_err(Error::InvalidSamplingEndpoint)
+}
+
+#[derive(Debug)]
+struct Inner {
+    fallback: Box<dyn ShouldSample>,
+    /// The last response received from the endpoint and the strategy built from it.
+    strategy: RwLock<Option<(StrategyResponse, Strategy)>>,
+}
+
+impl Inner {
+    fn should_sample(
+        &self,
+        parent_context: Option<&Context>,
+        trace_id: TraceId,
+        name: &str,
+        span_kind: &SpanKind,
+        attributes: &[KeyValue],
+        links: &[Link],
+    ) -> SamplingResult {
+        if let Ok(strategy) = self.strategy.read() {
+            if let Some((_, strategy)) = strategy.as_ref() {
+                return strategy.should_sample(
+                    parent_context,
+                    trace_id,
+                    name,
+                    span_kind,
+                    attributes,
+                    links,
+                );
+            }
+        }
+
+        self.fallback
+            .should_sample(parent_context, trace_id, name, span_kind, attributes, links)
+    }
+
+    /// Fetch the current strategy and replace the sampler if it changed.
+    async fn update(&self, http_client: &dyn HttpClient, uri: &Uri) -> Result<(), TraceError> {
+        let request = Request::get(uri.clone())
+            .body(Vec::new())
+            .map_err(|err| TraceError::Other(Box::new(err)))?;
+        let response = http_client.send(request).await?;
+        if !response.status().is_success() {
+            return Err(Error::SamplingEndpointFailed(response.status()).into());
+        }
+
+        let response: StrategyResponse = serde_json::from_slice(response.body())
+            .map_err(|err| TraceError::Other(Box::new(err)))?;
+        self.set_strategy(response)
+    }
+
+    fn set_strategy(&self, response: StrategyResponse) -> Result<(), TraceError> {
+        let mut current = self
+            .strategy
+            .write()
+            .map_err(|_| TraceError::from("jaeger remote sampler lock poisoned"))?;
+
+        // keep the current sampler, and with it the state of rate limiters, if
+        // nothing changed.
+        if matches!(current.as_ref(), Some((previous, _)) if *previous == response) {
+            return Ok(());
+        }
+
+        match Strategy::from_response(&response) {
+            Some(strategy) => {
+                *current = Some((response, strategy));
+                Ok(())
+            }
+            None => {
+                *current = None;
+                Err(TraceError::from(format!(
+                    "unsupported sampling strategy {:?}, using the fallback sampler",
+                    response
+                )))
+            }
+        }
+    }
+}
+
+/// Sampler built from a sampling strategy.
+#[derive(Debug)]
+enum Strategy {
+    Probabilistic(Sampler),
+    RateLimiting(RateLimitingSampler),
+    PerOperation {
+        default: Sampler,
+        operations: HashMap<String, Sampler>,
+    },
+}
+
+impl Strategy {
+    fn from_response(response: &StrategyResponse) -> Option<Self> {
+        if let Some(operation_sampling) = &response.operation_sampling {
+            let operations = operation_sampling
+                .per_operation_strategies
+                .iter()
+                .map(|strategy| {
+                    (
+                        strategy.operation.clone(),
+                        Sampler::TraceIdRatioBased(strategy.probabilistic_sampling.sampling_rate),
+                    )
+                })
+                .collect();
+
+            Some(Strategy::PerOperation {
+                default: Sampler::TraceIdRatioBased(
+                    operation_sampling.default_sampling_probability,
+                ),
+                operations,
+            })
+        } else if let Some(rate_limiting) = &response.rate_limiting_sampling {
+            Some(Strategy::RateLimiting(RateLimitingSampler::new(
+                rate_limiting.max_traces_per_second,
+            )))
+        } else {
+            response
+                .probabilistic_sampling
+                .as_ref()
+                .map(|probabilistic| {
+                    Strategy::Probabilistic(Sampler::TraceIdRatioBased(probabilistic.sampling_rate))
+                })
+        }
+    }
+
+    fn should_sample(
+        &self,
+        parent_context: Option<&Context>,
+        trace_id: TraceId,
+        name: &str,
+        span_kind: &SpanKind,
+        attributes: &[KeyValue],
+        links: &[Link],
+    ) -> SamplingResult {
+        let sampler: &dyn ShouldSample = match self {
+            Strategy::Probabilistic(sampler) => sampler,
+            Strategy::RateLimiting(sampler) => sampler,
+            Strategy::PerOperation {
+                default,
+                operations,
+            } => operations.get(name).unwrap_or(default),
+        };
+
+        sampler.should_sample(parent_context, trace_id, name, span_kind, attributes, links)
+    }
+
+    fn description(&self) -> String {
+        match self {
+            Strategy::Probabilistic(sampler) => sampler.description(),
+            Strategy::RateLimiting(sampler) => sampler.description(),
+            Strategy::PerOperation {
+                default,
+                operations,
+            } => format!(
+                "PerOperation{{default={},operations={}}}",
+                default.description(),
+                operations.len()
+            ),
+        }
+    }
+}
+
+/// Sampling strategy response of the Jaeger agent.
+#[derive(Debug, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+struct StrategyResponse {
+    probabilistic_sampling: Option<ProbabilisticStrategy>,
+    rate_limiting_sampling: Option<RateLimitingStrategy>,
+    operation_sampling: Option<PerOperationStrategies>,
+}
+
+#[derive(Debug, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+struct ProbabilisticStrategy {
+    sampling_rate: f64,
+}
+
+#[derive(Debug, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+struct RateLimitingStrategy {
+    max_traces_per_second: f64,
+}
+
+#[derive(Debug, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+struct PerOperationStrategies {
+    default_sampling_probability: f64,
+    #[serde(default)]
+    per_operation_strategies: Vec<OperationStrategy>,
+}
+
+#[derive(Debug, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+struct OperationStrategy {
+    operation: String,
+    probabilistic_sampling: ProbabilisticStrategy,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use async_trait::async_trait;
+    use bytes::Bytes;
+    use http::Response;
+    use opentelemetry::sdk::trace::SamplingDecision;
+    use opentelemetry_http::HttpError;
+
+    #[derive(Debug)]
+    struct MockHttpClient {
+        status: u16,
+        body: &'static str,
+    }
+
+    #[async_trait]
+    impl HttpClient for MockHttpClient {
+        async fn send(&self, request: Request<Vec<u8>>) -> Result<Response<Bytes>, HttpError> {
+            assert_eq!(request.uri().query(), Some("service=test-service"));
+            Ok(Response::builder()
+                .status(self.status)
+                .body(Bytes::from_static(self.body.as_bytes()))?)
+        }
+    }
+
+    fn new_inner() -> Inner {
+        Inner {
+            fallback: Box::new(Sampler::AlwaysOn),
+            strategy: RwLock::new(None),
+        }
+    }
+
+    fn decision(inner: &Inner, name: &str) -> SamplingDecision {
+        inner
+            .should_sample(
+                None,
+                TraceId::from_u128(u128::MAX),
+                name,
+                &SpanKind::Internal,
+                &[],
+                &[],
+            )
+            .decision
+    }
+
+    fn update(inner: &Inner, status: u16, body: &'static str) -> Result<(), TraceError> {
+        let uri = "http://localhost:5778/sampling?service=test-service"
+            .parse()
+            .unwrap();
+        futures::executor::block_on(inner.update(&MockHttpClient { status, body }, &uri))
+    }
+
+    #[test]
+    fn sampling_uri_encodes_service_name() {
+        let uri = sampling_uri("http://localhost:5778/sampling", "my service/ä&x=1").unwrap();
+        assert_eq!(uri.query(), Some("service=my%20service%2F%C3%A4%26x%3D1"));
+        assert_eq!(
+            sampling_uri("http://localhost:5778/sampling", "test-service.v1_~")
+                .unwrap()
+                .query(),
+            Some("service=test-service.v1_~")
+        );
+
+        assert!(matches!(
+            sampling_uri("http://local host:5778/sampling", "test-service"),
+            Err(Error::InvalidSamplingEndpoint(_))
+        ));
+    }
+
+    #[test]
+    fn uses_fallback_before_first_fetch() {
+        let inner = new_inner();
+        assert_eq!(decision(&inner, "op"), SamplingDecision::RecordAndSample);
+    }
+
+    #[test]
+    fn probabilistic_strategy() {
+        let inner = new_inner();
+        update(
+            &inner,
+            200,
+            r#"{"strategyType":"PROBABILISTIC","probabilisticSampling":{"samplingRate":0.0}}"#,
+        )
+        .unwrap();
+        assert_eq!(decision(&inner, "op"), SamplingDecision::Drop);
+    }
+
+    #[test]
+    fn rate_limiting_strategy() {
+        let inner = new_inner();
+        update(
+            &inner,
+            200,
+            r#"{"strategyType":"RATE_LIMITING","rateLimitingSampling":{"maxTracesPerSecond":1}}"#,
+        )
+        .unwrap();
+        assert_eq!(decision(&inner, "op"), SamplingDecision::RecordAndSample);
+        assert_eq!(decision(&inner, "op"), SamplingDecision::Drop);
+
+        // fetching the same strategy again keeps the rate limiter state
+        update(
+            &inner,
+            200,
+            r#"{"strategyType":"RATE_LIMITING","rateLimitingSampling":{"maxTracesPerSecond":1}}"#,
+        )
+        .unwrap();
+        assert_eq!(decision(&inner, "op"), SamplingDecision::Drop);
+    }
+
+    #[test]
+    fn per_operation_strategy() {
+        let inner = new_inner();
+        update(
+            &inner,
+            200,
+            r#"{
+                "strategyType": "PROBABILISTIC",
+                "probabilisticSampling": {"samplingRate": 1.0},
+                "operationSampling": {
+                    "defaultSamplingProbability": 0.0,
+                    "defaultLowerBoundTracesPerSecond": 0.0,
+                    "perOperationStrategies": [
+                        {"operation": "sampled", "probabilisticSampling": {"samplingRate": 1.0}}
+                    ]
+                }
+            }"#,
+        )
+        .unwrap();
+        assert_eq!(
+            decision(&inner, "sampled"),
+            SamplingDecision::RecordAndSample
+        );
+        assert_eq!(decision(&inner, "other"), SamplingDecision::Drop);
+    }
+
+    #[test]
+    fn failed_fetch_keeps_current_strategy() {
+        let inner = new_inner();
+        update(
+            &inner,
+            200,
+            r#"{"probabilisticSampling":{"samplingRate":0.0}}"#,
+        )
+        .unwrap();
+
+        assert!(update(&inner, 500, "").is_err());
+        assert!(update(&inner, 200, "not json").is_err());
+        assert_eq!(decision(&inner, "op"), SamplingDecision::Drop);
+
+        // an empty strategy falls back to the fallback sampler
+        assert!(update(&inner, 200, "{}").is_err());
+        assert_eq!(decision(&inner, "op"), SamplingDecision::RecordAndSample);
+    }
+}
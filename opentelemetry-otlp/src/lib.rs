@@ -6,8 +6,8 @@
 //! order to support open-source telemetry data formats (e.g. Jaeger,
 //! Prometheus, etc.) sending to multiple open-source or commercial back-ends.
 //!
-//! Currently, this crate only support sending tracing data or metrics in OTLP
-//! via grpc. Support for sending data via HTTP will be added in the future.
+//! Tracing data and metrics are sent in OTLP via grpc, or via http in the
+//! binary protobuf format with the `http-proto` feature.
 //!
 //! ## Quickstart
 //!
@@ -78,7 +78,7 @@
 //!
 //! ```no_run
 //! use opentelemetry::{KeyValue, trace::Tracer};
-//! use opentelemetry::sdk::{trace::{self, IdGenerator, Sampler}, Resource};
+//! use opentelemetry::sdk::{trace::{self, RandomIdGenerator, Sampler}, Resource};
 //! use opentelemetry_otlp::{Protocol};
 //! use std::time::Duration;
 //! use tonic::metadata::*;
@@ -97,7 +97,7 @@
 //!         .with_trace_config(
 //!             trace::config()
 //!                 .with_sampler(Sampler::AlwaysOn)
-//!                 .with_id_generator(IdGenerator::default())
+//!                 .with_id_generator(RandomIdGenerator::default())
 //!                 .with_max_events_per_span(64)
 //!                 .with_max_attributes_per_span(16)
 //!                 .with_max_events_per_span(16)
@@ -148,7 +148,6 @@
 
 use opentelemetry::{global, runtime::Runtime, sdk, trace::TracerProvider};
 
-#[cfg(any(feature = "grpc-sys", feature = "http-proto"))]
 use std::collections::HashMap;
 
 use std::str::FromStr;
@@ -174,7 +173,7 @@ mod span;
 mod transform;
 
 #[cfg(feature = "tonic")]
-use tonic::metadata::MetadataMap;
+use tonic::metadata::{MetadataKey, MetadataMap, MetadataValue};
 
 #[cfg(all(feature = "tonic", feature = "tls"))]
 use tonic::transport::ClientTlsConfig;
@@ -191,10 +190,14 @@ pub use crate::span::GrpcioConfig;
 pub use crate::span::HttpConfig;
 
 #[cfg(feature = "metrics")]
-pub use crate::metric::{new_metrics_pipeline, MetricsExporter, OtlpMetricPipelineBuilder};
+pub use crate::metric::{
+    new_metrics_pipeline, MetricsExporter, OtlpMetricPipelineBuilder, TemporalityPreference,
+};
+
+pub use crate::span::Compression;
 
 #[cfg(feature = "grpc-sys")]
-pub use crate::span::{Compression, Credentials};
+pub use crate::span::Credentials;
 
 #[cfg(feature = "http-proto")]
 use opentelemetry_http::HttpClient;
@@ -213,8 +216,28 @@ const OTEL_EXPORTER_OTLP_TIMEOUT_DEFAULT: u64 = 10;
 
 /// Target to which the exporter is going to send spans, defaults to https://localhost:4317.
 const OTEL_EXPORTER_OTLP_TRACES_ENDPOINT: &str = "OTEL_EXPORTER_OTLP_TRACES_ENDPOINT";
+/// Path appended to `OTEL_EXPORTER_OTLP_ENDPOINT` when spans are sent over HTTP.
+#[cfg(feature = "http-proto")]
+const OTEL_EXPORTER_OTLP_HTTP_TRACES_PATH: &str = "/v1/traces";
 /// Max waiting time for the backend to process each spans batch, defaults to 10s.
 const OTEL_EXPORTER_OTLP_TRACES_TIMEOUT: &str = "OTEL_EXPORTER_OTLP_TRACES_TIMEOUT";
+/// Compression applied to spans or metrics sent to the backend, `gzip` or `none`.
+const OTEL_EXPORTER_OTLP_COMPRESSION: &str = "OTEL_EXPORTER_OTLP_COMPRESSION";
+/// Compression applied to spans sent to the backend, `gzip` or `none`.
+const OTEL_EXPORTER_OTLP_TRACES_COMPRESSION: &str = "OTEL_EXPORTER_OTLP_TRACES_COMPRESSION";
+/// Headers sent with each export of spans or metrics, as comma separated `key=value` pairs.
+const OTEL_EXPORTER_OTLP_HEADERS: &str = "OTEL_EXPORTER_OTLP_HEADERS";
+/// Headers sent with each export of spans, as comma separated `key=value` pairs.
+const OTEL_EXPORTER_OTLP_TRACES_HEADERS: &str = "OTEL_EXPORTER_OTLP_TRACES_HEADERS";
+/// Path to the PEM encoded CA certificate used to verify the backend of spans or metrics.
+const OTEL_EXPORTER_OTLP_CERTIFICATE: &str = "OTEL_EXPORTER_OTLP_CERTIFICATE";
+/// Path to the PEM encoded CA certificate used to verify the backend of spans.
+const OTEL_EXPORTER_OTLP_TRACES_CERTIFICATE: &str = "OTEL_EXPORTER_OTLP_TRACES_CERTIFICATE";
+/// Temporality of the metrics sent to the backend, `cumulative` or `delta`, defaults to
+/// cumulative.
+#[cfg(feature = "metrics")]
+const OTEL_EXPORTER_OTLP_METRICS_TEMPORALITY_PREFERENCE: &str =
+    "OTEL_EXPORTER_OTLP_METRICS_TEMPORALITY_PREFERENCE";
 
 /// Create a new pipeline builder with the recommended configuration.
 ///
@@ -246,12 +269,31 @@ pub fn new_pipeline() -> OtlpPipelineBuilder {
 pub struct OtlpPipelineBuilder {
     exporter_config: ExporterConfig,
     trace_config: Option<sdk::trace::Config>,
+    headers: Option<HashMap<String, String>>,
+    certificate: Option<String>,
+    endpoint_set: bool,
+    env_endpoint: Option<EnvEndpoint>,
+}
+
+/// An endpoint read from the environment.
+#[derive(Debug)]
+enum EnvEndpoint {
+    /// From `OTEL_EXPORTER_OTLP_TRACES_ENDPOINT`, used as is.
+    Traces(String),
+    /// From `OTEL_EXPORTER_OTLP_ENDPOINT`, the base url of all signals.
+    General(String),
 }
 
 impl OtlpPipelineBuilder {
     /// Set the address of the OTLP collector. If not set, the default address is used.
+    ///
+    /// The endpoint set here wins over the one from the environment, see
+    /// [`with_env`].
+    ///
+    /// [`with_env`]: crate::OtlpPipelineBuilder::with_env
     pub fn with_endpoint<T: Into<String>>(mut self, endpoint: T) -> Self {
         self.exporter_config.endpoint = endpoint.into();
+        self.endpoint_set = true;
         self
     }
 
@@ -267,6 +309,30 @@ impl OtlpPipelineBuilder {
         self
     }
 
+    /// Set the compression algorithm to use when sending data to the collector.
+    ///
+    /// Data is sent uncompressed by default. Compression is supported by the
+    /// grpcio and http transports, building a tonic exporter with compression
+    /// fails with [`Error::UnsupportedCompression`].
+    pub fn with_compression(mut self, compression: Compression) -> Self {
+        self.exporter_config.compression = Some(compression);
+        self
+    }
+
+    /// Set the maximum size in bytes of the encoded spans of a single export
+    /// request.
+    ///
+    /// Batches encoding to more bytes are split into multiple requests, spans
+    /// exceeding the limit on their own are dropped and reported through the
+    /// global error handler. There is no limit by default.
+    ///
+    /// When only some requests of a batch fail, the batch is reported as failed
+    /// without being retried, so the spans already sent are not duplicated.
+    pub fn with_max_payload_size(mut self, max_payload_size: usize) -> Self {
+        self.exporter_config.max_payload_size = Some(max_payload_size);
+        self
+    }
+
     /// Set the trace provider configuration.
     pub fn with_trace_config(mut self, trace_config: sdk::trace::Config) -> Self {
         self.trace_config = Some(trace_config);
@@ -276,13 +342,31 @@ impl OtlpPipelineBuilder {
     /// Set the trace provider configuration from the given environment variables.
     ///
     /// If the value in environment variables is illegal, will fall back to use default value.
+    ///
+    /// The endpoint is read from `OTEL_EXPORTER_OTLP_TRACES_ENDPOINT`, which is
+    /// used as is, or from `OTEL_EXPORTER_OTLP_ENDPOINT`, to which the http
+    /// transport appends the `/v1/traces` path. An endpoint set with
+    /// [`with_endpoint`] is kept.
+    ///
+    /// [`with_endpoint`]: crate::OtlpPipelineBuilder::with_endpoint
+    ///
+    /// The headers of `OTEL_EXPORTER_OTLP_HEADERS` and
+    /// `OTEL_EXPORTER_OTLP_TRACES_HEADERS` are sent with every export, the
+    /// traces specific ones win when both set the same header.
+    ///
+    /// The CA certificate at the path in `OTEL_EXPORTER_OTLP_TRACES_CERTIFICATE`
+    /// or `OTEL_EXPORTER_OTLP_CERTIFICATE` is used to verify the collector when
+    /// tonic is used with the `tls` feature.
     pub fn with_env(mut self) -> Self {
-        let endpoint = match std::env::var(OTEL_EXPORTER_OTLP_TRACES_ENDPOINT) {
-            Ok(val) => val,
+        let env_endpoint = match std::env::var(OTEL_EXPORTER_OTLP_TRACES_ENDPOINT) {
+            Ok(val) => Some(EnvEndpoint::Traces(val)),
             Err(_) => std::env::var(OTEL_EXPORTER_OTLP_ENDPOINT)
-                .unwrap_or_else(|_| OTEL_EXPORTER_OTLP_ENDPOINT_DEFAULT.to_string()),
+                .ok()
+                .map(EnvEndpoint::General),
         };
-        self.exporter_config.endpoint = endpoint;
+        if env_endpoint.is_some() {
+            self.env_endpoint = env_endpoint;
+        }
 
         let timeout = match std::env::var(OTEL_EXPORTER_OTLP_TRACES_TIMEOUT) {
             Ok(val) => u64::from_str(&val).unwrap_or(OTEL_EXPORTER_OTLP_TIMEOUT_DEFAULT),
@@ -291,38 +375,107 @@ impl OtlpPipelineBuilder {
                 .unwrap_or(OTEL_EXPORTER_OTLP_TIMEOUT_DEFAULT),
         };
         self.exporter_config.timeout = Duration::from_secs(timeout);
+
+        let compression = std::env::var(OTEL_EXPORTER_OTLP_TRACES_COMPRESSION)
+            .or_else(|_| std::env::var(OTEL_EXPORTER_OTLP_COMPRESSION));
+        match compression.as_ref().map(|val| val.trim()) {
+            Ok("gzip") => self.exporter_config.compression = Some(Compression::Gzip),
+            Ok("none") => self.exporter_config.compression = None,
+            _ => {}
+        }
+
+        let mut headers = HashMap::new();
+        for name in &[
+            OTEL_EXPORTER_OTLP_HEADERS,
+            OTEL_EXPORTER_OTLP_TRACES_HEADERS,
+        ] {
+            if let Ok(val) = std::env::var(name) {
+                headers.extend(parse_headers(&val));
+            }
+        }
+        if !headers.is_empty() {
+            self.headers
+                .get_or_insert_with(HashMap::new)
+                .extend(headers);
+        }
+
+        if let Ok(certificate) = std::env::var(OTEL_EXPORTER_OTLP_TRACES_CERTIFICATE)
+            .or_else(|_| std::env::var(OTEL_EXPORTER_OTLP_CERTIFICATE))
+        {
+            self.certificate = Some(certificate);
+        }
         self
     }
 
+    /// Use the endpoint from the environment unless one was set explicitly.
+    ///
+    /// `signal_path` is appended to the general endpoint, as transports like
+    /// http use a different path for every signal.
+    fn resolve_endpoint(&mut self, signal_path: Option<&str>) {
+        let env_endpoint = match self.env_endpoint.take() {
+            Some(env_endpoint) if !self.endpoint_set => env_endpoint,
+            _ => return,
+        };
+
+        self.exporter_config.endpoint = match (env_endpoint, signal_path) {
+            (EnvEndpoint::Traces(endpoint), _) => endpoint,
+            (EnvEndpoint::General(endpoint), Some(path)) => {
+                format!("{}{}", endpoint.trim_end_matches('/'), path)
+            }
+            (EnvEndpoint::General(endpoint), None) => endpoint,
+        };
+    }
+
     /// Use tonic as grpc layer, return a `TonicPipelineBuilder` to config tonic and build the exporter.
     #[cfg(feature = "tonic")]
-    pub fn with_tonic(self) -> TonicPipelineBuilder {
-        TonicPipelineBuilder {
+    pub fn with_tonic(mut self) -> TonicPipelineBuilder {
+        self.resolve_endpoint(None);
+        let mut builder = TonicPipelineBuilder {
             exporter_config: self.exporter_config,
             trace_config: self.trace_config,
             tonic_config: TonicConfig::default(),
             channel: None,
+        };
+        #[cfg(feature = "tls")]
+        if let Some(tls_config) = self
+            .certificate
+            .as_deref()
+            .and_then(ca_certificate_tls_config)
+        {
+            builder = builder.with_tls_config(tls_config);
         }
+        if let Some(headers) = self.headers {
+            builder = builder.with_headers(headers);
+        }
+        builder
     }
 
     /// Use grpcio as grpc layer, return a `GrpcioPipelineBuilder` to config the grpcio and build the exporter.
     #[cfg(feature = "grpc-sys")]
-    pub fn with_grpcio(self) -> GrpcioPipelineBuilder {
+    pub fn with_grpcio(mut self) -> GrpcioPipelineBuilder {
+        self.resolve_endpoint(None);
         GrpcioPipelineBuilder {
             exporter_config: self.exporter_config,
             trace_config: self.trace_config,
-            grpcio_config: GrpcioConfig::default(),
+            grpcio_config: GrpcioConfig {
+                headers: self.headers,
+                ..GrpcioConfig::default()
+            },
         }
     }
 
     /// Use HTTP as transport layer, return a `HttpPipelineBuilder` to config the http transport
     /// and build the exporter
     #[cfg(feature = "http-proto")]
-    pub fn with_http(self) -> HttpPipelineBuilder {
+    pub fn with_http(mut self) -> HttpPipelineBuilder {
+        self.resolve_endpoint(Some(OTEL_EXPORTER_OTLP_HTTP_TRACES_PATH));
         HttpPipelineBuilder {
             exporter_config: self.exporter_config,
             trace_config: self.trace_config,
-            http_config: HttpConfig::default(),
+            http_config: HttpConfig {
+                headers: self.headers,
+                ..HttpConfig::default()
+            },
         }
     }
 }
@@ -351,6 +504,37 @@ pub struct TonicPipelineBuilder {
 #[cfg(feature = "tonic")]
 impl TonicPipelineBuilder {
     /// Set the TLS settings for the collector endpoint.
+    ///
+    /// The config holds the CA certificate used to verify the collector, the
+    /// client identity for mutual TLS and the domain name to verify if it is
+    /// not the one of the endpoint:
+    ///
+    /// ```no_run
+    /// # #[cfg(feature = "tls")]
+    /// # fn main() -> Result<(), Box<dyn std::error::Error + Send + Sync + 'static>> {
+    /// use tonic::transport::{Certificate, ClientTlsConfig, Identity};
+    ///
+    /// let tls_config = ClientTlsConfig::new()
+    ///     .ca_certificate(Certificate::from_pem(std::fs::read("ca.pem")?))
+    ///     .identity(Identity::from_pem(
+    ///         std::fs::read("client.pem")?,
+    ///         std::fs::read("client.key")?,
+    ///     ))
+    ///     .domain_name("collector.example.com");
+    ///
+    /// let tracer = opentelemetry_otlp::new_pipeline()
+    ///     .with_endpoint("https://collector:4317")
+    ///     .with_tonic()
+    ///     .with_tls_config(tls_config)
+    ///     .install_simple()?;
+    /// # Ok(())
+    /// # }
+    /// # #[cfg(not(feature = "tls"))]
+    /// # fn main() {}
+    /// ```
+    ///
+    /// Without an explicit config, `https` endpoints are verified against the
+    /// system roots when the `tls-roots` feature is enabled.
     #[cfg(feature = "tls")]
     pub fn with_tls_config(mut self, tls_config: ClientTlsConfig) -> Self {
         self.tonic_config.tls_config = Some(tls_config);
@@ -358,11 +542,34 @@ impl TonicPipelineBuilder {
     }
 
     /// Set custom metadata entries to send to the collector.
+    ///
+    /// The metadata is attached to every export request and replaces the
+    /// headers set from the environment or with [`with_headers`].
+    ///
+    /// [`with_headers`]: crate::TonicPipelineBuilder::with_headers
     pub fn with_metadata(mut self, metadata: MetadataMap) -> Self {
         self.tonic_config.metadata = Some(metadata);
         self
     }
 
+    /// Set additional headers to send to the collector with every export
+    /// request.
+    ///
+    /// Headers that are not valid metadata entries are reported through the
+    /// global error handler and skipped.
+    pub fn with_headers(mut self, headers: HashMap<String, String>) -> Self {
+        let metadata = self
+            .tonic_config
+            .metadata
+            .get_or_insert_with(MetadataMap::new);
+        for key_and_value in headers_to_metadata(&headers).iter() {
+            if let tonic::metadata::KeyAndValueRef::Ascii(key, value) = key_and_value {
+                metadata.insert(key.clone(), value.clone());
+            }
+        }
+        self
+    }
+
     /// Use `channel` as tonic's transport channel.
     /// this will override tls config and should only be used
     /// when working with non-HTTP transports.
@@ -382,6 +589,11 @@ impl TonicPipelineBuilder {
     ///
     /// `install_simple` will panic if not called within a tokio runtime
     ///
+    /// The simple span processor blocks the thread ending a span until it is
+    /// exported. On a current thread tokio runtime this stalls the runtime
+    /// driving the tonic client, so every export times out and the spans are
+    /// dropped. Use `install_batch` with such a runtime.
+    ///
     /// [`Tracer`]: opentelemetry::trace::Tracer
     /// [tonic]: https://github.com/hyperium/tonic
     pub fn install_simple(self) -> Result<sdk::trace::Tracer, TraceError> {
@@ -520,6 +732,9 @@ pub struct HttpPipelineBuilder {
 #[cfg(feature = "http-proto")]
 impl HttpPipelineBuilder {
     /// Assign client implementation
+    ///
+    /// TLS settings like custom CA certificates or client identities are
+    /// configured on the client itself, e.g. with reqwest's `ClientBuilder`.
     pub fn with_http_client<T: HttpClient + 'static>(mut self, client: T) -> Self {
         self.http_config.client = Some(Box::new(client));
         self
@@ -537,6 +752,11 @@ impl HttpPipelineBuilder {
     ///
     /// `install_simple` will panic if not called within a tokio runtime
     ///
+    /// The simple span processor blocks the thread ending a span until it is
+    /// exported. On a current thread tokio runtime this stalls the runtime
+    /// driving async http clients like reqwest, so every export times out and
+    /// the spans are dropped. Use `install_batch` with such a runtime.
+    ///
     /// [`Tracer`]: opentelemetry::trace::Tracer
     pub fn install_simple(self) -> Result<sdk::trace::Tracer, TraceError> {
         let exporter = TraceExporter::new_http(self.exporter_config, self.http_config)?;
@@ -561,6 +781,71 @@ impl HttpPipelineBuilder {
     }
 }
 
+/// Parse comma separated `key=value` pairs, skipping and reporting malformed
+/// entries.
+fn parse_headers(value: &str) -> HashMap<String, String> {
+    value
+        .split(',')
+        .filter(|pair| !pair.trim().is_empty())
+        .filter_map(|pair| {
+            let mut parts = pair.splitn(2, '=');
+            match (parts.next().map(str::trim), parts.next().map(str::trim)) {
+                (Some(key), Some(value)) if !key.is_empty() => {
+                    Some((key.to_string(), value.to_string()))
+                }
+                _ => {
+                    global::handle_error(TraceError::from(format!(
+                        "invalid OTLP header {:?}, expected key=value",
+                        pair
+                    )));
+                    None
+                }
+            }
+        })
+        .collect()
+}
+
+/// Build a TLS config trusting the PEM encoded CA certificate at `path`.
+///
+/// Returns `None` if the file cannot be read, the error is reported through
+/// the global error handler.
+#[cfg(feature = "tls")]
+fn ca_certificate_tls_config(path: &str) -> Option<ClientTlsConfig> {
+    match std::fs::read(path) {
+        Ok(pem) => Some(
+            ClientTlsConfig::new().ca_certificate(tonic::transport::Certificate::from_pem(pem)),
+        ),
+        Err(err) => {
+            global::handle_error(TraceError::from(format!(
+                "failed to read the OTLP CA certificate {}: {}",
+                path, err
+            )));
+            None
+        }
+    }
+}
+
+/// Convert headers to tonic metadata, skipping and reporting invalid entries.
+#[cfg(feature = "tonic")]
+fn headers_to_metadata(headers: &HashMap<String, String>) -> MetadataMap {
+    let mut metadata = MetadataMap::with_capacity(headers.len());
+    for (key, value) in headers {
+        match (
+            MetadataKey::from_bytes(key.as_bytes()),
+            MetadataValue::from_str(value),
+        ) {
+            (Ok(key), Ok(value)) => {
+                metadata.insert(key, value);
+            }
+            _ => global::handle_error(TraceError::from(format!(
+                "invalid OTLP header {:?}, skipping it",
+                key
+            ))),
+        }
+    }
+    metadata
+}
+
 fn build_simple_with_exporter(
     exporter: TraceExporter,
     trace_config: Option<sdk::trace::Config>,
@@ -609,6 +894,12 @@ pub enum Error {
     #[error("status error {0}")]
     Status(#[from] tonic::Status),
 
+    /// Compression was configured for the tonic transport, which does not
+    /// support it.
+    #[cfg(feature = "tonic")]
+    #[error("compression is not supported by the tonic transport")]
+    UnsupportedCompression,
+
     /// Error from grpcio module
     #[cfg(feature = "grpc-sys")]
     #[error("grpcio error {0}")]
@@ -624,6 +915,16 @@ pub enum Error {
     #[error("http request failed with {0}")]
     RequestFailed(#[from] http::Error),
 
+    /// The http client failed to send the request
+    #[cfg(feature = "http-proto")]
+    #[error("http client failed with {0}")]
+    HttpClientFailed(opentelemetry_http::HttpError),
+
+    /// The collector responded with a non-success status code
+    #[cfg(feature = "http-proto")]
+    #[error("http request failed with status {0}")]
+    UnsuccessfulResponse(http::StatusCode),
+
     /// Invalid Header Value
     #[cfg(feature = "http-proto")]
     #[error("http header value error {0}")]
@@ -639,6 +940,30 @@ pub enum Error {
     #[error("prost encoding error {0}")]
     EncodeError(#[from] prost::EncodeError),
 
+    /// Compressing the request body failed
+    #[cfg(feature = "http-proto")]
+    #[error("compression error {0}")]
+    CompressionFailed(#[from] std::io::Error),
+
+    /// Some of the requests a batch was split into failed, the spans of the
+    /// requests that were sent are not retried to avoid duplicating them.
+    #[error("{failed_requests} of {total_requests} export requests failed, dropping {failed_spans} spans: {source}")]
+    PartialExport {
+        /// The number of failed requests
+        failed_requests: usize,
+        /// The number of requests the batch was split into
+        total_requests: usize,
+        /// The number of spans in the failed requests
+        failed_spans: usize,
+        /// The error of the first failed request
+        source: Box<Error>,
+    },
+
+    /// The export did not complete before its timeout, the requests left are
+    /// not sent.
+    #[error("export timed out after {0:?}")]
+    ExportTimedOut(Duration),
+
     /// The lock in exporters has been poisoned.
     #[cfg(feature = "metrics")]
     #[error("the lock of the {0} has been poisoned")]
@@ -649,6 +974,57 @@ impl ExportError for Error {
     fn exporter_name(&self) -> &'static str {
         "otlp"
     }
+
+    fn is_retryable(&self) -> bool {
+        match self {
+            Error::ExportTimedOut(_) => true,
+            #[cfg(feature = "tonic")]
+            Error::Transport(_) => true,
+            #[cfg(feature = "tonic")]
+            Error::Status(status) => matches!(
+                status.code(),
+                tonic::Code::Unavailable
+                    | tonic::Code::DeadlineExceeded
+                    | tonic::Code::ResourceExhausted
+                    | tonic::Code::Aborted
+            ),
+            #[cfg(feature = "grpc-sys")]
+            Error::Grpcio(grpcio::Error::RpcFailure(status)) => matches!(
+                status.status,
+                grpcio::RpcStatusCode::UNAVAILABLE
+                    | grpcio::RpcStatusCode::DEADLINE_EXCEEDED
+                    | grpcio::RpcStatusCode::RESOURCE_EXHAUSTED
+                    | grpcio::RpcStatusCode::ABORTED
+            ),
+            #[cfg(feature = "http-proto")]
+            Error::HttpClientFailed(_) => true,
+            #[cfg(feature = "http-proto")]
+            Error::UnsuccessfulResponse(status) => matches!(
+                *status,
+                http::StatusCode::TOO_MANY_REQUESTS
+                    | http::StatusCode::BAD_GATEWAY
+                    | http::StatusCode::SERVICE_UNAVAILABLE
+                    | http::StatusCode::GATEWAY_TIMEOUT
+            ),
+            _ => false,
+        }
+    }
+}
+
+impl Error {
+    /// Whether the error is caused by the connection to the collector rather
+    /// than by the request.
+    pub(crate) fn is_transport(&self) -> bool {
+        match self {
+            #[cfg(feature = "tonic")]
+            Error::Transport(_) => true,
+            #[cfg(feature = "grpc-sys")]
+            Error::Grpcio(err) => !matches!(err, grpcio::Error::RpcFailure(_)),
+            #[cfg(feature = "http-proto")]
+            Error::HttpClientFailed(_) => true,
+            _ => false,
+        }
+    }
 }
 
 /// The communication protocol to use when exporting data.
@@ -665,8 +1041,9 @@ pub enum Protocol {
 #[cfg(test)]
 mod tests {
     use crate::{
-        new_pipeline, OTEL_EXPORTER_OTLP_ENDPOINT, OTEL_EXPORTER_OTLP_TIMEOUT,
-        OTEL_EXPORTER_OTLP_TIMEOUT_DEFAULT, OTEL_EXPORTER_OTLP_TRACES_ENDPOINT,
+        new_pipeline, Compression, OTEL_EXPORTER_OTLP_COMPRESSION, OTEL_EXPORTER_OTLP_ENDPOINT,
+        OTEL_EXPORTER_OTLP_TIMEOUT, OTEL_EXPORTER_OTLP_TIMEOUT_DEFAULT,
+        OTEL_EXPORTER_OTLP_TRACES_COMPRESSION, OTEL_EXPORTER_OTLP_TRACES_ENDPOINT,
         OTEL_EXPORTER_OTLP_TRACES_TIMEOUT,
     };
 
@@ -719,5 +1096,241 @@ mod tests {
         std::env::remove_var(OTEL_EXPORTER_OTLP_TRACES_TIMEOUT);
         assert!(std::env::var(OTEL_EXPORTER_OTLP_TRACES_ENDPOINT).is_err());
         assert!(std::env::var(OTEL_EXPORTER_OTLP_TRACES_TIMEOUT).is_err());
+
+        // endpoint resolution, kept in this test as it shares the env vars above
+        std::env::set_var(OTEL_EXPORTER_OTLP_ENDPOINT, "http://collector:4318/");
+        let mut builder = new_pipeline().with_env();
+        builder.resolve_endpoint(None);
+        assert_eq!(builder.exporter_config.endpoint, "http://collector:4318/");
+
+        let mut builder = new_pipeline().with_env();
+        builder.resolve_endpoint(Some("/v1/traces"));
+        assert_eq!(
+            builder.exporter_config.endpoint,
+            "http://collector:4318/v1/traces"
+        );
+
+        // an explicit endpoint wins, whatever the order of the calls
+        let mut builder = new_pipeline()
+            .with_endpoint("http://explicit:4317")
+            .with_env();
+        builder.resolve_endpoint(Some("/v1/traces"));
+        assert_eq!(builder.exporter_config.endpoint, "http://explicit:4317");
+
+        // the traces endpoint is used as is
+        std::env::set_var(
+            OTEL_EXPORTER_OTLP_TRACES_ENDPOINT,
+            "http://traces-collector:4318/custom",
+        );
+        let mut builder = new_pipeline().with_env();
+        builder.resolve_endpoint(Some("/v1/traces"));
+        assert_eq!(
+            builder.exporter_config.endpoint,
+            "http://traces-collector:4318/custom"
+        );
+
+        std::env::remove_var(OTEL_EXPORTER_OTLP_ENDPOINT);
+        std::env::remove_var(OTEL_EXPORTER_OTLP_TRACES_ENDPOINT);
+    }
+
+    #[test]
+    fn test_pipeline_builder_compression_from_env() {
+        assert_eq!(new_pipeline().with_env().exporter_config.compression, None);
+
+        std::env::set_var(OTEL_EXPORTER_OTLP_COMPRESSION, "gzip");
+        assert_eq!(
+            new_pipeline().with_env().exporter_config.compression,
+            Some(Compression::Gzip)
+        );
+
+        // the traces specific variable takes precedence
+        std::env::set_var(OTEL_EXPORTER_OTLP_TRACES_COMPRESSION, "none");
+        assert_eq!(
+            new_pipeline()
+                .with_compression(Compression::Gzip)
+                .with_env()
+                .exporter_config
+                .compression,
+            None
+        );
+
+        // unknown values leave the configured compression unchanged
+        std::env::set_var(OTEL_EXPORTER_OTLP_TRACES_COMPRESSION, "zstd");
+        assert_eq!(
+            new_pipeline()
+                .with_compression(Compression::Gzip)
+                .with_env()
+                .exporter_config
+                .compression,
+            Some(Compression::Gzip)
+        );
+
+        std::env::remove_var(OTEL_EXPORTER_OTLP_COMPRESSION);
+        std::env::remove_var(OTEL_EXPORTER_OTLP_TRACES_COMPRESSION);
+    }
+
+    #[cfg(feature = "metrics")]
+    #[test]
+    fn test_temporality_preference_from_env() {
+        use crate::{TemporalityPreference, OTEL_EXPORTER_OTLP_METRICS_TEMPORALITY_PREFERENCE};
+
+        assert_eq!(
+            TemporalityPreference::from_env(),
+            TemporalityPreference::Cumulative
+        );
+
+        std::env::set_var(OTEL_EXPORTER_OTLP_METRICS_TEMPORALITY_PREFERENCE, " Delta ");
+        assert_eq!(
+            TemporalityPreference::from_env(),
+            TemporalityPreference::Delta
+        );
+
+        std::env::set_var(
+            OTEL_EXPORTER_OTLP_METRICS_TEMPORALITY_PREFERENCE,
+            "lowmemory",
+        );
+        assert_eq!(
+            TemporalityPreference::from_env(),
+            TemporalityPreference::Cumulative
+        );
+
+        std::env::remove_var(OTEL_EXPORTER_OTLP_METRICS_TEMPORALITY_PREFERENCE);
+    }
+
+    #[test]
+    fn test_parse_headers() {
+        let headers = crate::parse_headers(" authorization = Bearer abc,tenant=a=b,,broken");
+        assert_eq!(headers.len(), 2);
+        assert_eq!(
+            headers.get("authorization").map(String::as_str),
+            Some("Bearer abc")
+        );
+        assert_eq!(headers.get("tenant").map(String::as_str), Some("a=b"));
+    }
+
+    #[cfg(feature = "tonic")]
+    #[test]
+    fn test_tonic_headers_from_env() {
+        std::env::set_var(
+            crate::OTEL_EXPORTER_OTLP_HEADERS,
+            "authorization=general,tenant=acme,bad key=skipped",
+        );
+        std::env::set_var(
+            crate::OTEL_EXPORTER_OTLP_TRACES_HEADERS,
+            "authorization=traces",
+        );
+
+        let builder = new_pipeline().with_env().with_tonic();
+        let metadata = builder
+            .tonic_config
+            .metadata
+            .clone()
+            .expect("metadata from env");
+        assert_eq!(metadata.len(), 2);
+        assert_eq!(metadata.get("authorization").unwrap(), "traces");
+        assert_eq!(metadata.get("tenant").unwrap(), "acme");
+
+        let mut extra = std::collections::HashMap::new();
+        extra.insert("x-scope".to_string(), "team".to_string());
+        let metadata = builder
+            .with_headers(extra)
+            .tonic_config
+            .metadata
+            .expect("metadata");
+        assert_eq!(metadata.len(), 3);
+        assert_eq!(metadata.get("x-scope").unwrap(), "team");
+
+        std::env::remove_var(crate::OTEL_EXPORTER_OTLP_HEADERS);
+        std::env::remove_var(crate::OTEL_EXPORTER_OTLP_TRACES_HEADERS);
+    }
+
+    #[cfg(feature = "tls")]
+    #[test]
+    fn test_tonic_certificate_from_env() {
+        let path = std::env::temp_dir().join("opentelemetry-otlp-test-ca.pem");
+        std::fs::write(&path, "not checked before connecting").unwrap();
+
+        std::env::set_var(
+            crate::OTEL_EXPORTER_OTLP_TRACES_CERTIFICATE,
+            path.to_str().unwrap(),
+        );
+        let builder = new_pipeline().with_env().with_tonic();
+        assert!(builder.tonic_config.tls_config.is_some());
+
+        // unreadable certificates are reported and ignored
+        std::env::set_var(
+            crate::OTEL_EXPORTER_OTLP_TRACES_CERTIFICATE,
+            path.with_extension("missing").to_str().unwrap(),
+        );
+        let builder = new_pipeline().with_env().with_tonic();
+        assert!(builder.tonic_config.tls_config.is_none());
+
+        std::env::remove_var(crate::OTEL_EXPORTER_OTLP_TRACES_CERTIFICATE);
+        let _ = std::fs::remove_file(path);
+    }
+
+    #[test]
+    fn test_retryable_errors() {
+        use crate::Error;
+        use opentelemetry::sdk::export::ExportError;
+
+        #[cfg(feature = "tonic")]
+        {
+            assert!(Error::Status(tonic::Status::unavailable("restarting")).is_retryable());
+            assert!(!Error::Status(tonic::Status::invalid_argument("malformed")).is_retryable());
+        }
+
+        #[cfg(feature = "http-proto")]
+        {
+            assert!(
+                Error::UnsuccessfulResponse(http::StatusCode::SERVICE_UNAVAILABLE).is_retryable()
+            );
+            assert!(!Error::UnsuccessfulResponse(http::StatusCode::BAD_REQUEST).is_retryable());
+            assert!(Error::HttpClientFailed("connection refused".into()).is_retryable());
+        }
+
+        assert!(Error::ExportTimedOut(std::time::Duration::from_secs(1)).is_retryable());
+    }
+
+    #[cfg(feature = "tonic")]
+    #[tokio::test]
+    async fn test_tonic_exporter_transport_error() {
+        use crate::{ExporterConfig, TonicConfig, TraceExporter};
+        use opentelemetry::sdk::export::trace::SpanExporter;
+        use opentelemetry::trace::TraceError;
+
+        // find a local port nobody listens on
+        let port = std::net::TcpListener::bind("127.0.0.1:0")
+            .unwrap()
+            .local_addr()
+            .unwrap()
+            .port();
+        let config = ExporterConfig {
+            endpoint: format!("http://127.0.0.1:{}", port),
+            timeout: std::time::Duration::from_secs(1),
+            ..ExporterConfig::default()
+        };
+        let mut exporter = TraceExporter::new_tonic(config, TonicConfig::default()).unwrap();
+
+        match exporter.export(vec![]).await {
+            Err(TraceError::ExportFailed(err)) => assert_eq!(err.exporter_name(), "otlp"),
+            other => panic!("expected an export error, got {:?}", other),
+        }
+    }
+
+    #[cfg(feature = "tonic")]
+    #[tokio::test]
+    async fn test_tonic_exporter_unsupported_compression() {
+        use crate::{Compression, Error, ExporterConfig, TonicConfig, TraceExporter};
+
+        let config = ExporterConfig {
+            compression: Some(Compression::Gzip),
+            ..ExporterConfig::default()
+        };
+
+        assert!(matches!(
+            TraceExporter::new_tonic(config, TonicConfig::default()),
+            Err(Error::UnsupportedCompression)
+        ));
     }
 }
@@ -2,24 +2,40 @@
 //!
 //! Defines a [Exporter] to send metric data to backend via OTEL protocol.
 //!
-//! Currently, OTEL metrics exporter only support GRPC connection via tonic on tokio runtime.
+//! The metrics are sent over GRPC via tonic, or over HTTP in the binary protobuf
+//! format with the `http-proto` feature. Both run on the tokio runtime.
 
 #[cfg(feature = "tonic")]
 use crate::proto::collector::metrics::v1::{
     metrics_service_client::MetricsServiceClient, ExportMetricsServiceRequest,
 };
+#[cfg(feature = "http-proto")]
+use crate::span::http_request;
 use crate::transform::{record_to_metric, sink, CheckpointedMetrics};
+#[cfg(feature = "http-proto")]
+use crate::HttpConfig;
 use crate::{Error, ExporterConfig, TonicConfig};
-use futures::Stream;
+use futures::{future::BoxFuture, Stream};
+#[cfg(feature = "http-proto")]
+use http::Uri;
+use opentelemetry::metrics::InstrumentKind;
+#[cfg(feature = "http-proto")]
+use opentelemetry::metrics::MetricsError;
 use opentelemetry::metrics::{Descriptor, Result};
-use opentelemetry::sdk::export::metrics::{AggregatorSelector, ExportKindSelector};
+use opentelemetry::sdk::export::metrics::{
+    AggregatorSelector, CumulativeTemporality, DeltaTemporality, TemporalitySelector,
+};
 use opentelemetry::sdk::metrics::{PushController, PushControllerWorker};
 use opentelemetry::sdk::{
     export::metrics::{CheckpointSet, ExportKind, ExportKindFor, Exporter},
     metrics::selectors,
-    InstrumentationLibrary, Resource,
+    Resource,
 };
 use opentelemetry::{global, KeyValue};
+#[cfg(feature = "http-proto")]
+use opentelemetry_http::HttpClient;
+#[cfg(feature = "http-proto")]
+use prost::Message;
 use std::fmt::{Debug, Formatter};
 use std::sync::Arc;
 use std::sync::Mutex;
@@ -32,23 +48,28 @@ use tonic::Request;
 
 /// Return a pipeline to build OTLP metrics exporter.
 ///
-/// Note that currently the OTLP metrics exporter only supports tonic as it's grpc layer and tokio as
-/// runtime.
+/// The metrics are exported with the temporality of
+/// [`TemporalityPreference::from_env`] unless configured otherwise with
+/// [`OtlpMetricPipelineBuilder::with_export_kind`].
+///
+/// Note that currently the OTLP metrics exporter only supports tokio as runtime.
 pub fn new_metrics_pipeline<SP, SO, I, IO>(
     spawn: SP,
     interval: I,
-) -> OtlpMetricPipelineBuilder<selectors::simple::Selector, ExportKindSelector, SP, SO, I, IO>
+) -> OtlpMetricPipelineBuilder<selectors::simple::Selector, TemporalityPreference, SP, SO, I, IO>
 where
     SP: Fn(PushControllerWorker) -> SO,
     I: Fn(time::Duration) -> IO,
 {
     OtlpMetricPipelineBuilder {
         aggregator_selector: selectors::simple::Selector::Inexpensive,
-        export_selector: ExportKindSelector::Cumulative,
+        export_selector: TemporalityPreference::from_env(),
         spawn,
         interval,
         export_config: None,
         tonic_config: None,
+        #[cfg(feature = "http-proto")]
+        http_config: None,
         resource: None,
         stateful: None,
         period: None,
@@ -58,8 +79,7 @@ where
 
 /// Pipeline to build OTLP metrics exporter
 ///
-/// Note that currently the OTLP metrics exporter only supports tonic as it's grpc layer and tokio as
-/// runtime.
+/// Note that currently the OTLP metrics exporter only supports tokio as runtime.
 #[derive(Debug)]
 pub struct OtlpMetricPipelineBuilder<AS, ES, SP, SO, I, IO>
 where
@@ -74,6 +94,8 @@ where
     interval: I,
     export_config: Option<ExporterConfig>,
     tonic_config: Option<TonicConfig>,
+    #[cfg(feature = "http-proto")]
+    http_config: Option<HttpConfig>,
     resource: Option<Resource>,
     stateful: Option<bool>,
     period: Option<time::Duration>,
@@ -112,6 +134,16 @@ where
         }
     }
 
+    /// Build with http configuration, the metrics are sent over HTTP instead
+    /// of GRPC.
+    #[cfg(feature = "http-proto")]
+    pub fn with_http_config(self, http_config: HttpConfig) -> Self {
+        OtlpMetricPipelineBuilder {
+            http_config: Some(http_config),
+            ..self
+        }
+    }
+
     /// Build with the aggregator selector
     pub fn with_aggregator_selector<T>(
         self,
@@ -127,6 +159,8 @@ where
             interval: self.interval,
             export_config: self.export_config,
             tonic_config: self.tonic_config,
+            #[cfg(feature = "http-proto")]
+            http_config: self.http_config,
             resource: self.resource,
             stateful: self.stateful,
             period: self.period,
@@ -168,7 +202,9 @@ where
         OtlpMetricPipelineBuilder { interval, ..self }
     }
 
-    /// Build with export kind selector
+    /// Build with export kind selector, which can also be a
+    /// [`TemporalitySelector`] choosing the temporality of each kind of
+    /// instrument.
     pub fn with_export_kind<E>(
         self,
         export_selector: E,
@@ -183,6 +219,8 @@ where
             interval: self.interval,
             export_config: self.export_config,
             tonic_config: self.tonic_config,
+            #[cfg(feature = "http-proto")]
+            http_config: self.http_config,
             resource: self.resource,
             stateful: self.stateful,
             period: self.period,
@@ -191,8 +229,24 @@ where
     }
 
     /// Build push controller
-    pub fn build(self) -> Result<PushController> {
-        #[cfg(feature = "tonic")]
+    pub fn build(self) -> Result<PushController>
+    where
+        I: Send + 'static,
+    {
+        #[cfg(feature = "http-proto")]
+        let exporter = match self.http_config {
+            Some(http_config) => MetricsExporter::new_http(
+                self.export_config.unwrap_or_default(),
+                http_config,
+                self.export_selector.clone(),
+            )?,
+            None => MetricsExporter::new(
+                self.export_config.unwrap_or_default(),
+                self.tonic_config.unwrap_or_default(),
+                self.export_selector.clone(),
+            )?,
+        };
+        #[cfg(not(feature = "http-proto"))]
         let exporter = MetricsExporter::new(
             self.export_config.unwrap_or_default(),
             self.tonic_config.unwrap_or_default(),
@@ -207,7 +261,7 @@ where
             self.interval,
         );
         if let Some(period) = self.period {
-            builder = builder.with_period(period);
+            builder = builder.with_collect_period(period);
         }
         if let Some(resource) = self.resource {
             builder = builder.with_resource(resource);
@@ -216,7 +270,7 @@ where
             builder = builder.with_stateful(stateful);
         }
         if let Some(timeout) = self.timeout {
-            builder = builder.with_timeout(timeout)
+            builder = builder.with_export_timeout(timeout)
         }
         let controller = builder.build();
         global::set_meter_provider(controller.provider());
@@ -224,9 +278,44 @@ where
     }
 }
 
+/// The temporality preference of the exported metrics.
+///
+/// With the delta preference, counters, value recorders and sum observers are
+/// exported with delta temporality while up down counters, up down sum
+/// observers and value observers stay cumulative, see [`DeltaTemporality`].
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum TemporalityPreference {
+    /// Export all metrics with cumulative temporality.
+    Cumulative,
+    /// Export metrics with delta temporality where it is meaningful.
+    Delta,
+}
+
+impl TemporalityPreference {
+    /// Read the preference from the `OTEL_EXPORTER_OTLP_METRICS_TEMPORALITY_PREFERENCE`
+    /// environment variable, `cumulative` or `delta` ignoring case.
+    ///
+    /// Defaults to cumulative when the variable is missing or has another value.
+    pub fn from_env() -> Self {
+        match std::env::var(crate::OTEL_EXPORTER_OTLP_METRICS_TEMPORALITY_PREFERENCE) {
+            Ok(value) if value.trim().eq_ignore_ascii_case("delta") => TemporalityPreference::Delta,
+            _ => TemporalityPreference::Cumulative,
+        }
+    }
+}
+
+impl TemporalitySelector for TemporalityPreference {
+    fn temporality(&self, kind: &InstrumentKind) -> ExportKind {
+        match self {
+            TemporalityPreference::Cumulative => CumulativeTemporality.temporality(kind),
+            TemporalityPreference::Delta => DeltaTemporality.temporality(kind),
+        }
+    }
+}
+
 enum ExportMsg {
     #[cfg(feature = "tonic")]
-    Export(tonic::Request<ExportMetricsServiceRequest>),
+    Export(ExportMetricsServiceRequest),
     Shutdown,
 }
 
@@ -239,10 +328,7 @@ pub struct MetricsExporter {
 
 impl Debug for MetricsExporter {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
-        #[cfg(feature = "tonic")]
-        f.debug_struct("OTLP Metric Exporter")
-            .field("grpc_client", &"tonic")
-            .finish()
+        f.debug_struct("OTLP Metric Exporter").finish()
     }
 }
 
@@ -260,6 +346,10 @@ impl MetricsExporter {
         tonic_config: TonicConfig,
         export_selector: T,
     ) -> Result<MetricsExporter> {
+        if config.compression.is_some() {
+            return Err(crate::Error::UnsupportedCompression.into());
+        }
+
         let endpoint =
             Channel::from_shared(config.endpoint).map_err::<crate::Error, _>(Into::into)?;
 
@@ -300,6 +390,57 @@ impl MetricsExporter {
             }
         };
 
+        Ok(MetricsExporter::spawn(export_selector, move |request| {
+            let mut client = client.clone();
+            Box::pin(async move {
+                client
+                    .export(Request::new(request))
+                    .await
+                    .map_err::<crate::Error, _>(Into::into)?;
+                Ok(())
+            })
+        }))
+    }
+
+    /// Create a new OTLP metrics exporter sending the metrics over HTTP in the
+    /// binary protobuf format.
+    #[cfg(feature = "http-proto")]
+    pub fn new_http<T: ExportKindFor + Send + Sync + 'static>(
+        config: ExporterConfig,
+        http_config: HttpConfig,
+        export_selector: T,
+    ) -> Result<MetricsExporter> {
+        let endpoint: Uri = config
+            .endpoint
+            .parse()
+            .map_err::<crate::Error, _>(Into::into)?;
+        let client: Arc<dyn HttpClient> = http_config.client.ok_or(Error::NoHttpClient)?.into();
+        let headers = http_config.headers;
+        let compression = config.compression;
+
+        Ok(MetricsExporter::spawn(export_selector, move |request| {
+            let request = encode_http_request(&request, &endpoint, headers.as_ref(), compression);
+            let client = client.clone();
+            Box::pin(async move {
+                let response = client
+                    .send(request?)
+                    .await
+                    .map_err(|err| MetricsError::Other(err.to_string()))?;
+                if !response.status().is_success() {
+                    return Err(Error::UnsuccessfulResponse(response.status()).into());
+                }
+                Ok(())
+            })
+        }))
+    }
+
+    /// Spawn the task sending the export requests with `send`, errors are
+    /// reported through the global error handler.
+    fn spawn<T, F>(export_selector: T, send: F) -> MetricsExporter
+    where
+        T: ExportKindFor + Send + Sync + 'static,
+        F: Fn(ExportMetricsServiceRequest) -> BoxFuture<'static, Result<()>> + Send + 'static,
+    {
         let (sender, mut receiver) = tokio::sync::mpsc::channel::<ExportMsg>(2);
         tokio::spawn(Box::pin(async move {
             while let Some(msg) = receiver.recv().await {
@@ -307,20 +448,36 @@ impl MetricsExporter {
                     ExportMsg::Shutdown => {
                         break;
                     }
-                    ExportMsg::Export(req) => {
-                        let _ = client.to_owned().export(req).await;
+                    ExportMsg::Export(request) => {
+                        if let Err(err) = send(request).await {
+                            global::handle_error(err);
+                        }
                     }
                 }
             }
         }));
 
-        Ok(MetricsExporter {
+        MetricsExporter {
             sender: Arc::new(Mutex::new(sender)),
             export_kind_selector: Arc::new(export_selector),
-        })
+        }
     }
 }
 
+#[cfg(feature = "http-proto")]
+fn encode_http_request(
+    request: &ExportMetricsServiceRequest,
+    endpoint: &Uri,
+    headers: Option<&std::collections::HashMap<String, String>>,
+    compression: Option<crate::Compression>,
+) -> Result<http::Request<Vec<u8>>> {
+    let mut buf = Vec::new();
+    request
+        .encode(&mut buf)
+        .map_err::<crate::Error, _>(Into::into)?;
+    Ok(http_request(endpoint, headers, compression, buf).map_err(|err| *err)?)
+}
+
 impl Exporter for MetricsExporter {
     fn export(&self, checkpoint_set: &mut dyn CheckpointSet) -> Result<()> {
         let mut resource_metrics: Vec<CheckpointedMetrics> = Vec::default();
@@ -331,10 +488,7 @@ impl Exporter for MetricsExporter {
                 Ok(metrics) => {
                     resource_metrics.push((
                         record.resource().clone().into(),
-                        InstrumentationLibrary::new(
-                            record.descriptor().instrumentation_name(),
-                            record.descriptor().instrumentation_version(),
-                        ),
+                        record.descriptor().instrumentation_library().clone(),
                         metrics,
                     ));
                     Ok(())
@@ -342,7 +496,7 @@ impl Exporter for MetricsExporter {
                 Err(err) => Err(err),
             }
         })?;
-        let request = Request::new(sink(resource_metrics));
+        let request = sink(resource_metrics);
         self.sender
             .lock()
             .map(|sender| {
@@ -360,3 +514,122 @@ impl Drop for MetricsExporter {
         });
     }
 }
+
+#[cfg(all(test, feature = "http-proto"))]
+mod tests {
+    use super::*;
+    use crate::Protocol;
+    use async_trait::async_trait;
+    use opentelemetry::labels::LabelSet;
+    use opentelemetry::metrics::{InstrumentKind, Number, NumberKind};
+    use opentelemetry::sdk::export::metrics::{record, Aggregator, Record};
+    use opentelemetry::sdk::metrics::aggregators::SumAggregator;
+    use opentelemetry_http::HttpError;
+    use std::collections::HashMap;
+    use std::sync::mpsc;
+
+    /// A checkpoint set holding a single counter.
+    #[derive(Debug)]
+    struct CounterCheckpoint {
+        descriptor: Descriptor,
+        labels: LabelSet,
+        resource: Resource,
+        aggregator: Arc<dyn Aggregator + Send + Sync>,
+    }
+
+    impl CheckpointSet for CounterCheckpoint {
+        fn try_for_each(
+            &mut self,
+            _export_selector: &dyn ExportKindFor,
+            f: &mut dyn FnMut(&Record<'_>) -> Result<()>,
+        ) -> Result<()> {
+            let now = time::SystemTime::now();
+            f(&record(
+                &self.descriptor,
+                &self.labels,
+                &self.resource,
+                Some(&self.aggregator),
+                now,
+                now,
+            ))
+        }
+    }
+
+    #[derive(Debug)]
+    struct RecordingHttpClient(Mutex<mpsc::Sender<http::Request<Vec<u8>>>>);
+
+    #[async_trait]
+    impl HttpClient for RecordingHttpClient {
+        async fn send(
+            &self,
+            request: http::Request<Vec<u8>>,
+        ) -> std::result::Result<http::Response<bytes::Bytes>, HttpError> {
+            self.0.lock().unwrap().send(request)?;
+            Ok(http::Response::builder()
+                .status(http::StatusCode::OK)
+                .body(bytes::Bytes::new())?)
+        }
+    }
+
+    #[tokio::test]
+    async fn test_http_export() {
+        let (sender, receiver) = mpsc::channel();
+        let mut headers = HashMap::new();
+        headers.insert("x-tenant".to_string(), "test".to_string());
+        let exporter = MetricsExporter::new_http(
+            ExporterConfig {
+                endpoint: "http://localhost:4318/v1/metrics".to_string(),
+                protocol: Protocol::HttpBinary,
+                ..ExporterConfig::default()
+            },
+            HttpConfig {
+                client: Some(Box::new(RecordingHttpClient(Mutex::new(sender)))),
+                headers: Some(headers),
+            },
+            TemporalityPreference::Cumulative,
+        )
+        .unwrap();
+
+        let descriptor = Descriptor::new(
+            "requests".to_string(),
+            "test-library",
+            None,
+            InstrumentKind::Counter,
+            NumberKind::U64,
+        );
+        let aggregator = SumAggregator::default();
+        aggregator.update(&Number::from(3u64), &descriptor).unwrap();
+        let mut checkpoint = CounterCheckpoint {
+            descriptor,
+            labels: LabelSet::from_labels(vec![KeyValue::new("method", "GET")].into_iter()),
+            resource: Resource::new(vec![KeyValue::new("service.name", "test-service")]),
+            aggregator: Arc::new(aggregator),
+        };
+        exporter.export(&mut checkpoint).unwrap();
+
+        // the request is sent by the exporter's task, running on this runtime
+        let request = tokio::task::spawn_blocking(move || {
+            receiver.recv_timeout(time::Duration::from_secs(5))
+        })
+        .await
+        .unwrap()
+        .expect("the exporter should send a request");
+
+        assert_eq!(request.uri(), "http://localhost:4318/v1/metrics");
+        assert_eq!(request.headers().get("x-tenant").unwrap(), "test");
+        let decoded = ExportMetricsServiceRequest::decode(request.body().as_slice()).unwrap();
+        assert_eq!(decoded.resource_metrics.len(), 1);
+        let resource_metrics = &decoded.resource_metrics[0];
+        let resource = resource_metrics.resource.as_ref().unwrap();
+        assert_eq!(resource.attributes[0].key, "service.name");
+        let library_metrics = &resource_metrics.instrumentation_library_metrics[0];
+        assert_eq!(
+            library_metrics
+                .instrumentation_library
+                .as_ref()
+                .map(|library| library.name.as_str()),
+            Some("test-library")
+        );
+        assert_eq!(library_metrics.metrics[0].name, "requests");
+    }
+}
@@ -36,10 +36,14 @@ use grpcio::{
 #[cfg(feature = "grpc-sys")]
 use protobuf::RepeatedField;
 
+#[cfg(feature = "http-proto")]
+use flate2::{write::GzEncoder, Compression as GzCompression};
 #[cfg(feature = "http-proto")]
 use prost::Message;
 #[cfg(feature = "http-proto")]
 use std::convert::TryFrom;
+#[cfg(feature = "http-proto")]
+use std::io::Write;
 
 use async_trait::async_trait;
 
@@ -48,17 +52,20 @@ use std::collections::HashMap;
 
 use std::fmt;
 use std::fmt::Debug;
+use std::future::Future;
+use std::time::Instant;
 
 #[cfg(feature = "grpc-sys")]
 use std::sync::Arc;
 
 use crate::{Protocol, OTEL_EXPORTER_OTLP_ENDPOINT_DEFAULT, OTEL_EXPORTER_OTLP_TIMEOUT_DEFAULT};
 use opentelemetry::sdk::export::trace::{ExportResult, SpanData, SpanExporter};
+use opentelemetry::sdk::export::ExportError;
 use std::time::Duration;
 
 #[cfg(feature = "http-proto")]
 use http::{
-    header::{HeaderName, HeaderValue, CONTENT_TYPE},
+    header::{HeaderName, HeaderValue, CONTENT_ENCODING, CONTENT_TYPE},
     Method, Uri,
 };
 #[cfg(feature = "http-proto")]
@@ -73,6 +80,8 @@ pub enum TraceExporter {
         timeout: Duration,
         /// Additional headers of the outbound requests.
         metadata: Option<MetadataMap>,
+        /// The maximum encoded size of the spans of a request.
+        max_payload_size: Option<usize>,
         /// The Grpc trace exporter
         trace_exporter: TonicTraceServiceClient<TonicChannel>,
     },
@@ -83,6 +92,8 @@ pub enum TraceExporter {
         timeout: Duration,
         /// Additional headers of the outbound requests.
         headers: Option<HashMap<String, String>>,
+        /// The maximum encoded size of the spans of a request.
+        max_payload_size: Option<usize>,
         /// The Grpc trace exporter
         trace_exporter: GrpcioTraceServiceClient,
     },
@@ -95,6 +106,10 @@ pub enum TraceExporter {
         headers: Option<HashMap<String, String>>,
         /// The Collector URL
         collector_endpoint: Uri,
+        /// The compression applied to the request bodies.
+        compression: Option<Compression>,
+        /// The maximum encoded size of the spans of a request.
+        max_payload_size: Option<usize>,
         /// The HTTP trace exporter
         trace_exporter: Option<Box<dyn HttpClient>>,
     },
@@ -111,6 +126,20 @@ pub struct ExporterConfig {
 
     /// The timeout to the collector.
     pub timeout: Duration,
+
+    /// The compression algorithm to use when sending data to the collector.
+    ///
+    /// Only supported by the grpcio and http transports, the tonic transport
+    /// always sends uncompressed data.
+    pub compression: Option<Compression>,
+
+    /// The maximum size in bytes of the encoded spans of a single trace
+    /// export request, before compression.
+    ///
+    /// Larger batches are split into multiple requests, spans exceeding the
+    /// limit on their own are dropped and reported through the global error
+    /// handler. There is no limit by default.
+    pub max_payload_size: Option<usize>,
 }
 
 /// Configuration for [tonic]
@@ -182,8 +211,7 @@ pub struct Credentials {
 }
 
 /// The compression algorithm to use when sending data.
-#[derive(Clone, Copy, Debug)]
-#[cfg(feature = "grpc-sys")]
+#[derive(Clone, Copy, Debug, PartialEq)]
 pub enum Compression {
     /// Compresses data using gzip.
     Gzip,
@@ -244,6 +272,8 @@ impl Default for ExporterConfig {
             endpoint: OTEL_EXPORTER_OTLP_ENDPOINT_DEFAULT.to_string(),
             protocol: Protocol::Grpc,
             timeout: Duration::from_secs(OTEL_EXPORTER_OTLP_TIMEOUT_DEFAULT),
+            compression: None,
+            max_payload_size: None,
         }
     }
 }
@@ -307,9 +337,9 @@ impl TraceExporter {
 
     /// Builds a new span exporter with given tonic channel.
     ///
-    /// This allows users to bring their own custom channel like UDS.
-    /// However, users MUST make sure the [`ExporterConfig::timeout`] is
-    /// the same as the channel's timeout.
+    /// This allows users to bring their own custom channel like UDS. The
+    /// [`ExporterConfig::timeout`] is sent as the deadline of each export
+    /// request, independently of the channel's own timeout.
     ///
     /// [`ExporterConfig::timeout`]: crate::span::ExporterConfig::timeout
     #[cfg(feature = "tonic")]
@@ -318,6 +348,10 @@ impl TraceExporter {
         tonic_config: TonicConfig,
         channel: tonic::transport::Channel,
     ) -> Result<Self, crate::Error> {
+        if config.compression.is_some() {
+            return Err(crate::Error::UnsupportedCompression);
+        }
+
         let client = match tonic_config.metadata.to_owned() {
             None => TonicTraceServiceClient::new(channel),
             Some(metadata) => {
@@ -341,6 +375,7 @@ impl TraceExporter {
         Ok(TraceExporter::Tonic {
             timeout: config.timeout,
             metadata: tonic_config.metadata,
+            max_payload_size: config.max_payload_size,
             trace_exporter: client,
         })
     }
@@ -352,7 +387,7 @@ impl TraceExporter {
             grpcio_config.completion_queue_count,
         )));
 
-        if let Some(compression) = grpcio_config.compression {
+        if let Some(compression) = grpcio_config.compression.or(config.compression) {
             builder = builder.default_compression_algorithm(compression.into());
         }
 
@@ -374,6 +409,7 @@ impl TraceExporter {
             trace_exporter: GrpcioTraceServiceClient::new(channel),
             timeout: config.timeout,
             headers: grpcio_config.headers,
+            max_payload_size: config.max_payload_size,
         }
     }
 
@@ -389,6 +425,8 @@ impl TraceExporter {
             trace_exporter: http_config.client,
             timeout: config.timeout,
             collector_endpoint: url,
+            compression: config.compression,
+            max_payload_size: config.max_payload_size,
             headers: http_config.headers,
         })
     }
@@ -402,17 +440,16 @@ impl SpanExporter for TraceExporter {
             TraceExporter::Grpcio {
                 timeout,
                 headers,
+                max_payload_size,
                 trace_exporter,
             } => {
-                let request = GrpcRequest {
-                    resource_spans: RepeatedField::from_vec(
-                        batch.into_iter().map(Into::into).collect(),
-                    ),
-                    unknown_fields: Default::default(),
-                    cached_size: Default::default(),
-                };
+                let chunks = split_payload(
+                    batch.into_iter().map(Into::into).collect(),
+                    *max_payload_size,
+                    |resource_spans| protobuf::Message::compute_size(resource_spans) as usize,
+                );
 
-                let mut call_options = CallOption::default().timeout(*timeout);
+                let mut call_options = CallOption::default();
 
                 if let Some(headers) = headers.clone() {
                     let mut metadata_builder: MetadataBuilder = MetadataBuilder::new();
@@ -424,67 +461,665 @@ impl SpanExporter for TraceExporter {
                     call_options = call_options.headers(metadata_builder.build());
                 }
 
-                let receiver = trace_exporter
-                    .export_async_opt(&request, call_options)
-                    .map_err::<crate::Error, _>(Into::into)?;
-                receiver.await.map_err::<crate::Error, _>(Into::into)?;
-                Ok(())
+                let trace_exporter = &*trace_exporter;
+                export_chunks(chunks, *timeout, |resource_spans, timeout| {
+                    let request = GrpcRequest {
+                        resource_spans: RepeatedField::from_vec(resource_spans),
+                        unknown_fields: Default::default(),
+                        cached_size: Default::default(),
+                    };
+                    let call_options = call_options.clone().timeout(timeout);
+
+                    async move {
+                        let receiver = trace_exporter
+                            .export_async_opt(&request, call_options)
+                            .map_err::<crate::Error, _>(Into::into)?;
+                        receiver.await.map_err::<crate::Error, _>(Into::into)?;
+                        Ok(())
+                    }
+                })
+                .await
             }
 
             #[cfg(feature = "tonic")]
-            TraceExporter::Tonic { trace_exporter, .. } => {
-                let request = Request::new(TonicRequest {
-                    resource_spans: batch.into_iter().map(Into::into).collect(),
-                });
-
-                trace_exporter
-                    .to_owned()
-                    .export(request)
-                    .await
-                    .map_err::<crate::Error, _>(Into::into)?;
-
-                Ok(())
+            TraceExporter::Tonic {
+                trace_exporter,
+                timeout,
+                max_payload_size,
+                ..
+            } => {
+                let chunks = split_payload(
+                    batch.into_iter().map(Into::into).collect(),
+                    *max_payload_size,
+                    prost::Message::encoded_len,
+                );
+
+                let trace_exporter = &*trace_exporter;
+                export_chunks(chunks, *timeout, |resource_spans, timeout| {
+                    let mut request = Request::new(TonicRequest { resource_spans });
+                    request.set_timeout(timeout);
+
+                    async move {
+                        trace_exporter
+                            .to_owned()
+                            .export(request)
+                            .await
+                            .map(|_| ())
+                            .map_err::<crate::Error, _>(Into::into)
+                    }
+                })
+                .await
             }
 
             #[cfg(feature = "http-proto")]
             TraceExporter::Http {
                 trace_exporter,
+                timeout,
                 collector_endpoint,
                 headers,
-                ..
+                compression,
+                max_payload_size,
             } => {
-                let req = ProstRequest {
-                    resource_spans: batch.into_iter().map(Into::into).collect(),
-                };
+                let chunks = split_payload(
+                    batch.into_iter().map(Into::into).collect(),
+                    *max_payload_size,
+                    prost::Message::encoded_len,
+                );
 
-                let mut buf = vec![];
-                req.encode(&mut buf)
-                    .map_err::<crate::Error, _>(Into::into)?;
+                let client = trace_exporter.as_ref().ok_or(crate::Error::NoHttpClient)?;
+                let (collector_endpoint, headers, compression) =
+                    (&*collector_endpoint, headers.as_ref(), *compression);
+                // `HttpClient` has no per request timeout, the deadline is only
+                // checked between requests
+                export_chunks(chunks, *timeout, |resource_spans, _timeout| async move {
+                    let req = ProstRequest { resource_spans };
 
-                let mut request = http::Request::builder()
-                    .method(Method::POST)
-                    .uri(collector_endpoint.clone())
-                    .header(CONTENT_TYPE, "application/x-protobuf")
-                    .body(buf)
-                    .map_err::<crate::Error, _>(Into::into)?;
+                    let mut buf = vec![];
+                    req.encode(&mut buf)
+                        .map_err::<crate::Error, _>(Into::into)?;
+                    let request = http_request(collector_endpoint, headers, compression, buf)
+                        .map_err(|err| *err)?;
 
-                if let Some(headers) = headers.clone() {
-                    for (k, val) in headers {
-                        let value = HeaderValue::from_str(val.as_ref())
-                            .map_err::<crate::Error, _>(Into::into)?;
-                        let key =
-                            HeaderName::try_from(&k).map_err::<crate::Error, _>(Into::into)?;
-                        request.headers_mut().insert(key, value);
+                    match client.send(request).await {
+                        Ok(response) if response.status().is_success() => Ok(()),
+                        Ok(response) => Err(crate::Error::UnsuccessfulResponse(response.status())),
+                        Err(err) => Err(crate::Error::HttpClientFailed(err)),
                     }
-                }
+                })
+                .await
+            }
+        }
+    }
 
-                if let Some(client) = trace_exporter {
-                    client.send(request).await?;
-                    Ok(())
-                } else {
-                    Err(crate::Error::NoHttpClient.into())
-                }
+    /// Caps the request timeout so requests give up before the processor
+    /// abandons the export.
+    fn set_export_timeout(&mut self, export_timeout: Duration) {
+        let timeout = match self {
+            #[cfg(feature = "tonic")]
+            TraceExporter::Tonic { timeout, .. } => timeout,
+            #[cfg(feature = "grpc-sys")]
+            TraceExporter::Grpcio { timeout, .. } => timeout,
+            #[cfg(feature = "http-proto")]
+            TraceExporter::Http { timeout, .. } => timeout,
+        };
+        *timeout = (*timeout).min(export_timeout);
+    }
+}
+
+/// Split the converted spans of a batch into the payloads of separate requests,
+/// each encoding to at most `max_payload_size` bytes.
+///
+/// Spans exceeding the limit on their own are dropped and reported through the
+/// global error handler. Without a limit the batch is sent as a single request.
+fn split_payload<T, F>(
+    items: Vec<T>,
+    max_payload_size: Option<usize>,
+    encoded_len: F,
+) -> Vec<Vec<T>>
+where
+    F: Fn(&T) -> usize,
+{
+    let max_payload_size = match max_payload_size {
+        Some(max_payload_size) => max_payload_size,
+        None => return vec![items],
+    };
+
+    let mut payloads = Vec::new();
+    let mut payload = Vec::new();
+    let mut payload_size = 0;
+    for item in items {
+        // each span is a length delimited entry of the repeated request field
+        let len = encoded_len(&item);
+        let size = 1 + varint_len(len) + len;
+
+        if size > max_payload_size {
+            opentelemetry::global::handle_error(opentelemetry::trace::TraceError::from(format!(
+                "dropping span encoded in {} bytes, larger than the otlp max payload size of {} bytes",
+                size, max_payload_size
+            )));
+            continue;
+        }
+
+        if payload_size + size > max_payload_size {
+            payloads.push(std::mem::take(&mut payload));
+            payload_size = 0;
+        }
+        payload.push(item);
+        payload_size += size;
+    }
+
+    if !payload.is_empty() {
+        payloads.push(payload);
+    }
+
+    payloads
+}
+
+/// Send the requests a batch was split into within a single `timeout`.
+///
+/// `send` is given the time left before the deadline. The remaining requests
+/// are not sent once the deadline passed or after a request failed with an
+/// error that is not retryable or is a transport error, they would fail the
+/// same way.
+async fn export_chunks<T, F, Fut>(
+    chunks: Vec<Vec<T>>,
+    timeout: Duration,
+    mut send: F,
+) -> ExportResult
+where
+    F: FnMut(Vec<T>, Duration) -> Fut,
+    Fut: Future<Output = Result<(), crate::Error>>,
+{
+    let deadline = Instant::now() + timeout;
+    let mut results = ChunkResults::new(chunks.len());
+    let mut chunks = chunks.into_iter();
+
+    for chunk in chunks.by_ref() {
+        let spans = chunk.len();
+        let remaining = deadline.saturating_duration_since(Instant::now());
+        let result = if remaining == Duration::from_secs(0) {
+            Err(crate::Error::ExportTimedOut(timeout))
+        } else {
+            send(chunk, remaining).await
+        };
+
+        let stop = match &result {
+            Err(err) => {
+                matches!(err, crate::Error::ExportTimedOut(_))
+                    || !err.is_retryable()
+                    || err.is_transport()
+            }
+            Ok(_) => false,
+        };
+        results.record(spans, result);
+
+        if stop {
+            for chunk in chunks {
+                results.skip(chunk.len());
             }
+            break;
         }
     }
+
+    results.finish()
+}
+
+/// The outcome of the requests a batch was split into.
+///
+/// A batch is only reported as retryable if none of its requests succeeded,
+/// see [`crate::Error::PartialExport`].
+struct ChunkResults {
+    total_requests: usize,
+    failed_requests: usize,
+    failed_spans: usize,
+    first_error: Option<crate::Error>,
+}
+
+impl ChunkResults {
+    fn new(total_requests: usize) -> Self {
+        ChunkResults {
+            total_requests,
+            failed_requests: 0,
+            failed_spans: 0,
+            first_error: None,
+        }
+    }
+
+    fn record(&mut self, spans: usize, result: Result<(), crate::Error>) {
+        if let Err(err) = result {
+            self.failed_requests += 1;
+            self.failed_spans += spans;
+            self.first_error.get_or_insert(err);
+        }
+    }
+
+    /// Count a request that was not sent as failed.
+    fn skip(&mut self, spans: usize) {
+        self.failed_requests += 1;
+        self.failed_spans += spans;
+    }
+
+    fn finish(self) -> ExportResult {
+        match self.first_error {
+            None => Ok(()),
+            // nothing was sent, the whole batch can be retried
+            Some(err) if self.failed_requests == self.total_requests => Err(err.into()),
+            Some(err) => Err(crate::Error::PartialExport {
+                failed_requests: self.failed_requests,
+                total_requests: self.total_requests,
+                failed_spans: self.failed_spans,
+                source: Box::new(err),
+            }
+            .into()),
+        }
+    }
+}
+
+/// Build the request posting an encoded protobuf message to an OTLP http
+/// endpoint.
+#[cfg(feature = "http-proto")]
+pub(crate) fn http_request(
+    endpoint: &Uri,
+    headers: Option<&HashMap<String, String>>,
+    compression: Option<Compression>,
+    body: Vec<u8>,
+) -> Result<http::Request<Vec<u8>>, Box<crate::Error>> {
+    fn boxed<E: Into<crate::Error>>(err: E) -> Box<crate::Error> {
+        Box::new(err.into())
+    }
+
+    let mut request = http::Request::builder()
+        .method(Method::POST)
+        .uri(endpoint.clone())
+        .header(CONTENT_TYPE, "application/x-protobuf");
+    let body = match compression {
+        Some(Compression::Gzip) => {
+            request = request.header(CONTENT_ENCODING, "gzip");
+            let mut encoder = GzEncoder::new(Vec::new(), GzCompression::default());
+            encoder
+                .write_all(&body)
+                .and_then(|_| encoder.finish())
+                .map_err(boxed)?
+        }
+        None => body,
+    };
+    let mut request = request.body(body).map_err(boxed)?;
+
+    if let Some(headers) = headers {
+        for (k, val) in headers {
+            let value = HeaderValue::from_str(val.as_ref()).map_err(boxed)?;
+            let key = HeaderName::try_from(k).map_err(boxed)?;
+            request.headers_mut().insert(key, value);
+        }
+    }
+
+    Ok(request)
+}
+
+/// Number of bytes of the protobuf varint encoding of `value`.
+fn varint_len(value: usize) -> usize {
+    let mut len = 1;
+    let mut value = value >> 7;
+    while value > 0 {
+        len += 1;
+        value >>= 7;
+    }
+    len
+}
+
+#[cfg(all(test, feature = "http-proto"))]
+mod tests {
+    use super::*;
+    use opentelemetry::trace::TraceError;
+    use opentelemetry_http::HttpError;
+    use std::io::Read;
+
+    #[derive(Debug)]
+    struct StatusHttpClient(http::StatusCode);
+
+    #[async_trait]
+    impl HttpClient for StatusHttpClient {
+        async fn send(
+            &self,
+            request: http::Request<Vec<u8>>,
+        ) -> Result<http::Response<bytes::Bytes>, HttpError> {
+            assert_eq!(
+                request.headers().get(CONTENT_TYPE),
+                Some(&HeaderValue::from_static("application/x-protobuf"))
+            );
+            Ok(http::Response::builder()
+                .status(self.0)
+                .body(bytes::Bytes::new())?)
+        }
+    }
+
+    #[derive(Debug)]
+    struct GzipCheckingHttpClient;
+
+    #[async_trait]
+    impl HttpClient for GzipCheckingHttpClient {
+        async fn send(
+            &self,
+            request: http::Request<Vec<u8>>,
+        ) -> Result<http::Response<bytes::Bytes>, HttpError> {
+            assert_eq!(
+                request.headers().get(CONTENT_ENCODING),
+                Some(&HeaderValue::from_static("gzip"))
+            );
+            let mut body = Vec::new();
+            flate2::read::GzDecoder::new(request.body().as_slice()).read_to_end(&mut body)?;
+            let decoded = ProstRequest::decode(body.as_slice()).expect("valid protobuf body");
+            assert!(decoded.resource_spans.is_empty());
+
+            Ok(http::Response::builder()
+                .status(http::StatusCode::OK)
+                .body(bytes::Bytes::new())?)
+        }
+    }
+
+    fn http_exporter(status: http::StatusCode) -> TraceExporter {
+        TraceExporter::new_http(
+            ExporterConfig {
+                endpoint: "http://localhost:4318/v1/traces".to_string(),
+                protocol: Protocol::HttpBinary,
+                ..ExporterConfig::default()
+            },
+            HttpConfig {
+                client: Some(Box::new(StatusHttpClient(status))),
+                headers: None,
+            },
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn test_http_export_status() {
+        let mut exporter = http_exporter(http::StatusCode::OK);
+        assert!(futures::executor::block_on(exporter.export(vec![])).is_ok());
+
+        let mut exporter = http_exporter(http::StatusCode::SERVICE_UNAVAILABLE);
+        match futures::executor::block_on(exporter.export(vec![])) {
+            Err(TraceError::ExportFailed(err)) => {
+                assert_eq!(err.exporter_name(), "otlp");
+                assert!(err.to_string().contains("503"), "{}", err);
+            }
+            other => panic!("expected an export error, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_http_export_gzip() {
+        let mut exporter = TraceExporter::new_http(
+            ExporterConfig {
+                endpoint: "http://localhost:4318/v1/traces".to_string(),
+                protocol: Protocol::HttpBinary,
+                compression: Some(Compression::Gzip),
+                ..ExporterConfig::default()
+            },
+            HttpConfig {
+                client: Some(Box::new(GzipCheckingHttpClient)),
+                headers: None,
+            },
+        )
+        .unwrap();
+
+        assert!(futures::executor::block_on(exporter.export(vec![])).is_ok());
+    }
+
+    /// Responds to the requests with the given statuses in order after
+    /// `delay`, `None` fails the request as if the collector was unreachable.
+    #[derive(Debug)]
+    struct SequenceHttpClient {
+        statuses: Vec<Option<http::StatusCode>>,
+        delay: Duration,
+        requests: std::sync::Arc<std::sync::atomic::AtomicUsize>,
+    }
+
+    #[async_trait]
+    impl HttpClient for SequenceHttpClient {
+        async fn send(
+            &self,
+            _request: http::Request<Vec<u8>>,
+        ) -> Result<http::Response<bytes::Bytes>, HttpError> {
+            let request = self
+                .requests
+                .fetch_add(1, std::sync::atomic::Ordering::SeqCst);
+            std::thread::sleep(self.delay);
+            match self.statuses[request] {
+                Some(status) => Ok(http::Response::builder()
+                    .status(status)
+                    .body(bytes::Bytes::new())?),
+                None => Err("connection refused".into()),
+            }
+        }
+    }
+
+    fn test_span(span_id: u64) -> SpanData {
+        SpanData {
+            span_context: opentelemetry::trace::SpanContext::new(
+                opentelemetry::trace::TraceId::from_u128(1),
+                opentelemetry::trace::SpanId::from_u64(span_id),
+                0,
+                false,
+                Default::default(),
+            ),
+            parent_span_id: opentelemetry::trace::SpanId::invalid(),
+            span_kind: opentelemetry::trace::SpanKind::Internal,
+            name: "test".into(),
+            start_time: std::time::SystemTime::UNIX_EPOCH,
+            end_time: std::time::SystemTime::UNIX_EPOCH,
+            attributes: opentelemetry::sdk::trace::EvictedHashMap::new(1, 0),
+            events: opentelemetry::sdk::trace::EvictedQueue::new(1),
+            links: opentelemetry::sdk::trace::EvictedQueue::new(1),
+            status: opentelemetry::trace::Status::Unset,
+            resource: None,
+            instrumentation_lib: Default::default(),
+        }
+    }
+
+    /// Export three spans in separate requests, returns the result and the
+    /// number of requests sent.
+    fn export_in_three_requests(
+        statuses: Vec<Option<http::StatusCode>>,
+        delay: Duration,
+        timeout: Duration,
+    ) -> (ExportResult, usize) {
+        // every request holds a single span
+        let span_size = prost::Message::encoded_len(
+            &crate::proto::prost::trace::v1::ResourceSpans::from(test_span(1)),
+        );
+        let requests = std::sync::Arc::new(std::sync::atomic::AtomicUsize::new(0));
+        let mut exporter = TraceExporter::new_http(
+            ExporterConfig {
+                endpoint: "http://localhost:4318/v1/traces".to_string(),
+                protocol: Protocol::HttpBinary,
+                max_payload_size: Some(span_size + 2),
+                timeout,
+                ..ExporterConfig::default()
+            },
+            HttpConfig {
+                client: Some(Box::new(SequenceHttpClient {
+                    statuses,
+                    delay,
+                    requests: requests.clone(),
+                })),
+                headers: None,
+            },
+        )
+        .unwrap();
+        let result = futures::executor::block_on(exporter.export(vec![
+            test_span(1),
+            test_span(2),
+            test_span(3),
+        ]));
+        (result, requests.load(std::sync::atomic::Ordering::SeqCst))
+    }
+
+    #[test]
+    fn test_http_export_partial_failure() {
+        let export = |statuses: Vec<http::StatusCode>| {
+            export_in_three_requests(
+                statuses.into_iter().map(Some).collect(),
+                Duration::from_secs(0),
+                Duration::from_secs(10),
+            )
+        };
+
+        // the requests after a retryable failure are still sent, and the batch
+        // is not retried as the other requests succeeded
+        let (result, requests) = export(vec![
+            http::StatusCode::OK,
+            http::StatusCode::SERVICE_UNAVAILABLE,
+            http::StatusCode::OK,
+        ]);
+        assert_eq!(requests, 3);
+        match result {
+            Err(TraceError::ExportFailed(err)) => {
+                assert!(!err.is_retryable());
+                assert!(
+                    err.to_string()
+                        .starts_with("1 of 3 export requests failed, dropping 1 spans"),
+                    "{}",
+                    err
+                );
+            }
+            other => panic!("expected a partial export error, got {:?}", other),
+        }
+
+        // the batch is retryable when no request succeeded
+        let (result, requests) = export(vec![http::StatusCode::SERVICE_UNAVAILABLE; 3]);
+        assert_eq!(requests, 3);
+        match result {
+            Err(TraceError::ExportFailed(err)) => assert!(err.is_retryable()),
+            other => panic!("expected an export error, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_http_export_stops_after_permanent_failure() {
+        // the requests after a non retryable failure are not sent
+        let (result, requests) = export_in_three_requests(
+            vec![
+                Some(http::StatusCode::OK),
+                Some(http::StatusCode::BAD_REQUEST),
+                Some(http::StatusCode::OK),
+            ],
+            Duration::from_secs(0),
+            Duration::from_secs(10),
+        );
+        assert_eq!(requests, 2);
+        match result {
+            Err(TraceError::ExportFailed(err)) => assert!(
+                err.to_string()
+                    .starts_with("2 of 3 export requests failed, dropping 2 spans"),
+                "{}",
+                err
+            ),
+            other => panic!("expected a partial export error, got {:?}", other),
+        }
+
+        // nor after a transport failure, the whole batch can be retried
+        let (result, requests) = export_in_three_requests(
+            vec![None, Some(http::StatusCode::OK), Some(http::StatusCode::OK)],
+            Duration::from_secs(0),
+            Duration::from_secs(10),
+        );
+        assert_eq!(requests, 1);
+        match result {
+            Err(TraceError::ExportFailed(err)) => {
+                assert!(err.is_retryable());
+                assert!(err.to_string().contains("connection refused"), "{}", err);
+            }
+            other => panic!("expected an export error, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_http_export_shares_timeout_between_requests() {
+        // the first request outlasts the timeout of the whole export
+        let (result, requests) = export_in_three_requests(
+            vec![Some(http::StatusCode::OK); 3],
+            Duration::from_millis(100),
+            Duration::from_millis(50),
+        );
+        assert_eq!(requests, 1);
+        match result {
+            Err(TraceError::ExportFailed(err)) => assert!(
+                err.to_string().starts_with(
+                    "2 of 3 export requests failed, dropping 2 spans: export timed out"
+                ),
+                "{}",
+                err
+            ),
+            other => panic!("expected a partial export error, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_set_export_timeout() {
+        let mut exporter = http_exporter(http::StatusCode::OK);
+        exporter.set_export_timeout(Duration::from_secs(1));
+        exporter.set_export_timeout(Duration::from_secs(60));
+        match exporter {
+            TraceExporter::Http { timeout, .. } => assert_eq!(timeout, Duration::from_secs(1)),
+            #[allow(unreachable_patterns)]
+            _ => unreachable!(),
+        }
+    }
+
+    #[test]
+    fn test_pipeline_timeout_is_kept_by_processors() {
+        let builder = crate::new_pipeline()
+            .with_endpoint("http://localhost:4318/v1/traces")
+            .with_timeout(Duration::from_secs(3))
+            .with_http()
+            .with_http_client(StatusHttpClient(http::StatusCode::OK));
+        let mut exporter =
+            TraceExporter::new_http(builder.exporter_config, builder.http_config).unwrap();
+        // the span processors set their default export timeout of 30 seconds
+        exporter.set_export_timeout(Duration::from_secs(30));
+        match exporter {
+            TraceExporter::Http { timeout, .. } => assert_eq!(timeout, Duration::from_secs(3)),
+            #[allow(unreachable_patterns)]
+            _ => unreachable!(),
+        }
+    }
+
+    #[test]
+    fn test_split_payload() {
+        // without a limit the whole batch is sent, even when empty
+        assert_eq!(
+            split_payload(vec![10, 20], None, |len| *len),
+            vec![vec![10, 20]]
+        );
+        assert_eq!(
+            split_payload(Vec::<usize>::new(), None, |len| *len),
+            vec![Vec::<usize>::new()]
+        );
+
+        // every entry adds a tag and a one byte length to its encoded length
+        assert_eq!(
+            split_payload(vec![8, 8, 8, 18], Some(20), |len| *len),
+            vec![vec![8, 8], vec![8], vec![18]]
+        );
+        assert!(split_payload(Vec::<usize>::new(), Some(20), |len| *len).is_empty());
+    }
+
+    #[test]
+    fn test_split_payload_drops_oversized_spans() {
+        assert_eq!(
+            split_payload(vec![5, 100, 5], Some(20), |len| *len),
+            vec![vec![5, 5]]
+        );
+        assert!(split_payload(vec![100], Some(20), |len| *len).is_empty());
+    }
+
+    #[test]
+    fn test_varint_len() {
+        assert_eq!(varint_len(0), 1);
+        assert_eq!(varint_len(127), 1);
+        assert_eq!(varint_len(128), 2);
+        assert_eq!(varint_len(16_383), 2);
+        assert_eq!(varint_len(16_384), 3);
+    }
 }
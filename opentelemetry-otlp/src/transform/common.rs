@@ -10,6 +10,9 @@ pub(crate) mod tonic {
         any_value, AnyValue, ArrayValue, InstrumentationLibrary, KeyValue,
     };
 
+    // The OTLP protocol version in use has neither instrumentation library
+    // attributes nor schema urls, they are dropped until the protocol is
+    // upgraded.
     impl From<opentelemetry::sdk::InstrumentationLibrary> for InstrumentationLibrary {
         fn from(library: opentelemetry::sdk::InstrumentationLibrary) -> Self {
             InstrumentationLibrary {
@@ -162,9 +165,19 @@ pub(crate) mod prost {
 #[cfg(feature = "grpc-sys")]
 pub(crate) mod grpcio {
     use super::*;
-    use crate::proto::grpcio::common::{AnyValue, ArrayValue, KeyValue};
+    use crate::proto::grpcio::common::{AnyValue, ArrayValue, InstrumentationLibrary, KeyValue};
     use protobuf::RepeatedField;
 
+    impl From<opentelemetry::sdk::InstrumentationLibrary> for InstrumentationLibrary {
+        fn from(library: opentelemetry::sdk::InstrumentationLibrary) -> Self {
+            InstrumentationLibrary {
+                name: library.name.to_string(),
+                version: library.version.unwrap_or("").to_string(),
+                ..Default::default()
+            }
+        }
+    }
+
     pub(crate) struct Attributes(
         pub(crate) ::protobuf::RepeatedField<crate::proto::grpcio::common::KeyValue>,
     );
@@ -16,12 +16,12 @@ pub(crate) mod tonic {
     };
     use opentelemetry::metrics::{MetricsError, Number, NumberKind};
     use opentelemetry::sdk::export::metrics::{
-        Count, ExportKind, ExportKindFor, Histogram as SdkHistogram, LastValue, Max, Min, Points,
-        Record, Sum as SdkSum,
+        Count, ExponentialBuckets, ExponentialHistogram, ExportKind, ExportKindFor,
+        Histogram as SdkHistogram, LastValue, Max, Min, Points, Record, Sum as SdkSum,
     };
     use opentelemetry::sdk::metrics::aggregators::{
-        ArrayAggregator, HistogramAggregator, LastValueAggregator, MinMaxSumCountAggregator,
-        SumAggregator,
+        ArrayAggregator, ExponentialHistogramAggregator, HistogramAggregator, LastValueAggregator,
+        MinMaxSumCountAggregator, SumAggregator,
     };
 
     use crate::transform::common::to_nanos;
@@ -159,6 +159,32 @@ pub(crate) mod tonic {
                             aggregation_temporality: temporality as i32,
                         })
                     })
+                } else if let Some(histogram) = aggregator
+                    .as_any()
+                    .downcast_ref::<ExponentialHistogramAggregator>()
+                {
+                    Some({
+                        let (sum, count, buckets) = (
+                            histogram.sum()?,
+                            histogram.count()?,
+                            histogram.exponential_histogram()?,
+                        );
+                        let (explicit_bounds, bucket_counts) = explicit_buckets(&buckets);
+                        Data::Histogram(Histogram {
+                            data_points: vec![HistogramDataPoint {
+                                attributes: labels,
+                                labels: vec![],
+                                start_time_unix_nano: to_nanos(*record.start_time()),
+                                time_unix_nano: to_nanos(*record.end_time()),
+                                count,
+                                sum: sum.to_f64(kind),
+                                bucket_counts,
+                                explicit_bounds,
+                                exemplars: Vec::default(),
+                            }],
+                            aggregation_temporality: temporality as i32,
+                        })
+                    })
                 } else if let Some(min_max_sum_count) = aggregator
                     .as_any()
                     .downcast_ref::<MinMaxSumCountAggregator>()
@@ -194,6 +220,93 @@ pub(crate) mod tonic {
         })
     }
 
+    /// Convert exponential buckets to the explicit bounds and bucket counts of
+    /// a histogram data point, the protocol version in use has no exponential
+    /// histogram data point.
+    ///
+    /// The counts of all buckets are kept, the scale and offset are not. The
+    /// explicit buckets are upper-inclusive while the negative exponential
+    /// bucket with index `i` holds the values in `[-base^(i + 1), -base^i)`,
+    /// so the negative bounds are moved to the next smaller float. Zeros are
+    /// counted in a bucket holding no other value.
+    fn explicit_buckets(buckets: &ExponentialBuckets) -> (Vec<f64>, Vec<u64>) {
+        // the lower boundary of the bucket with the given index
+        let scale_factor = 2f64.powi(-i32::from(buckets.scale()));
+        let boundary = |index: i32| 2f64.powf(f64::from(index) * scale_factor);
+
+        let mut bounds = Vec::new();
+        let mut counts = Vec::new();
+        let negative = buckets.negative();
+        for (i, count) in negative.counts().iter().enumerate().rev() {
+            let index = negative.offset() + i as i32;
+            push_bucket(
+                &mut bounds,
+                &mut counts,
+                next_below(-boundary(index + 1)),
+                next_below(-boundary(index)),
+                *count,
+            );
+        }
+        if buckets.zero_count() > 0 {
+            match bounds.last() {
+                // the largest negative float, so only zeros are in (lower, 0]
+                Some(_) => push_bucket(
+                    &mut bounds,
+                    &mut counts,
+                    next_below(-0.0),
+                    0.0,
+                    buckets.zero_count(),
+                ),
+                None => {
+                    bounds.push(0.0);
+                    counts.push(buckets.zero_count());
+                }
+            }
+        }
+        let positive = buckets.positive();
+        for (i, count) in positive.counts().iter().enumerate() {
+            let index = positive.offset() + i as i32;
+            push_bucket(
+                &mut bounds,
+                &mut counts,
+                boundary(index),
+                boundary(index + 1),
+                *count,
+            );
+        }
+
+        // values above the last bound
+        if !bounds.is_empty() {
+            counts.push(0);
+        }
+
+        (bounds, counts)
+    }
+
+    /// The next float towards negative infinity of a negative `value`.
+    fn next_below(value: f64) -> f64 {
+        f64::from_bits(value.to_bits() + 1)
+    }
+
+    /// Append the count of the values in `(lower, upper]` to the explicit
+    /// buckets, with an empty bucket for any gap since the previous bound.
+    fn push_bucket(
+        bounds: &mut Vec<f64>,
+        counts: &mut Vec<u64>,
+        lower: f64,
+        upper: f64,
+        count: u64,
+    ) {
+        #[allow(clippy::float_cmp)] // consecutive buckets share the exact same boundary
+        let contiguous = bounds.last() == Some(&lower);
+        if !contiguous {
+            bounds.push(lower);
+            counts.push(0);
+        }
+        bounds.push(upper);
+        counts.push(count);
+    }
+
     // Group metrics with resources and instrumentation libraries with resources first,
     // then instrumentation libraries.
     #[allow(clippy::map_entry)] // caused by https://github.com/rust-lang/rust-clippy/issues/4674
@@ -308,8 +421,9 @@ mod tests {
     mod tonic {
         use crate::proto::common::v1::{any_value, AnyValue, KeyValue};
         use crate::proto::metrics::v1::{
-            metric::Data, number_data_point, Gauge, Histogram, HistogramDataPoint,
-            InstrumentationLibraryMetrics, Metric, NumberDataPoint, ResourceMetrics, Sum,
+            metric::Data, number_data_point, AggregationTemporality, Gauge, Histogram,
+            HistogramDataPoint, InstrumentationLibraryMetrics, Metric, NumberDataPoint,
+            ResourceMetrics, Sum,
         };
         use crate::transform::common::tonic::Attributes;
         use crate::transform::metrics::tonic::merge;
@@ -319,9 +433,11 @@ mod tests {
         use opentelemetry::metrics::{
             Descriptor, InstrumentKind, MetricsError, Number, NumberKind,
         };
-        use opentelemetry::sdk::export::metrics::{record, Aggregator, ExportKindSelector};
+        use opentelemetry::sdk::export::metrics::{
+            record, Aggregator, DeltaTemporality, ExportKindSelector,
+        };
         use opentelemetry::sdk::metrics::aggregators::{
-            histogram, last_value, min_max_sum_count, SumAggregator,
+            exponential_histogram, histogram, last_value, min_max_sum_count, SumAggregator,
         };
         use opentelemetry::sdk::{InstrumentationLibrary, Resource};
         use std::cmp::Ordering;
@@ -547,6 +663,88 @@ mod tests {
                 assert_eq!(expect, metric);
             }
 
+            // Non-monotonic sum
+            {
+                let descriptor = Descriptor::new(
+                    "test".to_string(),
+                    "test",
+                    None,
+                    InstrumentKind::UpDownCounter,
+                    NumberKind::I64,
+                );
+                let aggregator = SumAggregator::default();
+                aggregator.update(&Number::from(-12_i64), &descriptor)?;
+                let wrapped_aggregator: Arc<dyn Aggregator + Send + Sync> = Arc::new(aggregator);
+                let record = record(
+                    &descriptor,
+                    &label_set,
+                    &resource,
+                    Some(&wrapped_aggregator),
+                    start_time.into(),
+                    end_time.into(),
+                );
+                let metric = record_to_metric(&record, &ExportKindSelector::Cumulative)?;
+
+                let expect = Metric {
+                    name: "test".to_string(),
+                    description: "".to_string(),
+                    unit: "".to_string(),
+                    data: Some(Data::Sum(Sum {
+                        data_points: vec![NumberDataPoint {
+                            attributes: str_kv_labels.clone(),
+                            labels: vec![],
+                            start_time_unix_nano: 1608891000000000000,
+                            time_unix_nano: 1608891030000000000,
+                            value: Some((-12i64).into()),
+                            exemplars: vec![],
+                        }],
+                        aggregation_temporality: 2,
+                        is_monotonic: false,
+                    })),
+                };
+
+                assert_eq!(expect, metric);
+            }
+
+            // Delta temporality
+            {
+                for (kind, expected) in &[
+                    (InstrumentKind::Counter, AggregationTemporality::Delta),
+                    (
+                        InstrumentKind::UpDownCounter,
+                        AggregationTemporality::Cumulative,
+                    ),
+                ] {
+                    let descriptor = Descriptor::new(
+                        "test".to_string(),
+                        "test",
+                        None,
+                        kind.clone(),
+                        NumberKind::I64,
+                    );
+                    let aggregator = SumAggregator::default();
+                    aggregator.update(&Number::from(12_i64), &descriptor)?;
+                    let wrapped_aggregator: Arc<dyn Aggregator + Send + Sync> =
+                        Arc::new(aggregator);
+                    let record = record(
+                        &descriptor,
+                        &label_set,
+                        &resource,
+                        Some(&wrapped_aggregator),
+                        start_time.into(),
+                        end_time.into(),
+                    );
+                    let metric = record_to_metric(&record, &DeltaTemporality)?;
+
+                    match metric.data {
+                        Some(Data::Sum(sum)) => {
+                            assert_eq!(sum.aggregation_temporality, *expected as i32)
+                        }
+                        other => panic!("unexpected data {:?}", other),
+                    }
+                }
+            }
+
             // Last Value
             {
                 let descriptor = Descriptor::new(
@@ -678,7 +876,7 @@ mod tests {
                     unit: "".to_string(),
                     data: Some(Data::Histogram(Histogram {
                         data_points: vec![HistogramDataPoint {
-                            attributes: str_kv_labels,
+                            attributes: str_kv_labels.clone(),
                             labels: vec![],
                             start_time_unix_nano: 1608891000000000000,
                             time_unix_nano: 1608891030000000000,
@@ -695,6 +893,73 @@ mod tests {
                 assert_eq!(expect, metric);
             }
 
+            // Exponential histogram
+            {
+                let descriptor = Descriptor::new(
+                    "test".to_string(),
+                    "test",
+                    None,
+                    InstrumentKind::ValueRecorder,
+                    NumberKind::I64,
+                );
+                // at scale 0 the bucket boundaries are the powers of 2
+                let aggregator = exponential_histogram(160, 0);
+                let below = |value: f64| f64::from_bits(value.to_bits() + 1);
+                let vals: [Number; 5] = [
+                    (-1i64).into(),
+                    0i64.into(),
+                    1i64.into(),
+                    2i64.into(),
+                    3i64.into(),
+                ];
+                for val in vals.iter() {
+                    aggregator.update(val, &descriptor)?;
+                }
+                let wrapped_aggregator: Arc<dyn Aggregator + Send + Sync> = Arc::new(aggregator);
+                let record = record(
+                    &descriptor,
+                    &label_set,
+                    &resource,
+                    Some(&wrapped_aggregator),
+                    start_time.into(),
+                    end_time.into(),
+                );
+                let metric = record_to_metric(&record, &ExportKindSelector::Cumulative)?;
+
+                let expect = Metric {
+                    name: "test".to_string(),
+                    description: "".to_string(),
+                    unit: "".to_string(),
+                    data: Some(Data::Histogram(Histogram {
+                        data_points: vec![HistogramDataPoint {
+                            attributes: str_kv_labels.clone(),
+                            labels: vec![],
+                            start_time_unix_nano: 1608891000000000000,
+                            time_unix_nano: 1608891030000000000,
+                            count: 5,
+                            sum: 5f64,
+                            // -1 in [-1, -0.5), 0 in its own bucket, nothing in (0, 0.5],
+                            // then 1, 2 and 3 in (0.5, 1], (1, 2] and (2, 4]
+                            bucket_counts: vec![0, 1, 0, 1, 0, 1, 1, 1, 0],
+                            explicit_bounds: vec![
+                                below(-1.0),
+                                below(-0.5),
+                                below(-0.0),
+                                0.0,
+                                0.5,
+                                1.0,
+                                2.0,
+                                4.0,
+                            ],
+                            exemplars: vec![],
+                        }],
+                        aggregation_temporality: 2,
+                    })),
+                };
+
+                assert_eq!(expect, metric);
+            }
+
             Ok(())
         }
 
@@ -739,7 +1004,7 @@ mod tests {
                         ResourceWrapper::from(Resource::new(kvs.into_iter().map(|(k, v)| {
                             opentelemetry::KeyValue::new(k.to_string(), v.to_string())
                         }))),
-                        InstrumentationLibrary::new(name, version),
+                        InstrumentationLibrary::new(name, version, None),
                         get_metric_with_name(
                             metric_name,
                             vec![(labels, start_time, end_time, value)],
@@ -1,6 +1,6 @@
 use crate::transform::common::to_nanos;
 use opentelemetry::sdk::{self, export::trace::SpanData};
-use opentelemetry::trace::{Link, SpanKind, StatusCode};
+use opentelemetry::trace::{self, Link, SpanKind};
 
 #[cfg(feature = "tonic")]
 mod tonic {
@@ -23,12 +23,22 @@ mod tonic {
         }
     }
 
-    impl From<StatusCode> for status::StatusCode {
-        fn from(status_code: StatusCode) -> Self {
-            match status_code {
-                StatusCode::Ok => status::StatusCode::Ok,
-                StatusCode::Unset => status::StatusCode::Unset,
-                StatusCode::Error => status::StatusCode::Error,
+    impl From<trace::Status> for Status {
+        fn from(span_status: trace::Status) -> Self {
+            match span_status {
+                trace::Status::Ok => Status {
+                    code: status::StatusCode::Ok.into(),
+                    ..Default::default()
+                },
+                trace::Status::Unset => Status {
+                    code: status::StatusCode::Unset.into(),
+                    ..Default::default()
+                },
+                trace::Status::Error { description } => Status {
+                    code: status::StatusCode::Error.into(),
+                    message: description.into_owned(),
+                    ..Default::default()
+                },
             }
         }
     }
@@ -67,7 +77,7 @@ mod tonic {
                     dropped_attributes_count: 0,
                 }),
                 instrumentation_library_spans: vec![InstrumentationLibrarySpans {
-                    instrumentation_library: Default::default(),
+                    instrumentation_library: Some(source_span.instrumentation_lib.into()),
                     spans: vec![Span {
                         trace_id: source_span
                             .span_context
@@ -108,11 +118,7 @@ mod tonic {
                             .collect(),
                         dropped_links_count: source_span.links.dropped_count(),
                         links: source_span.links.into_iter().map(Into::into).collect(),
-                        status: Some(Status {
-                            code: status::StatusCode::from(source_span.status_code).into(),
-                            message: source_span.status_message.into_owned(),
-                            ..Default::default()
-                        }),
+                        status: Some(source_span.status.into()),
                     }],
                 }],
             }
@@ -152,12 +158,22 @@ mod prost {
         }
     }
 
-    impl From<StatusCode> for status::StatusCode {
-        fn from(status_code: StatusCode) -> Self {
-            match status_code {
-                StatusCode::Ok => status::StatusCode::Ok,
-                StatusCode::Unset => status::StatusCode::Unset,
-                StatusCode::Error => status::StatusCode::Error,
+    impl From<trace::Status> for Status {
+        fn from(span_status: trace::Status) -> Self {
+            match span_status {
+                trace::Status::Ok => Status {
+                    code: status::StatusCode::Ok.into(),
+                    ..Default::default()
+                },
+                trace::Status::Unset => Status {
+                    code: status::StatusCode::Unset.into(),
+                    ..Default::default()
+                },
+                trace::Status::Error { description } => Status {
+                    code: status::StatusCode::Error.into(),
+                    message: description.into_owned(),
+                    ..Default::default()
+                },
             }
         }
     }
@@ -196,7 +212,7 @@ mod prost {
                     dropped_attributes_count: 0,
                 }),
                 instrumentation_library_spans: vec![InstrumentationLibrarySpans {
-                    instrumentation_library: Default::default(),
+                    instrumentation_library: Some(source_span.instrumentation_lib.into()),
                     spans: vec![Span {
                         trace_id: source_span
                             .span_context
@@ -237,11 +253,7 @@ mod prost {
                             .collect(),
                         dropped_links_count: source_span.links.dropped_count(),
                         links: source_span.links.into_iter().map(Into::into).collect(),
-                        status: Some(Status {
-                            code: status::StatusCode::from(source_span.status_code).into(),
-                            message: source_span.status_message.into_owned(),
-                            ..Default::default()
-                        }),
+                        status: Some(source_span.status.into()),
                     }],
                 }],
             }
@@ -284,12 +296,22 @@ mod grpcio {
         }
     }
 
-    impl From<StatusCode> for Status_StatusCode {
-        fn from(status_code: StatusCode) -> Self {
-            match status_code {
-                StatusCode::Ok => Status_StatusCode::STATUS_CODE_OK,
-                StatusCode::Unset => Status_StatusCode::STATUS_CODE_UNSET,
-                StatusCode::Error => Status_StatusCode::STATUS_CODE_ERROR,
+    impl From<trace::Status> for Status {
+        fn from(span_status: trace::Status) -> Self {
+            match span_status {
+                trace::Status::Ok => Status {
+                    code: Status_StatusCode::STATUS_CODE_OK,
+                    ..Default::default()
+                },
+                trace::Status::Unset => Status {
+                    code: Status_StatusCode::STATUS_CODE_UNSET,
+                    ..Default::default()
+                },
+                trace::Status::Error { description } => Status {
+                    code: Status_StatusCode::STATUS_CODE_ERROR,
+                    message: description.into_owned(),
+                    ..Default::default()
+                },
             }
         }
     }
@@ -330,7 +352,9 @@ mod grpcio {
                 })),
                 instrumentation_library_spans: RepeatedField::from_vec(vec![
                     InstrumentationLibrarySpans {
-                        instrumentation_library: Default::default(),
+                        instrumentation_library: SingularPtrField::some(
+                            source_span.instrumentation_lib.into(),
+                        ),
                         spans: RepeatedField::from_vec(vec![Span {
                             trace_id: source_span
                                 .span_context
@@ -376,11 +400,7 @@ mod grpcio {
                             links: RepeatedField::from_vec(
                                 source_span.links.into_iter().map(Into::into).collect(),
                             ),
-                            status: SingularPtrField::some(Status {
-                                code: Status_StatusCode::from(source_span.status_code),
-                                message: source_span.status_message.into_owned(),
-                                ..Default::default()
-                            }),
+                            status: SingularPtrField::some(source_span.status.into()),
                             ..Default::default()
                         }]),
                         ..Default::default()
@@ -403,3 +423,93 @@ mod grpcio {
             .into()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    #[cfg(feature = "tonic")]
+    mod tonic {
+        use crate::proto::trace::v1::{span, ResourceSpans};
+        use opentelemetry::sdk::export::trace::SpanData;
+        use opentelemetry::sdk::trace::{EvictedHashMap, EvictedQueue};
+        use opentelemetry::sdk::InstrumentationLibrary;
+        use opentelemetry::trace::{
+            Link, SpanContext, SpanId, SpanKind, Status, TraceId, TraceState,
+        };
+        use opentelemetry::KeyValue;
+        use std::time::SystemTime;
+
+        #[test]
+        fn test_span_kind() {
+            let kinds = vec![
+                (SpanKind::Client, span::SpanKind::Client),
+                (SpanKind::Server, span::SpanKind::Server),
+                (SpanKind::Producer, span::SpanKind::Producer),
+                (SpanKind::Consumer, span::SpanKind::Consumer),
+                (SpanKind::Internal, span::SpanKind::Internal),
+            ];
+
+            for (kind, expected) in kinds {
+                assert_eq!(span::SpanKind::from(kind), expected);
+            }
+        }
+
+        #[test]
+        fn test_link_attributes() {
+            let span_context = SpanContext::new(
+                TraceId::from_u128(1),
+                SpanId::from_u64(2),
+                0,
+                true,
+                TraceState::default(),
+            );
+            let link = Link::new(
+                span_context,
+                vec![
+                    KeyValue::new("relationship", "batch"),
+                    KeyValue::new("index", 3),
+                ],
+            );
+
+            let otlp_link = span::Link::from(link);
+            let keys: Vec<&str> = otlp_link
+                .attributes
+                .iter()
+                .map(|kv| kv.key.as_str())
+                .collect();
+            assert_eq!(keys, vec!["relationship", "index"]);
+            assert_eq!(otlp_link.dropped_attributes_count, 0);
+            assert_eq!(otlp_link.span_id, 2u64.to_be_bytes().to_vec());
+        }
+
+        #[test]
+        fn test_instrumentation_library() {
+            let mut instrumentation_lib =
+                InstrumentationLibrary::new("my-integration", Some("0.1.0"), None);
+            // the `InstrumentationLibrary` message of the opentelemetry-proto revision
+            // in use has no attributes field, they are dropped on export
+            instrumentation_lib.attributes = vec![KeyValue::new("feature", "async")].into();
+            let span_data = SpanData {
+                span_context: SpanContext::empty_context(),
+                parent_span_id: SpanId::invalid(),
+                span_kind: SpanKind::Internal,
+                name: "test".into(),
+                start_time: SystemTime::now(),
+                end_time: SystemTime::now(),
+                attributes: EvictedHashMap::new(1, 0),
+                events: EvictedQueue::new(1),
+                links: EvictedQueue::new(1),
+                status: Status::Unset,
+                resource: None,
+                instrumentation_lib,
+            };
+
+            let resource_spans = ResourceSpans::from(span_data);
+            let library = resource_spans.instrumentation_library_spans[0]
+                .instrumentation_library
+                .as_ref()
+                .expect("instrumentation library should be exported");
+            assert_eq!(library.name, "my-integration");
+            assert_eq!(library.version, "0.1.0");
+        }
+    }
+}
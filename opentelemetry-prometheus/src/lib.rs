@@ -29,7 +29,10 @@
 //! counter.add(100, &[KeyValue::new("key", "value")]);
 //! recorder.record(100, &[KeyValue::new("key", "value")]);
 //!
-//! // Encode data as text or protobuf
+//! // Render data in the text exposition format
+//! let rendered = exporter.render().unwrap();
+//!
+//! // Or encode it as text or protobuf yourself
 //! let encoder = TextEncoder::new();
 //! let metric_families = exporter.registry().gather();
 //! let mut result = Vec::new();
@@ -80,6 +83,7 @@ use opentelemetry::{
     metrics::{registry::RegistryMeterProvider, MetricsError, NumberKind},
     Key, Value,
 };
+use prometheus::{Encoder, TextEncoder};
 use std::sync::{Arc, Mutex};
 use std::time::Duration;
 
@@ -92,6 +96,10 @@ const DEFAULT_CACHE_PERIOD: Duration = Duration::from_secs(0);
 
 const EXPORT_KIND_SELECTOR: ExportKindSelector = ExportKindSelector::Cumulative;
 
+/// The `service.name` resource attribute is exported as the `job` label.
+const SERVICE_NAME: &str = "service.name";
+const JOB_LABEL: &str = "job";
+
 /// Create a new prometheus exporter builder.
 pub fn exporter() -> ExporterBuilder {
     ExporterBuilder::default()
@@ -247,6 +255,18 @@ impl PrometheusExporter {
         &self.registry
     }
 
+    /// Gathers the current metrics of the registry and renders them in the
+    /// prometheus text exposition format, ready to be served from a `/metrics`
+    /// endpoint.
+    pub fn render(&self) -> Result<String, MetricsError> {
+        let mut buffer = Vec::new();
+        TextEncoder::new()
+            .encode(&self.registry.gather(), &mut buffer)
+            .map_err(|err| MetricsError::Other(err.to_string()))?;
+
+        String::from_utf8(buffer).map_err(|err| MetricsError::Other(err.to_string()))
+    }
+
     /// Get this exporter's provider.
     pub fn provider(&self) -> Result<RegistryMeterProvider, MetricsError> {
         self.controller
@@ -439,8 +459,20 @@ fn build_label_pair(key: &Key, value: &Value) -> prometheus::proto::LabelPair {
 fn get_metric_labels(record: &Record<'_>) -> Vec<prometheus::proto::LabelPair> {
     // Duplicate keys are resolved by taking the record label value over
     // the resource value.
-    let iter = labels::merge_iters(record.labels().iter(), record.resource().iter());
-    iter.map(|(key, value)| build_label_pair(key, value))
+    let pairs =
+        labels::merge_iters(record.labels().iter(), record.resource().iter()).collect::<Vec<_>>();
+    // The service name identifies the scrape target, which prometheus calls
+    // `job`, unless a `job` label was already set explicitly.
+    let has_job = pairs.iter().any(|(key, _)| key.as_str() == JOB_LABEL);
+    pairs
+        .into_iter()
+        .map(|(key, value)| {
+            if !has_job && key.as_str() == SERVICE_NAME {
+                build_label_pair(&Key::from_static_str(JOB_LABEL), value)
+            } else {
+                build_label_pair(key, value)
+            }
+        })
         .collect()
 }
 
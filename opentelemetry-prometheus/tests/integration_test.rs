@@ -133,15 +133,42 @@ fn test_sanitization() {
     value_recorder.record(20.0, &labels);
 
     let expected = vec![
-        r#"http_server_duration_bucket{http_host="server",http_method="GET",service_name="Test Service",le="+Inf"} 4"#,
-        r#"http_server_duration_bucket{http_host="server",http_method="GET",service_name="Test Service",le="-0.5"} 1"#,
-        r#"http_server_duration_bucket{http_host="server",http_method="GET",service_name="Test Service",le="1"} 3"#,
-        r#"http_server_duration_count{http_host="server",http_method="GET",service_name="Test Service"} 4"#,
-        r#"http_server_duration_sum{http_host="server",http_method="GET",service_name="Test Service"} 19.6"#,
+        r#"http_server_duration_bucket{http_host="server",http_method="GET",job="Test Service",le="+Inf"} 4"#,
+        r#"http_server_duration_bucket{http_host="server",http_method="GET",job="Test Service",le="-0.5"} 1"#,
+        r#"http_server_duration_bucket{http_host="server",http_method="GET",job="Test Service",le="1"} 3"#,
+        r#"http_server_duration_count{http_host="server",http_method="GET",job="Test Service"} 4"#,
+        r#"http_server_duration_sum{http_host="server",http_method="GET",job="Test Service"} 19.6"#,
     ];
     compare_export(&exporter, expected)
 }
 
+#[test]
+fn render_maps_service_name_to_job() {
+    let exporter = opentelemetry_prometheus::exporter()
+        .with_resource(Resource::new(vec![
+            KeyValue::new("service.name", "my-service"),
+            KeyValue::new("R", "V"),
+        ]))
+        .init();
+    let meter = exporter.provider().unwrap().meter("test", None);
+
+    let counter = meter
+        .u64_counter("a.counter")
+        .with_description("Counts things")
+        .init();
+    counter.add(2, &[KeyValue::new("http/method", "GET")]);
+
+    let rendered = exporter.render().unwrap();
+    assert!(rendered.contains("# HELP a_counter Counts things"));
+    assert!(rendered.contains("# TYPE a_counter counter"));
+    assert!(rendered.contains(r#"a_counter{R="V",http_method="GET",job="my-service"} 2"#));
+
+    // an explicit job label takes precedence
+    counter.add(1, &[KeyValue::new("job", "explicit")]);
+    let rendered = exporter.render().unwrap();
+    assert!(rendered.contains(r#"a_counter{R="V",job="explicit",service_name="my-service"} 1"#));
+}
+
 fn compare_export(exporter: &PrometheusExporter, mut expected: Vec<&'static str>) {
     let mut output = Vec::new();
     let encoder = TextEncoder::new();
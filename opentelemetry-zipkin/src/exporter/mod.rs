@@ -17,8 +17,8 @@ use std::net::SocketAddr;
 /// Default Zipkin collector endpoint
 const DEFAULT_COLLECTOR_ENDPOINT: &str = "http://127.0.0.1:9411/api/v2/spans";
 
-/// Default service name if no service is configured.
-const DEFAULT_SERVICE_NAME: &str = "OpenTelemetry";
+/// Resource key holding the name of the service.
+const SERVICE_NAME_KEY: &str = "service.name";
 
 /// Zipkin span exporter
 #[derive(Debug)]
@@ -44,7 +44,7 @@ pub fn new_pipeline() -> ZipkinPipelineBuilder {
 /// Builder for `ExporterConfig` struct.
 #[derive(Debug)]
 pub struct ZipkinPipelineBuilder {
-    service_name: String,
+    service_name: Option<String>,
     service_addr: Option<SocketAddr>,
     collector_endpoint: String,
     trace_config: Option<sdk::trace::Config>,
@@ -75,7 +75,7 @@ impl Default for ZipkinPipelineBuilder {
             ))]
             client: None,
 
-            service_name: DEFAULT_SERVICE_NAME.to_string(),
+            service_name: None,
             service_addr: None,
             collector_endpoint: DEFAULT_COLLECTOR_ENDPOINT.to_string(),
             trace_config: None,
@@ -86,10 +86,34 @@ impl Default for ZipkinPipelineBuilder {
 impl ZipkinPipelineBuilder {
     /// Initial a Zipkin span exporter.
     ///
+    /// The local endpoint service name is the one assigned with
+    /// [`with_service_name`], or the `service.name` of the trace config
+    /// resource, or [`default_service_name`] if neither of them is set.
+    ///
     /// Returns error if the endpoint is not valid or if no http client is provided.
+    ///
+    /// [`with_service_name`]: ZipkinPipelineBuilder::with_service_name
+    /// [`default_service_name`]: opentelemetry::sdk::resource::default_service_name
     pub fn init_exporter(self) -> Result<Exporter, TraceError> {
+        self.init_exporter_with_config()
+            .map(|(exporter, _)| exporter)
+    }
+
+    fn init_exporter_with_config(
+        self,
+    ) -> Result<(Exporter, Option<sdk::trace::Config>), TraceError> {
         if let Some(client) = self.client {
-            let endpoint = Endpoint::new(self.service_name, self.service_addr);
+            let trace_config = self.trace_config;
+            let service_name = self
+                .service_name
+                .or_else(|| {
+                    trace_config
+                        .as_ref()
+                        .and_then(|config| config.resource.as_ref())
+                        .and_then(|resource| resource_service_name(resource))
+                })
+                .unwrap_or_else(sdk::resource::default_service_name);
+            let endpoint = Endpoint::new(service_name, self.service_addr);
             let exporter = Exporter::new(
                 endpoint,
                 client,
@@ -97,16 +121,15 @@ impl ZipkinPipelineBuilder {
                     .parse()
                     .map_err::<Error, _>(Into::into)?,
             );
-            Ok(exporter)
+            Ok((exporter, trace_config))
         } else {
             Err(Error::NoHttpClient.into())
         }
     }
 
     /// Install the Zipkin trace exporter pipeline with a simple span processor.
-    pub fn install_simple(mut self) -> Result<sdk::trace::Tracer, TraceError> {
-        let config = self.trace_config.take();
-        let exporter = self.init_exporter()?;
+    pub fn install_simple(self) -> Result<sdk::trace::Tracer, TraceError> {
+        let (exporter, config) = self.init_exporter_with_config()?;
         let mut provider_builder =
             sdk::trace::TracerProvider::builder().with_simple_exporter(exporter);
         if let Some(config) = config {
@@ -120,12 +143,8 @@ impl ZipkinPipelineBuilder {
 
     /// Install the Zipkin trace exporter pipeline with a batch span processor using the specified
     /// runtime.
-    pub fn install_batch<R: Runtime>(
-        mut self,
-        runtime: R,
-    ) -> Result<sdk::trace::Tracer, TraceError> {
-        let config = self.trace_config.take();
-        let exporter = self.init_exporter()?;
+    pub fn install_batch<R: Runtime>(self, runtime: R) -> Result<sdk::trace::Tracer, TraceError> {
+        let (exporter, config) = self.init_exporter_with_config()?;
         let mut provider_builder =
             sdk::trace::TracerProvider::builder().with_batch_exporter(exporter, runtime);
         if let Some(config) = config {
@@ -138,8 +157,10 @@ impl ZipkinPipelineBuilder {
     }
 
     /// Assign the service name under which to group traces.
+    ///
+    /// Takes precedence over the `service.name` of the trace config resource.
     pub fn with_service_name<T: Into<String>>(mut self, name: T) -> Self {
-        self.service_name = name.into();
+        self.service_name = Some(name.into());
         self
     }
 
@@ -168,6 +189,15 @@ impl ZipkinPipelineBuilder {
     }
 }
 
+fn resource_service_name(resource: &sdk::Resource) -> Option<String> {
+    resource
+        .iter()
+        .find(|(key, _)| key.as_str() == SERVICE_NAME_KEY)
+        .map(|(_, value)| value.to_string())
+}
+
+// `set_export_timeout` keeps its default, `HttpClient` has no per request
+// timeout and the span processors already abandon exports running too long.
 #[async_trait]
 impl trace::SpanExporter for Exporter {
     /// Export spans to Zipkin collector.
@@ -207,3 +237,68 @@ impl ExportError for Error {
         "zipkin"
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use async_trait::async_trait;
+    use bytes::Bytes;
+    use http::{Request, Response};
+    use opentelemetry::KeyValue;
+    use opentelemetry_http::HttpError;
+
+    #[derive(Debug)]
+    struct NoopHttpClient;
+
+    #[async_trait]
+    impl HttpClient for NoopHttpClient {
+        async fn send(&self, _request: Request<Vec<u8>>) -> Result<Response<Bytes>, HttpError> {
+            Ok(Response::new(Bytes::new()))
+        }
+    }
+
+    fn local_service_name(builder: ZipkinPipelineBuilder) -> serde_json::Value {
+        let exporter = builder
+            .with_http_client(NoopHttpClient)
+            .init_exporter()
+            .unwrap();
+        serde_json::to_value(&exporter.local_endpoint).unwrap()["serviceName"].clone()
+    }
+
+    fn config_with_resource(kvs: Vec<KeyValue>) -> sdk::trace::Config {
+        sdk::trace::config().with_resource(sdk::Resource::new(kvs))
+    }
+
+    #[test]
+    fn service_name_defaults_to_unknown_service() {
+        let default_service_name = sdk::resource::default_service_name();
+        assert!(default_service_name.starts_with("unknown_service:"));
+        assert_eq!(local_service_name(new_pipeline()), default_service_name);
+        assert_eq!(
+            local_service_name(new_pipeline().with_trace_config(config_with_resource(vec![
+                KeyValue::new("service.version", "1.0")
+            ]))),
+            default_service_name
+        );
+    }
+
+    #[test]
+    fn service_name_from_resource() {
+        let builder = new_pipeline().with_trace_config(config_with_resource(vec![KeyValue::new(
+            "service.name",
+            "resource-service",
+        )]));
+        assert_eq!(local_service_name(builder), "resource-service");
+    }
+
+    #[test]
+    fn explicit_service_name_overrides_resource() {
+        let builder = new_pipeline()
+            .with_service_name("explicit-service")
+            .with_trace_config(config_with_resource(vec![KeyValue::new(
+                "service.name",
+                "resource-service",
+            )]));
+        assert_eq!(local_service_name(builder), "explicit-service");
+    }
+}
@@ -1,6 +1,6 @@
 use opentelemetry::{
     sdk::export::trace,
-    trace::{SpanKind, StatusCode},
+    trace::{SpanKind, Status},
     Key, KeyValue,
 };
 use std::collections::HashMap;
@@ -17,16 +17,6 @@ const INSTRUMENTATION_LIBRARY_VERSION: &str = "otel.library.version";
 const OTEL_ERROR_DESCRIPTION: &str = "error";
 const OTEL_STATUS_CODE: &str = "otel.status_code";
 
-/// Converts StatusCode to Option<&'static str>
-/// `Unset` status code is unused.
-fn from_statuscode_to_str(status_code: StatusCode) -> Option<&'static str> {
-    match status_code {
-        StatusCode::Ok => Some("OK"),
-        StatusCode::Unset => None,
-        StatusCode::Error => Some("ERROR"),
-    }
-}
-
 /// Converts `SpanKind` into an `Option<span::Kind>`
 fn into_zipkin_span_kind(kind: SpanKind) -> Option<span::Kind> {
     match kind {
@@ -69,14 +59,12 @@ pub(crate) fn into_zipkin_span(local_endpoint: Endpoint, span_data: trace::SpanD
             )
             .filter(|kv| kv.key.as_str() != "error"),
     );
-    if let Some(status_code) = from_statuscode_to_str(span_data.status_code) {
-        if status_code == "ERROR" {
-            tags.insert(
-                OTEL_ERROR_DESCRIPTION.into(),
-                span_data.status_message.into_owned(),
-            );
-        }
-        tags.insert(OTEL_STATUS_CODE.into(), status_code.into());
+    // `Unset` status is not exported
+    if span_data.status != Status::Unset {
+        tags.insert(OTEL_STATUS_CODE.into(), span_data.status.as_str().into());
+    }
+    if let Status::Error { description } = span_data.status {
+        tags.insert(OTEL_ERROR_DESCRIPTION.into(), description.into_owned());
     }
 
     span::Span::builder()
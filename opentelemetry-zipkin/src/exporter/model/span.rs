@@ -61,7 +61,7 @@ mod tests {
     use crate::exporter::model::{into_zipkin_span, OTEL_ERROR_DESCRIPTION, OTEL_STATUS_CODE};
     use opentelemetry::sdk::export::trace::SpanData;
     use opentelemetry::sdk::trace::{EvictedHashMap, EvictedQueue};
-    use opentelemetry::trace::{SpanContext, SpanId, SpanKind, StatusCode, TraceId};
+    use opentelemetry::trace::{SpanContext, SpanId, SpanKind, Status, TraceId};
     use std::collections::HashMap;
     use std::net::Ipv4Addr;
     use std::time::SystemTime;
@@ -134,31 +134,50 @@ mod tests {
         );
     }
 
-    fn get_set_status_test_data() -> Vec<(
-        StatusCode,
-        String,
-        Option<&'static str>,
-        Option<&'static str>,
-    )> {
-        // status code, status message, whether OTEL_STATUS_CODE is set, whether OTEL_ERROR_DESCRIPTION is set, whether error tag is set
+    fn get_set_status_test_data() -> Vec<(Status, Option<&'static str>, Option<&'static str>)> {
+        // status, whether OTEL_STATUS_CODE is set, whether OTEL_ERROR_DESCRIPTION is set
         vec![
-            (StatusCode::Ok, "".into(), Some("OK"), None),
-            (StatusCode::Error, "".into(), Some("ERROR"), Some("")),
-            (
-                StatusCode::Error,
-                "error msg".into(),
-                Some("ERROR"),
-                Some("error msg"),
-            ),
-            (StatusCode::Unset, "whatever".into(), None, None),
+            (Status::Ok, Some("OK"), None),
+            (Status::error(""), Some("ERROR"), Some("")),
+            (Status::error("error msg"), Some("ERROR"), Some("error msg")),
+            (Status::Unset, None, None),
         ]
     }
 
+    #[test]
+    fn test_span_kind() {
+        let kinds = vec![
+            (SpanKind::Client, Some("CLIENT")),
+            (SpanKind::Server, Some("SERVER")),
+            (SpanKind::Producer, Some("PRODUCER")),
+            (SpanKind::Consumer, Some("CONSUMER")),
+            (SpanKind::Internal, None),
+        ];
+
+        for (span_kind, expected) in kinds {
+            let span_data = SpanData {
+                span_context: SpanContext::empty_context(),
+                parent_span_id: SpanId::from_u64(0),
+                span_kind,
+                name: "".into(),
+                start_time: SystemTime::now(),
+                end_time: SystemTime::now(),
+                attributes: EvictedHashMap::new(20, 20),
+                events: EvictedQueue::new(20),
+                links: EvictedQueue::new(20),
+                status: Status::Unset,
+                resource: None,
+                instrumentation_lib: Default::default(),
+            };
+            let span = into_zipkin_span(Endpoint::new("test".into(), None), span_data);
+            let json = serde_json::to_value(&span).unwrap();
+            assert_eq!(json.get("kind").and_then(|kind| kind.as_str()), expected);
+        }
+    }
+
     #[test]
     fn test_set_status() {
-        for (status_code, status_msg, status_tag_val, status_msg_tag_val) in
-            get_set_status_test_data()
-        {
+        for (status, status_tag_val, status_msg_tag_val) in get_set_status_test_data() {
             let span_data = SpanData {
                 span_context: SpanContext::new(
                     TraceId::from_u128(1),
@@ -175,8 +194,7 @@ mod tests {
                 attributes: EvictedHashMap::new(20, 20),
                 events: EvictedQueue::new(20),
                 links: EvictedQueue::new(20),
-                status_code,
-                status_message: status_msg.into(),
+                status,
                 resource: None,
                 instrumentation_lib: Default::default(),
             };
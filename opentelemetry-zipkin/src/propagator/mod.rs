@@ -11,8 +11,9 @@
 //!    X-B3-Sampled: {sampling_state}
 //!    X-B3-Flags: {debug_flag}
 //!
-//! If `inject_encoding` is set to `B3Encoding::SingleHeader` then `b3` header is used to inject
-//! and extract. Otherwise, separate headers are used to inject and extract.
+//! If `inject_encoding` is set to `B3Encoding::SingleHeader` then `b3` header is used to inject.
+//! Otherwise, separate headers are used to inject. Regardless of the encoding, extraction tries
+//! the `b3` header first and falls back to the separate headers if it is missing or malformed.
 use opentelemetry::{
     propagation::{text_map_propagator::FieldIter, Extractor, Injector, TextMapPropagator},
     trace::{
@@ -90,6 +91,27 @@ impl Propagator {
         }
     }
 
+    /// Create the propagator for a name of the `OTEL_PROPAGATORS` environment
+    /// variable, `b3` for the single header and `b3multi` for the multiple
+    /// header encoding.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use opentelemetry::global;
+    ///
+    /// global::set_text_map_propagator_from_env(|name| {
+    ///     opentelemetry_zipkin::Propagator::from_env_name(name).map(|p| Box::new(p) as _)
+    /// });
+    /// ```
+    pub fn from_env_name(name: &str) -> Option<Self> {
+        match name {
+            "b3" => Some(Propagator::with_encoding(B3Encoding::SingleHeader)),
+            "b3multi" => Some(Propagator::with_encoding(B3Encoding::MultipleHeader)),
+            _ => None,
+        }
+    }
+
     /// Extract trace id from hex encoded &str value.
     fn extract_trace_id(&self, trace_id: &str) -> Result<TraceId, ()> {
         // Only allow lower case hex string
@@ -114,17 +136,24 @@ impl Propagator {
         }
     }
 
-    /// Extract sampled state from encoded &str value
+    /// Extract sampled state from encoded &str value of the multiple headers.
     /// For legacy support and  being lenient to other tracing implementations we
     /// allow "true" and "false" as inputs for interop purposes.
     fn extract_sampled_state(&self, sampled: &str) -> Result<u8, ()> {
         match sampled {
             "0" | "false" => Ok(TRACE_FLAG_NOT_SAMPLED),
+            "1" | "true" => Ok(TRACE_FLAG_SAMPLED),
+            _ => Err(()),
+        }
+    }
+
+    /// Extract sampling state from the encoded &str value of the single header,
+    /// where `d` marks a debug trace.
+    fn extract_single_sampling_state(&self, sampling_state: &str) -> Result<u8, ()> {
+        match sampling_state {
+            "0" => Ok(TRACE_FLAG_NOT_SAMPLED),
             "1" => Ok(TRACE_FLAG_SAMPLED),
-            "true" if !self.inject_encoding.support(&B3Encoding::SingleHeader) => {
-                Ok(TRACE_FLAG_SAMPLED)
-            }
-            "d" if self.inject_encoding.support(&B3Encoding::SingleHeader) => Ok(TRACE_FLAG_DEBUG),
+            "d" => Ok(TRACE_FLAG_DEBUG),
             _ => Err(()),
         }
     }
@@ -149,7 +178,7 @@ impl Propagator {
         let trace_id = self.extract_trace_id(parts[0])?;
         let span_id = self.extract_span_id(parts[1])?;
         let trace_flags = if parts.len() > 2 {
-            self.extract_sampled_state(parts[2])?
+            self.extract_single_sampling_state(parts[2])?
         } else {
             TRACE_FLAG_DEFERRED
         };
@@ -545,6 +574,48 @@ mod tests {
             )
         }
 
+        // Extraction does not depend on the inject encoding
+        let mut extractor = HashMap::new();
+        extractor.insert(
+            B3_SINGLE_HEADER.to_string(),
+            "4bf92f3577b34da6a3ce929d0e0e4736-00f067aa0ba902b7-d".to_string(),
+        );
+        assert_eq!(
+            multi_header_propagator
+                .extract(&extractor)
+                .span()
+                .span_context()
+                .clone(),
+            SpanContext::new(
+                TraceId::from_u128(TRACE_ID_HEX),
+                SpanId::from_u64(SPAN_ID_HEX),
+                TRACE_FLAG_DEBUG,
+                true,
+                TraceState::default()
+            ),
+        );
+        let extractor = extract_extrator_from_test_data(
+            Some(TRACE_ID_STR),
+            Some(SPAN_ID_STR),
+            Some("true"),
+            None,
+            None,
+        );
+        assert_eq!(
+            single_multi_propagator
+                .extract(&extractor)
+                .span()
+                .span_context()
+                .clone(),
+            SpanContext::new(
+                TraceId::from_u128(TRACE_ID_HEX),
+                SpanId::from_u64(SPAN_ID_HEX),
+                TRACE_FLAG_SAMPLED,
+                true,
+                TraceState::default()
+            ),
+        );
+
         // Test invalid multiple headers
         for (trace, span, sampled, debug, parent) in multi_header_extract_invalid_data() {
             let extractor = extract_extrator_from_test_data(trace, span, sampled, debug, parent);
@@ -684,4 +755,23 @@ mod tests {
             ]
         );
     }
+
+    #[test]
+    fn test_from_env_name() {
+        assert_eq!(
+            Propagator::from_env_name("b3")
+                .unwrap()
+                .fields()
+                .collect::<Vec<_>>(),
+            vec![B3_SINGLE_HEADER]
+        );
+        assert_eq!(
+            Propagator::from_env_name("b3multi")
+                .unwrap()
+                .fields()
+                .count(),
+            4
+        );
+        assert!(Propagator::from_env_name("jaeger").is_none());
+    }
 }
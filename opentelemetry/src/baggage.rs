@@ -189,8 +189,42 @@ impl Baggage {
 
     /// Removes a name from the baggage, returning the value
     /// corresponding to the name if the pair was previously in the map.
+    ///
+    /// # Examples
+    ///
+    /// Stripping internal entries before propagating a context downstream:
+    ///
+    /// ```
+    /// use opentelemetry::{baggage::{Baggage, BaggageExt}, Context, KeyValue};
+    ///
+    /// let cx = Context::new().with_baggage(vec![
+    ///     KeyValue::new("user.id", "42"),
+    ///     KeyValue::new("internal.tenant", "acme"),
+    /// ]);
+    ///
+    /// let mut baggage: Baggage = cx
+    ///     .baggage()
+    ///     .iter()
+    ///     .map(|(key, value)| (key.clone(), value.clone()))
+    ///     .collect();
+    /// let internal_keys: Vec<_> = baggage
+    ///     .iter()
+    ///     .filter(|(key, _)| key.as_str().starts_with("internal."))
+    ///     .map(|(key, _)| key.clone())
+    ///     .collect();
+    /// for key in internal_keys {
+    ///     baggage.remove(key);
+    /// }
+    ///
+    /// let downstream_cx = cx.with_value(baggage);
+    /// assert_eq!(downstream_cx.baggage().len(), 1);
+    /// assert!(downstream_cx.baggage().get("internal.tenant").is_none());
+    /// ```
     pub fn remove<K: Into<Key>>(&mut self, key: K) -> Option<(Value, BaggageMetadata)> {
-        self.inner.remove(&key.into())
+        let (key, (value, metadata)) = self.inner.remove_entry(&key.into())?;
+        // free the length of the pair so it can be used by later insertions
+        self.kv_content_len -= metadata.as_str().len() + value.as_str().len() + key.as_str().len();
+        Some((value, metadata))
     }
 
     /// Returns the number of attributes for this baggage
@@ -203,7 +237,7 @@ impl Baggage {
         self.inner.is_empty()
     }
 
-    /// Gets an iterator over the baggage items, sorted by name.
+    /// Gets an iterator over the baggage items, in arbitrary order.
     pub fn iter(&self) -> Iter<'_> {
         self.into_iter()
     }
@@ -475,6 +509,26 @@ mod tests {
     use super::*;
     use std::iter::FromIterator;
 
+    #[test]
+    fn remove_frees_length() {
+        let mut baggage = Baggage::new();
+        let value = "v".repeat(MAX_BYTES_FOR_ONE_PAIR - 10);
+        for i in 0..(MAX_LEN_OF_ALL_PAIRS / MAX_BYTES_FOR_ONE_PAIR) {
+            baggage.insert(format!("key{}", i), value.clone());
+        }
+        assert_eq!(baggage.len(), 2);
+        baggage.insert("extra", value.clone());
+        assert_eq!(baggage.len(), 2, "total length limit reached");
+
+        assert!(baggage.remove("key0").is_some());
+        assert!(baggage.remove("key0").is_none());
+        assert_eq!(baggage.len(), 1);
+
+        baggage.insert("extra", value);
+        assert_eq!(baggage.len(), 2);
+        assert_eq!(baggage.iter().count(), 2);
+    }
+
     #[test]
     fn insert_non_ascii_key() {
         let mut baggage = Baggage::new();
@@ -7,7 +7,7 @@ use std::marker::PhantomData;
 use std::sync::Arc;
 
 thread_local! {
-    static CURRENT_CONTEXT: RefCell<Context> = RefCell::new(Context::default());
+    static CURRENT_CONTEXT: RefCell<ContextStack> = RefCell::new(ContextStack::default());
     static DEFAULT_CONTEXT: Context = Context::default();
 }
 
@@ -33,10 +33,38 @@ thread_local! {
 /// Contexts can be associated with the caller's current execution unit on a
 /// given thread via the [`attach`] method, and previous contexts can be restored
 /// by dropping the returned [`ContextGuard`]. Context can be nested, and will
-/// restore their parent outer context when detached on drop. To access the
+/// restore their parent outer context when detached on drop. If guards are
+/// dropped out of order, the most recently attached context that is still
+/// attached remains current. To access the
 /// values of the context, a snapshot can be created via the [`Context::current`]
 /// method.
 ///
+/// ## Propagating contexts across threads
+///
+/// A [`Context`] is `Clone + Send + Sync`, including the active span and
+/// baggage it carries, as all values are required to be `Send + Sync`. The
+/// current context is however stored per thread, so work moved to another
+/// thread has to capture it first and attach it on the new thread:
+///
+/// ```
+/// use opentelemetry::Context;
+/// use std::thread;
+///
+/// #[derive(Debug, PartialEq)]
+/// struct RequestId(u64);
+///
+/// let _guard = Context::new().with_value(RequestId(42)).attach();
+///
+/// // capture the current context before spawning
+/// let cx = Context::current();
+/// thread::spawn(move || {
+///     let _guard = cx.attach();
+///     assert_eq!(Context::current().get(), Some(&RequestId(42)));
+/// })
+/// .join()
+/// .unwrap();
+/// ```
+///
 /// [`Context::current`]: Context::current()
 /// [`get`]: Context::get()
 /// [`with_value`]: Context::with_value()
@@ -283,12 +311,12 @@ impl Context {
     /// assert_eq!(Context::current().get::<ValueA>(), None);
     /// ```
     pub fn attach(self) -> ContextGuard {
-        let previous_cx = CURRENT_CONTEXT
-            .try_with(|current| current.replace(self))
+        let cx_id = CURRENT_CONTEXT
+            .try_with(|stack| stack.borrow_mut().push(self))
             .ok();
 
         ContextGuard {
-            previous_cx,
+            cx_id,
             _marker: PhantomData,
         }
     }
@@ -303,28 +331,70 @@ impl fmt::Debug for Context {
 }
 
 /// A guard that resets the current context to the prior context when dropped.
+///
+/// The guard is `!Send` as the current context is stored per thread:
+///
+/// ```compile_fail
+/// fn assert_send<T: Send>() {}
+/// assert_send::<opentelemetry::ContextGuard>();
+/// ```
 #[allow(missing_debug_implementations)]
 pub struct ContextGuard {
-    previous_cx: Option<Context>,
+    cx_id: Option<u64>,
     // ensure this type is !Send as it relies on thread locals
     _marker: PhantomData<*const ()>,
 }
 
 impl Drop for ContextGuard {
     fn drop(&mut self) {
-        if let Some(previous_cx) = self.previous_cx.take() {
-            let _ = CURRENT_CONTEXT.try_with(|current| current.replace(previous_cx));
+        if let Some(cx_id) = self.cx_id.take() {
+            // the detached context is dropped after the stack is released, as
+            // dropping its values may access the current context again.
+            let _detached = CURRENT_CONTEXT
+                .try_with(|stack| stack.borrow_mut().remove(cx_id))
+                .ok()
+                .flatten();
         }
     }
 }
 
+/// The contexts attached to a thread, in the order they were attached.
+///
+/// Each attached context is identified by the id held in its [`ContextGuard`],
+/// so guards can be dropped in any order without restoring a context that was
+/// already detached.
+#[derive(Default)]
+struct ContextStack {
+    base: Context,
+    next_id: u64,
+    attached: Vec<(u64, Context)>,
+}
+
+impl ContextStack {
+    fn current(&self) -> &Context {
+        self.attached.last().map(|(_, cx)| cx).unwrap_or(&self.base)
+    }
+
+    fn push(&mut self, cx: Context) -> u64 {
+        let cx_id = self.next_id;
+        self.next_id = self.next_id.wrapping_add(1);
+        self.attached.push((cx_id, cx));
+        cx_id
+    }
+
+    fn remove(&mut self, cx_id: u64) -> Option<Context> {
+        let pos = self.attached.iter().rposition(|(id, _)| *id == cx_id)?;
+        Some(self.attached.remove(pos).1)
+    }
+}
+
 /// Executes a closure with a reference to this thread's current context.
 ///
 /// Note: This function will panic if you attempt to attach another context
 /// while the context is still borrowed.
 fn get_current<F: FnMut(&Context) -> T, T>(mut f: F) -> T {
     CURRENT_CONTEXT
-        .try_with(|cx| f(&*cx.borrow()))
+        .try_with(|stack| f(stack.borrow().current()))
         .unwrap_or_else(|_| DEFAULT_CONTEXT.with(|cx| f(&*cx)))
 }
 
@@ -354,6 +424,29 @@ impl Hasher for IdHasher {
 mod tests {
     use super::*;
 
+    #[test]
+    fn context_is_send_sync_clone() {
+        fn assert_send_sync_clone<T: Send + Sync + Clone>() {}
+        assert_send_sync_clone::<Context>();
+    }
+
+    #[test]
+    fn attach_captured_context_on_other_thread() {
+        #[derive(Debug, PartialEq)]
+        struct ValueA(&'static str);
+        let _guard = Context::new().with_value(ValueA("a")).attach();
+
+        let cx = Context::current();
+        std::thread::spawn(move || {
+            // the current context is per thread
+            assert_eq!(Context::current().get::<ValueA>(), None);
+            let _guard = cx.attach();
+            assert_eq!(Context::current().get(), Some(&ValueA("a")));
+        })
+        .join()
+        .unwrap();
+    }
+
     #[test]
     fn nested_contexts() {
         #[derive(Debug, PartialEq)]
@@ -380,4 +473,32 @@ mod tests {
         assert_eq!(current.get(), Some(&ValueA("a")));
         assert_eq!(current.get::<ValueB>(), None);
     }
+
+    #[test]
+    fn guards_dropped_out_of_order() {
+        #[derive(Debug, PartialEq)]
+        struct ValueA(u64);
+
+        let outer_guard = Context::new().with_value(ValueA(1)).attach();
+        {
+            let middle_guard = Context::current_with_value(ValueA(2)).attach();
+            let inner_guard = Context::current_with_value(ValueA(3)).attach();
+            assert_eq!(Context::current().get(), Some(&ValueA(3)));
+
+            // dropping a guard below the top keeps the most recent context current
+            drop(middle_guard);
+            assert_eq!(Context::current().get(), Some(&ValueA(3)));
+
+            // and the detached context is never restored
+            drop(inner_guard);
+            assert_eq!(Context::current().get(), Some(&ValueA(1)));
+
+            let _scoped_guard = Context::current_with_value(ValueA(4)).attach();
+            assert_eq!(Context::current().get(), Some(&ValueA(4)));
+        }
+
+        assert_eq!(Context::current().get(), Some(&ValueA(1)));
+        drop(outer_guard);
+        assert_eq!(Context::current().get::<ValueA>(), None);
+    }
 }
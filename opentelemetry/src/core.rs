@@ -94,6 +94,21 @@ impl fmt::Display for Key {
 }
 
 /// Array of homogeneous values
+///
+/// Each variant holds values of a single type, mixed-type arrays can not be
+/// constructed.
+///
+/// # Examples
+///
+/// ```
+/// use opentelemetry::{Array, KeyValue, Value};
+///
+/// let kv = KeyValue::new("http.request.header.accept", vec!["text/html", "application/json"]);
+/// assert_eq!(
+///     kv.value,
+///     Value::Array(Array::String(vec!["text/html".into(), "application/json".into()]))
+/// );
+/// ```
 #[cfg_attr(feature = "serialize", derive(Deserialize, Serialize))]
 #[derive(Clone, Debug, PartialEq)]
 pub enum Array {
@@ -157,6 +172,20 @@ into_array!(
     (Vec<Cow<'static, str>>, Array::String),
 );
 
+impl From<Vec<&'static str>> for Array {
+    /// Convenience method for creating an `Array` from `&'static str` values.
+    fn from(values: Vec<&'static str>) -> Self {
+        Array::String(values.into_iter().map(Cow::Borrowed).collect())
+    }
+}
+
+impl From<Vec<String>> for Array {
+    /// Convenience method for creating an `Array` from `String` values.
+    fn from(values: Vec<String>) -> Self {
+        Array::String(values.into_iter().map(Cow::Owned).collect())
+    }
+}
+
 /// Value types for use in `KeyValue` pairs.
 #[cfg_attr(feature = "serialize", derive(Deserialize, Serialize))]
 #[derive(Clone, Debug, PartialEq)]
@@ -209,6 +238,28 @@ from_values!(
     (i64, Value::I64);
     (f64, Value::F64);
     (Cow<'static, str>, Value::String);
+    (Array, Value::Array);
+);
+
+macro_rules! from_array_values {
+    ($($t:ty,)+) => {
+        $(
+            impl From<$t> for Value {
+                fn from(t: $t) -> Self {
+                    Value::Array(t.into())
+                }
+            }
+        )+
+    }
+}
+
+from_array_values!(
+    Vec<bool>,
+    Vec<i64>,
+    Vec<f64>,
+    Vec<Cow<'static, str>>,
+    Vec<&'static str>,
+    Vec<String>,
 );
 
 impl From<&'static str> for Value {
@@ -283,3 +334,32 @@ impl AsRef<str> for Unit {
         self.0.as_ref()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn array_values_from_vecs() {
+        assert_eq!(
+            Value::from(vec![true, false]),
+            Value::Array(Array::Bool(vec![true, false]))
+        );
+        assert_eq!(
+            Value::from(vec![1, 2]),
+            Value::Array(Array::I64(vec![1, 2]))
+        );
+        assert_eq!(
+            Value::from(vec![1.5, 2.5]),
+            Value::Array(Array::F64(vec![1.5, 2.5]))
+        );
+        assert_eq!(
+            Value::from(vec!["a".to_string(), "b".to_string()]),
+            Value::from(vec!["a", "b"])
+        );
+        assert_eq!(
+            KeyValue::new("key", vec!["a", "b"]).value.as_str(),
+            r#"["a","b"]"#
+        );
+    }
+}
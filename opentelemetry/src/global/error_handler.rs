@@ -57,6 +57,10 @@ pub fn handle_error<T: Into<Error>>(err: T) {
 }
 
 /// Set global error handler.
+///
+/// The handler receives every error reported through [`handle_error`], like
+/// failed exports, dropped spans, and malformed environment variables, instead
+/// of writing them to stderr.
 pub fn set_error_handler<F>(f: F) -> std::result::Result<(), Error>
 where
     F: Fn(Error) + Send + Sync + 'static,
@@ -66,3 +70,44 @@ where
         .map(|mut handler| *handler = Some(ErrorHandler(Box::new(f))))
         .map_err(Into::into)
 }
+
+#[cfg(all(test, feature = "trace"))]
+mod tests {
+    use super::*;
+    use std::sync::{Arc, Mutex};
+
+    /// Restores the default error handler when dropped.
+    struct DefaultHandlerGuard;
+
+    impl Drop for DefaultHandlerGuard {
+        fn drop(&mut self) {
+            if let Ok(mut handler) = GLOBAL_ERROR_HANDLER.write() {
+                *handler = None;
+            }
+        }
+    }
+
+    #[test]
+    fn custom_error_handler_receives_errors() {
+        let _guard = DefaultHandlerGuard;
+        let received = Arc::new(Mutex::new(Vec::new()));
+        let handler_received = received.clone();
+        set_error_handler(move |err| {
+            // handlers can tell the signals apart
+            let message = match err {
+                Error::Trace(err) => format!("trace: {}", err),
+                other => other.to_string(),
+            };
+            handler_received.lock().unwrap().push(message);
+        })
+        .unwrap();
+
+        handle_error(TraceError::from("custom handler test error"));
+        handle_error(Error::Other("custom handler other error".to_string()));
+
+        // other tests may report errors through the global handler concurrently
+        let received = received.lock().unwrap();
+        assert!(received.contains(&"trace: custom handler test error".to_string()));
+        assert!(received.contains(&"custom handler other error".to_string()));
+    }
+}
@@ -56,7 +56,12 @@ pub fn meter_provider() -> GlobalMeterProvider {
 ///
 /// If the name is an empty string, the provider will use a default name.
 ///
+/// Until a provider is installed with [`set_meter_provider`], meters are
+/// created by a [`NoopMeterProvider`] and their instruments record nothing.
+///
 /// This is a more convenient way of expressing `global::meter_provider().meter(name)`.
+///
+/// [`NoopMeterProvider`]: crate::metrics::noop::NoopMeterProvider
 pub fn meter(name: &'static str) -> Meter {
     meter_provider().meter(name, None)
 }
@@ -65,3 +70,43 @@ pub fn meter(name: &'static str) -> Meter {
 pub fn meter_with_version(name: &'static str, version: &'static str) -> Meter {
     meter_provider().meter(name, Some(version))
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::metrics::noop::NoopMeterProvider;
+    use std::sync::Mutex;
+
+    #[derive(Debug, Default)]
+    struct RecordingMeterProvider {
+        names: Arc<Mutex<Vec<&'static str>>>,
+    }
+
+    impl MeterProvider for RecordingMeterProvider {
+        fn meter(&self, name: &'static str, version: Option<&'static str>) -> Meter {
+            self.names.lock().unwrap().push(name);
+            NoopMeterProvider::new().meter(name, version)
+        }
+    }
+
+    #[test]
+    fn global_meter_uses_installed_provider() {
+        // the default no-op meter accepts measurements without recording them
+        let default_meter = meter("default");
+        default_meter.u64_counter("counter").init().add(1, &[]);
+        default_meter
+            .f64_value_recorder("recorder")
+            .init()
+            .record(1.0, &[]);
+
+        let provider = RecordingMeterProvider::default();
+        let names = provider.names.clone();
+        set_meter_provider(provider);
+
+        meter("installed");
+        meter_with_version("versioned", "1.0");
+        assert_eq!(*names.lock().unwrap(), vec!["installed", "versioned"]);
+
+        set_meter_provider(NoopMeterProvider::new());
+    }
+}
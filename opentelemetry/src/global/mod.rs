@@ -152,11 +152,13 @@ pub use metrics::{
 };
 #[cfg(feature = "trace")]
 #[cfg_attr(docsrs, doc(cfg(feature = "trace")))]
-pub use propagation::{get_text_map_propagator, set_text_map_propagator};
+pub use propagation::{
+    get_text_map_propagator, set_text_map_propagator, set_text_map_propagator_from_env,
+};
 #[cfg(feature = "trace")]
 #[cfg_attr(docsrs, doc(cfg(feature = "trace")))]
 pub use trace::{
     force_flush_tracer_provider, set_tracer_provider, shutdown_tracer_provider, tracer,
-    tracer_provider, tracer_with_version, BoxedSpan, BoxedTracer, GenericTracer,
+    tracer_provider, tracer_with_version, BoxedSpan, BoxedTracer, GenericSpan, GenericTracer,
     GenericTracerProvider, GlobalTracerProvider,
 };
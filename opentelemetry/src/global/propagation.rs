@@ -28,6 +28,26 @@ pub fn set_text_map_propagator<P: TextMapPropagator + Send + Sync + 'static>(pro
         .map(|mut global_propagator| *global_propagator = Box::new(propagator));
 }
 
+/// Sets the propagators listed in the `OTEL_PROPAGATORS` environment variable
+/// as the current global propagator.
+///
+/// See [`TextMapCompositePropagator::from_env`] for the supported names and
+/// how other propagators are looked up with `resolve`.
+///
+/// # Examples
+///
+/// ```
+/// use opentelemetry::global;
+///
+/// global::set_text_map_propagator_from_env(|_| None);
+/// ```
+pub fn set_text_map_propagator_from_env<F>(resolve: F)
+where
+    F: Fn(&str) -> Option<Box<dyn TextMapPropagator + Send + Sync>>,
+{
+    set_text_map_propagator(TextMapCompositePropagator::from_env(resolve))
+}
+
 /// Executes a closure with a reference to the current global [`TextMapPropagator`] propagator.
 ///
 /// # Examples
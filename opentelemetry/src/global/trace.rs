@@ -14,7 +14,7 @@ use std::time::SystemTime;
 #[derive(Debug)]
 pub struct BoxedSpan(Box<DynSpan>);
 
-type DynSpan = dyn trace::Span + Send + Sync;
+type DynSpan = dyn GenericSpan + Send + Sync;
 
 impl trace::Span for BoxedSpan {
     /// Records events at a specific time in the context of a given `Span`.
@@ -51,15 +51,23 @@ impl trace::Span for BoxedSpan {
         self.0.set_attribute(attribute)
     }
 
+    /// Adds a `Link` to another `Span` after this `Span` was started.
+    fn add_link(&mut self, link: trace::Link) {
+        self.0.add_link(link)
+    }
+
     /// Sets the status of the `Span`. If used, this will override the default `Span`
     /// status, which is `Unset`.
-    fn set_status(&mut self, code: trace::StatusCode, message: String) {
-        self.0.set_status(code, message)
+    fn set_status(&mut self, status: trace::Status) {
+        self.0.set_status(status)
     }
 
     /// Updates the `Span`'s name.
-    fn update_name(&mut self, new_name: String) {
-        self.0.update_name(new_name)
+    fn update_name<T>(&mut self, new_name: T)
+    where
+        T: Into<Cow<'static, str>>,
+    {
+        self.0.update_name_boxed(new_name.into())
     }
 
     /// Finishes the span with given timestamp.
@@ -68,6 +76,23 @@ impl trace::Span for BoxedSpan {
     }
 }
 
+/// Allows a specific [`Span`] to be used generically by [`BoxedSpan`]
+/// instances by mirroring the methods of the interface that take generic
+/// parameters.
+///
+/// [`Span`]: crate::trace::Span
+pub trait GenericSpan: trace::Span {
+    /// Updates the `Span`'s name.
+    fn update_name_boxed(&mut self, new_name: Cow<'static, str>);
+}
+
+impl<S: trace::Span> GenericSpan for S {
+    /// Updates the `Span`'s name.
+    fn update_name_boxed(&mut self, new_name: Cow<'static, str>) {
+        self.update_name(new_name)
+    }
+}
+
 /// Wraps the [`GlobalTracerProvider`]'s [`Tracer`] so it can be used generically by
 /// applications without knowing the underlying type.
 ///
@@ -170,6 +195,22 @@ pub trait GenericTracerProvider: fmt::Debug + 'static {
         version: Option<&'static str>,
     ) -> Box<dyn GenericTracer + Send + Sync>;
 
+    /// Creates a named tracer instance with a schema url that is a trait object
+    /// through the underlying `TracerProvider`.
+    fn versioned_tracer_boxed(
+        &self,
+        name: &'static str,
+        version: Option<&'static str>,
+        schema_url: Option<&'static str>,
+    ) -> Box<dyn GenericTracer + Send + Sync>;
+
+    /// Creates a tracer instance described by the config that is a trait object
+    /// through the underlying `TracerProvider`.
+    fn tracer_with_config_boxed(
+        &self,
+        config: trace::TracerConfig,
+    ) -> Box<dyn GenericTracer + Send + Sync>;
+
     /// Force flush all remaining spans in span processors and return results.
     fn force_flush(&self) -> Vec<TraceResult<()>>;
 }
@@ -189,6 +230,24 @@ where
         Box::new(self.get_tracer(name, version))
     }
 
+    /// Return a boxed generic tracer with schema url
+    fn versioned_tracer_boxed(
+        &self,
+        name: &'static str,
+        version: Option<&'static str>,
+        schema_url: Option<&'static str>,
+    ) -> Box<dyn GenericTracer + Send + Sync> {
+        Box::new(self.versioned_tracer(name, version, schema_url))
+    }
+
+    /// Return a boxed generic tracer described by the config
+    fn tracer_with_config_boxed(
+        &self,
+        config: trace::TracerConfig,
+    ) -> Box<dyn GenericTracer + Send + Sync> {
+        Box::new(self.tracer_with_config(config))
+    }
+
     fn force_flush(&self) -> Vec<TraceResult<()>> {
         self.force_flush()
     }
@@ -226,6 +285,24 @@ impl trace::TracerProvider for GlobalTracerProvider {
         BoxedTracer(self.provider.get_tracer_boxed(name, version))
     }
 
+    /// Find or create a named tracer with schema url using the global provider.
+    fn versioned_tracer(
+        &self,
+        name: &'static str,
+        version: Option<&'static str>,
+        schema_url: Option<&'static str>,
+    ) -> Self::Tracer {
+        BoxedTracer(
+            self.provider
+                .versioned_tracer_boxed(name, version, schema_url),
+        )
+    }
+
+    /// Create a tracer described by the config using the global provider.
+    fn tracer_with_config(&self, config: trace::TracerConfig) -> Self::Tracer {
+        BoxedTracer(self.provider.tracer_with_config_boxed(config))
+    }
+
     /// Force flush all remaining spans in span processors and return results.
     fn force_flush(&self) -> Vec<TraceResult<()>> {
         self.provider.force_flush()
@@ -275,6 +352,10 @@ pub fn tracer_with_version(name: &'static str, version: &'static str) -> BoxedTr
 /// It returns the [`TracerProvider`] instance that was previously mounted as global provider
 /// (e.g. [`NoopTracerProvider`] if a provider had not been set before).
 ///
+/// The previous provider is not flushed, spans buffered in its span processors may be lost once it
+/// is dropped. Call [`force_flush_tracer_provider`] before swapping providers to export them when
+/// reconfiguring the global provider at runtime.
+///
 /// [`TracerProvider`]: crate::trace::TracerProvider
 pub fn set_tracer_provider<P, T, S>(new_provider: P) -> GlobalTracerProvider
 where
@@ -42,6 +42,20 @@ impl Descriptor {
         }
     }
 
+    /// A copy of this descriptor with a different name, used by views to
+    /// rename the exported metric.
+    pub(crate) fn with_name(&self, name: String) -> Self {
+        let mut descriptor = Descriptor::new(
+            name,
+            self.instrumentation_name(),
+            self.instrumentation_version(),
+            self.instrument_kind.clone(),
+            self.number_kind.clone(),
+        );
+        descriptor.config = self.config.clone();
+        descriptor
+    }
+
     /// The metric instrument's name.
     pub fn name(&self) -> &str {
         self.name.as_str()
@@ -55,13 +55,14 @@ impl Meter {
             instrumentation_library: InstrumentationLibrary::new(
                 instrumentation_name.into(),
                 instrumentation_version.map(Into::into),
+                None,
             ),
             core,
         }
     }
 
-    pub(crate) fn instrumentation_library(&self) -> InstrumentationLibrary {
-        self.instrumentation_library
+    pub(crate) fn instrumentation_library(&self) -> &InstrumentationLibrary {
+        &self.instrumentation_library
     }
 
     /// Creates a new integer `CounterBuilder` for `u64` values with the given name.
@@ -233,6 +234,36 @@ impl Meter {
         )
     }
 
+    /// Creates a new integer observable gauge for `u64` values with the given
+    /// name and callback.
+    ///
+    /// Observable gauges are [`ValueObserver`]s, the callback is invoked on
+    /// every collection and the last value observed for a label set is kept.
+    ///
+    /// [`ValueObserver`]: crate::metrics::ValueObserver
+    pub fn u64_observable_gauge<T, F>(&self, name: T, callback: F) -> ValueObserverBuilder<'_, u64>
+    where
+        T: Into<String>,
+        F: Fn(ObserverResult<u64>) + Send + Sync + 'static,
+    {
+        self.u64_value_observer(name, callback)
+    }
+
+    /// Creates a new floating point observable gauge for `f64` values with the
+    /// given name and callback.
+    ///
+    /// Observable gauges are [`ValueObserver`]s, the callback is invoked on
+    /// every collection and the last value observed for a label set is kept.
+    ///
+    /// [`ValueObserver`]: crate::metrics::ValueObserver
+    pub fn f64_observable_gauge<T, F>(&self, name: T, callback: F) -> ValueObserverBuilder<'_, f64>
+    where
+        T: Into<String>,
+        F: Fn(ObserverResult<f64>) + Send + Sync + 'static,
+    {
+        self.f64_value_observer(name, callback)
+    }
+
     /// Creates a new `BatchObserver` that supports making batches of observations
     /// for multiple instruments or returns an error if instrument initialization
     /// fails.
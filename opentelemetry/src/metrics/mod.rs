@@ -72,6 +72,9 @@ pub enum MetricsError {
     /// Fail to export metrics
     #[error("Metrics exporter {} failed with {0}", .0.exporter_name())]
     ExportErr(Box<dyn ExportError>),
+    /// Export took longer than the configured timeout.
+    #[error("Exporting metrics took longer than {} ms", .0.as_millis())]
+    ExportTimedOut(std::time::Duration),
 }
 
 impl<T: ExportError> From<T> for MetricsError {
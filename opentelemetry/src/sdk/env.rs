@@ -2,9 +2,14 @@
 //!
 //! Implementation of `ResourceDetector` to extract a `Resource` from environment
 //! variables.
-use crate::sdk::{resource::ResourceDetector, Resource};
-use crate::KeyValue;
+use crate::sdk::{
+    resource::{ResourceDetector, OTEL_SERVICE_NAME, SERVICE_NAME},
+    Resource,
+};
+use crate::{global, KeyValue};
+use percent_encoding::percent_decode_str;
 use std::env;
+use std::str::FromStr;
 use std::time::Duration;
 
 static OTEL_RESOURCE_ATTRIBUTES: &str = "OTEL_RESOURCE_ATTRIBUTES";
@@ -12,6 +17,9 @@ static OTEL_RESOURCE_ATTRIBUTES: &str = "OTEL_RESOURCE_ATTRIBUTES";
 /// Resource detector implements ResourceDetector and is used to extract
 /// general SDK configuration from environment.
 ///
+/// The attributes are read from `OTEL_RESOURCE_ATTRIBUTES`, a `service.name`
+/// set via `OTEL_SERVICE_NAME` takes precedence over the one in the attributes.
+///
 /// See
 /// [semantic conventions](https://github.com/open-telemetry/opentelemetry-specification/tree/master/specification/resource/semantic_conventions#telemetry-sdk)
 /// for details.
@@ -22,10 +30,12 @@ pub struct EnvResourceDetector {
 
 impl ResourceDetector for EnvResourceDetector {
     fn detect(&self, _timeout: Duration) -> Resource {
-        match env::var(OTEL_RESOURCE_ATTRIBUTES) {
+        let resource = match env::var(OTEL_RESOURCE_ATTRIBUTES) {
             Ok(s) if !s.is_empty() => construct_otel_resources(s),
             Ok(_) | Err(_) => Resource::new(vec![]), // return empty resource
-        }
+        };
+
+        with_service_name(resource, env::var(OTEL_SERVICE_NAME).ok())
     }
 }
 
@@ -44,29 +54,134 @@ impl Default for EnvResourceDetector {
 
 /// Extract key value pairs and construct a resource from resources string like
 /// key1=value1,key2=value2,...
+///
+/// Keys and values may be percent-encoded, malformed entries and entries that
+/// fail to decode as UTF-8 are reported through the global error handler and
+/// skipped.
 fn construct_otel_resources(s: String) -> Resource {
     Resource::new(s.split_terminator(',').filter_map(|entry| {
-        let mut parts = entry.splitn(2, '=');
-        let key = parts.next()?.trim();
-        let value = parts.next()?.trim();
-        if value.find('=').is_some() {
-            return None;
+        let attribute = parse_resource_attribute(entry);
+        if attribute.is_none() {
+            global::handle_error(global::Error::Other(format!(
+                "invalid entry {:?} in {}, ignoring it",
+                entry, OTEL_RESOURCE_ATTRIBUTES
+            )));
         }
-
-        Some(KeyValue::new(key.to_owned(), value.to_owned()))
+        attribute
     }))
 }
 
+/// Override the `service.name` of the resource with a non-empty service name
+/// from `OTEL_SERVICE_NAME`.
+fn with_service_name(resource: Resource, service_name: Option<String>) -> Resource {
+    match service_name.filter(|name| !name.is_empty()) {
+        Some(name) => Resource::new(vec![KeyValue::new(SERVICE_NAME, name)]).merge(&resource),
+        None => resource,
+    }
+}
+
+fn parse_resource_attribute(entry: &str) -> Option<KeyValue> {
+    let mut parts = entry.splitn(2, '=');
+    let key = parts.next()?.trim();
+    let value = parts.next()?.trim();
+    if value.find('=').is_some() {
+        return None;
+    }
+
+    let key = percent_decode_str(key).decode_utf8().ok()?;
+    let value = percent_decode_str(value).decode_utf8().ok()?;
+
+    Some(KeyValue::new(key.into_owned(), value.into_owned()))
+}
+
+/// Parse the first of the given environment variables that is set.
+///
+/// Returns `None` if none of them is set, or if the value cannot be parsed, in
+/// which case the error is reported through the global error handler.
+pub(crate) fn parse_env<T: FromStr>(names: &[&str]) -> Option<T> {
+    let (name, value) = names
+        .iter()
+        .find_map(|name| env::var(name).ok().map(|value| (name, value)))?;
+
+    match T::from_str(value.trim()) {
+        Ok(parsed) => Some(parsed),
+        Err(_) => {
+            global::handle_error(global::Error::Other(format!(
+                "invalid value {:?} for environment variable {}, using the default instead",
+                value, name
+            )));
+            None
+        }
+    }
+}
+
+/// Serializes tests that mutate the process environment and removes the given
+/// environment variables when dropped.
+#[cfg(test)]
+pub(crate) struct EnvGuard {
+    names: &'static [&'static str],
+    _lock: std::sync::MutexGuard<'static, ()>,
+}
+
+#[cfg(test)]
+lazy_static::lazy_static! {
+    static ref ENV_LOCK: std::sync::Mutex<()> = std::sync::Mutex::new(());
+}
+
+#[cfg(test)]
+impl EnvGuard {
+    pub(crate) fn new(names: &'static [&'static str]) -> Self {
+        // a failed test must not fail every other test that touches the environment
+        let lock = ENV_LOCK.lock().unwrap_or_else(|err| err.into_inner());
+        EnvGuard { names, _lock: lock }
+    }
+}
+
+#[cfg(test)]
+impl Drop for EnvGuard {
+    fn drop(&mut self) {
+        for name in self.names {
+            env::remove_var(name);
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
-    use crate::sdk::env::OTEL_RESOURCE_ATTRIBUTES;
+    use crate::sdk::env::{parse_env, with_service_name, EnvGuard, OTEL_RESOURCE_ATTRIBUTES};
     use crate::sdk::resource::{Resource, ResourceDetector};
     use crate::sdk::EnvResourceDetector;
-    use crate::KeyValue;
+    use crate::{Key, KeyValue, Value};
     use std::{env, time};
 
+    #[test]
+    fn test_service_name_takes_precedence() {
+        let resource = Resource::new(vec![
+            KeyValue::new("service.name", "from-attributes"),
+            KeyValue::new("k", "v"),
+        ]);
+
+        let overridden = with_service_name(resource.clone(), Some("from-env".to_string()));
+        assert_eq!(overridden.len(), 2);
+        assert_eq!(
+            overridden.get(Key::new("service.name")),
+            Some(Value::from("from-env"))
+        );
+
+        assert_eq!(
+            with_service_name(resource.clone(), Some(String::new())),
+            resource
+        );
+        assert_eq!(with_service_name(resource.clone(), None), resource);
+        assert_eq!(
+            with_service_name(Resource::empty(), Some("from-env".to_string())),
+            Resource::new(vec![KeyValue::new("service.name", "from-env")])
+        );
+    }
+
     #[test]
     fn test_read_from_env() {
+        let _guard = EnvGuard::new(&["OTEL_RESOURCE_ATTRIBUTES", "IRRELEVANT"]);
         env::set_var(OTEL_RESOURCE_ATTRIBUTES, "key=value, k = v , a= x, a=z");
         env::set_var("irrelevant".to_uppercase(), "20200810");
 
@@ -82,6 +197,19 @@ mod tests {
             ])
         );
 
+        env::set_var(
+            OTEL_RESOURCE_ATTRIBUTES,
+            "service.name=my%20service,deployment%2Eenv=prod%2Cblue,bad=%FF",
+        );
+        let resource = EnvResourceDetector::new().detect(time::Duration::from_secs(5));
+        assert_eq!(
+            resource,
+            Resource::new(vec![
+                KeyValue::new("service.name", "my service"),
+                KeyValue::new("deployment.env", "prod,blue"),
+            ])
+        );
+
         // Test this case in same test to avoid race condition when running tests in parallel.
         env::set_var(OTEL_RESOURCE_ATTRIBUTES, "");
 
@@ -89,4 +217,24 @@ mod tests {
         let resource = detector.detect(time::Duration::from_secs(5));
         assert!(resource.is_empty());
     }
+
+    #[test]
+    fn test_parse_env() {
+        let name = "OTEL_SDK_TEST_PARSE_ENV";
+        let fallback = "OTEL_SDK_TEST_PARSE_ENV_FALLBACK";
+        let _guard = EnvGuard::new(&[
+            "OTEL_SDK_TEST_PARSE_ENV",
+            "OTEL_SDK_TEST_PARSE_ENV_FALLBACK",
+        ]);
+        assert_eq!(parse_env::<u64>(&[name, fallback]), None);
+
+        env::set_var(fallback, "10");
+        assert_eq!(parse_env::<u64>(&[name, fallback]), Some(10));
+
+        // the first variable that is set wins, even with an invalid value
+        env::set_var(name, " 1500 ");
+        assert_eq!(parse_env::<u64>(&[name, fallback]), Some(1500));
+        env::set_var(name, "1.5s");
+        assert_eq!(parse_env::<u64>(&[name, fallback]), None);
+    }
 }
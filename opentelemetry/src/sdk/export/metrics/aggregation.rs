@@ -79,3 +79,88 @@ pub trait Histogram: Sum + Count {
 
 /// MinMaxSumCount supports the Min, Max, Sum, and Count interfaces.
 pub trait MinMaxSumCount: Min + Max + Sum + Count {}
+
+/// The counts of a contiguous range of exponential histogram buckets.
+#[derive(Debug)]
+pub struct ExponentialBucketCounts {
+    /// The bucket index of the first count.
+    offset: i32,
+
+    /// The counts of the buckets `offset`, `offset + 1`, ...
+    counts: Vec<u64>,
+}
+
+impl ExponentialBucketCounts {
+    /// Create new bucket counts
+    pub fn new(offset: i32, counts: Vec<u64>) -> Self {
+        ExponentialBucketCounts { offset, counts }
+    }
+
+    /// The bucket index of the first count
+    pub fn offset(&self) -> i32 {
+        self.offset
+    }
+
+    /// Counts of the buckets starting at `offset`
+    pub fn counts(&self) -> &Vec<u64> {
+        &self.counts
+    }
+}
+
+/// ExponentialBuckets represent the base-2 exponential buckets of a histogram.
+///
+/// At a given `scale` the bucket with index `i` counts the values in
+/// `(base^i, base^(i + 1)]` where `base = 2^(2^-scale)`. Negative values are
+/// counted in the negative buckets by their absolute value, and values equal
+/// to zero are counted separately in `zero_count`.
+#[derive(Debug)]
+pub struct ExponentialBuckets {
+    scale: i8,
+    zero_count: u64,
+    positive: ExponentialBucketCounts,
+    negative: ExponentialBucketCounts,
+}
+
+impl ExponentialBuckets {
+    /// Create new exponential buckets
+    pub fn new(
+        scale: i8,
+        zero_count: u64,
+        positive: ExponentialBucketCounts,
+        negative: ExponentialBucketCounts,
+    ) -> Self {
+        ExponentialBuckets {
+            scale,
+            zero_count,
+            positive,
+            negative,
+        }
+    }
+
+    /// The resolution of the buckets
+    pub fn scale(&self) -> i8 {
+        self.scale
+    }
+
+    /// The number of values equal to zero
+    pub fn zero_count(&self) -> u64 {
+        self.zero_count
+    }
+
+    /// Buckets of the positive values
+    pub fn positive(&self) -> &ExponentialBucketCounts {
+        &self.positive
+    }
+
+    /// Buckets of the absolute value of the negative values
+    pub fn negative(&self) -> &ExponentialBucketCounts {
+        &self.negative
+    }
+}
+
+/// ExponentialHistogram returns the count of events in base-2 exponential
+/// buckets.
+pub trait ExponentialHistogram: Sum + Count {
+    /// Exponential buckets for this histogram.
+    fn exponential_histogram(&self) -> Result<ExponentialBuckets>;
+}
@@ -4,6 +4,7 @@ use crate::{
     labels,
     metrics::{Descriptor, InstrumentKind, Number, Result},
 };
+use futures::future::{self, BoxFuture};
 use std::any::Any;
 use std::fmt;
 use std::sync::Arc;
@@ -13,7 +14,8 @@ mod aggregation;
 pub mod stdout;
 
 pub use aggregation::{
-    Buckets, Count, Histogram, LastValue, Max, Min, MinMaxSumCount, Points, Sum,
+    Buckets, Count, ExponentialBucketCounts, ExponentialBuckets, ExponentialHistogram, Histogram,
+    LastValue, Max, Min, MinMaxSumCount, Points, Sum,
 };
 pub use stdout::stdout;
 
@@ -173,6 +175,22 @@ pub trait Exporter: ExportKindFor {
     /// The CheckpointSet interface refers to the Processor that just completed
     /// collection.
     fn export(&self, checkpoint_set: &mut dyn CheckpointSet) -> Result<()>;
+
+    /// Export the checkpoint without blocking the calling thread. The returned
+    /// future resolves once the export finished.
+    ///
+    /// The push controller drops the future when it overruns the export
+    /// timeout, exporters sending requests should return them as the future so
+    /// that they can be cancelled. The default implementation calls
+    /// [`export`] eagerly and returns a ready future.
+    ///
+    /// [`export`]: Exporter::export
+    fn export_async(
+        &self,
+        checkpoint_set: &mut dyn CheckpointSet,
+    ) -> BoxFuture<'static, Result<()>> {
+        Box::pin(future::ready(self.export(checkpoint_set)))
+    }
 }
 
 /// ExportKindSelector is a sub-interface of Exporter used to indicate
@@ -430,3 +448,54 @@ impl ExportKindFor for ExportKindSelector {
         }
     }
 }
+
+/// TemporalitySelector chooses the export kind, or aggregation temporality, of
+/// the metrics of each kind of instrument.
+///
+/// Every temporality selector is also an [`ExportKindFor`], so it can be used
+/// wherever an export kind selector is expected.
+pub trait TemporalitySelector: fmt::Debug {
+    /// The export kind of the metrics of instruments of the given kind.
+    fn temporality(&self, kind: &InstrumentKind) -> ExportKind;
+}
+
+impl<T: TemporalitySelector> ExportKindFor for T {
+    fn export_kind_for(&self, descriptor: &Descriptor) -> ExportKind {
+        self.temporality(descriptor.instrument_kind())
+    }
+}
+
+/// A temporality selector exporting the metrics of all instruments with
+/// [`ExportKind::Cumulative`], the default of the specification.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct CumulativeTemporality;
+
+impl TemporalitySelector for CumulativeTemporality {
+    fn temporality(&self, _kind: &InstrumentKind) -> ExportKind {
+        ExportKind::Cumulative
+    }
+}
+
+/// A temporality selector exporting the metrics of counters, value recorders
+/// and sum observers with [`ExportKind::Delta`], so their sums and histograms
+/// only contain the measurements of the last collection interval.
+///
+/// Up down counters and up down sum observers are still exported with
+/// [`ExportKind::Cumulative`] as the specification recommends, their deltas
+/// are rarely useful on their own. Value observers are gauges and are always
+/// cumulative.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct DeltaTemporality;
+
+impl TemporalitySelector for DeltaTemporality {
+    fn temporality(&self, kind: &InstrumentKind) -> ExportKind {
+        match kind {
+            InstrumentKind::UpDownCounter
+            | InstrumentKind::UpDownSumObserver
+            | InstrumentKind::ValueObserver => ExportKind::Cumulative,
+            InstrumentKind::Counter
+            | InstrumentKind::ValueRecorder
+            | InstrumentKind::SumObserver => ExportKind::Delta,
+        }
+    }
+}
@@ -316,7 +316,10 @@ where
     }
 
     /// Build a new push controller, returning errors if they arise.
-    pub fn init(mut self) -> PushController {
+    pub fn init(mut self) -> PushController
+    where
+        I: Send + 'static,
+    {
         let period = self.period.take();
         let exporter = StdoutExporter {
             writer: self.writer,
@@ -334,7 +337,7 @@ where
         )
         .with_stateful(true);
         if let Some(period) = period {
-            push_builder = push_builder.with_period(period);
+            push_builder = push_builder.with_collect_period(period);
         }
 
         let controller = push_builder.build();
@@ -10,4 +10,12 @@ pub mod trace;
 pub trait ExportError: std::error::Error + Send + Sync + 'static {
     /// The name of exporter that returned this error
     fn exporter_name(&self) -> &'static str;
+
+    /// Whether the export may succeed if it is retried, e.g. because the
+    /// backend was temporarily unavailable.
+    ///
+    /// Errors are permanent by default.
+    fn is_retryable(&self) -> bool {
+        false
+    }
 }
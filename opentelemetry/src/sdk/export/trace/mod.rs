@@ -1,7 +1,7 @@
 //! Trace exporters
 use crate::{
     sdk,
-    trace::{Event, Link, SpanContext, SpanId, SpanKind, StatusCode, TraceError},
+    trace::{Event, Link, SpanContext, SpanId, SpanKind, Status, TraceError},
 };
 use async_trait::async_trait;
 #[cfg(feature = "serialize")]
@@ -9,7 +9,7 @@ use serde::{Deserialize, Serialize};
 use std::borrow::Cow;
 use std::fmt::Debug;
 use std::sync::Arc;
-use std::time::SystemTime;
+use std::time::{Duration, SystemTime};
 
 pub mod stdout;
 
@@ -35,10 +35,27 @@ pub trait SpanExporter: Send + Debug {
     /// This function must not block indefinitely, there must be a reasonable
     /// upper limit after which the call must time out with an error result.
     ///
-    /// Any retry logic that is required by the exporter is the responsibility
-    /// of the exporter.
+    /// The [`BatchSpanProcessor`] retries exports failing with a retryable
+    /// error, see [`ExportError::is_retryable`]. Any other retry logic that is
+    /// required by the exporter is the responsibility of the exporter.
+    ///
+    /// [`BatchSpanProcessor`]: crate::sdk::trace::BatchSpanProcessor
+    /// [`ExportError::is_retryable`]: crate::sdk::export::ExportError::is_retryable
     async fn export(&mut self, batch: Vec<SpanData>) -> ExportResult;
 
+    /// Sets the maximum duration of a single call to `export`.
+    ///
+    /// The built-in processors call this with their export timeout when they
+    /// are created, e.g. [`BatchConfig`]'s max export timeout. They abandon
+    /// exports running longer than the timeout and report a
+    /// [`TraceError::ExportTimedOut`] error, exporters with a transport level
+    /// timeout can use this to give up on their own requests in time.
+    ///
+    /// The default implementation ignores the timeout.
+    ///
+    /// [`BatchConfig`]: crate::sdk::trace::BatchConfig
+    fn set_export_timeout(&mut self, _timeout: Duration) {}
+
     /// Shuts down the exporter. Called when SDK is shut down. This is an
     /// opportunity for exporter to do any cleanup required.
     ///
@@ -55,6 +72,10 @@ pub trait SpanExporter: Send + Debug {
 
 /// `SpanData` contains all the information collected by a `Span` and can be used
 /// by exporters as a standard input.
+///
+/// The number of attributes, events and links dropped because of the span
+/// limits is available through the `dropped_count` method of the respective
+/// collections.
 #[cfg_attr(feature = "serialize", derive(Deserialize, Serialize))]
 #[derive(Clone, Debug, PartialEq)]
 pub struct SpanData {
@@ -76,10 +97,8 @@ pub struct SpanData {
     pub events: sdk::trace::EvictedQueue<Event>,
     /// Span Links
     pub links: sdk::trace::EvictedQueue<Link>,
-    /// Span status code
-    pub status_code: StatusCode,
-    /// Span status message
-    pub status_message: Cow<'static, str>,
+    /// Span status
+    pub status: Status,
     /// Resource contains attributes representing an entity that produced this span.
     pub resource: Option<Arc<sdk::Resource>>,
     /// Instrumentation library that produced this span
@@ -119,8 +138,7 @@ mod tests {
         let events = sdk::trace::EvictedQueue::new(capacity);
         let links = sdk::trace::EvictedQueue::new(capacity);
 
-        let status_code = StatusCode::Ok;
-        let status_message = "".into();
+        let status = Status::Ok;
         let resource = None;
 
         let span_data = SpanData {
@@ -133,10 +151,9 @@ mod tests {
             attributes,
             events,
             links,
-            status_code,
-            status_message,
+            status,
             resource,
-            instrumentation_lib: sdk::InstrumentationLibrary::new("", None),
+            instrumentation_lib: sdk::InstrumentationLibrary::new("", None, None),
         };
 
         let encoded: Vec<u8> = bincode::serialize(&span_data).unwrap();
@@ -161,3 +161,30 @@ impl ExportError for Error {
         "stdout"
     }
 }
+
+#[cfg(all(test, feature = "testing"))]
+mod tests {
+    use super::*;
+    use crate::testing::trace::new_test_export_span_data;
+    use futures::executor::block_on;
+
+    fn export_to_string(pretty_print: bool) -> String {
+        let mut exporter = Exporter::new(Vec::new(), pretty_print);
+        block_on(exporter.export(vec![new_test_export_span_data()])).unwrap();
+        String::from_utf8(exporter.writer).unwrap()
+    }
+
+    #[test]
+    fn exports_one_line_per_span_by_default() {
+        let output = export_to_string(false);
+        assert_eq!(output.lines().count(), 1);
+        assert!(output.starts_with("SpanData {"));
+    }
+
+    #[test]
+    fn exports_indented_spans_with_pretty_print() {
+        let output = export_to_string(true);
+        assert!(output.lines().count() > 1);
+        assert!(output.contains("\n    span_context: "));
+    }
+}
@@ -2,24 +2,158 @@
 //! See `OTEPS-0083` for details.
 //!
 //! [OTEPS-0083](https://github.com/open-telemetry/oteps/blob/master/text/0083-component.md)
+use crate::{Array, KeyValue, Value};
+use std::hash::{Hash, Hasher};
+use std::mem;
+use std::sync::Arc;
 
 /// InstrumentationLibrary contains information about instrumentation library.
 ///
 /// See `Instrumentation Libraries` for more information.
 ///
 /// [`Instrumentation Libraries`](https://github.com/open-telemetry/opentelemetry-specification/blob/master/specification/overview.md#instrumentation-libraries)
-#[derive(Debug, Default, Hash, Copy, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone)]
 #[non_exhaustive]
 pub struct InstrumentationLibrary {
     /// instrumentation library name, cannot be empty
     pub name: &'static str,
     /// instrumentation library version, can be empty
     pub version: Option<&'static str>,
+    /// [Schema url] of the telemetry emitted by the instrumentation library, can be empty
+    ///
+    /// [Schema url]: https://github.com/open-telemetry/opentelemetry-specification/blob/main/specification/schemas/overview.md#schema-url
+    pub schema_url: Option<&'static str>,
+    /// Attributes describing the instrumentation library, e.g. the feature that
+    /// enabled it, can be empty
+    ///
+    /// They are shared by all clones, so that cloning the library into the
+    /// data of each span stays cheap. Float attributes are compared by their
+    /// bit pattern, so a `NaN` value is equal to itself.
+    pub attributes: Arc<[KeyValue]>,
+}
+
+impl Default for InstrumentationLibrary {
+    fn default() -> Self {
+        InstrumentationLibrary::new("", None, None)
+    }
 }
 
 impl InstrumentationLibrary {
-    /// Create an InstrumentationLibrary from name and version.
-    pub fn new(name: &'static str, version: Option<&'static str>) -> InstrumentationLibrary {
-        InstrumentationLibrary { name, version }
+    /// Create an InstrumentationLibrary from name, version and schema url.
+    pub fn new(
+        name: &'static str,
+        version: Option<&'static str>,
+        schema_url: Option<&'static str>,
+    ) -> InstrumentationLibrary {
+        InstrumentationLibrary {
+            name,
+            version,
+            schema_url,
+            attributes: Arc::new([]),
+        }
+    }
+}
+
+/// Compare attribute values with total equality, floats by their bit pattern.
+fn value_eq(a: &Value, b: &Value) -> bool {
+    match (a, b) {
+        (Value::F64(a), Value::F64(b)) => a.to_bits() == b.to_bits(),
+        (Value::Array(Array::F64(a)), Value::Array(Array::F64(b))) => {
+            a.len() == b.len() && a.iter().zip(b).all(|(a, b)| a.to_bits() == b.to_bits())
+        }
+        (a, b) => a == b,
+    }
+}
+
+/// Hash attribute values consistently with `value_eq`.
+fn hash_value<H: Hasher>(value: &Value, state: &mut H) {
+    mem::discriminant(value).hash(state);
+    match value {
+        Value::Bool(v) => v.hash(state),
+        Value::I64(v) => v.hash(state),
+        Value::F64(v) => v.to_bits().hash(state),
+        Value::String(v) => v.hash(state),
+        Value::Array(array) => {
+            mem::discriminant(array).hash(state);
+            match array {
+                Array::Bool(v) => v.hash(state),
+                Array::I64(v) => v.hash(state),
+                Array::F64(v) => {
+                    v.len().hash(state);
+                    for f in v {
+                        f.to_bits().hash(state);
+                    }
+                }
+                Array::String(v) => v.hash(state),
+            }
+        }
+    }
+}
+
+impl PartialEq for InstrumentationLibrary {
+    fn eq(&self, other: &Self) -> bool {
+        self.name == other.name
+            && self.version == other.version
+            && self.schema_url == other.schema_url
+            && (Arc::ptr_eq(&self.attributes, &other.attributes)
+                || self.attributes.len() == other.attributes.len()
+                    && self
+                        .attributes
+                        .iter()
+                        .zip(other.attributes.iter())
+                        .all(|(a, b)| a.key == b.key && value_eq(&a.value, &b.value)))
+    }
+}
+
+impl Eq for InstrumentationLibrary {}
+
+impl Hash for InstrumentationLibrary {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.name.hash(state);
+        self.version.hash(state);
+        self.schema_url.hash(state);
+        self.attributes.len().hash(state);
+        for attribute in self.attributes.iter() {
+            attribute.key.hash(state);
+            hash_value(&attribute.value, state);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashSet;
+
+    fn library(attributes: Vec<KeyValue>) -> InstrumentationLibrary {
+        let mut library = InstrumentationLibrary::new("test", Some("0.1.0"), None);
+        library.attributes = attributes.into();
+        library
+    }
+
+    #[test]
+    fn test_attributes_eq_and_hash() {
+        let nan = library(vec![KeyValue::new("ratio", f64::NAN)]);
+        assert_eq!(nan, nan.clone());
+
+        let mut set = HashSet::new();
+        set.insert(nan.clone());
+        set.insert(nan);
+        set.insert(library(vec![KeyValue::new(
+            "ratio",
+            Value::Array(vec![f64::NAN].into()),
+        )]));
+        set.insert(library(vec![KeyValue::new(
+            "ratio",
+            Value::Array(vec![f64::NAN].into()),
+        )]));
+        assert_eq!(set.len(), 2);
+
+        // values of different types are not equal, even with the same string representation
+        assert_ne!(
+            library(vec![KeyValue::new("version", 1)]),
+            library(vec![KeyValue::new("version", "1")])
+        );
+        assert_ne!(library(vec![KeyValue::new("version", 1)]), library(vec![]));
     }
 }
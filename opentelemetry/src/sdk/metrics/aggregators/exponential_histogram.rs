@@ -0,0 +1,486 @@
+use crate::metrics::{AtomicNumber, Descriptor, MetricsError, Number, NumberKind, Result};
+use crate::sdk::export::metrics::{
+    Count, ExponentialBucketCounts, ExponentialBuckets, ExponentialHistogram, Sum,
+};
+use crate::sdk::metrics::export::metrics::Aggregator;
+use std::mem;
+use std::sync::{Arc, RwLock};
+
+/// The default maximum number of buckets for each of the positive and negative
+/// ranges.
+pub const DEFAULT_MAX_SIZE: u32 = 160;
+/// The default maximum scale, i.e. the scale new histograms start with.
+pub const DEFAULT_MAX_SCALE: i8 = 20;
+/// The smallest scale a histogram can be downscaled to. At this scale a single
+/// bucket covers the full range of positive `f64` values.
+const MIN_SCALE: i8 = -10;
+/// The largest supported scale, bucket indices of larger scales overflow for
+/// the largest `f64` values.
+const MAX_SCALE: i8 = 20;
+
+/// Create a new exponential histogram aggregator which keeps at most
+/// `max_size` buckets for each of the positive and negative ranges and starts
+/// recording at `max_scale`.
+///
+/// `max_scale` is clamped to the range `-10..=20`.
+pub fn exponential_histogram(max_size: u32, max_scale: i8) -> ExponentialHistogramAggregator {
+    #[allow(clippy::manual_clamp)] // `Ord::clamp` requires rust 1.50
+    let max_scale = max_scale.max(MIN_SCALE).min(MAX_SCALE);
+    ExponentialHistogramAggregator {
+        inner: RwLock::new(Inner {
+            max_size: max_size.max(1) as usize,
+            max_scale,
+            state: State::empty(max_scale),
+        }),
+    }
+}
+
+/// This aggregator observes events and counts them in base-2 exponential
+/// buckets, automatically reducing the scale of the buckets when the recorded
+/// values span more buckets than the configured maximum size. It also
+/// calculates the sum and count of all events.
+///
+/// At scale `s` the bucket with index `i` contains the values in
+/// `(base^i, base^(i + 1)]` with `base = 2^(2^-s)`, negative values are
+/// counted by their absolute value.
+#[derive(Debug)]
+pub struct ExponentialHistogramAggregator {
+    inner: RwLock<Inner>,
+}
+
+#[derive(Debug)]
+struct Inner {
+    max_size: usize,
+    max_scale: i8,
+    state: State,
+}
+
+#[derive(Debug)]
+struct State {
+    scale: i8,
+    zero_count: u64,
+    positive: Buckets,
+    negative: Buckets,
+    count: AtomicNumber,
+    sum: AtomicNumber,
+}
+
+impl State {
+    fn empty(scale: i8) -> Self {
+        State {
+            scale,
+            zero_count: 0,
+            positive: Buckets::default(),
+            negative: Buckets::default(),
+            count: NumberKind::U64.zero().to_atomic(),
+            sum: NumberKind::U64.zero().to_atomic(),
+        }
+    }
+
+    /// Reduce the scale of both bucket ranges by `change`.
+    fn downscale(&mut self, change: u32) {
+        if change == 0 {
+            return;
+        }
+        self.positive.downscale(change);
+        self.negative.downscale(change);
+        self.scale -= change as i8;
+    }
+
+    fn record(&mut self, value: f64, max_size: usize) {
+        if value == 0.0 {
+            self.zero_count += 1;
+            return;
+        }
+
+        let abs_value = value.abs();
+        let index = map_to_index(abs_value, self.scale);
+        let buckets = if value > 0.0 {
+            &self.positive
+        } else {
+            &self.negative
+        };
+        let change = buckets.scale_change(index, index, self.scale, max_size);
+        self.downscale(change);
+
+        let index = index >> change;
+        let buckets = if value > 0.0 {
+            &mut self.positive
+        } else {
+            &mut self.negative
+        };
+        buckets.increment(index, 1);
+    }
+}
+
+/// A contiguous range of bucket counts, starting at bucket index `offset`.
+#[derive(Clone, Debug, Default, PartialEq)]
+struct Buckets {
+    offset: i32,
+    counts: Vec<u64>,
+}
+
+impl Buckets {
+    /// The index of the last bucket, only meaningful for non-empty buckets.
+    fn end(&self) -> i32 {
+        self.offset + self.counts.len() as i32 - 1
+    }
+
+    /// The scale reduction needed to fit the indices `low..=high` into these
+    /// buckets with at most `max_size` buckets.
+    fn scale_change(&self, low: i32, high: i32, scale: i8, max_size: usize) -> u32 {
+        let (mut low, mut high) = (low, high);
+        if !self.counts.is_empty() {
+            low = low.min(self.offset);
+            high = high.max(self.end());
+        }
+
+        let mut change = 0;
+        while scale - (change as i8) > MIN_SCALE
+            && ((high >> change) as i64 - (low >> change) as i64 + 1) > max_size as i64
+        {
+            change += 1;
+        }
+        change
+    }
+
+    /// Merge neighbouring buckets, the bucket at index `i` moves to `i >> change`.
+    fn downscale(&mut self, change: u32) {
+        if self.counts.is_empty() {
+            self.offset >>= change;
+            return;
+        }
+
+        let offset = self.offset >> change;
+        let mut counts = vec![0; ((self.end() >> change) - offset + 1) as usize];
+        for (idx, count) in self.counts.iter().enumerate() {
+            let index = (self.offset + idx as i32) >> change;
+            counts[(index - offset) as usize] += count;
+        }
+        self.offset = offset;
+        self.counts = counts;
+    }
+
+    /// Add `count` to the bucket at `index`, growing the range as needed.
+    fn increment(&mut self, index: i32, count: u64) {
+        if self.counts.is_empty() {
+            self.offset = index;
+            self.counts.push(count);
+            return;
+        }
+
+        if index < self.offset {
+            let missing = (self.offset - index) as usize;
+            let mut counts = vec![0; missing];
+            counts.append(&mut self.counts);
+            self.counts = counts;
+            self.offset = index;
+        } else if index > self.end() {
+            let len = (index - self.offset + 1) as usize;
+            self.counts.resize(len, 0);
+        }
+        self.counts[(index - self.offset) as usize] += count;
+    }
+}
+
+/// Maps a positive value to the index of the bucket containing it at `scale`.
+fn map_to_index(value: f64, scale: i8) -> i32 {
+    let bits = value.to_bits();
+    let raw_exponent = ((bits >> 52) & 0x7ff) as i32;
+    let mantissa = bits & ((1 << 52) - 1);
+
+    // exact powers of two are the inclusive upper boundary of their bucket
+    if raw_exponent != 0 && mantissa == 0 {
+        let exponent = raw_exponent - 1023;
+        return if scale > 0 {
+            (exponent << scale) - 1
+        } else {
+            (exponent - 1) >> -scale
+        };
+    }
+
+    if scale > 0 {
+        let scale_factor = f64::from(1u32 << scale) / std::f64::consts::LN_2;
+        (value.ln() * scale_factor).floor() as i32
+    } else {
+        // floor(log2(value)) for values that are not powers of two
+        let exponent = if raw_exponent == 0 {
+            value.log2().floor() as i32
+        } else {
+            raw_exponent - 1023
+        };
+        exponent >> -scale
+    }
+}
+
+impl Sum for ExponentialHistogramAggregator {
+    fn sum(&self) -> Result<Number> {
+        self.inner
+            .read()
+            .map_err(From::from)
+            .map(|inner| inner.state.sum.load())
+    }
+}
+
+impl Count for ExponentialHistogramAggregator {
+    fn count(&self) -> Result<u64> {
+        self.inner
+            .read()
+            .map_err(From::from)
+            .map(|inner| inner.state.count.load().to_u64(&NumberKind::U64))
+    }
+}
+
+impl ExponentialHistogram for ExponentialHistogramAggregator {
+    fn exponential_histogram(&self) -> Result<ExponentialBuckets> {
+        self.inner.read().map_err(From::from).map(|inner| {
+            let state = &inner.state;
+            ExponentialBuckets::new(
+                state.scale,
+                state.zero_count,
+                ExponentialBucketCounts::new(state.positive.offset, state.positive.counts.clone()),
+                ExponentialBucketCounts::new(state.negative.offset, state.negative.counts.clone()),
+            )
+        })
+    }
+}
+
+impl Aggregator for ExponentialHistogramAggregator {
+    fn update(&self, number: &Number, descriptor: &Descriptor) -> Result<()> {
+        self.inner.write().map_err(From::from).map(|mut inner| {
+            let kind = descriptor.number_kind();
+            let max_size = inner.max_size;
+
+            inner.state.record(number.to_f64(kind), max_size);
+            inner.state.count.fetch_add(&NumberKind::U64, &1u64.into());
+            inner.state.sum.fetch_add(kind, number);
+        })
+    }
+
+    fn synchronized_move(
+        &self,
+        other: &Arc<dyn Aggregator + Send + Sync>,
+        _descriptor: &Descriptor,
+    ) -> Result<()> {
+        if let Some(other) = other.as_any().downcast_ref::<Self>() {
+            self.inner
+                .write()
+                .map_err(From::from)
+                .and_then(|mut inner| {
+                    other.inner.write().map_err(From::from).map(|mut other| {
+                        let empty = State::empty(inner.max_scale);
+                        other.state = mem::replace(&mut inner.state, empty)
+                    })
+                })
+        } else {
+            Err(MetricsError::InconsistentAggregator(format!(
+                "Expected {:?}, got: {:?}",
+                self, other
+            )))
+        }
+    }
+
+    fn merge(&self, other: &(dyn Aggregator + Send + Sync), desc: &Descriptor) -> Result<()> {
+        if let Some(other) = other
+            .as_any()
+            .downcast_ref::<ExponentialHistogramAggregator>()
+        {
+            self.inner
+                .write()
+                .map_err(From::from)
+                .and_then(|mut inner| {
+                    other.inner.read().map_err(From::from).map(|other| {
+                        let max_size = inner.max_size;
+                        let state = &mut inner.state;
+                        let other = &other.state;
+
+                        // bring both histograms to a common scale which fits
+                        // the buckets of both
+                        state.downscale((state.scale - state.scale.min(other.scale)) as u32);
+                        let other_change = (other.scale - state.scale) as u32;
+                        let mut change = 0;
+                        for (buckets, other_buckets) in [
+                            (&state.positive, &other.positive),
+                            (&state.negative, &other.negative),
+                        ]
+                        .iter()
+                        {
+                            if !other_buckets.counts.is_empty() {
+                                change = change.max(buckets.scale_change(
+                                    other_buckets.offset >> other_change,
+                                    other_buckets.end() >> other_change,
+                                    state.scale,
+                                    max_size,
+                                ));
+                            }
+                        }
+                        state.downscale(change);
+
+                        let other_change = (other.scale - state.scale) as u32;
+                        for (idx, count) in other.positive.counts.iter().enumerate() {
+                            let index = (other.positive.offset + idx as i32) >> other_change;
+                            state.positive.increment(index, *count);
+                        }
+                        for (idx, count) in other.negative.counts.iter().enumerate() {
+                            let index = (other.negative.offset + idx as i32) >> other_change;
+                            state.negative.increment(index, *count);
+                        }
+                        state.zero_count += other.zero_count;
+                        state.sum.fetch_add(desc.number_kind(), &other.sum.load());
+                        state.count.fetch_add(&NumberKind::U64, &other.count.load());
+                    })
+                })
+        } else {
+            Err(MetricsError::InconsistentAggregator(format!(
+                "Expected {:?}, got: {:?}",
+                self, other
+            )))
+        }
+    }
+
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::metrics::InstrumentKind;
+
+    fn descriptor() -> Descriptor {
+        Descriptor::new(
+            "test".to_string(),
+            "test",
+            None,
+            InstrumentKind::ValueRecorder,
+            NumberKind::F64,
+        )
+    }
+
+    fn record(aggregator: &ExponentialHistogramAggregator, values: &[f64]) {
+        let descriptor = descriptor();
+        for value in values {
+            aggregator
+                .update(&Number::from(*value), &descriptor)
+                .unwrap();
+        }
+    }
+
+    #[test]
+    fn test_map_to_index() {
+        // at scale 0 the buckets are (2^i, 2^(i+1)]
+        assert_eq!(map_to_index(1.0, 0), -1);
+        assert_eq!(map_to_index(1.5, 0), 0);
+        assert_eq!(map_to_index(2.0, 0), 0);
+        assert_eq!(map_to_index(3.0, 0), 1);
+        assert_eq!(map_to_index(0.3, 0), -2);
+        // at scale -1 the buckets are (4^i, 4^(i+1)]
+        assert_eq!(map_to_index(4.0, -1), 0);
+        assert_eq!(map_to_index(5.0, -1), 1);
+        // at scale 1 the buckets are (sqrt(2)^i, sqrt(2)^(i+1)]
+        assert_eq!(map_to_index(2.0, 1), 1);
+        assert_eq!(map_to_index(1.5, 1), 1);
+        assert_eq!(map_to_index(1.4, 1), 0);
+        assert_eq!(map_to_index(4.0, 3), 15);
+    }
+
+    #[test]
+    fn test_max_scale_is_clamped() {
+        let aggregator = exponential_histogram(DEFAULT_MAX_SIZE, i8::MAX);
+        record(&aggregator, &[f64::MAX, f64::MIN_POSITIVE]);
+        let buckets = aggregator.exponential_histogram().unwrap();
+        assert!(buckets.scale() <= MAX_SCALE);
+        assert_eq!(buckets.positive().counts().iter().sum::<u64>(), 2);
+
+        let aggregator = exponential_histogram(DEFAULT_MAX_SIZE, i8::MAX);
+        record(&aggregator, &[1.5]);
+        assert_eq!(
+            aggregator.exponential_histogram().unwrap().scale(),
+            MAX_SCALE
+        );
+
+        let aggregator = exponential_histogram(DEFAULT_MAX_SIZE, i8::MIN);
+        record(&aggregator, &[1.5]);
+        assert_eq!(
+            aggregator.exponential_histogram().unwrap().scale(),
+            MIN_SCALE
+        );
+    }
+
+    #[test]
+    fn test_record() {
+        let aggregator = exponential_histogram(DEFAULT_MAX_SIZE, 0);
+        record(&aggregator, &[0.0, 1.0, 2.0, 3.0, 4.0, -1.5]);
+
+        let buckets = aggregator.exponential_histogram().unwrap();
+        assert_eq!(buckets.scale(), 0);
+        assert_eq!(buckets.zero_count(), 1);
+        assert_eq!(buckets.positive().offset(), -1);
+        assert_eq!(buckets.positive().counts(), &vec![1, 1, 2]);
+        assert_eq!(buckets.negative().offset(), 0);
+        assert_eq!(buckets.negative().counts(), &vec![1]);
+        assert_eq!(aggregator.count().unwrap(), 6);
+        assert_eq!(aggregator.sum().unwrap().to_f64(&NumberKind::F64), 8.5);
+    }
+
+    #[test]
+    fn test_rescale_on_overflow() {
+        let aggregator = exponential_histogram(4, DEFAULT_MAX_SCALE);
+        record(&aggregator, &[1.0, 2.0, 4.0, 8.0, 16.0]);
+
+        let buckets = aggregator.exponential_histogram().unwrap();
+        assert_eq!(buckets.scale(), -1);
+        assert_eq!(buckets.positive().offset(), -1);
+        assert_eq!(buckets.positive().counts(), &vec![1, 2, 2]);
+    }
+
+    #[test]
+    fn test_rescale_many_orders_of_magnitude() {
+        let max_size = 20;
+        let aggregator = exponential_histogram(max_size, DEFAULT_MAX_SCALE);
+        let values: Vec<f64> = (-9..=12).map(|exp| 10f64.powi(exp) * 1.5).collect();
+        record(&aggregator, &values);
+
+        let buckets = aggregator.exponential_histogram().unwrap();
+        let positive = buckets.positive();
+        assert!(buckets.scale() < DEFAULT_MAX_SCALE);
+        assert!(positive.counts().len() <= max_size as usize);
+        assert_eq!(positive.counts().iter().sum::<u64>(), values.len() as u64);
+
+        // every value is inside the range covered by the buckets
+        let base = 2f64.powf(2f64.powi(-(buckets.scale() as i32)));
+        let lower = base.powi(positive.offset());
+        let upper = base.powi(positive.offset() + positive.counts().len() as i32);
+        for value in values {
+            assert!(lower < value && value <= upper, "{} not in buckets", value);
+        }
+
+        // the scale is as high as possible for the recorded range
+        let low = map_to_index(1.5e-9, buckets.scale() + 1);
+        let high = map_to_index(1.5e12, buckets.scale() + 1);
+        assert!((high - low + 1) as u32 > max_size);
+    }
+
+    #[test]
+    fn test_merge() {
+        let descriptor = descriptor();
+        let narrow = exponential_histogram(DEFAULT_MAX_SIZE, DEFAULT_MAX_SCALE);
+        record(&narrow, &[1.5, 1.6]);
+        let wide = exponential_histogram(DEFAULT_MAX_SIZE, 0);
+        record(&wide, &[1.0, 1024.0, 0.0]);
+
+        narrow.merge(&wide, &descriptor).unwrap();
+
+        let buckets = narrow.exponential_histogram().unwrap();
+        assert_eq!(buckets.scale(), 0);
+        assert_eq!(buckets.zero_count(), 1);
+        assert_eq!(buckets.positive().offset(), -1);
+        let counts = buckets.positive().counts();
+        assert_eq!(counts.len(), 11);
+        assert_eq!(counts[0], 1);
+        assert_eq!(counts[1], 2);
+        assert_eq!(counts[10], 1);
+        assert_eq!(narrow.count().unwrap(), 5);
+    }
+}
@@ -1,6 +1,7 @@
 use crate::metrics::{AtomicNumber, Descriptor, MetricsError, Number, NumberKind, Result};
 use crate::sdk::export::metrics::{Buckets, Count, Histogram, Sum};
 use crate::sdk::metrics::export::metrics::Aggregator;
+use std::collections::HashMap;
 use std::mem;
 use std::sync::{Arc, RwLock};
 
@@ -19,6 +20,81 @@ pub fn histogram(desc: &Descriptor, boundaries: &[f64]) -> HistogramAggregator {
     }
 }
 
+/// Histogram configuration with explicit bucket boundaries.
+///
+/// The boundaries are exclusive upper bounds of the buckets, values greater
+/// than or equal to the last boundary are counted in an implicit `+Inf` bucket.
+/// Boundaries can be set for all instruments and be overridden per instrument.
+///
+/// # Examples
+///
+/// ```
+/// use opentelemetry::sdk::metrics::aggregators::HistogramConfig;
+///
+/// let config = HistogramConfig::new(vec![0.5, 0.9, 0.99])?
+///     .with_boundaries("http.server.duration", vec![0.05, 0.1, 0.25, 0.5, 1.0])?;
+/// # Ok::<(), opentelemetry::metrics::MetricsError>(())
+/// ```
+#[derive(Clone, Debug)]
+pub struct HistogramConfig {
+    boundaries: Vec<f64>,
+    instrument_boundaries: HashMap<String, Vec<f64>>,
+}
+
+impl HistogramConfig {
+    /// Create a new histogram config using `boundaries` for all instruments.
+    ///
+    /// Returns an error if the boundaries are not finite or not sorted in
+    /// increasing order.
+    pub fn new(boundaries: Vec<f64>) -> Result<Self> {
+        validate_boundaries(&boundaries)?;
+        Ok(HistogramConfig {
+            boundaries,
+            instrument_boundaries: HashMap::new(),
+        })
+    }
+
+    /// Use `boundaries` for the instrument named `instrument_name` instead of
+    /// the default boundaries.
+    ///
+    /// Returns an error if the boundaries are not finite or not sorted in
+    /// increasing order.
+    pub fn with_boundaries<T: Into<String>>(
+        mut self,
+        instrument_name: T,
+        boundaries: Vec<f64>,
+    ) -> Result<Self> {
+        validate_boundaries(&boundaries)?;
+        self.instrument_boundaries
+            .insert(instrument_name.into(), boundaries);
+        Ok(self)
+    }
+
+    /// The boundaries used for the instrument described by `descriptor`.
+    pub fn boundaries(&self, descriptor: &Descriptor) -> &[f64] {
+        self.instrument_boundaries
+            .get(descriptor.name())
+            .unwrap_or(&self.boundaries)
+    }
+}
+
+fn validate_boundaries(boundaries: &[f64]) -> Result<()> {
+    if let Some(boundary) = boundaries.iter().find(|boundary| !boundary.is_finite()) {
+        return Err(MetricsError::Other(format!(
+            "histogram boundaries must be finite, got {}",
+            boundary
+        )));
+    }
+    if boundaries.windows(2).any(|pair| pair[0] >= pair[1]) {
+        return Err(MetricsError::Other(format!(
+            "histogram boundaries must be sorted in increasing order, got {:?}",
+            boundaries
+        )));
+    }
+
+    Ok(())
+}
+
 /// This aggregator observes events and counts them in pre-determined buckets. It
 /// also calculates the sum and count of all events.
 #[derive(Debug)]
@@ -153,3 +229,64 @@ impl Aggregator for HistogramAggregator {
         self
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::{histogram, HistogramConfig};
+    use crate::metrics::{Descriptor, InstrumentKind, NumberKind};
+    use crate::sdk::export::metrics::{Aggregator, Count, Histogram};
+
+    fn descriptor(name: &str) -> Descriptor {
+        Descriptor::new(
+            name.to_string(),
+            "test",
+            None,
+            InstrumentKind::ValueRecorder,
+            NumberKind::F64,
+        )
+    }
+
+    #[test]
+    fn config_validates_boundaries() {
+        assert!(HistogramConfig::new(vec![]).is_ok());
+        assert!(HistogramConfig::new(vec![1.0, 2.0, 5.0]).is_ok());
+        assert!(HistogramConfig::new(vec![1.0, 1.0]).is_err());
+        assert!(HistogramConfig::new(vec![2.0, 1.0]).is_err());
+        assert!(HistogramConfig::new(vec![1.0, f64::NAN]).is_err());
+        assert!(HistogramConfig::new(vec![1.0, f64::INFINITY]).is_err());
+        assert!(HistogramConfig::new(vec![1.0])
+            .unwrap()
+            .with_boundaries("latency", vec![f64::NEG_INFINITY])
+            .is_err());
+    }
+
+    #[test]
+    fn config_boundaries_per_instrument() {
+        let config = HistogramConfig::new(vec![1.0])
+            .unwrap()
+            .with_boundaries("latency", vec![10.0, 20.0])
+            .unwrap();
+        assert_eq!(config.boundaries(&descriptor("latency")), &[10.0, 20.0]);
+        assert_eq!(config.boundaries(&descriptor("other")), &[1.0]);
+    }
+
+    #[test]
+    fn counts_values_per_bucket() {
+        let desc = descriptor("latency");
+        let config = HistogramConfig::new(vec![])
+            .unwrap()
+            .with_boundaries("latency", vec![10.0, 50.0, 100.0])
+            .unwrap();
+        let agg = histogram(&desc, config.boundaries(&desc));
+
+        for value in &[1.0, 9.9, 10.0, 49.0, 99.0, 100.0, 250.0, 1000.0] {
+            agg.update(&(*value).into(), &desc).unwrap();
+        }
+
+        let buckets = agg.histogram().unwrap();
+        assert_eq!(buckets.boundaries(), &vec![10.0, 50.0, 100.0]);
+        // the last count is the implicit +Inf bucket
+        assert_eq!(buckets.counts(), &vec![2.0, 2.0, 1.0, 3.0]);
+        assert_eq!(agg.count().unwrap(), 8);
+    }
+}
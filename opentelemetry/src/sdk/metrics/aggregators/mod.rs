@@ -3,6 +3,7 @@ use crate::metrics::{Descriptor, InstrumentKind, MetricsError, Number, NumberKin
 
 mod array;
 mod ddsketch;
+mod exponential_histogram;
 mod histogram;
 mod last_value;
 mod min_max_sum_count;
@@ -10,7 +11,10 @@ mod sum;
 
 pub use array::{array, ArrayAggregator};
 pub use ddsketch::{ddsketch, DdSketchAggregator, DdSketchConfig};
-pub use histogram::{histogram, HistogramAggregator};
+pub use exponential_histogram::{
+    exponential_histogram, ExponentialHistogramAggregator, DEFAULT_MAX_SCALE, DEFAULT_MAX_SIZE,
+};
+pub use histogram::{histogram, HistogramAggregator, HistogramConfig};
 pub use last_value::{last_value, LastValueAggregator};
 pub use min_max_sum_count::{min_max_sum_count, MinMaxSumCountAggregator};
 pub use sum::{sum, SumAggregator};
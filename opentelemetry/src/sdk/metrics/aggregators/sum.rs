@@ -34,7 +34,7 @@ impl Subtractor for SumAggregator {
             (Some(op), Some(res)) => {
                 res.value.store(&self.value.load());
                 res.value
-                    .fetch_add(descriptor.number_kind(), &op.value.load());
+                    .fetch_sub(descriptor.number_kind(), &op.value.load());
                 Ok(())
             }
             _ => Err(MetricsError::InconsistentAggregator(format!(
@@ -79,3 +79,28 @@ impl Aggregator for SumAggregator {
         self
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::metrics::{InstrumentKind, NumberKind};
+
+    #[test]
+    fn test_subtract_to_negative() {
+        let descriptor = Descriptor::new(
+            "test".to_string(),
+            "test",
+            None,
+            InstrumentKind::UpDownSumObserver,
+            NumberKind::I64,
+        );
+        let current = sum();
+        current.update(&Number::from(-2_i64), &descriptor).unwrap();
+        let previous = sum();
+        previous.update(&Number::from(3_i64), &descriptor).unwrap();
+        let delta = sum();
+
+        current.subtract(&previous, &delta, &descriptor).unwrap();
+        assert_eq!(delta.sum().unwrap().to_i64(&NumberKind::I64), -5);
+    }
+}
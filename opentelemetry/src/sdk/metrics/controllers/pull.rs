@@ -4,7 +4,8 @@ use crate::sdk::{
     metrics::{
         accumulator,
         processors::{self, BasicProcessor},
-        Accumulator,
+        view::{ViewAggregations, ViewAggregatorSelector},
+        Accumulator, View,
     },
     Resource,
 };
@@ -99,6 +100,9 @@ pub struct PullControllerBuilder {
     /// `true`, `CheckpointSet::try_for_each` will visit metrics that were not
     /// updated in the most recent interval. Default true.
     memory: bool,
+
+    /// Views applied to the instruments of meters created by the controller.
+    views: Vec<View>,
 }
 
 impl PullControllerBuilder {
@@ -113,6 +117,19 @@ impl PullControllerBuilder {
             resource: None,
             cache_period: None,
             memory: true,
+            views: Vec::new(),
+        }
+    }
+
+    /// Replace the aggregator selector choosing the aggregation of the
+    /// instruments without a view aggregation.
+    pub fn with_aggregator_selector<T>(self, aggregator_selector: T) -> Self
+    where
+        T: AggregatorSelector + Send + Sync + 'static,
+    {
+        PullControllerBuilder {
+            aggregator_selector: Box::new(aggregator_selector),
+            ..self
         }
     }
 
@@ -139,16 +156,35 @@ impl PullControllerBuilder {
         PullControllerBuilder { memory, ..self }
     }
 
+    /// Register a view for the instruments of this controller. Views are
+    /// matched in the order they are registered.
+    pub fn with_view(mut self, view: View) -> Self {
+        self.views.push(view);
+        self
+    }
+
     /// Build a new `PullController` from the current configuration.
     pub fn build(self) -> PullController {
+        let view_aggregations = Arc::new(ViewAggregations::default());
+        let aggregator_selector: Box<dyn AggregatorSelector + Send + Sync> =
+            if self.views.is_empty() {
+                self.aggregator_selector
+            } else {
+                Box::new(ViewAggregatorSelector::new(
+                    self.aggregator_selector,
+                    view_aggregations.clone(),
+                ))
+            };
         let processor = Arc::new(processors::basic(
-            self.aggregator_selector,
+            aggregator_selector,
             self.export_selector,
             self.memory,
         ));
 
         let accumulator = accumulator(processor.clone())
             .with_resource(self.resource.unwrap_or_default())
+            .with_views(self.views)
+            .with_view_aggregations(view_aggregations)
             .build();
         let provider = registry::meter_provider(Arc::new(accumulator.clone()));
 
@@ -1,15 +1,19 @@
 use crate::global;
-use crate::metrics::registry;
+use crate::metrics::{registry, MetricsError, Result};
 use crate::sdk::{
+    env::parse_env,
     export::metrics::{AggregatorSelector, Checkpointer, ExportKindFor, Exporter},
     metrics::{
         self,
         processors::{self, BasicProcessor},
-        Accumulator,
+        view::{ViewAggregations, ViewAggregatorSelector},
+        Accumulator, View,
     },
     Resource,
 };
-use futures::{channel::mpsc, task, Future, Stream, StreamExt};
+use futures::{
+    channel::mpsc, future::BoxFuture, stream::BoxStream, task, Future, Stream, StreamExt,
+};
 use std::pin::Pin;
 use std::sync::{Arc, Mutex};
 use std::time;
@@ -18,6 +22,11 @@ lazy_static::lazy_static! {
     static ref DEFAULT_PUSH_PERIOD: time::Duration = time::Duration::from_secs(10);
 }
 
+/// Interval between two consecutive collections, in milliseconds.
+const OTEL_METRIC_EXPORT_INTERVAL: &str = "OTEL_METRIC_EXPORT_INTERVAL";
+/// Maximum allowed time to export data, in milliseconds.
+const OTEL_METRIC_EXPORT_TIMEOUT: &str = "OTEL_METRIC_EXPORT_TIMEOUT";
+
 /// Create a new `PushControllerBuilder`.
 pub fn push<AS, ES, E, SP, SO, I, IO>(
     aggregator_selector: AS,
@@ -43,6 +52,7 @@ where
         stateful: None,
         period: None,
         timeout: None,
+        views: Vec::new(),
     }
 }
 
@@ -67,19 +77,70 @@ pub struct PushControllerWorker {
     accumulator: Accumulator,
     processor: Arc<BasicProcessor>,
     exporter: Box<dyn Exporter + Send + Sync>,
-    _timeout: time::Duration,
+    interval: Box<dyn Fn(time::Duration) -> BoxStream<'static, ()> + Send>,
+    timeout: time::Duration,
+    pending_export: Option<PendingExport>,
+}
+
+/// An export that has not finished yet.
+struct PendingExport {
+    export: BoxFuture<'static, Result<()>>,
+    deadline: time::Instant,
+    // ticks of the controller's interval until the deadline has passed
+    timer: BoxStream<'static, ()>,
 }
 
 impl PushControllerWorker {
+    /// Collect and start exporting, unless the previous export is still
+    /// running.
     fn on_tick(&mut self) {
-        // TODO handle timeout
-        if let Err(err) = self.processor.lock().and_then(|mut checkpointer| {
+        if self.pending_export.is_some() {
+            global::handle_error(MetricsError::Other(
+                "previous export is still running, skipping this collection".into(),
+            ));
+            return;
+        }
+
+        let export = self.processor.lock().and_then(|mut checkpointer| {
             checkpointer.start_collection();
             self.accumulator.0.collect(&mut checkpointer);
             checkpointer.finish_collection()?;
-            self.exporter.export(checkpointer.checkpoint_set())
-        }) {
-            global::handle_error(err)
+            Ok(self.exporter.export_async(checkpointer.checkpoint_set()))
+        });
+        match export {
+            Ok(export) => {
+                self.pending_export = Some(PendingExport {
+                    export,
+                    deadline: time::Instant::now() + self.timeout,
+                    timer: (self.interval)(self.timeout),
+                })
+            }
+            Err(err) => global::handle_error(err),
+        }
+    }
+
+    /// Drive the pending export, cancelling it once it overran the timeout.
+    fn poll_export(&mut self, cx: &mut task::Context<'_>) {
+        let pending = match self.pending_export.as_mut() {
+            Some(pending) => pending,
+            None => return,
+        };
+
+        if let task::Poll::Ready(result) = pending.export.as_mut().poll(cx) {
+            if let Err(err) = result {
+                global::handle_error(err);
+            }
+            self.pending_export = None;
+            return;
+        }
+
+        while let task::Poll::Ready(tick) = pending.timer.poll_next_unpin(cx) {
+            // a finished interval cannot tell when the deadline passed anymore
+            if tick.is_none() || time::Instant::now() >= pending.deadline {
+                global::handle_error(MetricsError::ExportTimedOut(self.timeout));
+                self.pending_export = None;
+                return;
+            }
         }
     }
 }
@@ -87,10 +148,14 @@ impl PushControllerWorker {
 impl Future for PushControllerWorker {
     type Output = ();
     fn poll(mut self: Pin<&mut Self>, cx: &mut task::Context<'_>) -> task::Poll<Self::Output> {
+        self.poll_export(cx);
         loop {
             match futures::ready!(self.messages.poll_next_unpin(cx)) {
                 // Span batch interval time reached, export current spans.
-                Some(PushMessage::Tick) => self.on_tick(),
+                Some(PushMessage::Tick) => {
+                    self.on_tick();
+                    self.poll_export(cx);
+                }
                 // Stream has terminated or processor is shutdown, return to finish execution.
                 None | Some(PushMessage::Shutdown) => {
                     return task::Poll::Ready(());
@@ -102,8 +167,16 @@ impl Future for PushControllerWorker {
 
 impl Drop for PushControllerWorker {
     fn drop(&mut self) {
-        // Try to push data one last time
-        self.on_tick()
+        // Cancel an unfinished export and push data one last time
+        self.pending_export = None;
+        if let Err(err) = self.processor.lock().and_then(|mut checkpointer| {
+            checkpointer.start_collection();
+            self.accumulator.0.collect(&mut checkpointer);
+            checkpointer.finish_collection()?;
+            self.exporter.export(checkpointer.checkpoint_set())
+        }) {
+            global::handle_error(err)
+        }
     }
 }
 
@@ -134,6 +207,7 @@ pub struct PushControllerBuilder<S, I> {
     stateful: Option<bool>,
     period: Option<time::Duration>,
     timeout: Option<time::Duration>,
+    views: Vec<View>,
 }
 
 impl<S, SO, I, IS, ISI> PushControllerBuilder<S, I>
@@ -150,10 +224,31 @@ where
         }
     }
 
+    /// Configure the interval between two collections of this controller.
+    ///
+    /// Defaults to the `OTEL_METRIC_EXPORT_INTERVAL` environment variable in
+    /// milliseconds, or 10 seconds if it is not set.
+    pub fn with_collect_period(self, period: time::Duration) -> Self {
+        PushControllerBuilder {
+            period: Some(period),
+            ..self
+        }
+    }
+
     /// Configure the period of this controller
+    #[deprecated(since = "0.15.0", note = "use `with_collect_period` instead")]
     pub fn with_period(self, period: time::Duration) -> Self {
+        self.with_collect_period(period)
+    }
+
+    /// Replace the aggregator selector choosing the aggregation of the
+    /// instruments without a view aggregation.
+    pub fn with_aggregator_selector<T>(self, aggregator_selector: T) -> Self
+    where
+        T: AggregatorSelector + Send + Sync + 'static,
+    {
         PushControllerBuilder {
-            period: Some(period),
+            aggregator_selector: Box::new(aggregator_selector),
             ..self
         }
     }
@@ -166,19 +261,57 @@ where
         }
     }
 
-    /// Config the timeout of one request.
-    pub fn with_timeout(self, duration: time::Duration) -> Self {
+    /// Configure the maximum time one export may take.
+    ///
+    /// The future returned by [`Exporter::export_async`] is dropped when it
+    /// takes longer, and the timeout is reported through the global error
+    /// handler. The timeout is measured with the interval of the controller,
+    /// an export is cancelled on the first tick of `interval(timeout)` after
+    /// the timeout elapsed. Collections due while an export is running are
+    /// skipped.
+    ///
+    /// Defaults to the `OTEL_METRIC_EXPORT_TIMEOUT` environment variable in
+    /// milliseconds, or 10 seconds if it is not set.
+    pub fn with_export_timeout(self, duration: time::Duration) -> Self {
         PushControllerBuilder {
             timeout: Some(duration),
             ..self
         }
     }
 
+    /// Config the timeout of one request.
+    #[deprecated(since = "0.15.0", note = "use `with_export_timeout` instead")]
+    pub fn with_timeout(self, duration: time::Duration) -> Self {
+        self.with_export_timeout(duration)
+    }
+
+    /// Register a view for the instruments of this controller. Views are
+    /// matched in the order they are registered.
+    pub fn with_view(mut self, view: View) -> Self {
+        self.views.push(view);
+        self
+    }
+
     /// Build a new `PushController` with this configuration.
-    pub fn build(self) -> PushController {
-        let processor = processors::basic(self.aggregator_selector, self.export_selector, false);
+    pub fn build(self) -> PushController
+    where
+        I: Send + 'static,
+    {
+        let view_aggregations = Arc::new(ViewAggregations::default());
+        let aggregator_selector: Box<dyn AggregatorSelector + Send + Sync> =
+            if self.views.is_empty() {
+                self.aggregator_selector
+            } else {
+                Box::new(ViewAggregatorSelector::new(
+                    self.aggregator_selector,
+                    view_aggregations.clone(),
+                ))
+            };
+        let processor = processors::basic(aggregator_selector, self.export_selector, false);
         let processor = Arc::new(processor);
-        let mut accumulator = metrics::accumulator(processor.clone());
+        let mut accumulator = metrics::accumulator(processor.clone())
+            .with_views(self.views)
+            .with_view_aggregations(view_aggregations);
 
         if let Some(resource) = self.resource {
             accumulator = accumulator.with_resource(resource);
@@ -187,15 +320,25 @@ where
         let provider = registry::meter_provider(Arc::new(accumulator.clone()));
 
         let (message_sender, message_receiver) = mpsc::channel(256);
-        let ticker =
-            (self.interval)(self.period.unwrap_or(*DEFAULT_PUSH_PERIOD)).map(|_| PushMessage::Tick);
+        let period = self
+            .period
+            .or_else(|| parse_env(&[OTEL_METRIC_EXPORT_INTERVAL]).map(time::Duration::from_millis))
+            .unwrap_or(*DEFAULT_PUSH_PERIOD);
+        let timeout = self
+            .timeout
+            .or_else(|| parse_env(&[OTEL_METRIC_EXPORT_TIMEOUT]).map(time::Duration::from_millis))
+            .unwrap_or(*DEFAULT_PUSH_PERIOD);
+        let ticker = (self.interval)(period).map(|_| PushMessage::Tick);
+        let interval = self.interval;
 
         (self.spawn)(PushControllerWorker {
             messages: Box::pin(futures::stream::select(message_receiver, ticker)),
             accumulator,
             processor,
             exporter: self.exporter,
-            _timeout: self.timeout.unwrap_or(*DEFAULT_PUSH_PERIOD),
+            interval: Box::new(move |period| interval(period).map(|_| ()).boxed()),
+            timeout,
+            pending_export: None,
         });
 
         PushController {
@@ -204,3 +347,190 @@ where
         }
     }
 }
+
+#[cfg(all(test, feature = "testing"))]
+mod tests {
+    use super::*;
+    use crate::metrics::{Descriptor, Result};
+    use crate::sdk::export::metrics::{CheckpointSet, ExportKind, ExportKindSelector};
+    use crate::sdk::metrics::selectors;
+    use futures::future;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    const PERIOD: time::Duration = time::Duration::from_secs(60);
+
+    /// Counts the exports, whose futures never finish if `hang` is set.
+    #[derive(Debug, Default)]
+    struct TestExporter {
+        hang: bool,
+        started: Arc<AtomicUsize>,
+        finished: Arc<AtomicUsize>,
+        cancelled: Arc<AtomicUsize>,
+    }
+
+    /// Counts the cancelled exports when dropped.
+    struct CancelGuard(Arc<AtomicUsize>);
+
+    impl Drop for CancelGuard {
+        fn drop(&mut self) {
+            self.0.fetch_add(1, Ordering::SeqCst);
+        }
+    }
+
+    impl ExportKindFor for TestExporter {
+        fn export_kind_for(&self, descriptor: &Descriptor) -> ExportKind {
+            ExportKindSelector::Cumulative.export_kind_for(descriptor)
+        }
+    }
+
+    impl Exporter for TestExporter {
+        fn export(&self, _checkpoint_set: &mut dyn CheckpointSet) -> Result<()> {
+            self.finished.fetch_add(1, Ordering::SeqCst);
+            Ok(())
+        }
+
+        fn export_async(
+            &self,
+            checkpoint_set: &mut dyn CheckpointSet,
+        ) -> BoxFuture<'static, Result<()>> {
+            self.started.fetch_add(1, Ordering::SeqCst);
+            if self.hang {
+                let guard = CancelGuard(self.cancelled.clone());
+                Box::pin(async move {
+                    let _guard = guard;
+                    future::pending().await
+                })
+            } else {
+                Box::pin(future::ready(self.export(checkpoint_set)))
+            }
+        }
+    }
+
+    /// Interval streams that only tick when told to.
+    #[derive(Clone, Debug, Default)]
+    struct ManualIntervals(Arc<Mutex<Vec<(time::Duration, mpsc::UnboundedSender<()>)>>>);
+
+    impl ManualIntervals {
+        fn interval(&self, period: time::Duration) -> mpsc::UnboundedReceiver<()> {
+            let (sender, receiver) = mpsc::unbounded();
+            self.0.lock().unwrap().push((period, sender));
+            receiver
+        }
+
+        /// Tick all intervals of `period`.
+        fn tick(&self, period: time::Duration) {
+            for (interval_period, sender) in self.0.lock().unwrap().iter() {
+                if *interval_period == period {
+                    let _ = sender.unbounded_send(());
+                }
+            }
+        }
+    }
+
+    /// Builds a worker collecting every `PERIOD` and its controller.
+    fn worker(
+        exporter: TestExporter,
+        timeout: time::Duration,
+        intervals: &ManualIntervals,
+    ) -> (PushController, PushControllerWorker) {
+        let worker = Arc::new(Mutex::new(None));
+        let worker_slot = worker.clone();
+        let intervals = intervals.clone();
+        let controller = push(
+            selectors::simple::Selector::Exact,
+            ExportKindSelector::Cumulative,
+            exporter,
+            move |worker| *worker_slot.lock().unwrap() = Some(worker),
+            move |period| intervals.interval(period),
+        )
+        .with_collect_period(PERIOD)
+        .with_export_timeout(timeout)
+        .build();
+
+        let worker = worker.lock().unwrap().take().expect("worker was spawned");
+        (controller, worker)
+    }
+
+    fn poll(worker: &mut PushControllerWorker) {
+        let waker = futures::task::noop_waker();
+        let mut cx = task::Context::from_waker(&waker);
+        // the controller is alive, so the worker never finishes
+        assert!(Pin::new(worker).poll(&mut cx).is_pending());
+    }
+
+    #[test]
+    fn test_export_within_timeout() {
+        let exporter = TestExporter::default();
+        let finished = exporter.finished.clone();
+        let intervals = ManualIntervals::default();
+        let (_controller, mut worker) = worker(exporter, time::Duration::from_secs(10), &intervals);
+
+        for _ in 0..3 {
+            intervals.tick(PERIOD);
+            poll(&mut worker);
+        }
+        assert_eq!(finished.load(Ordering::SeqCst), 3);
+    }
+
+    #[test]
+    fn test_export_overrun_is_cancelled() {
+        let exporter = TestExporter {
+            hang: true,
+            ..TestExporter::default()
+        };
+        let (started, cancelled) = (exporter.started.clone(), exporter.cancelled.clone());
+        let intervals = ManualIntervals::default();
+        let timeout = time::Duration::from_millis(0);
+        let (_controller, mut worker) = worker(exporter, timeout, &intervals);
+
+        intervals.tick(PERIOD);
+        poll(&mut worker);
+        assert_eq!(started.load(Ordering::SeqCst), 1);
+        assert_eq!(cancelled.load(Ordering::SeqCst), 0);
+
+        // the timeout elapsed, the export is dropped
+        intervals.tick(timeout);
+        poll(&mut worker);
+        assert_eq!(cancelled.load(Ordering::SeqCst), 1);
+
+        // and the next collection happens on schedule
+        intervals.tick(PERIOD);
+        poll(&mut worker);
+        assert_eq!(started.load(Ordering::SeqCst), 2);
+    }
+
+    #[test]
+    fn test_collections_are_skipped_during_export() {
+        let exporter = TestExporter {
+            hang: true,
+            ..TestExporter::default()
+        };
+        let started = exporter.started.clone();
+        let intervals = ManualIntervals::default();
+        let (_controller, mut worker) = worker(exporter, time::Duration::from_secs(10), &intervals);
+
+        for _ in 0..3 {
+            intervals.tick(PERIOD);
+            poll(&mut worker);
+        }
+        assert_eq!(started.load(Ordering::SeqCst), 1);
+    }
+
+    #[test]
+    fn test_final_export_on_drop() {
+        let exporter = TestExporter {
+            hang: true,
+            ..TestExporter::default()
+        };
+        let (finished, cancelled) = (exporter.finished.clone(), exporter.cancelled.clone());
+        let intervals = ManualIntervals::default();
+        let (_controller, mut worker) = worker(exporter, time::Duration::from_secs(10), &intervals);
+
+        intervals.tick(PERIOD);
+        poll(&mut worker);
+        drop(worker);
+        // the unfinished export is cancelled and the data is still pushed
+        assert_eq!(cancelled.load(Ordering::SeqCst), 1);
+        assert_eq!(finished.load(Ordering::SeqCst), 1);
+    }
+}
@@ -17,6 +17,7 @@ use crate::{
 };
 use fnv::FnvHasher;
 use std::any::Any;
+use std::borrow::Cow;
 use std::cmp::Ordering;
 use std::collections::HashMap;
 use std::hash::{Hash, Hasher};
@@ -26,14 +27,19 @@ pub mod aggregators;
 pub mod controllers;
 pub mod processors;
 pub mod selectors;
+pub mod view;
 
 pub use controllers::{PullController, PushController, PushControllerWorker};
+pub use view::View;
+use view::ViewAggregations;
 
 /// Creates a new accumulator builder
 pub fn accumulator(processor: Arc<dyn Processor + Send + Sync>) -> AccumulatorBuilder {
     AccumulatorBuilder {
         processor,
         resource: None,
+        views: Vec::new(),
+        view_aggregations: Arc::default(),
     }
 }
 
@@ -42,6 +48,8 @@ pub fn accumulator(processor: Arc<dyn Processor + Send + Sync>) -> AccumulatorBu
 pub struct AccumulatorBuilder {
     processor: Arc<dyn Processor + Send + Sync>,
     resource: Option<Resource>,
+    views: Vec<View>,
+    view_aggregations: Arc<ViewAggregations>,
 }
 
 impl AccumulatorBuilder {
@@ -53,11 +61,32 @@ impl AccumulatorBuilder {
         }
     }
 
+    /// The views used to rename instruments, filter their labels and choose
+    /// their aggregation, see [`View::with_aggregation`].
+    ///
+    /// The processors of the controllers allocate aggregators of the view
+    /// aggregation too, other processors have to select aggregators of the
+    /// same kind for the renamed instruments.
+    pub fn with_views(self, views: Vec<View>) -> Self {
+        AccumulatorBuilder { views, ..self }
+    }
+
+    /// The registry the view aggregations of the instruments are recorded in,
+    /// shared with the processor's aggregator selector.
+    pub(crate) fn with_view_aggregations(self, view_aggregations: Arc<ViewAggregations>) -> Self {
+        AccumulatorBuilder {
+            view_aggregations,
+            ..self
+        }
+    }
+
     /// Create a new accumulator from this configuration
     pub fn build(self) -> Accumulator {
         Accumulator(Arc::new(AccumulatorCore::new(
             self.processor,
             self.resource.unwrap_or_default(),
+            self.views,
+            self.view_aggregations,
         )))
     }
 }
@@ -129,16 +158,55 @@ struct AccumulatorCore {
     processor: Arc<dyn Processor + Send + Sync>,
     /// The resource applied to all records in this Accumulator.
     resource: Resource,
+    /// The views applied to the instruments of this Accumulator.
+    views: Vec<View>,
+    /// The aggregations of the views, by renamed instrument descriptor.
+    view_aggregations: Arc<ViewAggregations>,
 }
 
 impl AccumulatorCore {
-    fn new(processor: Arc<dyn Processor + Send + Sync>, resource: Resource) -> Self {
+    fn new(
+        processor: Arc<dyn Processor + Send + Sync>,
+        resource: Resource,
+        views: Vec<View>,
+        view_aggregations: Arc<ViewAggregations>,
+    ) -> Self {
         AccumulatorCore {
             current: dashmap::DashMap::new(),
             async_instruments: Mutex::new(AsyncInstrumentState::default()),
             current_epoch: NumberKind::U64.zero().to_atomic(),
             processor,
             resource,
+            views,
+            view_aggregations,
+        }
+    }
+
+    /// Create the instrument state, applying the first view matching the
+    /// instrument name.
+    fn new_instrument(&self, meter: &Accumulator, descriptor: Descriptor) -> Instrument {
+        match self
+            .views
+            .iter()
+            .find(|view| view.matches(descriptor.name()))
+        {
+            Some(view) => {
+                let descriptor = view.apply_to_descriptor(descriptor);
+                if let Some(aggregation) = view.aggregation() {
+                    self.view_aggregations
+                        .insert(&descriptor, aggregation.clone());
+                }
+                Instrument {
+                    descriptor,
+                    view: Some(view.clone()),
+                    meter: meter.clone(),
+                }
+            }
+            None => Instrument {
+                descriptor,
+                view: None,
+                meter: meter.clone(),
+            },
         }
     }
 
@@ -306,6 +374,7 @@ struct SyncInstrument {
 
 impl SyncInstrument {
     fn acquire_handle(&self, labels: &[KeyValue]) -> Arc<Record> {
+        let labels = self.instrument.filter_labels(labels);
         let mut hasher = FnvHasher::default();
         self.instrument
             .descriptor
@@ -327,20 +396,8 @@ impl SyncInstrument {
             collected_count: NumberKind::U64.zero().to_atomic(),
             labels: LabelSet::from_labels(labels.iter().cloned()),
             instrument: self.clone(),
-            current: self
-                .instrument
-                .meter
-                .0
-                .processor
-                .aggregation_selector()
-                .aggregator_for(&self.instrument.descriptor),
-            checkpoint: self
-                .instrument
-                .meter
-                .0
-                .processor
-                .aggregation_selector()
-                .aggregator_for(&self.instrument.descriptor),
+            current: self.instrument.new_aggregator(),
+            checkpoint: self.instrument.new_aggregator(),
         });
         current.insert(map_key, record.clone());
 
@@ -385,7 +442,8 @@ impl AsyncInstrument {
         if let Err(err) = aggregators::range_test(number, &self.instrument.descriptor) {
             global::handle_error(err);
         }
-        if let Some(recorder) = self.get_recorder(labels) {
+        let labels = self.instrument.filter_label_set(labels);
+        if let Some(recorder) = self.get_recorder(&labels) {
             if let Err(err) = recorder.update(number, &self.instrument.descriptor) {
                 global::handle_error(err)
             }
@@ -408,26 +466,14 @@ impl AsyncInstrument {
                 if recorder.observed_epoch == current_epoch {
                     // last value wins for Observers, so if we see the same labels
                     // in the current epoch, we replace the old recorder
-                    return self
-                        .instrument
-                        .meter
-                        .0
-                        .processor
-                        .aggregation_selector()
-                        .aggregator_for(&self.instrument.descriptor);
+                    recorder.observed = self.instrument.new_aggregator();
                 } else {
                     recorder.observed_epoch = current_epoch;
                 }
                 return recorder.observed.clone();
             }
 
-            let recorder = self
-                .instrument
-                .meter
-                .0
-                .processor
-                .aggregation_selector()
-                .aggregator_for(&self.instrument.descriptor);
+            let recorder = self.instrument.new_aggregator();
             if recorders.is_none() {
                 *recorders = Some(HashMap::new());
             }
@@ -515,13 +561,43 @@ impl sdk_api::SyncBoundInstrumentCore for Record {
 
 struct Instrument {
     descriptor: Descriptor,
+    view: Option<View>,
     meter: Accumulator,
 }
 
+impl Instrument {
+    fn new_aggregator(&self) -> Option<Arc<dyn Aggregator + Send + Sync>> {
+        match self.view.as_ref().and_then(|view| view.aggregation()) {
+            Some(aggregation) => aggregation.aggregator_for(&self.descriptor),
+            None => self
+                .meter
+                .0
+                .processor
+                .aggregation_selector()
+                .aggregator_for(&self.descriptor),
+        }
+    }
+
+    fn filter_labels<'a>(&self, labels: &'a [KeyValue]) -> Cow<'a, [KeyValue]> {
+        match &self.view {
+            Some(view) => view.filter_labels(labels),
+            None => Cow::Borrowed(labels),
+        }
+    }
+
+    fn filter_label_set<'a>(&self, labels: &'a LabelSet) -> Cow<'a, LabelSet> {
+        match &self.view {
+            Some(view) => view.filter_label_set(labels),
+            None => Cow::Borrowed(labels),
+        }
+    }
+}
+
 impl std::fmt::Debug for Instrument {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         f.debug_struct("Instrument")
             .field("descriptor", &self.descriptor)
+            .field("view", &self.view)
             .field("meter", &"Accumulator")
             .finish()
     }
@@ -539,10 +615,7 @@ impl sdk_api::MeterCore for Accumulator {
         descriptor: Descriptor,
     ) -> Result<Arc<dyn sdk_api::SyncInstrumentCore>> {
         Ok(Arc::new(SyncInstrument {
-            instrument: Arc::new(Instrument {
-                descriptor,
-                meter: self.clone(),
-            }),
+            instrument: Arc::new(self.0.new_instrument(self, descriptor)),
         }))
     }
 
@@ -571,10 +644,7 @@ impl sdk_api::MeterCore for Accumulator {
         runner: Option<AsyncRunner>,
     ) -> Result<Arc<dyn sdk_api::AsyncInstrumentCore>> {
         let instrument = Arc::new(AsyncInstrument {
-            instrument: Arc::new(Instrument {
-                descriptor,
-                meter: self.clone(),
-            }),
+            instrument: Arc::new(self.0.new_instrument(self, descriptor)),
             recorders: Arc::new(Mutex::new(None)),
         });
 
@@ -590,10 +660,405 @@ impl sdk_api::MeterCore for Accumulator {
 
 #[cfg(test)]
 mod tests {
-    use crate::metrics::MeterProvider;
-    use crate::sdk::export::metrics::ExportKindSelector;
+    use crate::labels::LabelSet;
+    use crate::metrics::{Descriptor, InstrumentKind, MeterProvider, Number, NumberKind};
+    use crate::sdk::export::metrics::{
+        Aggregator, AggregatorSelector, CheckpointSet, DeltaTemporality, ExportKindSelector,
+        Histogram, LastValue, Record, Sum,
+    };
+    use crate::sdk::metrics::aggregators::{
+        self, HistogramAggregator, LastValueAggregator, SumAggregator,
+    };
     use crate::sdk::metrics::controllers::pull;
     use crate::sdk::metrics::selectors::simple::Selector;
+    use crate::sdk::metrics::View;
+    use crate::KeyValue;
+    use std::sync::atomic::{AtomicU64, Ordering};
+    use std::sync::Arc;
+
+    fn collect_records(
+        controller: &mut crate::sdk::metrics::PullController,
+    ) -> Vec<(String, LabelSet, Number)> {
+        controller.collect().unwrap();
+        let mut records = Vec::new();
+        controller
+            .try_for_each(&ExportKindSelector::Cumulative, &mut |record: &Record<
+                '_,
+            >| {
+                let sum = record
+                    .aggregator()
+                    .and_then(|agg| agg.as_any().downcast_ref::<SumAggregator>())
+                    .map(|agg| agg.sum().unwrap())
+                    .unwrap_or_default();
+                records.push((
+                    record.descriptor().name().to_string(),
+                    record.labels().clone(),
+                    sum,
+                ));
+                Ok(())
+            })
+            .unwrap();
+        records
+    }
+
+    #[test]
+    fn test_observable_gauge_keeps_last_value() {
+        let mut controller = pull(
+            Box::new(Selector::Exact),
+            Box::new(ExportKindSelector::Cumulative),
+        )
+        .with_cache_period(std::time::Duration::from_secs(0))
+        .build();
+        let meter = controller.provider().meter("test", None);
+        let depth = Arc::new(AtomicU64::new(3));
+        let callback_depth = depth.clone();
+        let _gauge = meter
+            .u64_observable_gauge("queue.depth", move |observer| {
+                let depth = callback_depth.load(Ordering::SeqCst);
+                // the last observation of a label set in a cycle wins
+                observer.observe(depth * 10, &[KeyValue::new("queue", "a")]);
+                observer.observe(depth, &[KeyValue::new("queue", "a")]);
+                observer.observe(depth + 1, &[KeyValue::new("queue", "b")]);
+            })
+            .init();
+
+        let collect = |controller: &mut crate::sdk::metrics::PullController| {
+            controller.collect().unwrap();
+            let mut values = Vec::new();
+            controller
+                .try_for_each(&ExportKindSelector::Cumulative, &mut |record| {
+                    let last_value = record
+                        .aggregator()
+                        .and_then(|agg| agg.as_any().downcast_ref::<LastValueAggregator>())
+                        .map(|agg| agg.last_value().unwrap().0)
+                        .unwrap();
+                    let labels: Vec<KeyValue> = record
+                        .labels()
+                        .iter()
+                        .map(|(key, value)| KeyValue::new(key.clone(), value.clone()))
+                        .collect();
+                    values.push((labels, last_value.to_u64(&NumberKind::U64)));
+                    Ok(())
+                })
+                .unwrap();
+            values.sort_by_key(|(_, value)| *value);
+            values
+        };
+
+        let values = collect(&mut controller);
+        assert_eq!(values[0].0, vec![KeyValue::new("queue", "a")]);
+        assert_eq!(values.iter().map(|v| v.1).collect::<Vec<_>>(), vec![3, 4]);
+
+        // the callback runs again on the next collection
+        depth.store(7, Ordering::SeqCst);
+        let values = collect(&mut controller);
+        assert_eq!(values.iter().map(|v| v.1).collect::<Vec<_>>(), vec![7, 8]);
+    }
+
+    #[test]
+    fn test_delta_temporality() {
+        let mut controller = pull(Box::new(Selector::Exact), Box::new(DeltaTemporality))
+            .with_cache_period(std::time::Duration::from_secs(0))
+            .build();
+        let meter = controller.provider().meter("test", None);
+        let requests = meter.u64_counter("requests").init();
+        let in_flight = meter.i64_up_down_counter("requests.in_flight").init();
+
+        let collect = |controller: &mut crate::sdk::metrics::PullController| {
+            controller.collect().unwrap();
+            let mut values = Vec::new();
+            controller
+                .try_for_each(&DeltaTemporality, &mut |record| {
+                    let sum = record
+                        .aggregator()
+                        .and_then(|agg| agg.as_any().downcast_ref::<SumAggregator>())
+                        .map(|agg| agg.sum().unwrap())
+                        .unwrap();
+                    let kind = record.descriptor().number_kind();
+                    values.push((record.descriptor().name().to_string(), sum.to_i64(kind)));
+                    Ok(())
+                })
+                .unwrap();
+            values.sort();
+            values
+        };
+
+        requests.add(3, &[]);
+        in_flight.add(2, &[]);
+        assert_eq!(
+            collect(&mut controller),
+            vec![
+                ("requests".to_string(), 3),
+                ("requests.in_flight".to_string(), 2)
+            ]
+        );
+
+        // counters report the increments of the interval, up down counters
+        // their running total
+        requests.add(2, &[]);
+        in_flight.add(1, &[]);
+        assert_eq!(
+            collect(&mut controller),
+            vec![
+                ("requests".to_string(), 2),
+                ("requests.in_flight".to_string(), 3)
+            ]
+        );
+    }
+
+    #[test]
+    fn test_delta_temporality_kinds() {
+        use crate::sdk::export::metrics::{ExportKind, TemporalitySelector};
+
+        for (kind, delta) in vec![
+            (InstrumentKind::Counter, true),
+            (InstrumentKind::ValueRecorder, true),
+            (InstrumentKind::SumObserver, true),
+            (InstrumentKind::UpDownCounter, false),
+            (InstrumentKind::UpDownSumObserver, false),
+            // value observers are gauges, their deltas are meaningless
+            (InstrumentKind::ValueObserver, false),
+        ] {
+            let temporality = DeltaTemporality.temporality(&kind);
+            assert_eq!(
+                matches!(temporality, ExportKind::Delta),
+                delta,
+                "{:?}",
+                kind
+            );
+        }
+    }
+
+    #[test]
+    fn test_up_down_counter_negative_values() {
+        let mut controller = pull(
+            Box::new(Selector::Exact),
+            Box::new(ExportKindSelector::Cumulative),
+        )
+        .with_cache_period(std::time::Duration::from_secs(0))
+        .build();
+        let meter = controller.provider().meter("test", None);
+        let in_flight = meter.i64_up_down_counter("requests.in_flight").init();
+        let queued = meter.f64_up_down_counter("requests.queued").init();
+
+        in_flight.add(5, &[]);
+        in_flight.add(-8, &[]);
+        queued.add(1.5, &[]);
+        queued.add(-4.0, &[]);
+
+        let mut records = collect_records(&mut controller);
+        records.sort_by(|a, b| a.0.cmp(&b.0));
+        assert_eq!(records[0].0, "requests.in_flight");
+        assert_eq!(records[0].2.to_i64(&NumberKind::I64), -3);
+        assert_eq!(records[1].0, "requests.queued");
+        assert!((records[1].2.to_f64(&NumberKind::F64) + 2.5).abs() < f64::EPSILON);
+    }
+
+    #[test]
+    fn test_view_filters_labels_before_aggregation() {
+        let mut controller = pull(
+            Box::new(Selector::Exact),
+            Box::new(ExportKindSelector::Cumulative),
+        )
+        .with_cache_period(std::time::Duration::from_secs(0))
+        .with_view(
+            View::new("http.server.*")
+                .with_allowed_label_keys(vec!["http.method".into(), "http.status_code".into()]),
+        )
+        .build();
+        let meter = controller.provider().meter("test", None);
+        let requests = meter.u64_counter("http.server.requests").init();
+        let other = meter.u64_counter("other.requests").init();
+
+        for user in &["a", "b", "c"] {
+            let labels = [
+                KeyValue::new("http.method", "GET"),
+                KeyValue::new("http.status_code", 200),
+                KeyValue::new("user.id", *user),
+            ];
+            requests.add(1, &labels);
+            other.add(1, &labels);
+        }
+
+        let mut records = collect_records(&mut controller);
+        records.sort_by(|a, b| a.0.cmp(&b.0));
+        let requests: Vec<_> = records
+            .iter()
+            .filter(|(name, _, _)| name == "http.server.requests")
+            .collect();
+        assert_eq!(requests.len(), 1, "cardinality is reduced by the view");
+        assert_eq!(requests[0].1.len(), 2);
+        assert_eq!(requests[0].2.to_u64(&NumberKind::U64), 3);
+        assert_eq!(
+            records
+                .iter()
+                .filter(|(name, _, _)| name == "other.requests")
+                .count(),
+            3,
+            "instruments not matching the view keep all labels"
+        );
+    }
+
+    #[test]
+    fn test_view_renames_and_changes_aggregation() {
+        let boundaries = vec![10.0, 100.0];
+        let mut controller = pull(
+            Box::new(Selector::Exact),
+            Box::new(ExportKindSelector::Cumulative),
+        )
+        .with_cache_period(std::time::Duration::from_secs(0))
+        .with_view(
+            View::new("request.latency")
+                .with_name("http.server.duration")
+                .with_aggregation(Selector::Histogram(boundaries.clone())),
+        )
+        .build();
+        let meter = controller.provider().meter("test", None);
+        let latency = meter.f64_value_recorder("request.latency").init();
+        // record twice so the processor merges into its own aggregator
+        latency.record(5.0, &[]);
+        controller.collect().unwrap();
+        latency.record(50.0, &[]);
+        controller.collect().unwrap();
+
+        let mut seen = 0;
+        controller
+            .try_for_each(&ExportKindSelector::Cumulative, &mut |record: &Record<
+                '_,
+            >| {
+                assert_eq!(record.descriptor().name(), "http.server.duration");
+                let histogram = record
+                    .aggregator()
+                    .and_then(|agg| agg.as_any().downcast_ref::<HistogramAggregator>())
+                    .expect("view aggregation is used");
+                let buckets = histogram.histogram().unwrap();
+                assert_eq!(buckets.boundaries(), &boundaries);
+                assert_eq!(buckets.counts(), &vec![1.0, 1.0, 0.0]);
+                seen += 1;
+                Ok(())
+            })
+            .unwrap();
+        assert_eq!(seen, 1);
+    }
+
+    #[test]
+    fn test_view_aggregation_with_colliding_names() {
+        let mut controller = pull(
+            Box::new(Selector::Exact),
+            Box::new(ExportKindSelector::Cumulative),
+        )
+        .with_cache_period(std::time::Duration::from_secs(0))
+        .with_view(View::new("http.*").with_aggregation(Selector::Histogram(vec![1.0])))
+        // renamed into the name pattern of the previous view
+        .with_view(View::new("foo").with_name("http.foo"))
+        // renamed to the name of an instrument without a view
+        .with_view(
+            View::new("baz")
+                .with_name("bar")
+                .with_aggregation(Selector::Histogram(vec![1.0])),
+        )
+        .build();
+        let meter = controller.provider().meter("test", None);
+        let foo = meter.f64_value_recorder("foo").init();
+        let bar = meter.f64_value_recorder("bar").init();
+        for _ in 0..2 {
+            foo.record(0.5, &[]);
+            bar.record(0.5, &[]);
+            controller.collect().unwrap();
+        }
+
+        let mut names = Vec::new();
+        controller
+            .try_for_each(&ExportKindSelector::Cumulative, &mut |record: &Record<
+                '_,
+            >| {
+                let is_histogram = record
+                    .aggregator()
+                    .map(|agg| agg.as_any().is::<HistogramAggregator>())
+                    .unwrap_or_default();
+                assert!(
+                    !is_histogram,
+                    "{} uses the aggregation of another view",
+                    record.descriptor().name()
+                );
+                names.push(record.descriptor().name().to_string());
+                Ok(())
+            })
+            .unwrap();
+        names.sort();
+        assert_eq!(names, vec!["bar".to_string(), "http.foo".to_string()]);
+    }
+
+    /// Uses histograms with per instrument boundaries for value recorders.
+    #[derive(Debug)]
+    struct BoundariesSelector;
+
+    impl AggregatorSelector for BoundariesSelector {
+        fn aggregator_for(
+            &self,
+            descriptor: &Descriptor,
+        ) -> Option<Arc<dyn Aggregator + Send + Sync>> {
+            match descriptor.instrument_kind() {
+                InstrumentKind::ValueRecorder if descriptor.name() == "response.size" => Some(
+                    Arc::new(aggregators::histogram(descriptor, &[1024.0, 65536.0])),
+                ),
+                InstrumentKind::ValueRecorder => {
+                    Some(Arc::new(aggregators::histogram(descriptor, &[0.1, 1.0])))
+                }
+                _ => Some(Arc::new(aggregators::sum())),
+            }
+        }
+    }
+
+    #[test]
+    fn test_with_aggregator_selector() {
+        let mut controller = pull(
+            Box::new(Selector::Exact),
+            Box::new(ExportKindSelector::Cumulative),
+        )
+        .with_aggregator_selector(BoundariesSelector)
+        .with_cache_period(std::time::Duration::from_secs(0))
+        .build();
+        let meter = controller.provider().meter("test", None);
+        meter
+            .f64_value_recorder("response.size")
+            .init()
+            .record(2048.0, &[]);
+        meter.f64_value_recorder("latency").init().record(0.5, &[]);
+        controller.collect().unwrap();
+
+        let mut boundaries = Vec::new();
+        controller
+            .try_for_each(&ExportKindSelector::Cumulative, &mut |record: &Record<
+                '_,
+            >| {
+                let histogram = record
+                    .aggregator()
+                    .and_then(|agg| agg.as_any().downcast_ref::<HistogramAggregator>())
+                    .expect("selected aggregation is used");
+                let buckets = histogram.histogram().unwrap();
+                boundaries.push((
+                    record.descriptor().name().to_string(),
+                    buckets.boundaries().clone(),
+                    buckets.counts().clone(),
+                ));
+                Ok(())
+            })
+            .unwrap();
+        boundaries.sort_by(|a, b| a.0.cmp(&b.0));
+
+        assert_eq!(
+            boundaries,
+            vec![
+                ("latency".to_string(), vec![0.1, 1.0], vec![0.0, 1.0, 0.0]),
+                (
+                    "response.size".to_string(),
+                    vec![1024.0, 65536.0],
+                    vec![0.0, 1.0, 0.0]
+                ),
+            ]
+        );
+    }
 
     // Prevent the debug message to get into loop
     #[test]
@@ -24,6 +24,20 @@ pub enum Selector {
     /// for metrics. This selector uses more memory than `Inexpensive` because
     /// it uses a counter per bucket.
     Histogram(Vec<f64>),
+    /// A simple aggregation selector that uses sum, and histogram aggregators
+    /// for metrics, with explicit bucket boundaries that can be configured per
+    /// instrument.
+    ExplicitHistogram(aggregators::HistogramConfig),
+    /// A simple aggregation selector that uses sum, and base-2 exponential
+    /// histogram aggregators for metrics. The histograms keep at most
+    /// `max_size` positive and negative buckets and start at `max_scale`,
+    /// reducing the scale as the range of recorded values grows.
+    ExponentialHistogram {
+        /// The maximum number of buckets for each of the positive and negative ranges.
+        max_size: u32,
+        /// The initial and highest scale of the histograms.
+        max_scale: i8,
+    },
 }
 
 impl AggregatorSelector for Selector {
@@ -56,6 +70,24 @@ impl AggregatorSelector for Selector {
                 }
                 _ => Some(Arc::new(aggregators::sum())),
             },
+            Selector::ExplicitHistogram(config) => match descriptor.instrument_kind() {
+                InstrumentKind::ValueObserver => Some(Arc::new(aggregators::last_value())),
+                InstrumentKind::ValueRecorder => Some(Arc::new(aggregators::histogram(
+                    descriptor,
+                    config.boundaries(descriptor),
+                ))),
+                _ => Some(Arc::new(aggregators::sum())),
+            },
+            Selector::ExponentialHistogram {
+                max_size,
+                max_scale,
+            } => match descriptor.instrument_kind() {
+                InstrumentKind::ValueObserver => Some(Arc::new(aggregators::last_value())),
+                InstrumentKind::ValueRecorder => Some(Arc::new(
+                    aggregators::exponential_histogram(*max_size, *max_scale),
+                )),
+                _ => Some(Arc::new(aggregators::sum())),
+            },
         }
     }
 }
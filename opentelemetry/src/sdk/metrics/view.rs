@@ -0,0 +1,301 @@
+//! Metric Views
+//!
+//! A [`View`] customizes the metrics produced by the instruments it matches.
+//! Views can rename the exported metric, reduce the set of label keys that are
+//! aggregated, and change the aggregation used for the instruments.
+//!
+//! Views are registered on the push and pull controllers and are matched by
+//! instrument name in the order they were registered, only the first matching
+//! view is applied to an instrument.
+//!
+//! # Examples
+//!
+//! ```
+//! use opentelemetry::sdk::export::metrics::ExportKindSelector;
+//! use opentelemetry::sdk::metrics::{controllers, selectors, View};
+//!
+//! // only keep the method and status code of http server metrics
+//! let view = View::new("http.server.*")
+//!     .with_allowed_label_keys(vec!["http.method".into(), "http.status_code".into()]);
+//!
+//! let controller = controllers::pull(
+//!     Box::new(selectors::simple::Selector::Exact),
+//!     Box::new(ExportKindSelector::Cumulative),
+//! )
+//! .with_view(view)
+//! .build();
+//! ```
+use crate::labels::LabelSet;
+use crate::metrics::Descriptor;
+use crate::sdk::export::metrics::{Aggregator, AggregatorSelector};
+use crate::{Key, KeyValue};
+use std::borrow::Cow;
+use std::collections::{HashMap, HashSet};
+use std::sync::{Arc, RwLock};
+
+/// Configures the metrics produced by the instruments matching its name pattern.
+#[derive(Clone, Debug)]
+pub struct View {
+    instrument_name: String,
+    name: Option<String>,
+    allowed_label_keys: Option<HashSet<Key>>,
+    dropped_label_keys: HashSet<Key>,
+    aggregation: Option<Arc<dyn AggregatorSelector + Send + Sync>>,
+}
+
+impl View {
+    /// Create a new view matching instruments by name.
+    ///
+    /// The name may contain `*` wildcards that match any sequence of
+    /// characters, e.g. `http.server.*` or `*`.
+    pub fn new<T: Into<String>>(instrument_name: T) -> Self {
+        View {
+            instrument_name: instrument_name.into(),
+            name: None,
+            allowed_label_keys: None,
+            dropped_label_keys: HashSet::new(),
+            aggregation: None,
+        }
+    }
+
+    /// Export the matched instrument under a new name.
+    ///
+    /// Renaming should be used with views matching a single instrument, as all
+    /// matched instruments would otherwise be exported with the same name.
+    pub fn with_name<T: Into<String>>(self, name: T) -> Self {
+        View {
+            name: Some(name.into()),
+            ..self
+        }
+    }
+
+    /// Only aggregate the labels with the given keys, all other labels are
+    /// dropped before aggregation.
+    pub fn with_allowed_label_keys<T: IntoIterator<Item = Key>>(self, keys: T) -> Self {
+        View {
+            allowed_label_keys: Some(keys.into_iter().collect()),
+            ..self
+        }
+    }
+
+    /// Drop the labels with the given keys before aggregation.
+    pub fn with_dropped_label_keys<T: IntoIterator<Item = Key>>(self, keys: T) -> Self {
+        View {
+            dropped_label_keys: keys.into_iter().collect(),
+            ..self
+        }
+    }
+
+    /// Use the given selector to choose the aggregator of the matched
+    /// instruments instead of the controller's aggregator selector.
+    pub fn with_aggregation<T>(self, aggregation: T) -> Self
+    where
+        T: AggregatorSelector + Send + Sync + 'static,
+    {
+        View {
+            aggregation: Some(Arc::new(aggregation)),
+            ..self
+        }
+    }
+
+    /// Whether this view applies to the instrument with the given name.
+    pub fn matches(&self, instrument_name: &str) -> bool {
+        wildcard_match(&self.instrument_name, instrument_name)
+    }
+
+    /// The aggregation of this view, if it overrides the controller's one.
+    pub(crate) fn aggregation(&self) -> Option<&Arc<dyn AggregatorSelector + Send + Sync>> {
+        self.aggregation.as_ref()
+    }
+
+    /// Apply the renaming of this view to a descriptor.
+    pub(crate) fn apply_to_descriptor(&self, descriptor: Descriptor) -> Descriptor {
+        match &self.name {
+            Some(name) => descriptor.with_name(name.clone()),
+            None => descriptor,
+        }
+    }
+
+    /// Whether this view removes any labels.
+    fn filters_labels(&self) -> bool {
+        self.allowed_label_keys.is_some() || !self.dropped_label_keys.is_empty()
+    }
+
+    fn keep_label(&self, key: &Key) -> bool {
+        let allowed = match &self.allowed_label_keys {
+            Some(allowed) => allowed.contains(key),
+            None => true,
+        };
+
+        allowed && !self.dropped_label_keys.contains(key)
+    }
+
+    /// Remove the labels that should not be aggregated.
+    pub(crate) fn filter_labels<'a>(&self, labels: &'a [KeyValue]) -> Cow<'a, [KeyValue]> {
+        if !self.filters_labels() {
+            return Cow::Borrowed(labels);
+        }
+
+        Cow::Owned(
+            labels
+                .iter()
+                .filter(|kv| self.keep_label(&kv.key))
+                .cloned()
+                .collect(),
+        )
+    }
+
+    /// Remove the labels that should not be aggregated from a label set.
+    pub(crate) fn filter_label_set<'a>(&self, labels: &'a LabelSet) -> Cow<'a, LabelSet> {
+        if !self.filters_labels() {
+            return Cow::Borrowed(labels);
+        }
+
+        Cow::Owned(LabelSet::from_labels(
+            labels
+                .iter()
+                .filter(|(key, _)| self.keep_label(key))
+                .map(|(key, value)| KeyValue::new(key.clone(), value.clone())),
+        ))
+    }
+}
+
+/// Matches `name` against `pattern`, where `*` in the pattern matches any
+/// sequence of characters.
+fn wildcard_match(pattern: &str, name: &str) -> bool {
+    let mut parts = pattern.split('*');
+    // split always yields at least one part
+    let first = parts.next().unwrap_or_default();
+    let mut rest = match name.strip_prefix(first) {
+        Some(rest) => rest,
+        None => return false,
+    };
+
+    let mut parts = parts.peekable();
+    while let Some(part) = parts.next() {
+        if parts.peek().is_none() {
+            // the last part has to match the end of the name
+            return rest.ends_with(part);
+        }
+        match rest.find(part) {
+            Some(idx) => rest = &rest[idx + part.len()..],
+            None => return false,
+        }
+    }
+
+    // no wildcard in the pattern
+    rest.is_empty()
+}
+
+/// The aggregations of the views applied to the instruments of an accumulator,
+/// keyed by the hash of their descriptor after renaming.
+///
+/// The accumulator records the aggregation of each instrument it creates, so
+/// the processor sharing this registry allocates aggregators of the same kind.
+#[derive(Debug, Default)]
+pub(crate) struct ViewAggregations(RwLock<HashMap<u64, Arc<dyn AggregatorSelector + Send + Sync>>>);
+
+impl ViewAggregations {
+    /// Record the aggregation of the instrument with the given descriptor.
+    pub(crate) fn insert(
+        &self,
+        descriptor: &Descriptor,
+        aggregation: Arc<dyn AggregatorSelector + Send + Sync>,
+    ) {
+        if let Ok(mut aggregations) = self.0.write() {
+            aggregations.insert(descriptor.attribute_hash(), aggregation);
+        }
+    }
+
+    fn get(&self, descriptor: &Descriptor) -> Option<Arc<dyn AggregatorSelector + Send + Sync>> {
+        self.0
+            .read()
+            .ok()
+            .and_then(|aggregations| aggregations.get(&descriptor.attribute_hash()).cloned())
+    }
+}
+
+/// Aggregator selector of the processor, using the view aggregations recorded
+/// by the accumulator and falling back to the configured selector for
+/// instruments without a view aggregation.
+#[derive(Debug)]
+pub(crate) struct ViewAggregatorSelector {
+    fallback: Box<dyn AggregatorSelector + Send + Sync>,
+    aggregations: Arc<ViewAggregations>,
+}
+
+impl ViewAggregatorSelector {
+    pub(crate) fn new(
+        fallback: Box<dyn AggregatorSelector + Send + Sync>,
+        aggregations: Arc<ViewAggregations>,
+    ) -> Self {
+        ViewAggregatorSelector {
+            fallback,
+            aggregations,
+        }
+    }
+}
+
+impl AggregatorSelector for ViewAggregatorSelector {
+    fn aggregator_for(&self, descriptor: &Descriptor) -> Option<Arc<dyn Aggregator + Send + Sync>> {
+        match self.aggregations.get(descriptor) {
+            Some(aggregation) => aggregation.aggregator_for(descriptor),
+            None => self.fallback.aggregator_for(descriptor),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_wildcard_match() {
+        let test_cases = vec![
+            ("http.server.duration", "http.server.duration", true),
+            ("http.server.duration", "http.server.duration2", false),
+            ("http.server.*", "http.server.duration", true),
+            ("http.server.*", "http.server.", true),
+            ("http.server.*", "http.client.duration", false),
+            ("*", "anything", true),
+            ("*.duration", "http.server.duration", true),
+            ("*.duration", "http.server.size", false),
+            ("http.*.duration", "http.server.duration", true),
+            ("http.*.duration", "http.server.size", false),
+            ("a*b*c", "abc", true),
+            ("a*b*c", "acb", false),
+        ];
+
+        for (pattern, name, expected) in test_cases {
+            assert_eq!(
+                wildcard_match(pattern, name),
+                expected,
+                "{} matching {}",
+                pattern,
+                name
+            );
+        }
+    }
+
+    #[test]
+    fn test_filter_labels() {
+        let labels = vec![
+            KeyValue::new("http.method", "GET"),
+            KeyValue::new("http.status_code", 200),
+            KeyValue::new("user.id", "42"),
+        ];
+
+        let unfiltered = View::new("*");
+        assert_eq!(
+            unfiltered.filter_labels(&labels).as_ref(),
+            labels.as_slice()
+        );
+
+        let allowed = View::new("*")
+            .with_allowed_label_keys(vec!["http.method".into(), "http.status_code".into()]);
+        assert_eq!(allowed.filter_labels(&labels).as_ref(), &labels[..2]);
+
+        let dropped = View::new("*").with_dropped_label_keys(vec!["http.method".into()]);
+        assert_eq!(dropped.filter_labels(&labels).as_ref(), &labels[1..]);
+    }
+}
@@ -22,4 +22,6 @@ pub mod trace;
 
 pub use env::EnvResourceDetector;
 pub use instrumentation::InstrumentationLibrary;
-pub use resource::Resource;
+pub use resource::{
+    OsResourceDetector, ProcessResourceDetector, Resource, TelemetryResourceDetector,
+};
@@ -49,7 +49,13 @@ use percent_encoding::{percent_decode_str, utf8_percent_encode, AsciiSet, CONTRO
 use std::iter;
 
 static BAGGAGE_HEADER: &str = "baggage";
-const FRAGMENT: &AsciiSet = &CONTROLS.add(b' ').add(b'"').add(b';').add(b',').add(b'=');
+const FRAGMENT: &AsciiSet = &CONTROLS
+    .add(b' ')
+    .add(b'"')
+    .add(b'%')
+    .add(b';')
+    .add(b',')
+    .add(b'=');
 
 lazy_static::lazy_static! {
     static ref BAGGAGE_FIELDS: [String; 1] = [BAGGAGE_HEADER.to_string()];
@@ -117,13 +123,13 @@ impl TextMapPropagator for BaggagePropagator {
             let header_value = baggage
                 .iter()
                 .map(|(name, (value, metadata))| {
-                    let metadata_str = metadata.as_str().trim();
+                    let metadata_str = encode_metadata(metadata.as_str());
                     let metadata_prefix = if metadata_str.is_empty() { "" } else { ";" };
                     utf8_percent_encode(name.as_str().trim(), FRAGMENT)
                         .chain(iter::once("="))
                         .chain(utf8_percent_encode(value.as_str().trim(), FRAGMENT))
                         .chain(iter::once(metadata_prefix))
-                        .chain(iter::once(metadata_str))
+                        .chain(iter::once(metadata_str.as_str()))
                         .collect()
                 })
                 .collect::<Vec<String>>()
@@ -141,7 +147,8 @@ impl TextMapPropagator for BaggagePropagator {
                     .collect::<Vec<&str>>()
                     .split_first()
                 {
-                    let mut iter = name_and_value.split('=');
+                    // values may contain `=`, only the first one separates name and value
+                    let mut iter = name_and_value.splitn(2, '=');
                     if let (Some(name), Some(value)) = (iter.next(), iter.next()) {
                         let name = percent_decode_str(name).decode_utf8().map_err(|_| ())?;
                         let value = percent_decode_str(value).decode_utf8().map_err(|_| ())?;
@@ -180,6 +187,29 @@ impl TextMapPropagator for BaggagePropagator {
     }
 }
 
+/// Percent encodes the `;` separated properties of the metadata, keeping the
+/// separators and the `=` between property keys and values.
+fn encode_metadata(metadata: &str) -> String {
+    metadata
+        .split(';')
+        .map(str::trim)
+        .filter(|prop| !prop.is_empty())
+        .map(|prop| {
+            let mut iter = prop.splitn(2, '=');
+            let key = iter.next().unwrap_or_default();
+            match iter.next() {
+                Some(value) => format!(
+                    "{}={}",
+                    utf8_percent_encode(key.trim(), FRAGMENT),
+                    utf8_percent_encode(value.trim(), FRAGMENT)
+                ),
+                None => utf8_percent_encode(key, FRAGMENT).to_string(),
+            }
+        })
+        .collect::<Vec<String>>()
+        .join(";")
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -200,6 +230,8 @@ mod tests {
             ("key1=val1,key2=val2,a,val3", vec![(Key::new("key1"), Value::from("val1")), (Key::new("key2"), Value::from("val2"))].into_iter().collect()),
             // "valid header with no value"
             ("key1=,key2=val2", vec![(Key::new("key1"), Value::from("")), (Key::new("key2"), Value::from("val2"))].into_iter().collect()),
+            // "valid header with unescaped equal sign in value"
+            ("key1=dmFsMQ==,key2=val2", vec![(Key::new("key1"), Value::from("dmFsMQ==")), (Key::new("key2"), Value::from("val2"))].into_iter().collect()),
         ]
     }
 
@@ -291,6 +323,49 @@ mod tests {
         }
     }
 
+    #[test]
+    fn extract_baggage_limits_entries() {
+        let propagator = BaggagePropagator::new();
+        let header_value = (0..200)
+            .map(|i| format!("key{}=val{}", i, i))
+            .collect::<Vec<_>>()
+            .join(",");
+        let mut extractor: HashMap<String, String> = HashMap::new();
+        extractor.insert(BAGGAGE_HEADER.to_string(), header_value);
+
+        let context = propagator.extract(&extractor);
+        assert_eq!(context.baggage().len(), 180);
+    }
+
+    #[test]
+    fn composite_with_trace_context() {
+        use crate::sdk::propagation::{TextMapCompositePropagator, TraceContextPropagator};
+        use crate::trace::TraceContextExt;
+
+        let composite = TextMapCompositePropagator::new(vec![
+            Box::new(TraceContextPropagator::new()),
+            Box::new(BaggagePropagator::new()),
+        ]);
+        let mut extractor: HashMap<String, String> = HashMap::new();
+        extractor.insert(
+            "traceparent".to_string(),
+            "00-4bf92f3577b34da6a3ce929d0e0e4736-00f067aa0ba902b7-01".to_string(),
+        );
+        extractor.insert(BAGGAGE_HEADER.to_string(), "tenant=acme%20corp".to_string());
+
+        let cx = composite.extract(&extractor);
+        assert!(cx.span().span_context().is_valid());
+        assert_eq!(cx.baggage().get("tenant"), Some(&Value::from("acme corp")));
+
+        let mut injector = HashMap::new();
+        composite.inject_context(&cx, &mut injector);
+        assert_eq!(
+            injector.get(BAGGAGE_HEADER),
+            Some(&"tenant=acme%20corp".to_string())
+        );
+        assert_eq!(injector.get("traceparent"), extractor.get("traceparent"));
+    }
+
     #[test]
     fn inject_baggage() {
         let propagator = BaggagePropagator::new();
@@ -339,4 +414,36 @@ mod tests {
             }
         }
     }
+
+    #[test]
+    fn baggage_metadata_round_trip() {
+        let propagator = BaggagePropagator::new();
+        let kvm = vec![
+            KeyValueMetadata::new("key1", "val1", "propagate-only"),
+            KeyValueMetadata::new("key2", "50%", "owner=team a,team b;ttl = 3"),
+            KeyValue::new("key3", "val3").into(),
+        ];
+
+        let mut injector = HashMap::new();
+        propagator.inject_context(&Context::current_with_baggage(kvm), &mut injector);
+        let extracted = propagator.extract(&injector);
+        let baggage = extracted.baggage();
+
+        assert_eq!(baggage.len(), 3);
+        assert_eq!(
+            baggage.get_with_metadata("key1"),
+            Some(&(Value::from("val1"), BaggageMetadata::from("propagate-only")))
+        );
+        assert_eq!(
+            baggage.get_with_metadata("key2"),
+            Some(&(
+                Value::from("50%"),
+                BaggageMetadata::from("owner=team a,team b;ttl=3")
+            ))
+        );
+        assert_eq!(
+            baggage.get_with_metadata("key3"),
+            Some(&(Value::from("val3"), BaggageMetadata::default()))
+        );
+    }
 }
@@ -1,8 +1,16 @@
 use crate::{
+    global,
     propagation::{text_map_propagator::FieldIter, Extractor, Injector, TextMapPropagator},
+    sdk::propagation::{BaggagePropagator, TraceContextPropagator},
     Context,
 };
 use std::collections::HashSet;
+use std::env;
+
+/// Comma separated list of the propagators to use.
+const OTEL_PROPAGATORS: &str = "OTEL_PROPAGATORS";
+/// Default propagators.
+const OTEL_PROPAGATORS_DEFAULT: &str = "tracecontext,baggage";
 
 /// Composite propagator
 ///
@@ -67,20 +75,90 @@ pub struct TextMapCompositePropagator {
 impl TextMapCompositePropagator {
     /// Constructs a new propagator out of instances of [`TextMapPropagator`].
     ///
+    /// The fields of the composite propagator are the fields of the given
+    /// propagators in order, without duplicates.
+    ///
     /// [`TextMapPropagator`]: crate::propagation::TextMapPropagator
     pub fn new(propagators: Vec<Box<dyn TextMapPropagator + Send + Sync>>) -> Self {
-        let mut fields = HashSet::new();
+        let mut seen = HashSet::new();
+        let mut fields = Vec::new();
         for propagator in &propagators {
             for field in propagator.fields() {
-                fields.insert(field.to_string());
+                if seen.insert(field) {
+                    fields.push(field.to_string());
+                }
             }
         }
 
         TextMapCompositePropagator {
             propagators,
-            fields: fields.into_iter().collect(),
+            fields,
         }
     }
+
+    /// Constructs a new propagator out of the propagators listed in the
+    /// `OTEL_PROPAGATORS` environment variable, `tracecontext,baggage` by
+    /// default.
+    ///
+    /// `tracecontext`, `baggage` and `none` are supported by this crate, the
+    /// propagators of all other names, e.g. `b3`, `b3multi` or `jaeger`, are
+    /// looked up with `resolve`. Names that cannot be resolved are reported
+    /// through the global error handler and skipped. If `none` is listed, the
+    /// returned propagator is a no-op.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use opentelemetry::sdk::propagation::TextMapCompositePropagator;
+    ///
+    /// // only use the propagators of this crate
+    /// let propagator = TextMapCompositePropagator::from_env(|_| None);
+    /// ```
+    pub fn from_env<F>(resolve: F) -> Self
+    where
+        F: Fn(&str) -> Option<Box<dyn TextMapPropagator + Send + Sync>>,
+    {
+        let names = env::var(OTEL_PROPAGATORS)
+            .ok()
+            .filter(|names| !names.trim().is_empty())
+            .unwrap_or_else(|| OTEL_PROPAGATORS_DEFAULT.to_string());
+
+        Self::from_names(&names, resolve)
+    }
+
+    fn from_names<F>(names: &str, resolve: F) -> Self
+    where
+        F: Fn(&str) -> Option<Box<dyn TextMapPropagator + Send + Sync>>,
+    {
+        let names = names
+            .split(',')
+            .map(str::trim)
+            .filter(|name| !name.is_empty())
+            .collect::<Vec<_>>();
+        if names.contains(&"none") {
+            return TextMapCompositePropagator::new(vec![]);
+        }
+
+        let propagators = names
+            .into_iter()
+            .filter_map(|name| {
+                let propagator: Option<Box<dyn TextMapPropagator + Send + Sync>> = match name {
+                    "tracecontext" => Some(Box::new(TraceContextPropagator::new())),
+                    "baggage" => Some(Box::new(BaggagePropagator::new())),
+                    _ => resolve(name),
+                };
+                if propagator.is_none() {
+                    global::handle_error(global::Error::Other(format!(
+                        "unknown propagator {:?} in {}, skipping it",
+                        name, OTEL_PROPAGATORS
+                    )));
+                }
+                propagator
+            })
+            .collect();
+
+        TextMapCompositePropagator::new(propagators)
+    }
 }
 
 impl TextMapPropagator for TextMapCompositePropagator {
@@ -268,6 +346,33 @@ mod tests {
         }
     }
 
+    fn fields(propagator: &TextMapCompositePropagator) -> Vec<String> {
+        propagator.fields().map(|s| s.to_string()).collect()
+    }
+
+    #[test]
+    fn test_from_names() {
+        let resolve = |name: &str| -> Option<Box<dyn TextMapPropagator + Send + Sync>> {
+            match name {
+                "test" => Some(Box::new(TestPropagator::new())),
+                _ => None,
+            }
+        };
+
+        let propagator = TextMapCompositePropagator::from_names("tracecontext, test", resolve);
+        assert_eq!(
+            fields(&propagator),
+            vec!["traceparent", "tracestate", "testheader"]
+        );
+
+        // unknown names are skipped
+        let propagator = TextMapCompositePropagator::from_names("unknown,baggage", resolve);
+        assert_eq!(fields(&propagator), vec!["baggage"]);
+
+        let propagator = TextMapCompositePropagator::from_names("tracecontext,none", resolve);
+        assert!(fields(&propagator).is_empty());
+    }
+
     #[test]
     fn test_get_fields() {
         let test_propagator = TestPropagator::new();
@@ -285,20 +390,19 @@ mod tests {
         let composite_propagator = TextMapCompositePropagator::new(vec![
             Box::new(test_propagator),
             Box::new(trace_context),
+            Box::new(TraceContextPropagator::new()),
         ]);
 
-        let mut fields = composite_propagator
+        let fields = composite_propagator
             .fields()
             .map(|s| s.to_string())
             .collect::<Vec<String>>();
-        fields.sort();
 
-        let mut expected = vec![b3_fields, trace_context_fields]
+        // union of the fields in the order of the propagators
+        let expected = vec![b3_fields, trace_context_fields]
             .into_iter()
             .flatten()
             .collect::<Vec<String>>();
-        expected.sort();
-        expected.dedup();
 
         assert_eq!(fields, expected);
     }
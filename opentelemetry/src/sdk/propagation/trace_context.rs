@@ -19,7 +19,10 @@
 //! [w3c trace-context docs]: https://w3c.github.io/trace-context/
 use crate::{
     propagation::{text_map_propagator::FieldIter, Extractor, Injector, TextMapPropagator},
-    trace::{SpanContext, SpanId, TraceContextExt, TraceId, TraceState, TRACE_FLAG_SAMPLED},
+    trace::{
+        SpanContext, SpanId, TraceContextExt, TraceId, TraceState, TRACE_FLAG_NOT_SAMPLED,
+        TRACE_FLAG_SAMPLED,
+    },
     Context,
 };
 use std::str::FromStr;
@@ -53,20 +56,24 @@ impl TraceContextPropagator {
     /// Extract span context from w3c trace-context header.
     fn extract_span_context(&self, extractor: &dyn Extractor) -> Result<SpanContext, ()> {
         let header_value = extractor.get(TRACEPARENT_HEADER).unwrap_or("").trim();
-        let parts = header_value.split_terminator('-').collect::<Vec<&str>>();
+        let parts = header_value.split('-').collect::<Vec<&str>>();
         // Ensure parts are not out of range.
         if parts.len() < 4 {
             return Err(());
         }
 
-        // Ensure version is within range, for version 0 there must be 4 parts.
-        let version = u8::from_str_radix(parts[0], 16).map_err(|_| ())?;
-        if version > MAX_VERSION || version == 0 && parts.len() != 4 {
+        // Ensure every field is lowercase hex of the expected length.
+        if !is_lower_hex(parts[0], 2)
+            || !is_lower_hex(parts[1], 32)
+            || !is_lower_hex(parts[2], 16)
+            || !is_lower_hex(parts[3], 2)
+        {
             return Err(());
         }
 
-        // Ensure trace id is lowercase
-        if parts[1].chars().any(|c| c.is_ascii_uppercase()) {
+        // Ensure version is within range, for version 0 there must be 4 parts.
+        let version = u8::from_str_radix(parts[0], 16).map_err(|_| ())?;
+        if version > MAX_VERSION || version == 0 && parts.len() != 4 {
             return Err(());
         }
 
@@ -75,11 +82,6 @@ impl TraceContextPropagator {
             .map_err(|_| ())
             .map(TraceId::from_u128)?;
 
-        // Ensure span id is lowercase
-        if parts[2].chars().any(|c| c.is_ascii_uppercase()) {
-            return Err(());
-        }
-
         // Parse span id section
         let span_id = u64::from_str_radix(parts[2], 16)
             .map_err(|_| ())
@@ -113,6 +115,14 @@ impl TraceContextPropagator {
     }
 }
 
+/// Whether `value` consists of exactly `len` lowercase hex digits.
+fn is_lower_hex(value: &str, len: usize) -> bool {
+    value.len() == len
+        && value
+            .bytes()
+            .all(|b| b.is_ascii_digit() || (b'a'..=b'f').contains(&b))
+}
+
 impl TextMapPropagator for TraceContextPropagator {
     /// Properly encodes the values of the `SpanContext` and injects them
     /// into the `Injector`.
@@ -120,12 +130,17 @@ impl TextMapPropagator for TraceContextPropagator {
         let span = cx.span();
         let span_context = span.span_context();
         if span_context.is_valid() {
+            let trace_flags = if span_context.is_sampled() {
+                TRACE_FLAG_SAMPLED
+            } else {
+                TRACE_FLAG_NOT_SAMPLED
+            };
             let header_value = format!(
                 "{:02x}-{:032x}-{:016x}-{:02x}",
                 SUPPORTED_VERSION,
                 span_context.trace_id().to_u128(),
                 span_context.span_id().to_u64(),
-                span_context.trace_flags() & TRACE_FLAG_SAMPLED
+                trace_flags
             );
             injector.set(TRACEPARENT_HEADER, header_value);
             injector.set(TRACESTATE_HEADER, span_context.trace_state().header());
@@ -167,7 +182,6 @@ mod tests {
             ("02-4bf92f3577b34da6a3ce929d0e0e4736-00f067aa0ba902b7-09", "foo=bar", SpanContext::new(TraceId::from_u128(0x4bf9_2f35_77b3_4da6_a3ce_929d_0e0e_4736), SpanId::from_u64(0x00f0_67aa_0ba9_02b7), 1, true, TraceState::from_str("foo=bar").unwrap())),
             ("02-4bf92f3577b34da6a3ce929d0e0e4736-00f067aa0ba902b7-08", "foo=bar", SpanContext::new(TraceId::from_u128(0x4bf9_2f35_77b3_4da6_a3ce_929d_0e0e_4736), SpanId::from_u64(0x00f0_67aa_0ba9_02b7), 0, true, TraceState::from_str("foo=bar").unwrap())),
             ("02-4bf92f3577b34da6a3ce929d0e0e4736-00f067aa0ba902b7-09-XYZxsf09", "foo=bar", SpanContext::new(TraceId::from_u128(0x4bf9_2f35_77b3_4da6_a3ce_929d_0e0e_4736), SpanId::from_u64(0x00f0_67aa_0ba9_02b7), 1, true, TraceState::from_str("foo=bar").unwrap())),
-            ("00-4bf92f3577b34da6a3ce929d0e0e4736-00f067aa0ba902b7-01-", "foo=bar", SpanContext::new(TraceId::from_u128(0x4bf9_2f35_77b3_4da6_a3ce_929d_0e0e_4736), SpanId::from_u64(0x00f0_67aa_0ba9_02b7), 1, true, TraceState::from_str("foo=bar").unwrap())),
             ("01-4bf92f3577b34da6a3ce929d0e0e4736-00f067aa0ba902b7-09-", "foo=bar", SpanContext::new(TraceId::from_u128(0x4bf9_2f35_77b3_4da6_a3ce_929d_0e0e_4736), SpanId::from_u64(0x00f0_67aa_0ba9_02b7), 1, true, TraceState::from_str("foo=bar").unwrap())),
         ]
     }
@@ -191,6 +205,19 @@ mod tests {
             ("00-ab000000000000000000000000000000-cd00000000000000-09",   "trace-flag unused bits set"),
             ("00-4bf92f3577b34da6a3ce929d0e0e4736-00f067aa0ba902b7",      "missing options"),
             ("00-4bf92f3577b34da6a3ce929d0e0e4736-00f067aa0ba902b7-",     "empty options"),
+            ("0-4bf92f3577b34da6a3ce929d0e0e4736-00f067aa0ba902b7-01",    "short version"),
+            ("ff-4bf92f3577b34da6a3ce929d0e0e4736-00f067aa0ba902b7-01",   "forbidden version"),
+            ("00-4bf92f3577b34da6a3ce929d0e0e473-00f067aa0ba902b7-01",    "short trace ID"),
+            ("00-4bf92f3577b34da6a3ce929d0e0e4736-00f067aa0ba902b-01",    "short span ID"),
+            ("00-4bf92f3577b34da6a3ce929d0e0e4736-00f067aa0ba902b7-1",    "short trace flag"),
+            ("00-ab-cd-01",                                             "short IDs"),
+            ("00-+bf92f3577b34da6a3ce929d0e0e4736-00f067aa0ba902b7-01",   "signed trace ID"),
+            ("00-4bf92f3577b34da6a3ce929d0e0e4736-+0f067aa0ba902b7-01",   "signed span ID"),
+            ("00-00000000000000000000000000000000-00f067aa0ba902b7-01",   "zero trace ID"),
+            ("00-4bf92f3577b34da6a3ce929d0e0e4736-0000000000000000-01",   "zero span ID"),
+            ("00-4bf92f3577b34da6a3ce929d0e0e4736-00f067aa0ba902b7-01-",  "trailing separator for version 0"),
+            ("00-4bf92f3577b34da6a3ce929d0e0e4736-00f067aa0ba902b7-01-ab", "trailing data for version 0"),
+            ("01-4bf92f3577b34da6a3ce929d0e0e4736-00f067aa0ba902b7-0100", "missing separator before future version data"),
         ]
     }
 
@@ -220,6 +247,31 @@ mod tests {
         }
     }
 
+    #[test]
+    fn extract_span_context_without_attaching() {
+        let propagator = TraceContextPropagator::new();
+        let mut extractor = HashMap::new();
+        extractor.insert(
+            TRACEPARENT_HEADER.to_string(),
+            "00-4bf92f3577b34da6a3ce929d0e0e4736-00f067aa0ba902b7-01".to_string(),
+        );
+
+        let cx = propagator.extract(&extractor);
+        assert_eq!(
+            cx.span_context().trace_id(),
+            TraceId::from_u128(0x4bf9_2f35_77b3_4da6_a3ce_929d_0e0e_4736)
+        );
+        assert_eq!(
+            cx.span_context().span_id(),
+            SpanId::from_u64(0x00f0_67aa_0ba9_02b7)
+        );
+        assert!(cx.span_context().is_remote());
+
+        // the current context is untouched and reports an invalid span context
+        assert!(!Context::current().span_context().is_valid());
+        assert!(!Context::new().span_context().is_valid());
+    }
+
     #[test]
     fn extract_w3c_tracestate() {
         let propagator = TraceContextPropagator::new();
@@ -258,6 +310,34 @@ mod tests {
         }
     }
 
+    #[test]
+    fn extract_w3c_invalid_keeps_context() {
+        let propagator = TraceContextPropagator::new();
+        let parent_context = SpanContext::new(
+            TraceId::from_u128(1),
+            SpanId::from_u64(1),
+            1,
+            true,
+            TraceState::default(),
+        );
+        let cx = Context::current_with_span(TestSpan(parent_context.clone()));
+
+        for (invalid_header, reason) in extract_data_invalid() {
+            let mut extractor = HashMap::new();
+            extractor.insert(TRACEPARENT_HEADER.to_string(), invalid_header.to_string());
+
+            assert_eq!(
+                propagator
+                    .extract_with_context(&cx, &extractor)
+                    .span()
+                    .span_context(),
+                &parent_context,
+                "{}",
+                reason
+            )
+        }
+    }
+
     #[test]
     fn inject_w3c() {
         let propagator = TraceContextPropagator::new();
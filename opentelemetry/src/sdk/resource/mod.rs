@@ -13,15 +13,30 @@
 //! produced by any `Tracer` from the provider are associated with this `Resource`.
 //!
 //! [`TracerProvider`]: crate::trace::TracerProvider
+mod os;
+mod process;
+mod telemetry;
+
+pub use os::OsResourceDetector;
+pub use process::ProcessResourceDetector;
+pub use telemetry::TelemetryResourceDetector;
+
 #[cfg(feature = "metrics")]
 use crate::labels;
 use crate::sdk::EnvResourceDetector;
 use crate::{Key, KeyValue, Value};
 #[cfg(feature = "serialize")]
 use serde::{Deserialize, Serialize};
+use std::borrow::Cow;
 use std::collections::{btree_map, BTreeMap};
+use std::env;
 use std::time::Duration;
 
+/// Key of the logical name of the service.
+pub(crate) const SERVICE_NAME: &str = "service.name";
+/// Service name to use if none is configured.
+pub(crate) const OTEL_SERVICE_NAME: &str = "OTEL_SERVICE_NAME";
+
 /// Describes an entity about which identifying information and metadata is exposed.
 ///
 /// Items are sorted by their key, and are only overwritten if the value is an empty string.
@@ -32,10 +47,18 @@ pub struct Resource {
 }
 
 impl Default for Resource {
+    /// The resource detected from the `OTEL_RESOURCE_ATTRIBUTES` and
+    /// `OTEL_SERVICE_NAME` environment variables, together with the
+    /// `telemetry.sdk.*` attributes describing this SDK.
+    ///
+    /// The telemetry SDK attributes cannot be overridden by the environment.
     fn default() -> Self {
         Self::from_detectors(
             Duration::from_secs(0),
-            vec![Box::new(EnvResourceDetector::new())],
+            vec![
+                Box::new(EnvResourceDetector::new()),
+                Box::new(TelemetryResourceDetector),
+            ],
         )
     }
 }
@@ -62,6 +85,11 @@ impl Resource {
         resource
     }
 
+    /// Create a new `Resource` with the given `service.name`.
+    pub fn new_with_service_name<T: Into<Cow<'static, str>>>(service_name: T) -> Self {
+        Resource::new(vec![KeyValue::new(SERVICE_NAME, service_name.into())])
+    }
+
     /// Create a new `Resource` from resource detectors.
     ///
     /// timeout will be applied to each detector.
@@ -80,8 +108,24 @@ impl Resource {
 
     /// Create a new `Resource` by combining two resources.
     ///
-    /// Keys from the `other` resource have priority over keys from this resource, even if the
-    /// updated value is empty.
+    /// Keys from this resource have priority over keys from the `other` resource, unless the
+    /// value in this resource is an empty string and the one in `other` is not.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use opentelemetry::{sdk::Resource, KeyValue};
+    ///
+    /// let manual = Resource::new(vec![KeyValue::new("service.name", "checkout")]);
+    /// let detected = Resource::new(vec![
+    ///     KeyValue::new("service.name", "unknown_service"),
+    ///     KeyValue::new("host.name", "web-1"),
+    /// ]);
+    ///
+    /// let resource = manual.merge(&detected);
+    /// assert_eq!(resource.get("service.name".into()), Some("checkout".into()));
+    /// assert_eq!(resource.len(), 2);
+    /// ```
     pub fn merge(&self, other: &Self) -> Self {
         if self.attrs.is_empty() {
             return other.clone();
@@ -90,14 +134,13 @@ impl Resource {
             return self.clone();
         }
 
-        let mut resource = Resource::empty();
-
-        // attrs from self must be added first so they have priority
+        let mut resource = other.clone();
         for (k, v) in self.attrs.iter() {
-            resource.attrs.insert(k.clone(), v.clone());
-        }
-        for (k, v) in other.attrs.iter() {
-            resource.attrs.insert(k.clone(), v.clone());
+            let keep_other = is_empty_string(v)
+                && matches!(resource.attrs.get(k), Some(other_value) if !is_empty_string(other_value));
+            if !keep_other {
+                resource.attrs.insert(k.clone(), v.clone());
+            }
         }
 
         resource
@@ -118,6 +161,11 @@ impl Resource {
         self.into_iter()
     }
 
+    /// Retrieve the value from resource associate with given key.
+    pub fn get(&self, key: Key) -> Option<Value> {
+        self.attrs.get(&key).cloned()
+    }
+
     /// Encoded labels
     #[cfg(feature = "metrics")]
     #[cfg_attr(docsrs, doc(cfg(feature = "metrics")))]
@@ -126,6 +174,31 @@ impl Resource {
     }
 }
 
+/// The service name to use if none is configured.
+///
+/// This is the value of the `OTEL_SERVICE_NAME` environment variable if it is
+/// set, or `unknown_service:<process executable name>` otherwise.
+pub fn default_service_name() -> String {
+    if let Some(service_name) = env::var(OTEL_SERVICE_NAME)
+        .ok()
+        .filter(|name| !name.is_empty())
+    {
+        return service_name;
+    }
+
+    match std::env::current_exe().ok().and_then(|path| {
+        path.file_name()
+            .map(|name| name.to_string_lossy().into_owned())
+    }) {
+        Some(executable) => format!("unknown_service:{}", executable),
+        None => "unknown_service".to_string(),
+    }
+}
+
+fn is_empty_string(value: &Value) -> bool {
+    matches!(value, Value::String(s) if s.is_empty())
+}
+
 /// An owned iterator over the entries of a `Resource`.
 #[derive(Debug)]
 pub struct IntoIter(btree_map::IntoIter<Key, Value>);
@@ -187,6 +260,7 @@ pub trait ResourceDetector {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::sdk::env::EnvGuard;
     use crate::sdk::EnvResourceDetector;
     use std::collections::BTreeMap;
     use std::{env, time};
@@ -220,11 +294,12 @@ mod tests {
             KeyValue::new("d", ""),
         ]);
 
+        // keys of resource a win, unless its value is empty
         let mut expected_attrs = BTreeMap::new();
         expected_attrs.insert(Key::new("a"), Value::from("a-value"));
         expected_attrs.insert(Key::new("b"), Value::from("b-value"));
         expected_attrs.insert(Key::new("c"), Value::from("c-value"));
-        expected_attrs.insert(Key::new("d"), Value::from(""));
+        expected_attrs.insert(Key::new("d"), Value::from("d-value"));
 
         assert_eq!(
             resource_a.merge(&resource_b),
@@ -234,8 +309,60 @@ mod tests {
         );
     }
 
+    #[test]
+    fn merge_resource_service_name() {
+        let manual = Resource::new(vec![KeyValue::new("service.name", "checkout")]);
+        let detected = Resource::new(vec![
+            KeyValue::new("service.name", "unknown_service"),
+            KeyValue::new("host.name", "web-1"),
+        ]);
+
+        let merged = manual.merge(&detected);
+        assert_eq!(merged.len(), 2);
+        assert_eq!(
+            merged.get(Key::new("service.name")),
+            Some(Value::from("checkout"))
+        );
+        assert_eq!(
+            detected.merge(&manual).get(Key::new("service.name")),
+            Some(Value::from("unknown_service"))
+        );
+        assert_eq!(Resource::empty().merge(&manual), manual);
+    }
+
+    #[test]
+    fn new_with_service_name() {
+        let resource = Resource::new_with_service_name("checkout");
+        assert_eq!(resource.len(), 1);
+        assert_eq!(
+            resource.get(Key::new("service.name")),
+            Some(Value::from("checkout"))
+        );
+    }
+
+    #[test]
+    fn default_service_name_from_process() {
+        // OTEL_SERVICE_NAME is not set by the tests
+        assert!(default_service_name().starts_with("unknown_service:"));
+    }
+
+    #[test]
+    fn default_resource_has_telemetry_attributes() {
+        let resource = Resource::default();
+        assert_eq!(
+            resource.get(Key::new("telemetry.sdk.name")),
+            Some(Value::from("opentelemetry"))
+        );
+        assert_eq!(
+            resource.get(Key::new("telemetry.sdk.language")),
+            Some(Value::from("rust"))
+        );
+        assert!(resource.get(Key::new("telemetry.sdk.version")).is_some());
+    }
+
     #[test]
     fn detect_resource() {
+        let _guard = EnvGuard::new(&["OTEL_RESOURCE_ATTRIBUTES", "IRRELEVANT"]);
         env::set_var("OTEL_RESOURCE_ATTRIBUTES", "key=value, k = v , a= x, a=z");
         env::set_var("irrelevant".to_uppercase(), "20200810");
 
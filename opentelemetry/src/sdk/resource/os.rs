@@ -0,0 +1,82 @@
+//! OS resource detector
+//!
+//! Detect the operating system the process is running on.
+
+use crate::sdk::resource::ResourceDetector;
+use crate::sdk::Resource;
+use crate::KeyValue;
+use std::env::consts::OS;
+use std::time::Duration;
+
+/// Detect operating system information.
+///
+/// This resource detector returns the following information:
+///
+/// - operating system type (`os.type`), e.g. `linux`, `windows` or `darwin`
+/// - operating system description (`os.description`), only on linux where it
+///   is read from `/etc/os-release`
+///
+/// See [semantic conventions](https://github.com/open-telemetry/opentelemetry-specification/blob/main/specification/resource/semantic_conventions/os.md)
+/// for details.
+#[derive(Debug)]
+pub struct OsResourceDetector;
+
+impl ResourceDetector for OsResourceDetector {
+    fn detect(&self, _timeout: Duration) -> Resource {
+        let mut attributes = vec![KeyValue::new("os.type", os_type())];
+        if let Some(description) = os_description() {
+            attributes.push(KeyValue::new("os.description", description));
+        }
+
+        Resource::new(attributes)
+    }
+}
+
+/// Map the rust target os to the values of the `os.type` semantic convention.
+fn os_type() -> &'static str {
+    match OS {
+        "macos" | "ios" => "darwin",
+        "dragonfly" => "dragonflybsd",
+        "solaris" | "illumos" => "solaris",
+        other => other,
+    }
+}
+
+#[cfg(target_os = "linux")]
+fn os_description() -> Option<String> {
+    let os_release = std::fs::read_to_string("/etc/os-release").ok()?;
+    os_release.lines().find_map(|line| {
+        line.strip_prefix("PRETTY_NAME=")
+            .map(|name| name.trim_matches('"').to_string())
+            .filter(|name| !name.is_empty())
+    })
+}
+
+#[cfg(not(target_os = "linux"))]
+fn os_description() -> Option<String> {
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::OsResourceDetector;
+    use crate::sdk::resource::ResourceDetector;
+    use crate::{Key, Value};
+    use std::time::Duration;
+
+    #[test]
+    fn test_os_resource_detector() {
+        let resource = OsResourceDetector.detect(Duration::from_secs(0));
+        let os_type = resource
+            .iter()
+            .find(|(key, _)| **key == Key::new("os.type"))
+            .map(|(_, value)| value.clone());
+
+        #[cfg(target_os = "linux")]
+        assert_eq!(os_type, Some(Value::from("linux")));
+        #[cfg(target_os = "macos")]
+        assert_eq!(os_type, Some(Value::from("darwin")));
+        #[cfg(not(any(target_os = "linux", target_os = "macos")))]
+        assert!(os_type.is_some());
+    }
+}
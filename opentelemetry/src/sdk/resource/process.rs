@@ -0,0 +1,71 @@
+//! Process resource detector
+//!
+//! Detect process related information like pid, executable name.
+
+use crate::sdk::resource::ResourceDetector;
+use crate::sdk::Resource;
+use crate::{Array, KeyValue, Value};
+use std::borrow::Cow;
+use std::env::{args_os, current_exe};
+use std::process::id;
+use std::time::Duration;
+
+/// Detect process information.
+///
+/// This resource detector returns the following information:
+///
+/// - process id (`process.pid`)
+/// - process executable name (`process.executable.name`)
+/// - command line arguments (`process.command_args`)
+///
+/// See [semantic conventions](https://github.com/open-telemetry/opentelemetry-specification/blob/main/specification/resource/semantic_conventions/process.md)
+/// for details.
+#[derive(Debug)]
+pub struct ProcessResourceDetector;
+
+impl ResourceDetector for ProcessResourceDetector {
+    fn detect(&self, _timeout: Duration) -> Resource {
+        let mut attributes = vec![KeyValue::new("process.pid", id() as i64)];
+
+        if let Some(name) = current_exe().ok().and_then(|path| {
+            path.file_name()
+                .map(|name| name.to_string_lossy().into_owned())
+        }) {
+            attributes.push(KeyValue::new("process.executable.name", name));
+        }
+
+        let arguments = args_os()
+            .map(|arg| Cow::Owned(arg.to_string_lossy().into_owned()))
+            .collect::<Vec<_>>();
+        attributes.push(KeyValue::new(
+            "process.command_args",
+            Value::Array(Array::String(arguments)),
+        ));
+
+        Resource::new(attributes)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::ProcessResourceDetector;
+    use crate::sdk::resource::ResourceDetector;
+    use crate::{Array, Key, Value};
+    use std::time::Duration;
+
+    #[test]
+    fn test_processor_resource_detector() {
+        let resource = ProcessResourceDetector.detect(Duration::from_secs(0));
+        assert_eq!(resource.len(), 3);
+
+        let attributes: Vec<_> = resource.iter().collect();
+        assert!(attributes.contains(&(
+            &Key::new("process.pid"),
+            &Value::I64(std::process::id() as i64)
+        )));
+        assert!(attributes
+            .iter()
+            .any(|(key, value)| key.as_str() == "process.command_args"
+                && matches!(value, Value::Array(Array::String(args)) if !args.is_empty())));
+    }
+}
@@ -0,0 +1,53 @@
+//! Telemetry resource detector
+//!
+//! Detect the telemetry SDK producing the telemetry.
+
+use crate::sdk::resource::ResourceDetector;
+use crate::sdk::Resource;
+use crate::KeyValue;
+use std::time::Duration;
+
+/// Detect information about the telemetry SDK.
+///
+/// This resource detector returns the following information:
+///
+/// - the name of the SDK (`telemetry.sdk.name`), `opentelemetry`
+/// - the language of the SDK (`telemetry.sdk.language`), `rust`
+/// - the version of the SDK (`telemetry.sdk.version`)
+///
+/// See [semantic conventions](https://github.com/open-telemetry/opentelemetry-specification/tree/main/specification/resource/semantic_conventions#telemetry-sdk)
+/// for details.
+#[derive(Debug)]
+pub struct TelemetryResourceDetector;
+
+impl ResourceDetector for TelemetryResourceDetector {
+    fn detect(&self, _timeout: Duration) -> Resource {
+        Resource::new(vec![
+            KeyValue::new("telemetry.sdk.name", "opentelemetry"),
+            KeyValue::new("telemetry.sdk.language", "rust"),
+            KeyValue::new("telemetry.sdk.version", env!("CARGO_PKG_VERSION")),
+        ])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::TelemetryResourceDetector;
+    use crate::sdk::resource::ResourceDetector;
+    use crate::Value;
+    use std::time::Duration;
+
+    #[test]
+    fn test_telemetry_resource_detector() {
+        let resource = TelemetryResourceDetector.detect(Duration::from_secs(0));
+        assert_eq!(resource.len(), 3);
+        assert_eq!(
+            resource.get("telemetry.sdk.language".into()),
+            Some(Value::from("rust"))
+        );
+        assert_eq!(
+            resource.get("telemetry.sdk.version".into()),
+            Some(Value::from(env!("CARGO_PKG_VERSION")))
+        );
+    }
+}
@@ -0,0 +1,107 @@
+//! # Clock
+//!
+//! The SDK reads the current time from a [`Clock`] whenever it stamps the
+//! start time, end time or event time of a span, so that tests can control it.
+use std::fmt;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, SystemTime};
+
+/// Source of the current time used by the SDK to timestamp spans and events.
+pub trait Clock: Send + Sync + fmt::Debug {
+    /// Returns the current time.
+    fn now(&self) -> SystemTime;
+}
+
+/// A [`Clock`] reading the system time, used by default.
+#[derive(Clone, Debug, Default)]
+pub struct SystemClock;
+
+impl Clock for SystemClock {
+    fn now(&self) -> SystemTime {
+        crate::time::now()
+    }
+}
+
+/// A [`Clock`] that only moves when told to, for deterministic tests.
+///
+/// Clones share the same time, so a clone can be kept to advance the clock
+/// after handing it to the tracer provider.
+///
+/// # Examples
+///
+/// ```
+/// use opentelemetry::sdk::trace::{ManualClock, TracerProvider};
+/// use opentelemetry::trace::{Span, Tracer, TracerProvider as _};
+/// use std::time::{Duration, SystemTime};
+///
+/// let clock = ManualClock::new(SystemTime::UNIX_EPOCH);
+/// let provider = TracerProvider::builder()
+///     .with_clock(clock.clone())
+///     .build();
+///
+/// let mut span = provider.get_tracer("test", None).start("timed");
+/// clock.advance(Duration::from_millis(250));
+/// span.end();
+/// ```
+#[derive(Clone, Debug)]
+pub struct ManualClock {
+    now: Arc<Mutex<SystemTime>>,
+}
+
+impl ManualClock {
+    /// Create a new clock starting at `now`.
+    pub fn new(now: SystemTime) -> Self {
+        ManualClock {
+            now: Arc::new(Mutex::new(now)),
+        }
+    }
+
+    /// Move the clock forward by `duration`.
+    pub fn advance(&self, duration: Duration) {
+        if let Ok(mut now) = self.now.lock() {
+            *now += duration;
+        }
+    }
+
+    /// Set the clock to `now`.
+    pub fn set(&self, now: SystemTime) {
+        if let Ok(mut current) = self.now.lock() {
+            *current = now;
+        }
+    }
+}
+
+impl Default for ManualClock {
+    /// A clock starting at the current system time.
+    fn default() -> Self {
+        ManualClock::new(crate::time::now())
+    }
+}
+
+impl Clock for ManualClock {
+    fn now(&self) -> SystemTime {
+        self.now
+            .lock()
+            .map(|now| *now)
+            .unwrap_or_else(|_| crate::time::now())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Clock, ManualClock};
+    use std::time::{Duration, SystemTime};
+
+    #[test]
+    fn manual_clock_only_moves_when_told() {
+        let clock = ManualClock::new(SystemTime::UNIX_EPOCH);
+        let handle = clock.clone();
+        assert_eq!(clock.now(), SystemTime::UNIX_EPOCH);
+
+        handle.advance(Duration::from_secs(3));
+        assert_eq!(clock.now(), SystemTime::UNIX_EPOCH + Duration::from_secs(3));
+
+        handle.set(SystemTime::UNIX_EPOCH + Duration::from_secs(1));
+        assert_eq!(clock.now(), SystemTime::UNIX_EPOCH + Duration::from_secs(1));
+    }
+}
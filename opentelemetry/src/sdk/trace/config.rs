@@ -4,8 +4,6 @@
 //! can be set for the default OpenTelemetry limits and Sampler.
 use crate::sdk::trace::span_limit::SpanLimits;
 use crate::{sdk, sdk::trace::Sampler, trace::IdGenerator};
-use std::env;
-use std::str::FromStr;
 use std::sync::Arc;
 
 /// Default trace configuration
@@ -20,9 +18,16 @@ pub struct Config {
     pub sampler: Box<dyn sdk::trace::ShouldSample>,
     /// The id generator that the sdk should use
     pub id_generator: Box<dyn IdGenerator>,
+    /// The clock that the sdk should use to timestamp spans and events
+    pub clock: Box<dyn sdk::trace::Clock>,
     /// span limits
     pub span_limits: SpanLimits,
     /// Contains attributes representing an entity that produces telemetry.
+    ///
+    /// Defaults to [`Resource::default`], which is detected from the
+    /// environment.
+    ///
+    /// [`Resource::default`]: crate::sdk::Resource::default
     pub resource: Option<Arc<sdk::Resource>>,
 }
 
@@ -39,6 +44,12 @@ impl Config {
         self
     }
 
+    /// Specify the clock to be used.
+    pub fn with_clock<T: sdk::trace::Clock + 'static>(mut self, clock: T) -> Self {
+        self.clock = Box::new(clock);
+        self
+    }
+
     /// Specify the number of events to be recorded per span.
     pub fn with_max_events_per_span(mut self, max_events: u32) -> Self {
         self.span_limits.max_events_per_span = max_events;
@@ -51,7 +62,7 @@ impl Config {
         self
     }
 
-    /// Specify the number of events to be recorded per span.
+    /// Specify the number of links to be recorded per span.
     pub fn with_max_links_per_span(mut self, max_links: u32) -> Self {
         self.span_limits.max_links_per_span = max_links;
         self
@@ -69,6 +80,20 @@ impl Config {
         self
     }
 
+    /// Specify the max length of string attribute values, longer values are truncated.
+    pub fn with_max_attribute_value_length(mut self, max_length: u32) -> Self {
+        self.span_limits.max_attribute_value_length = Some(max_length);
+        self
+    }
+
+    /// Specify the max length of event names, longer names are truncated.
+    ///
+    /// A length of `0` disables truncation.
+    pub fn with_max_event_name_length(mut self, max_length: u32) -> Self {
+        self.span_limits.max_event_name_length = Some(max_length);
+        self
+    }
+
     /// Specify all limit via the span_limits
     pub fn with_span_limits(mut self, span_limits: SpanLimits) -> Self {
         self.span_limits = span_limits;
@@ -76,8 +101,13 @@ impl Config {
     }
 
     /// Specify the attributes representing the entity that produces telemetry
+    ///
+    /// The resource is merged into [`Resource::default`], so the attributes detected from the
+    /// environment and the telemetry SDK are kept unless `resource` sets the same keys.
+    ///
+    /// [`Resource::default`]: crate::sdk::Resource::default
     pub fn with_resource(mut self, resource: sdk::Resource) -> Self {
-        self.resource = Some(Arc::new(resource));
+        self.resource = Some(Arc::new(resource.merge(&sdk::Resource::default())));
         self
     }
 }
@@ -85,34 +115,37 @@ impl Config {
 impl Default for Config {
     /// Create default global sdk configuration.
     fn default() -> Self {
-        let mut config = Config {
+        Config {
             sampler: Box::new(Sampler::ParentBased(Box::new(Sampler::AlwaysOn))),
-            id_generator: Box::new(sdk::trace::IdGenerator::default()),
-            span_limits: SpanLimits::default(),
-            resource: None,
-        };
-
-        if let Some(max_attributes_per_span) = env::var("OTEL_SPAN_ATTRIBUTE_COUNT_LIMIT")
-            .ok()
-            .and_then(|count_limit| u32::from_str(&count_limit).ok())
-        {
-            config.span_limits.max_attributes_per_span = max_attributes_per_span;
-        }
-
-        if let Some(max_events_per_span) = env::var("OTEL_SPAN_EVENT_COUNT_LIMIT")
-            .ok()
-            .and_then(|max_events| u32::from_str(&max_events).ok())
-        {
-            config.span_limits.max_events_per_span = max_events_per_span;
-        }
-
-        if let Some(max_links_per_span) = env::var("OTEL_SPAN_LINK_COUNT_LIMIT")
-            .ok()
-            .and_then(|max_links| u32::from_str(&max_links).ok())
-        {
-            config.span_limits.max_links_per_span = max_links_per_span;
+            id_generator: Box::new(sdk::trace::RandomIdGenerator::default()),
+            clock: Box::new(sdk::trace::SystemClock),
+            span_limits: SpanLimits::from_env(),
+            resource: Some(Arc::new(sdk::Resource::default())),
         }
+    }
+}
 
-        config
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::sdk::env::EnvGuard;
+    use std::env;
+
+    #[test]
+    fn test_span_limits_from_env() {
+        let _guard = EnvGuard::new(&[
+            "OTEL_SPAN_ATTRIBUTE_COUNT_LIMIT",
+            "OTEL_SPAN_EVENT_COUNT_LIMIT",
+        ]);
+        env::set_var("OTEL_SPAN_ATTRIBUTE_COUNT_LIMIT", "256");
+        env::set_var("OTEL_SPAN_EVENT_COUNT_LIMIT", "not a number");
+        let config = Config::default();
+
+        assert_eq!(config.span_limits.max_attributes_per_span, 256);
+        // invalid values fall back to the default
+        assert_eq!(
+            config.span_limits.max_events_per_span,
+            SpanLimits::default().max_events_per_span
+        );
     }
 }
@@ -15,6 +15,7 @@ pub struct EvictedHashMap {
     evict_list: LinkedList<Key>,
     max_len: u32,
     dropped_count: u32,
+    truncated_count: u32,
 }
 
 impl EvictedHashMap {
@@ -25,6 +26,7 @@ impl EvictedHashMap {
             evict_list: LinkedList::new(),
             max_len,
             dropped_count: 0,
+            truncated_count: 0,
         }
     }
 
@@ -72,6 +74,20 @@ impl EvictedHashMap {
         self.dropped_count
     }
 
+    /// Returns the number of attribute values that were truncated because they
+    /// exceeded the configured max attribute value length.
+    ///
+    /// Only the attributes of this map are counted, the truncated attribute
+    /// values of span events and links are not reported.
+    pub fn truncated_count(&self) -> u32 {
+        self.truncated_count
+    }
+
+    /// Count an attribute value truncated before it was inserted.
+    pub(crate) fn record_truncated(&mut self) {
+        self.truncated_count += 1;
+    }
+
     /// Returns a front-to-back iterator.
     pub fn iter(&self) -> Iter<'_> {
         Iter(self.map.iter())
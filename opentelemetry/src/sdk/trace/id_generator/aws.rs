@@ -37,7 +37,7 @@ use std::time::{Duration, UNIX_EPOCH};
 /// [xray-trace-id]: https://docs.aws.amazon.com/xray/latest/devguide/xray-api-sendingdata.html#xray-api-traceids
 #[derive(Debug, Default)]
 pub struct XrayIdGenerator {
-    sdk_default_generator: sdk::trace::IdGenerator,
+    sdk_default_generator: sdk::trace::RandomIdGenerator,
 }
 
 impl IdGenerator for XrayIdGenerator {
@@ -56,6 +56,7 @@ impl IdGenerator for XrayIdGenerator {
             .as_secs();
 
         TraceId::from_hex(format!("{:08x}{}", epoch_time_seconds, default_trace_id).as_str())
+            .unwrap_or_else(|_| TraceId::invalid())
     }
 
     /// Generates a new `SpanId` that can be converted to an X-Ray Segment ID
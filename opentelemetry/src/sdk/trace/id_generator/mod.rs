@@ -8,11 +8,11 @@ use std::cell::RefCell;
 /// Default [`crate::trace::IdGenerator`] implementation.
 /// Generates Trace and Span ids using a random number generator.
 #[derive(Clone, Debug, Default)]
-pub struct IdGenerator {
+pub struct RandomIdGenerator {
     _private: (),
 }
 
-impl crate::trace::IdGenerator for IdGenerator {
+impl crate::trace::IdGenerator for RandomIdGenerator {
     /// Generate new `TraceId` using thread local rng
     fn new_trace_id(&self) -> TraceId {
         CURRENT_RNG.with(|rng| TraceId::from_u128(rng.borrow_mut().gen()))
@@ -6,6 +6,7 @@
 //! * The `Span` struct with is a mutable object storing information about the
 //! current operation execution.
 //! * The `TracerProvider` struct which configures and produces `Tracer`s.
+mod clock;
 mod config;
 mod evicted_hash_map;
 mod evicted_queue;
@@ -17,15 +18,20 @@ mod span_limit;
 mod span_processor;
 mod tracer;
 
+pub use clock::{Clock, ManualClock, SystemClock};
 pub use config::{config, Config};
 pub use evicted_hash_map::EvictedHashMap;
 pub use evicted_queue::EvictedQueue;
-pub use id_generator::{aws::XrayIdGenerator, IdGenerator};
+pub use id_generator::{aws::XrayIdGenerator, RandomIdGenerator};
 pub use provider::{Builder, TracerProvider};
-pub use sampler::{Sampler, SamplingDecision, SamplingResult, ShouldSample};
+pub use sampler::{
+    AttributeBasedSampler, CachedSampler, ConsistentSampler, ParentBased, ParentBasedBuilder,
+    RateLimitingSampler, Sampler, SamplingDecision, SamplingResult, ShouldSample,
+};
 pub use span::Span;
 pub use span_limit::SpanLimits;
 pub use span_processor::{
-    BatchConfig, BatchSpanProcessor, BatchSpanProcessorBuilder, SimpleSpanProcessor, SpanProcessor,
+    BackpressurePolicy, BatchConfig, BatchSpanProcessor, BatchSpanProcessorBuilder,
+    BatchSpanProcessorMetrics, RetryPolicy, SimpleSpanProcessor, SpanProcessor,
 };
 pub use tracer::Tracer;
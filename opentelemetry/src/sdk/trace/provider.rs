@@ -8,13 +8,26 @@
 //! propagators) are provided by the `TracerProvider`. `Tracer` instances do
 //! not duplicate this data to avoid that different `Tracer` instances
 //! of the `TracerProvider` have different versions of these data.
-use crate::trace::TraceResult;
+use crate::trace::{IdGenerator, TraceError, TraceResult, TracerConfig};
 use crate::{
     global,
     runtime::Runtime,
-    sdk::{self, export::trace::SpanExporter, trace::SpanProcessor},
+    sdk::{
+        self,
+        export::trace::{SpanData, SpanExporter},
+        trace::{
+            span_processor::{block_on_timeout, OnEndProcessor},
+            SpanProcessor,
+        },
+    },
 };
+use futures::{executor, future, Future};
+use std::borrow::Cow;
+use std::pin::Pin;
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::Arc;
+use std::task::Poll;
+use std::time::Duration;
 
 /// Default tracer name if empty string is provided.
 const DEFAULT_COMPONENT_NAME: &str = "rust.opentelemetry.io/sdk/tracer";
@@ -24,12 +37,30 @@ const DEFAULT_COMPONENT_NAME: &str = "rust.opentelemetry.io/sdk/tracer";
 pub(crate) struct TracerProviderInner {
     processors: Vec<Box<dyn SpanProcessor>>,
     config: sdk::trace::Config,
+    is_shutdown: AtomicBool,
+}
+
+impl TracerProviderInner {
+    /// Shuts down the processors unless the provider was already shut down.
+    fn shutdown(&self) -> Vec<TraceResult<()>> {
+        if self.is_shutdown.swap(true, Ordering::SeqCst) {
+            return vec![Err(TraceError::from("tracer provider already shut down"))];
+        }
+
+        self.processors
+            .iter()
+            .map(|processor| processor.shutdown())
+            .collect()
+    }
 }
 
 impl Drop for TracerProviderInner {
     fn drop(&mut self) {
-        for processor in &mut self.processors {
-            if let Err(err) = processor.shutdown() {
+        if self.is_shutdown.load(Ordering::SeqCst) {
+            return;
+        }
+        for result in self.shutdown() {
+            if let Err(err) = result {
                 global::handle_error(err);
             }
         }
@@ -68,6 +99,76 @@ impl TracerProvider {
     pub fn config(&self) -> &sdk::trace::Config {
         &self.inner.config
     }
+
+    /// Whether the provider was shut down with [`TracerProvider::shutdown`].
+    pub fn is_shutdown(&self) -> bool {
+        self.inner.is_shutdown.load(Ordering::SeqCst)
+    }
+
+    /// Shuts down the span processors, exporting the spans they still hold,
+    /// and returns their results in the order they were registered.
+    ///
+    /// The background export tasks of the processors are stopped, their
+    /// exporters are shut down, and spans created by the tracers of this
+    /// provider afterwards are dropped. Processors are only shut down once,
+    /// later calls return an error.
+    pub fn shutdown(&self) -> Vec<TraceResult<()>> {
+        self.inner.shutdown()
+    }
+
+    /// Force flush all remaining spans in span processors without blocking the
+    /// current thread, and return results in the order the processors were
+    /// registered.
+    pub async fn force_flush_async(&self) -> Vec<TraceResult<()>> {
+        future::join_all(
+            self.span_processors()
+                .iter()
+                .map(|processor| processor.force_flush_async()),
+        )
+        .await
+    }
+
+    /// Force flush all remaining spans in span processors, waiting at most
+    /// `timeout` in total, and return results in the order the processors
+    /// were registered.
+    ///
+    /// All processors are flushed concurrently via their `force_flush_async`
+    /// and share the same deadline. A processor that cannot finish in time
+    /// reports [`TraceError::ExportTimedOut`] without affecting the results
+    /// of the others.
+    ///
+    /// [`TraceError::ExportTimedOut`]: crate::trace::TraceError::ExportTimedOut
+    pub fn force_flush_timeout(&self, timeout: Duration) -> Vec<TraceResult<()>> {
+        let mut flushes: Vec<_> = self
+            .span_processors()
+            .iter()
+            .map(|processor| future::maybe_done(processor.force_flush_async()))
+            .collect();
+
+        block_on_timeout(
+            future::poll_fn(|cx| {
+                let mut done = true;
+                for flush in flushes.iter_mut() {
+                    done &= Pin::new(flush).poll(cx).is_ready();
+                }
+                if done {
+                    Poll::Ready(())
+                } else {
+                    Poll::Pending
+                }
+            }),
+            timeout,
+        );
+
+        flushes
+            .iter_mut()
+            .map(|flush| {
+                Pin::new(flush)
+                    .take_output()
+                    .unwrap_or(Err(TraceError::ExportTimedOut(timeout)))
+            })
+            .collect()
+    }
 }
 
 impl crate::trace::TracerProvider for TracerProvider {
@@ -76,23 +177,43 @@ impl crate::trace::TracerProvider for TracerProvider {
 
     /// Find or create `Tracer` instance by name.
     fn get_tracer(&self, name: &'static str, version: Option<&'static str>) -> Self::Tracer {
+        self.versioned_tracer(name, version, None)
+    }
+
+    /// Find or create `Tracer` instance by name, version and schema url.
+    fn versioned_tracer(
+        &self,
+        name: &'static str,
+        version: Option<&'static str>,
+        schema_url: Option<&'static str>,
+    ) -> Self::Tracer {
+        self.tracer_with_config(TracerConfig {
+            name,
+            version,
+            schema_url,
+            attributes: Vec::new(),
+        })
+    }
+
+    /// Create a `Tracer` whose instrumentation library carries the config's
+    /// scope attributes.
+    fn tracer_with_config(&self, config: TracerConfig) -> Self::Tracer {
         // Use default value if name is invalid empty string
-        let component_name = if name.is_empty() {
+        let component_name = if config.name.is_empty() {
             DEFAULT_COMPONENT_NAME
         } else {
-            name
+            config.name
         };
-        let instrumentation_lib = sdk::InstrumentationLibrary::new(component_name, version);
+        let mut instrumentation_lib =
+            sdk::InstrumentationLibrary::new(component_name, config.version, config.schema_url);
+        instrumentation_lib.attributes = config.attributes.into();
 
         sdk::trace::Tracer::new(instrumentation_lib, Arc::downgrade(&self.inner))
     }
 
     /// Force flush all remaining spans in span processors and return results.
     fn force_flush(&self) -> Vec<TraceResult<()>> {
-        self.span_processors()
-            .iter()
-            .map(|processor| processor.force_flush())
-            .collect()
+        executor::block_on(self.force_flush_async())
     }
 }
 
@@ -125,6 +246,9 @@ impl Builder {
     }
 
     /// The `SpanProcessor` that this provider should use.
+    ///
+    /// Multiple processors can be registered, they are invoked in the order
+    /// they were added.
     pub fn with_span_processor<T: SpanProcessor + 'static>(self, processor: T) -> Self {
         let mut processors = self.processors;
         processors.push(Box::new(processor));
@@ -132,17 +256,90 @@ impl Builder {
         Builder { processors, ..self }
     }
 
+    /// A function this provider calls with every finished span, for example to
+    /// record span metrics.
+    ///
+    /// The function is called synchronously when spans end, in the order it was
+    /// registered relative to the span processors. It receives the finalized
+    /// span data, including start and end time and status. Panics in the
+    /// function are caught and reported through the global error handler.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use opentelemetry::sdk::trace::TracerProvider;
+    /// use std::sync::atomic::{AtomicUsize, Ordering};
+    /// use std::sync::Arc;
+    ///
+    /// let ended = Arc::new(AtomicUsize::new(0));
+    /// let counter = ended.clone();
+    /// let provider = TracerProvider::builder()
+    ///     .with_on_end(move |span| {
+    ///         let _duration = span.end_time.duration_since(span.start_time);
+    ///         counter.fetch_add(1, Ordering::Relaxed);
+    ///     })
+    ///     .build();
+    /// ```
+    pub fn with_on_end<F>(self, on_end: F) -> Self
+    where
+        F: Fn(&SpanData) + Send + Sync + 'static,
+    {
+        self.with_span_processor(OnEndProcessor::new(on_end))
+    }
+
     /// The sdk `Config` that this provider will use.
     pub fn with_config(self, config: sdk::trace::Config) -> Self {
         Builder { config, ..self }
     }
 
+    /// The `IdGenerator` that this provider will use to generate trace and span ids.
+    ///
+    /// This replaces the id generator of the current `Config`, setting a new `Config`
+    /// afterwards via `with_config` overrides it again.
+    pub fn with_id_generator<T: IdGenerator + 'static>(mut self, id_generator: T) -> Self {
+        self.config.id_generator = Box::new(id_generator);
+        self
+    }
+
+    /// The `Clock` that this provider will use to timestamp spans and events.
+    ///
+    /// This replaces the clock of the current `Config`, setting a new `Config`
+    /// afterwards via `with_config` overrides it again.
+    pub fn with_clock<T: sdk::trace::Clock + 'static>(mut self, clock: T) -> Self {
+        self.config.clock = Box::new(clock);
+        self
+    }
+
+    /// The `SpanLimits` that this provider will enforce on recorded spans.
+    ///
+    /// This replaces the span limits of the current `Config`, setting a new
+    /// `Config` afterwards via `with_config` overrides it again.
+    pub fn with_span_limits(mut self, span_limits: sdk::trace::SpanLimits) -> Self {
+        self.config.span_limits = span_limits;
+        self
+    }
+
+    /// The `service.name` of the resource this provider will use.
+    ///
+    /// This is merged into the resource of the current `Config`, setting a new
+    /// `Config` afterwards via `with_config` overrides it again.
+    pub fn with_service_name<T: Into<Cow<'static, str>>>(mut self, service_name: T) -> Self {
+        let service_name = sdk::Resource::new_with_service_name(service_name);
+        let resource = match self.config.resource.take() {
+            Some(resource) => service_name.merge(&resource),
+            None => service_name,
+        };
+        self.config.resource = Some(Arc::new(resource));
+        self
+    }
+
     /// Create a new provider from this configuration.
     pub fn build(self) -> TracerProvider {
         TracerProvider {
             inner: Arc::new(TracerProviderInner {
                 processors: self.processors,
                 config: self.config,
+                is_shutdown: AtomicBool::new(false),
             }),
         }
     }
@@ -150,12 +347,20 @@ impl Builder {
 
 #[cfg(test)]
 mod tests {
+    use crate::runtime;
     use crate::sdk::export::trace::SpanData;
     use crate::sdk::trace::provider::TracerProviderInner;
-    use crate::sdk::trace::{Span, SpanProcessor};
-    use crate::trace::{TraceError, TraceResult, TracerProvider};
-    use crate::Context;
-    use std::sync::Arc;
+    use crate::sdk::trace::{BatchConfig, BatchSpanProcessor, Span, SpanProcessor};
+    use crate::trace::{
+        IdGenerator, NoopSpanExporter, Span as _, SpanId, Status, TraceContextExt, TraceError,
+        TraceId, TraceResult, Tracer, TracerConfig, TracerProvider,
+    };
+    use crate::{Context, KeyValue};
+    use futures::future::{self, BoxFuture};
+    use std::collections::HashSet;
+    use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+    use std::sync::{Arc, Mutex};
+    use std::time::Duration;
 
     #[derive(Debug)]
     struct TestSpanProcessor {
@@ -163,7 +368,7 @@ mod tests {
     }
 
     impl SpanProcessor for TestSpanProcessor {
-        fn on_start(&self, _span: &Span, _cx: &Context) {
+        fn on_start(&self, _span: &mut Span, _cx: &Context) {
             unimplemented!()
         }
 
@@ -179,11 +384,200 @@ mod tests {
             }
         }
 
-        fn shutdown(&mut self) -> TraceResult<()> {
+        fn shutdown(&self) -> TraceResult<()> {
             self.force_flush()
         }
     }
 
+    /// Never finishes flushing.
+    #[derive(Debug)]
+    struct SlowSpanProcessor;
+
+    impl SpanProcessor for SlowSpanProcessor {
+        fn on_start(&self, _span: &mut Span, _cx: &Context) {}
+
+        fn on_end(&self, _span: SpanData) {}
+
+        fn force_flush(&self) -> TraceResult<()> {
+            Ok(())
+        }
+
+        fn force_flush_async(&self) -> BoxFuture<'static, TraceResult<()>> {
+            Box::pin(future::pending())
+        }
+
+        fn shutdown(&self) -> TraceResult<()> {
+            Ok(())
+        }
+    }
+
+    /// Records every call into a log shared between processors.
+    #[derive(Debug)]
+    struct RecordingSpanProcessor {
+        name: &'static str,
+        log: Arc<Mutex<Vec<String>>>,
+    }
+
+    impl RecordingSpanProcessor {
+        fn record(&self, call: String) {
+            self.log
+                .lock()
+                .unwrap()
+                .push(format!("{} {}", self.name, call));
+        }
+    }
+
+    impl SpanProcessor for RecordingSpanProcessor {
+        fn on_start(&self, span: &mut Span, _cx: &Context) {
+            self.record(format!("on_start {:?}", span.span_context().span_id()));
+        }
+
+        fn on_end(&self, span: SpanData) {
+            self.record(format!("on_end {:?}", span.span_context.span_id()));
+        }
+
+        fn force_flush(&self) -> TraceResult<()> {
+            self.record("force_flush".to_string());
+            Ok(())
+        }
+
+        fn shutdown(&self) -> TraceResult<()> {
+            self.record("shutdown".to_string());
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn test_span_processors_called_in_order() {
+        let log = Arc::new(Mutex::new(Vec::new()));
+        let provider = super::TracerProvider::builder()
+            .with_span_processor(RecordingSpanProcessor {
+                name: "first",
+                log: log.clone(),
+            })
+            .with_span_processor(RecordingSpanProcessor {
+                name: "second",
+                log: log.clone(),
+            })
+            .build();
+
+        let span = provider.get_tracer("test", None).start("span");
+        let span_id = span.span_context().span_id();
+        drop(span);
+        let results = provider.force_flush();
+        drop(provider);
+
+        assert_eq!(results.len(), 2);
+        assert_eq!(
+            *log.lock().unwrap(),
+            vec![
+                format!("first on_start {:?}", span_id),
+                format!("second on_start {:?}", span_id),
+                format!("first on_end {:?}", span_id),
+                format!("second on_end {:?}", span_id),
+                "first force_flush".to_string(),
+                "second force_flush".to_string(),
+                "first shutdown".to_string(),
+                "second shutdown".to_string(),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_with_on_end() {
+        let clock = crate::sdk::trace::ManualClock::default();
+        let observed = Arc::new(Mutex::new(Vec::new()));
+        let log = Arc::new(Mutex::new(Vec::new()));
+        let provider = super::TracerProvider::builder()
+            .with_on_end(|_| panic!("broken callback"))
+            .with_on_end({
+                let observed = observed.clone();
+                move |span| {
+                    observed.lock().unwrap().push((
+                        span.name.clone(),
+                        span.end_time.duration_since(span.start_time).unwrap(),
+                        span.status.clone(),
+                    ))
+                }
+            })
+            .with_span_processor(RecordingSpanProcessor {
+                name: "exporter",
+                log: log.clone(),
+            })
+            .with_clock(clock.clone())
+            .build();
+        let tracer = provider.get_tracer("test", None);
+
+        let mut span = tracer.start("observed");
+        span.set_status(Status::error("failed"));
+        clock.advance(Duration::from_millis(42));
+        span.end();
+
+        assert_eq!(
+            *observed.lock().unwrap(),
+            vec![(
+                "observed".into(),
+                Duration::from_millis(42),
+                Status::error("failed")
+            )]
+        );
+        // the panicking callback does not break processors registered after it
+        assert_eq!(log.lock().unwrap().len(), 2);
+    }
+
+    #[test]
+    #[cfg(feature = "testing")]
+    fn test_shutdown() {
+        use crate::testing::trace::new_test_exporter;
+
+        let log = Arc::new(Mutex::new(Vec::new()));
+        let (exporter, rx_export, rx_shutdown) = new_test_exporter();
+        let provider = super::TracerProvider::builder()
+            .with_simple_exporter(exporter)
+            .with_span_processor(RecordingSpanProcessor {
+                name: "recording",
+                log: log.clone(),
+            })
+            .build();
+        let tracer = provider.get_tracer("test", None);
+
+        tracer.start("before").end();
+        // spans still running are dropped when they end after the shutdown
+        let mut running = tracer.start("running");
+
+        let results = provider.shutdown();
+        assert_eq!(results.len(), 2);
+        assert!(results.iter().all(Result::is_ok));
+        assert!(provider.is_shutdown());
+        // the export thread terminated and shut the exporter down
+        assert!(rx_shutdown.try_recv().is_ok());
+
+        running.end();
+        tracer.start("after").end();
+        let late = provider.get_tracer("late", None).start("late");
+        assert!(!late.is_recording());
+        drop(late);
+
+        assert_eq!(
+            rx_export
+                .try_iter()
+                .map(|span| span.name)
+                .collect::<Vec<_>>(),
+            vec!["before"]
+        );
+        // processors are only shut down once, including when dropped
+        assert!(provider.shutdown()[0].is_err());
+        drop(provider);
+        assert_eq!(
+            log.lock()
+                .unwrap()
+                .iter()
+                .filter(|call| call.ends_with("shutdown"))
+                .count(),
+            1
+        );
+    }
+
     #[test]
     fn test_force_flush() {
         let tracer_provider = super::TracerProvider::new(Arc::from(TracerProviderInner {
@@ -192,9 +586,218 @@ mod tests {
                 Box::from(TestSpanProcessor { success: false }),
             ],
             config: Default::default(),
+            is_shutdown: AtomicBool::new(false),
         }));
 
         let results = tracer_provider.force_flush();
         assert_eq!(results.len(), 2);
     }
+
+    #[test]
+    fn test_force_flush_async() {
+        let tracer_provider = super::TracerProvider::new(Arc::from(TracerProviderInner {
+            processors: vec![
+                Box::from(TestSpanProcessor { success: true }),
+                Box::from(TestSpanProcessor { success: false }),
+            ],
+            config: Default::default(),
+            is_shutdown: AtomicBool::new(false),
+        }));
+
+        let results = futures::executor::block_on(tracer_provider.force_flush_async());
+        assert_eq!(results.len(), 2);
+        assert!(results[0].is_ok());
+        assert!(results[1].is_err());
+    }
+
+    #[test]
+    fn test_force_flush_timeout() {
+        let tracer_provider = super::TracerProvider::new(Arc::from(TracerProviderInner {
+            processors: vec![
+                Box::from(SlowSpanProcessor),
+                Box::from(BatchSpanProcessor::new(
+                    vec![Box::new(NoopSpanExporter::new())],
+                    BatchConfig::default(),
+                    runtime::AsyncStd,
+                )),
+                Box::from(TestSpanProcessor { success: true }),
+            ],
+            config: Default::default(),
+            is_shutdown: AtomicBool::new(false),
+        }));
+
+        // the slow processor comes first and uses the whole timeout, the
+        // others are flushed concurrently and still finish
+        let results = tracer_provider.force_flush_timeout(Duration::from_millis(200));
+        assert_eq!(results.len(), 3);
+        assert!(matches!(results[0], Err(TraceError::ExportTimedOut(_))));
+        assert!(results[1].is_ok(), "{:?}", results[1]);
+        assert!(results[2].is_ok());
+    }
+
+    #[test]
+    fn test_default_resource() {
+        let provider = super::TracerProvider::builder().build();
+        let resource = provider.config().resource.as_ref().unwrap();
+        assert_eq!(
+            resource.get("telemetry.sdk.language".into()),
+            Some("rust".into())
+        );
+
+        // explicitly configured resources are merged over the default one
+        let provider = super::TracerProvider::builder()
+            .with_config(
+                crate::sdk::trace::config().with_resource(crate::sdk::Resource::new(vec![
+                    crate::KeyValue::new("service.name", "x"),
+                    crate::KeyValue::new("telemetry.sdk.name", "custom"),
+                ])),
+            )
+            .build();
+        let resource = provider.config().resource.as_ref().unwrap();
+        assert_eq!(resource.get("service.name".into()), Some("x".into()));
+        assert_eq!(
+            resource.get("telemetry.sdk.name".into()),
+            Some("custom".into())
+        );
+        assert_eq!(
+            resource.get("telemetry.sdk.language".into()),
+            Some("rust".into())
+        );
+    }
+
+    #[test]
+    fn test_explicit_resource_exports_sdk_attributes() {
+        let exporter = crate::testing::trace::InMemorySpanExporter::default();
+        let provider = super::TracerProvider::builder()
+            .with_simple_exporter(exporter.clone())
+            .with_config(
+                crate::sdk::trace::config().with_resource(crate::sdk::Resource::new(vec![
+                    KeyValue::new("service.name", "x"),
+                ])),
+            )
+            .build();
+
+        provider.get_tracer("test", None).in_span("span", |_cx| {});
+
+        let spans = exporter.get_finished_spans();
+        assert_eq!(spans.len(), 1);
+        let resource = spans[0].resource.as_ref().unwrap();
+        assert_eq!(resource.get("service.name".into()), Some("x".into()));
+        assert_eq!(
+            resource.get("telemetry.sdk.language".into()),
+            Some("rust".into())
+        );
+    }
+
+    #[test]
+    fn test_with_service_name() {
+        let provider = super::TracerProvider::builder()
+            .with_config(
+                crate::sdk::trace::config().with_resource(crate::sdk::Resource::new(vec![
+                    crate::KeyValue::new("service.name", "configured"),
+                    crate::KeyValue::new("service.version", "1.0"),
+                ])),
+            )
+            .with_service_name("checkout")
+            .build();
+
+        let resource = provider.config().resource.as_ref().unwrap();
+        assert_eq!(resource.get("service.name".into()), Some("checkout".into()));
+        assert_eq!(resource.get("service.version".into()), Some("1.0".into()));
+    }
+
+    #[test]
+    fn test_versioned_tracer_schema_url() {
+        let tracer_provider = super::TracerProvider::default();
+
+        let plain = tracer_provider.get_tracer("test", Some("1.0"));
+        assert_eq!(plain.instrumentation_library().schema_url, None);
+
+        let with_schema = tracer_provider.versioned_tracer(
+            "test",
+            Some("1.0"),
+            Some("https://opentelemetry.io/schemas/1.4.0"),
+        );
+        assert_eq!(
+            with_schema.instrumentation_library().schema_url,
+            Some("https://opentelemetry.io/schemas/1.4.0")
+        );
+
+        // tracers that only differ by schema url must not be collapsed
+        let libraries: HashSet<_> = vec![
+            plain.instrumentation_library().clone(),
+            with_schema.instrumentation_library().clone(),
+        ]
+        .into_iter()
+        .collect();
+        assert_eq!(libraries.len(), 2);
+    }
+
+    #[test]
+    fn test_tracer_with_config_attributes() {
+        let tracer_provider = super::TracerProvider::default();
+
+        let plain = tracer_provider.tracer_with_config(TracerConfig::new("test"));
+        let with_attributes = tracer_provider.tracer_with_config(
+            TracerConfig::new("test").with_attributes(vec![KeyValue::new("feature", "async")]),
+        );
+        assert!(plain.instrumentation_library().attributes.is_empty());
+        assert_eq!(
+            *with_attributes.instrumentation_library().attributes,
+            [KeyValue::new("feature", "async")]
+        );
+
+        // tracers that only differ by scope attributes must not be collapsed
+        let libraries: HashSet<_> = vec![
+            plain.instrumentation_library().clone(),
+            with_attributes.instrumentation_library().clone(),
+            tracer_provider
+                .get_tracer("test", None)
+                .instrumentation_library()
+                .clone(),
+        ]
+        .into_iter()
+        .collect();
+        assert_eq!(libraries.len(), 2);
+    }
+
+    #[derive(Debug, Default)]
+    struct CountingIdGenerator {
+        trace_ids: AtomicU64,
+        span_ids: AtomicU64,
+    }
+
+    impl IdGenerator for CountingIdGenerator {
+        fn new_trace_id(&self) -> TraceId {
+            TraceId::from_u128(u128::from(
+                self.trace_ids.fetch_add(1, Ordering::SeqCst) + 1,
+            ))
+        }
+
+        fn new_span_id(&self) -> SpanId {
+            SpanId::from_u64(self.span_ids.fetch_add(1, Ordering::SeqCst) + 1)
+        }
+    }
+
+    #[test]
+    fn test_with_id_generator() {
+        let provider = super::TracerProvider::builder()
+            .with_id_generator(CountingIdGenerator::default())
+            .build();
+        let tracer = provider.get_tracer("test", None);
+
+        let parent = tracer.start("parent");
+        let cx = Context::current_with_span(parent);
+        let child = tracer.start_with_context("child", cx.clone());
+        let root = tracer.start("root");
+
+        let parent_context = cx.span().span_context().clone();
+        assert_eq!(parent_context.trace_id(), TraceId::from_u128(1));
+        assert_eq!(parent_context.span_id(), SpanId::from_u64(1));
+        // children keep the trace id of their parent
+        assert_eq!(child.span_context().trace_id(), TraceId::from_u128(1));
+        assert_eq!(child.span_context().span_id(), SpanId::from_u64(2));
+        assert_eq!(root.span_context().trace_id(), TraceId::from_u128(2));
+        assert_eq!(root.span_context().span_id(), SpanId::from_u64(3));
+    }
 }
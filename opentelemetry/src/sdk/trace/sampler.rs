@@ -38,9 +38,13 @@
 //! MUST NOT allow this combination.
 
 use crate::{
+    sdk::trace::{Clock, SystemClock},
     trace::{Link, SpanKind, TraceContextExt, TraceId, TraceState},
-    Context, KeyValue,
+    Context, Key, KeyValue, Value,
 };
+use std::collections::{BTreeMap, HashMap};
+use std::sync::Mutex;
+use std::time::{Duration, SystemTime};
 
 /// The `ShouldSample` interface allows implementations to provide samplers
 /// which will return a sampling `SamplingResult` based on information that
@@ -57,6 +61,12 @@ pub trait ShouldSample: Send + Sync + std::fmt::Debug {
         attributes: &[KeyValue],
         links: &[Link],
     ) -> SamplingResult;
+
+    /// Returns a short description of the sampler and its configuration,
+    /// e.g. `TraceIdRatioBased{0.25}`, for diagnostics.
+    fn description(&self) -> String {
+        format!("{:?}", self)
+    }
 }
 
 /// The result of sampling logic for a given `Span`.
@@ -66,7 +76,10 @@ pub struct SamplingResult {
     pub decision: SamplingDecision,
     /// Extra attributes added by this result
     pub attributes: Vec<KeyValue>,
-    /// Trace state from parent context, might be modified by sampler
+    /// Trace state from parent context, might be modified by sampler.
+    ///
+    /// It becomes the trace state of the new span, even if the span is dropped,
+    /// so samplers can propagate their decision downstream.
     pub trace_state: TraceState,
 }
 
@@ -112,22 +125,30 @@ impl ShouldSample for Sampler {
             Sampler::AlwaysOn => SamplingDecision::RecordAndSample,
             // Never sample the trace
             Sampler::AlwaysOff => SamplingDecision::Drop,
-            // The parent decision if sampled; otherwise the decision of delegate_sampler
+            // The parent decision if there is a parent, local or remote; otherwise the
+            // decision of delegate_sampler, which is only consulted for root spans.
             Sampler::ParentBased(delegate_sampler) => {
-                parent_context.filter(|cx| cx.has_active_span()).map_or(
-                    delegate_sampler
-                        .should_sample(parent_context, trace_id, name, span_kind, attributes, links)
-                        .decision,
-                    |ctx| {
-                        let span = ctx.span();
-                        let parent_span_context = span.span_context();
-                        if parent_span_context.is_sampled() {
+                match parent_context.filter(|cx| cx.has_active_span()) {
+                    Some(cx) => {
+                        if cx.span().span_context().is_sampled() {
                             SamplingDecision::RecordAndSample
                         } else {
                             SamplingDecision::Drop
                         }
-                    },
-                )
+                    }
+                    None => {
+                        delegate_sampler
+                            .should_sample(
+                                parent_context,
+                                trace_id,
+                                name,
+                                span_kind,
+                                attributes,
+                                links,
+                            )
+                            .decision
+                    }
+                }
             }
             // Probabilistically sample the trace.
             Sampler::TraceIdRatioBased(prob) => {
@@ -152,21 +173,719 @@ impl ShouldSample for Sampler {
             // No extra attributes ever set by the SDK samplers.
             attributes: Vec::new(),
             // all sampler in SDK will not modify trace state.
-            trace_state: match parent_context {
-                Some(ctx) => ctx.span().span_context().trace_state().clone(),
-                None => TraceState::default(),
+            trace_state: parent_trace_state(parent_context),
+        }
+    }
+
+    fn description(&self) -> String {
+        match self {
+            Sampler::AlwaysOn => "AlwaysOnSampler".to_string(),
+            Sampler::AlwaysOff => "AlwaysOffSampler".to_string(),
+            Sampler::ParentBased(root) => format!("ParentBased{{root={}}}", root.description()),
+            Sampler::TraceIdRatioBased(prob) => format!("TraceIdRatioBased{{{}}}", prob),
+        }
+    }
+}
+
+/// A sampler that respects the sampling decision of the parent span, with a
+/// separate delegate sampler for each kind of parent.
+///
+/// Spans without a parent are sampled by the `root` sampler. By default, spans
+/// with a sampled parent are sampled and spans with an unsampled parent are
+/// dropped, regardless of whether the parent is local or remote, which matches
+/// [`Sampler::ParentBased`].
+///
+/// # Examples
+///
+/// ```
+/// use opentelemetry::sdk::trace::{config, ParentBased, Sampler};
+///
+/// // always sample local children of sampled spans, but give remote
+/// // unsampled parents a second chance
+/// let sampler = ParentBased::builder(Sampler::TraceIdRatioBased(0.1))
+///     .with_local_parent_sampled(Sampler::AlwaysOn)
+///     .with_remote_parent_not_sampled(Sampler::TraceIdRatioBased(0.01))
+///     .build();
+/// let config = config().with_sampler(sampler);
+/// ```
+#[derive(Debug)]
+pub struct ParentBased {
+    root: Box<dyn ShouldSample>,
+    remote_parent_sampled: Box<dyn ShouldSample>,
+    remote_parent_not_sampled: Box<dyn ShouldSample>,
+    local_parent_sampled: Box<dyn ShouldSample>,
+    local_parent_not_sampled: Box<dyn ShouldSample>,
+}
+
+impl ParentBased {
+    /// Create a new builder using `root` to sample spans without a parent.
+    pub fn builder<T: ShouldSample + 'static>(root: T) -> ParentBasedBuilder {
+        ParentBasedBuilder {
+            sampler: ParentBased {
+                root: Box::new(root),
+                remote_parent_sampled: Box::new(Sampler::AlwaysOn),
+                remote_parent_not_sampled: Box::new(Sampler::AlwaysOff),
+                local_parent_sampled: Box::new(Sampler::AlwaysOn),
+                local_parent_not_sampled: Box::new(Sampler::AlwaysOff),
+            },
+        }
+    }
+}
+
+impl ShouldSample for ParentBased {
+    fn should_sample(
+        &self,
+        parent_context: Option<&Context>,
+        trace_id: TraceId,
+        name: &str,
+        span_kind: &SpanKind,
+        attributes: &[KeyValue],
+        links: &[Link],
+    ) -> SamplingResult {
+        let delegate = match parent_context.filter(|cx| cx.has_active_span()) {
+            None => &self.root,
+            Some(cx) => {
+                let span = cx.span();
+                let parent_span_context = span.span_context();
+                match (
+                    parent_span_context.is_remote(),
+                    parent_span_context.is_sampled(),
+                ) {
+                    (true, true) => &self.remote_parent_sampled,
+                    (true, false) => &self.remote_parent_not_sampled,
+                    (false, true) => &self.local_parent_sampled,
+                    (false, false) => &self.local_parent_not_sampled,
+                }
+            }
+        };
+
+        delegate.should_sample(parent_context, trace_id, name, span_kind, attributes, links)
+    }
+
+    fn description(&self) -> String {
+        format!(
+            "ParentBased{{root={},remoteParentSampled={},remoteParentNotSampled={},localParentSampled={},localParentNotSampled={}}}",
+            self.root.description(),
+            self.remote_parent_sampled.description(),
+            self.remote_parent_not_sampled.description(),
+            self.local_parent_sampled.description(),
+            self.local_parent_not_sampled.description(),
+        )
+    }
+}
+
+/// A builder for creating [`ParentBased`] samplers.
+#[derive(Debug)]
+pub struct ParentBasedBuilder {
+    sampler: ParentBased,
+}
+
+impl ParentBasedBuilder {
+    /// Set the sampler used for spans whose remote parent is sampled.
+    pub fn with_remote_parent_sampled<T: ShouldSample + 'static>(mut self, sampler: T) -> Self {
+        self.sampler.remote_parent_sampled = Box::new(sampler);
+        self
+    }
+
+    /// Set the sampler used for spans whose remote parent is not sampled.
+    pub fn with_remote_parent_not_sampled<T: ShouldSample + 'static>(mut self, sampler: T) -> Self {
+        self.sampler.remote_parent_not_sampled = Box::new(sampler);
+        self
+    }
+
+    /// Set the sampler used for spans whose local parent is sampled.
+    pub fn with_local_parent_sampled<T: ShouldSample + 'static>(mut self, sampler: T) -> Self {
+        self.sampler.local_parent_sampled = Box::new(sampler);
+        self
+    }
+
+    /// Set the sampler used for spans whose local parent is not sampled.
+    pub fn with_local_parent_not_sampled<T: ShouldSample + 'static>(mut self, sampler: T) -> Self {
+        self.sampler.local_parent_not_sampled = Box::new(sampler);
+        self
+    }
+
+    /// Build the parent based sampler
+    pub fn build(self) -> ParentBased {
+        self.sampler
+    }
+}
+
+fn parent_trace_state(parent_context: Option<&Context>) -> TraceState {
+    match parent_context {
+        Some(ctx) => ctx.span().span_context().trace_state().clone(),
+        None => TraceState::default(),
+    }
+}
+
+/// A sampler that samples at most a fixed number of spans per second.
+///
+/// Sampling is driven by a token bucket which holds up to one second worth of
+/// tokens and refills continuously at the configured rate. Each sampled root
+/// span consumes a token, spans are dropped while the bucket is empty.
+///
+/// Spans with a parent, local or remote, follow the sampling decision of
+/// their parent and do not consume tokens.
+///
+/// # Examples
+///
+/// ```
+/// use opentelemetry::sdk::trace::{config, RateLimitingSampler};
+///
+/// // sample at most 100 spans per second
+/// let config = config().with_sampler(RateLimitingSampler::new(100.0));
+/// ```
+#[derive(Debug)]
+pub struct RateLimitingSampler {
+    max_per_second: f64,
+    bucket: Mutex<TokenBucket>,
+}
+
+#[derive(Debug)]
+struct TokenBucket {
+    balance: f64,
+    last_tick: SystemTime,
+}
+
+impl RateLimitingSampler {
+    /// Create a sampler that samples at most `max_per_second` spans per second.
+    ///
+    /// Negative rates are treated as zero, in which case no span is sampled.
+    pub fn new(max_per_second: f64) -> Self {
+        let max_per_second = max_per_second.max(0.0);
+        RateLimitingSampler {
+            max_per_second,
+            bucket: Mutex::new(TokenBucket {
+                balance: max_per_second.max(1.0),
+                last_tick: crate::time::now(),
+            }),
+        }
+    }
+
+    /// Refill the bucket for the time elapsed since the last call and try to
+    /// take a token from it.
+    fn try_acquire(&self, now: SystemTime) -> bool {
+        if self.max_per_second == 0.0 {
+            return false;
+        }
+
+        let mut bucket = match self.bucket.lock() {
+            Ok(bucket) => bucket,
+            Err(_) => return false,
+        };
+
+        // a clock moving backwards does not refill the bucket
+        let elapsed = now.duration_since(bucket.last_tick).unwrap_or_default();
+        bucket.last_tick = bucket.last_tick.max(now);
+        bucket.balance = (bucket.balance + elapsed.as_secs_f64() * self.max_per_second)
+            .min(self.max_per_second.max(1.0));
+
+        if bucket.balance >= 1.0 {
+            bucket.balance -= 1.0;
+            true
+        } else {
+            false
+        }
+    }
+}
+
+impl ShouldSample for RateLimitingSampler {
+    fn should_sample(
+        &self,
+        parent_context: Option<&Context>,
+        _trace_id: TraceId,
+        _name: &str,
+        _span_kind: &SpanKind,
+        _attributes: &[KeyValue],
+        _links: &[Link],
+    ) -> SamplingResult {
+        let sampled = match parent_context.filter(|cx| cx.has_active_span()) {
+            Some(cx) => cx.span().span_context().is_sampled(),
+            None => self.try_acquire(crate::time::now()),
+        };
+        let decision = if sampled {
+            SamplingDecision::RecordAndSample
+        } else {
+            SamplingDecision::Drop
+        };
+
+        SamplingResult {
+            decision,
+            attributes: Vec::new(),
+            trace_state: parent_trace_state(parent_context),
+        }
+    }
+
+    fn description(&self) -> String {
+        format!("RateLimitingSampler{{{}}}", self.max_per_second)
+    }
+}
+
+/// A sampler that drops spans with an attribute matching one of the given values
+/// and delegates all other sampling decisions.
+///
+/// Only the attributes known when the span is started, e.g. the ones set with
+/// [`SpanBuilder::with_attributes`], are considered.
+///
+/// # Examples
+///
+/// ```
+/// use opentelemetry::sdk::trace::{config, AttributeBasedSampler, Sampler};
+///
+/// // drop health checks, sample everything else
+/// let sampler = AttributeBasedSampler::new("http.target", vec!["/health".into()], Sampler::AlwaysOn);
+/// let config = config().with_sampler(sampler);
+/// ```
+///
+/// [`SpanBuilder::with_attributes`]: crate::trace::SpanBuilder::with_attributes
+#[derive(Debug)]
+pub struct AttributeBasedSampler {
+    key: Key,
+    dropped_values: Vec<Value>,
+    delegate: Box<dyn ShouldSample>,
+}
+
+impl AttributeBasedSampler {
+    /// Create a sampler dropping spans whose `key` attribute is equal to one of
+    /// `dropped_values`, the `delegate` samples all other spans.
+    pub fn new<K, T>(key: K, dropped_values: Vec<Value>, delegate: T) -> Self
+    where
+        K: Into<Key>,
+        T: ShouldSample + 'static,
+    {
+        AttributeBasedSampler {
+            key: key.into(),
+            dropped_values,
+            delegate: Box::new(delegate),
+        }
+    }
+}
+
+impl ShouldSample for AttributeBasedSampler {
+    fn should_sample(
+        &self,
+        parent_context: Option<&Context>,
+        trace_id: TraceId,
+        name: &str,
+        span_kind: &SpanKind,
+        attributes: &[KeyValue],
+        links: &[Link],
+    ) -> SamplingResult {
+        let dropped = attributes
+            .iter()
+            .any(|kv| kv.key == self.key && self.dropped_values.contains(&kv.value));
+        if dropped {
+            return SamplingResult {
+                decision: SamplingDecision::Drop,
+                attributes: Vec::new(),
+                trace_state: parent_trace_state(parent_context),
+            };
+        }
+
+        self.delegate
+            .should_sample(parent_context, trace_id, name, span_kind, attributes, links)
+    }
+
+    fn description(&self) -> String {
+        format!(
+            "AttributeBased{{key={},delegate={}}}",
+            self.key.as_str(),
+            self.delegate.description()
+        )
+    }
+}
+
+/// The default time after which an idle trace is evicted from the cache of a
+/// [`CachedSampler`].
+const DEFAULT_CACHE_IDLE_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// A sampler caching the decisions of its delegate per trace id, so spans of
+/// the same trace reuse the decision instead of sampling again.
+///
+/// Only the decisions of the root span of a trace in this service are cached,
+/// i.e. spans without a parent or with a remote parent. Spans with a local
+/// parent are sampled by the delegate when their trace is not cached.
+///
+/// The cache holds at most `max_traces` traces and evicts the least recently
+/// used one when it is full. Traces without new spans for the idle timeout,
+/// 30 seconds by default, are evicted as well. Idle time is measured with the
+/// sampler's [`Clock`], the [`SystemClock`] unless set via `with_clock`.
+///
+/// # Examples
+///
+/// ```
+/// use opentelemetry::sdk::trace::{config, CachedSampler, Sampler};
+/// use std::time::Duration;
+///
+/// // remember the decisions of the last 1000 traces for at most 10 seconds
+/// let sampler = CachedSampler::new(Sampler::TraceIdRatioBased(0.5), 1000)
+///     .with_idle_timeout(Duration::from_secs(10));
+/// let config = config().with_sampler(sampler);
+/// ```
+#[derive(Debug)]
+pub struct CachedSampler {
+    delegate: Box<dyn ShouldSample>,
+    max_traces: usize,
+    idle_timeout: Duration,
+    clock: Box<dyn Clock>,
+    cache: Mutex<DecisionCache>,
+}
+
+#[derive(Debug, Default)]
+struct DecisionCache {
+    entries: HashMap<TraceId, CachedDecision>,
+    // trace ids by the tick of their last use, the first one is the least
+    // recently used
+    lru: BTreeMap<u64, TraceId>,
+    tick: u64,
+}
+
+#[derive(Debug)]
+struct CachedDecision {
+    decision: SamplingDecision,
+    attributes: Vec<KeyValue>,
+    last_used: SystemTime,
+    tick: u64,
+}
+
+impl DecisionCache {
+    /// Remove the traces that have been idle for longer than `idle_timeout`.
+    fn evict_idle(&mut self, now: SystemTime, idle_timeout: Duration) {
+        while let Some((&tick, trace_id)) = self.lru.iter().next() {
+            let idle = now
+                .duration_since(self.entries[trace_id].last_used)
+                .unwrap_or_default();
+            if idle < idle_timeout {
+                break;
+            }
+            let trace_id = self.lru.remove(&tick).expect("tick exists");
+            self.entries.remove(&trace_id);
+        }
+    }
+
+    fn next_tick(&mut self) -> u64 {
+        self.tick = self.tick.wrapping_add(1);
+        self.tick
+    }
+}
+
+impl CachedSampler {
+    /// Create a sampler caching the decisions of `delegate` for at most
+    /// `max_traces` traces.
+    ///
+    /// A `max_traces` of zero disables caching.
+    pub fn new<T: ShouldSample + 'static>(delegate: T, max_traces: usize) -> Self {
+        CachedSampler {
+            delegate: Box::new(delegate),
+            max_traces,
+            idle_timeout: DEFAULT_CACHE_IDLE_TIMEOUT,
+            clock: Box::new(SystemClock),
+            cache: Mutex::new(DecisionCache::default()),
+        }
+    }
+
+    /// Evict the traces without new spans for `idle_timeout` from the cache.
+    pub fn with_idle_timeout(self, idle_timeout: Duration) -> Self {
+        CachedSampler {
+            idle_timeout,
+            ..self
+        }
+    }
+
+    /// Measure the idle time of cached traces with `clock`.
+    pub fn with_clock<T: Clock + 'static>(self, clock: T) -> Self {
+        CachedSampler {
+            clock: Box::new(clock),
+            ..self
+        }
+    }
+
+    /// Return the cached decision of a trace, marking it as recently used.
+    fn lookup(&self, trace_id: TraceId, now: SystemTime) -> Option<SamplingResult> {
+        if self.max_traces == 0 {
+            return None;
+        }
+        let mut cache = self.cache.lock().ok()?;
+        cache.evict_idle(now, self.idle_timeout);
+        let tick = cache.next_tick();
+        let DecisionCache { entries, lru, .. } = &mut *cache;
+        let entry = entries.get_mut(&trace_id)?;
+        lru.remove(&entry.tick);
+        lru.insert(tick, trace_id);
+        entry.tick = tick;
+        entry.last_used = now;
+
+        Some(SamplingResult {
+            decision: entry.decision.clone(),
+            attributes: entry.attributes.clone(),
+            trace_state: TraceState::default(),
+        })
+    }
+
+    /// Cache the decision of a trace, evicting the least recently used trace
+    /// if the cache is full.
+    fn store(&self, trace_id: TraceId, result: &SamplingResult, now: SystemTime) {
+        if self.max_traces == 0 {
+            return;
+        }
+        let mut cache = match self.cache.lock() {
+            Ok(cache) => cache,
+            Err(_) => return,
+        };
+        cache.evict_idle(now, self.idle_timeout);
+        let tick = cache.next_tick();
+        let DecisionCache { entries, lru, .. } = &mut *cache;
+        if let Some(previous) = entries.get(&trace_id) {
+            lru.remove(&previous.tick);
+        } else if entries.len() >= self.max_traces {
+            if let Some((&oldest, _)) = lru.iter().next() {
+                let evicted = lru.remove(&oldest).expect("tick exists");
+                entries.remove(&evicted);
+            }
+        }
+        lru.insert(tick, trace_id);
+        entries.insert(
+            trace_id,
+            CachedDecision {
+                decision: result.decision.clone(),
+                attributes: result.attributes.clone(),
+                last_used: now,
+                tick,
+            },
+        );
+    }
+}
+
+impl ShouldSample for CachedSampler {
+    fn should_sample(
+        &self,
+        parent_context: Option<&Context>,
+        trace_id: TraceId,
+        name: &str,
+        span_kind: &SpanKind,
+        attributes: &[KeyValue],
+        links: &[Link],
+    ) -> SamplingResult {
+        let now = self.clock.now();
+        if let Some(cached) = self.lookup(trace_id, now) {
+            return SamplingResult {
+                trace_state: parent_trace_state(parent_context),
+                ..cached
+            };
+        }
+
+        let result = self.delegate.should_sample(
+            parent_context,
+            trace_id,
+            name,
+            span_kind,
+            attributes,
+            links,
+        );
+        let is_local_root = parent_context
+            .map(|cx| {
+                let span = cx.span();
+                let parent = span.span_context();
+                !parent.is_valid() || parent.is_remote()
+            })
+            .unwrap_or(true);
+        if is_local_root {
+            self.store(trace_id, &result, now);
+        }
+
+        result
+    }
+
+    fn description(&self) -> String {
+        format!(
+            "Cached{{max_traces={},delegate={}}}",
+            self.max_traces,
+            self.delegate.description()
+        )
+    }
+}
+
+/// The `tracestate` key of OpenTelemetry's own sampling values.
+const OT_TRACE_STATE_KEY: &str = "ot";
+/// The largest valid r-value.
+const MAX_R_VALUE: u8 = 62;
+/// The p-value of a zero sampling probability.
+const ZERO_PROBABILITY_P_VALUE: u8 = 63;
+
+/// A sampler making consistent probability sampling decisions across the
+/// services of a trace.
+///
+/// Sampling probabilities are powers of two, `2^-p` for a p-value between `0`
+/// and `62`, and a p-value of `63` for a zero probability. Each trace carries a
+/// random r-value in the `ot=r:<r>` entry of its `tracestate`, the root span
+/// draws it such that `r >= k` with probability `2^-k`. A span is sampled if
+/// `p <= r`, so services working with the same r-value and probability reach
+/// the same decision, and services with higher probabilities sample a superset
+/// of the spans sampled by services with lower ones.
+///
+/// Configured probabilities which are not powers of two are approximated by
+/// randomly choosing between the two closest p-values, with the configured
+/// probability on average.
+///
+/// The p-value of sampled spans is written to the `tracestate` next to the
+/// r-value as `ot=p:<p>;r:<r>`, so backends can estimate the span counts with
+/// the product of the inverse sampling probabilities.
+///
+/// # Examples
+///
+/// ```
+/// use opentelemetry::sdk::trace::{config, ConsistentSampler};
+///
+/// // sample one in four traces, consistently with the other services
+/// let config = config().with_sampler(ConsistentSampler::new(0.25));
+/// ```
+#[derive(Clone, Debug)]
+pub struct ConsistentSampler {
+    probability: f64,
+}
+
+impl ConsistentSampler {
+    /// Create a sampler sampling traces with the given probability.
+    ///
+    /// Probabilities are clamped to `0.0..=1.0`.
+    pub fn new(probability: f64) -> Self {
+        ConsistentSampler {
+            // also maps NaN to zero
+            probability: if probability >= 1.0 {
+                1.0
+            } else if probability > 0.0 {
+                probability
+            } else {
+                0.0
+            },
+        }
+    }
+
+    /// Choose the p-value of a decision, randomly between the two p-values
+    /// closest to the configured probability.
+    fn p_value<R: rand::Rng>(&self, rng: &mut R) -> u8 {
+        if self.probability <= 0.0 {
+            return ZERO_PROBABILITY_P_VALUE;
+        }
+
+        let p_low = (-self.probability.log2()).floor();
+        if p_low >= f64::from(MAX_R_VALUE) {
+            // `2^-62` is the lowest non zero probability
+            return if rng.gen_bool((self.probability * 2f64.powi(62)).min(1.0)) {
+                MAX_R_VALUE
+            } else {
+                ZERO_PROBABILITY_P_VALUE
+            };
+        }
+
+        let probability_low = 2f64.powf(-p_low);
+        let probability_high = probability_low / 2.0;
+        let p_low = p_low as u8;
+        if self.probability >= probability_low {
+            return p_low;
+        }
+
+        // choose the higher probability, i.e. the lower p-value, often enough
+        // for the average to match the configured probability
+        let low_p_odds =
+            (self.probability - probability_high) / (probability_low - probability_high);
+        // the probability is below `probability_low`, the odds are below one
+        if rng.gen_bool(low_p_odds.max(0.0)) {
+            p_low
+        } else {
+            p_low + 1
+        }
+    }
+}
+
+/// Draw an r-value, which is `>= k` with probability `2^-k`.
+fn random_r_value<R: rand::Rng>(rng: &mut R) -> u8 {
+    (rng.gen::<u64>().leading_zeros() as u8).min(MAX_R_VALUE)
+}
+
+/// The sub-keys of the `ot` trace state entry, e.g. `p:2;r:5`.
+fn ot_values(trace_state: &TraceState) -> Vec<(&str, &str)> {
+    trace_state
+        .get(OT_TRACE_STATE_KEY)
+        .map(|value| {
+            value
+                .split(';')
+                .filter_map(|entry| {
+                    let mut parts = entry.splitn(2, ':');
+                    Some((parts.next()?, parts.next()?))
+                })
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+impl ShouldSample for ConsistentSampler {
+    fn should_sample(
+        &self,
+        parent_context: Option<&Context>,
+        _trace_id: TraceId,
+        _name: &str,
+        _span_kind: &SpanKind,
+        _attributes: &[KeyValue],
+        _links: &[Link],
+    ) -> SamplingResult {
+        let trace_state = parent_trace_state(parent_context);
+        let mut ot_values = ot_values(&trace_state);
+        let mut rng = rand::thread_rng();
+
+        let r_value = ot_values
+            .iter()
+            .find(|(key, _)| *key == "r")
+            .and_then(|(_, value)| value.parse::<u8>().ok())
+            .filter(|r| *r <= MAX_R_VALUE)
+            .unwrap_or_else(|| random_r_value(&mut rng));
+        let p_value = self.p_value(&mut rng);
+        let sampled = p_value <= r_value;
+
+        // other sub-keys are kept for the sake of forward compatibility
+        ot_values.retain(|(key, _)| *key != "p" && *key != "r");
+        let mut ot_value = String::new();
+        if sampled {
+            ot_value.push_str(&format!("p:{};", p_value));
+        }
+        ot_value.push_str(&format!("r:{}", r_value));
+        for (key, value) in ot_values {
+            ot_value.push_str(&format!(";{}:{}", key, value));
+        }
+
+        let trace_state = match trace_state.insert(OT_TRACE_STATE_KEY, ot_value) {
+            Ok(updated) => updated,
+            Err(err) => {
+                crate::global::handle_error(err);
+                trace_state
+            }
+        };
+
+        SamplingResult {
+            decision: if sampled {
+                SamplingDecision::RecordAndSample
+            } else {
+                SamplingDecision::Drop
             },
+            attributes: Vec::new(),
+            trace_state,
         }
     }
+
+    fn description(&self) -> String {
+        format!("ConsistentProbabilityBased{{{}}}", self.probability)
+    }
 }
 
 #[cfg(all(test, feature = "testing", feature = "trace"))]
 mod tests {
     use super::*;
-    use crate::sdk::trace::{Sampler, SamplingDecision, ShouldSample};
+    use crate::sdk::trace::{ManualClock, Sampler, SamplingDecision, ShouldSample};
     use crate::testing::trace::TestSpan;
     use crate::trace::{SpanContext, SpanId, TraceState, TRACE_FLAG_SAMPLED};
     use rand::Rng;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Arc;
 
     #[rustfmt::skip]
     fn sampler_data() -> Vec<(&'static str, Sampler, f64, bool, bool)> {
@@ -289,4 +1008,524 @@ mod tests {
 
         assert_eq!(result.decision, SamplingDecision::RecordAndSample);
     }
+
+    #[test]
+    fn rate_limiting_sampler_refills_tokens() {
+        let sampler = RateLimitingSampler::new(2.0);
+        let start = sampler.bucket.lock().unwrap().last_tick;
+
+        // the bucket starts full
+        assert!(sampler.try_acquire(start));
+        assert!(sampler.try_acquire(start));
+        assert!(!sampler.try_acquire(start));
+
+        // half a second refills one token
+        let later = start + Duration::from_millis(500);
+        assert!(sampler.try_acquire(later));
+        assert!(!sampler.try_acquire(later));
+
+        // the bucket never holds more than one second worth of tokens
+        let much_later = later + Duration::from_secs(60);
+        assert!(sampler.try_acquire(much_later));
+        assert!(sampler.try_acquire(much_later));
+        assert!(!sampler.try_acquire(much_later));
+
+        // a clock moving backwards does not refill the bucket
+        assert!(!sampler.try_acquire(start));
+    }
+
+    #[test]
+    fn rate_limiting_sampler_decisions() {
+        let sampler = RateLimitingSampler::new(1.0);
+        let sample = || {
+            sampler
+                .should_sample(
+                    None,
+                    TraceId::from_u128(1),
+                    "rate limited",
+                    &SpanKind::Internal,
+                    &[],
+                    &[],
+                )
+                .decision
+        };
+
+        assert_eq!(sample(), SamplingDecision::RecordAndSample);
+        assert_eq!(sample(), SamplingDecision::Drop);
+
+        let never = RateLimitingSampler::new(0.0);
+        assert!(!never.try_acquire(crate::time::now() + Duration::from_secs(10)));
+    }
+
+    #[test]
+    fn rate_limiting_sampler_follows_parent() {
+        let sampler = RateLimitingSampler::new(1.0);
+        let sample = |parent_context: Option<&Context>| {
+            sampler
+                .should_sample(
+                    parent_context,
+                    TraceId::from_u128(1),
+                    "rate limited",
+                    &SpanKind::Internal,
+                    &[],
+                    &[],
+                )
+                .decision
+        };
+
+        // child spans do not consume the token of the bucket
+        for &is_remote in &[true, false] {
+            assert_eq!(
+                sample(Some(&parent_context(is_remote, true))),
+                SamplingDecision::RecordAndSample
+            );
+            assert_eq!(
+                sample(Some(&parent_context(is_remote, false))),
+                SamplingDecision::Drop
+            );
+        }
+
+        assert_eq!(sample(None), SamplingDecision::RecordAndSample);
+        assert_eq!(sample(None), SamplingDecision::Drop);
+
+        // an empty bucket does not drop sampled children
+        assert_eq!(
+            sample(Some(&parent_context(false, true))),
+            SamplingDecision::RecordAndSample
+        );
+    }
+
+    #[test]
+    fn attribute_based_sampler_drops_matching_spans() {
+        let sampler = AttributeBasedSampler::new(
+            "http.target",
+            vec!["/health".into(), "/ready".into()],
+            Sampler::AlwaysOn,
+        );
+        let sample = |attributes: &[KeyValue]| {
+            sampler
+                .should_sample(
+                    None,
+                    TraceId::from_u128(1),
+                    "GET",
+                    &SpanKind::Server,
+                    attributes,
+                    &[],
+                )
+                .decision
+        };
+
+        assert_eq!(
+            sample(&[KeyValue::new("http.target", "/health")]),
+            SamplingDecision::Drop
+        );
+        assert_eq!(
+            sample(&[KeyValue::new("http.target", "/ready")]),
+            SamplingDecision::Drop
+        );
+        assert_eq!(
+            sample(&[KeyValue::new("http.target", "/users")]),
+            SamplingDecision::RecordAndSample
+        );
+        assert_eq!(sample(&[]), SamplingDecision::RecordAndSample);
+        assert_eq!(
+            sampler.description(),
+            "AttributeBased{key=http.target,delegate=AlwaysOnSampler}"
+        );
+    }
+
+    #[test]
+    fn sampler_description() {
+        assert_eq!(
+            RateLimitingSampler::new(100.0).description(),
+            "RateLimitingSampler{100}"
+        );
+        assert_eq!(
+            Sampler::ParentBased(Box::new(Sampler::TraceIdRatioBased(0.25))).description(),
+            "ParentBased{root=TraceIdRatioBased{0.25}}"
+        );
+        assert_eq!(Sampler::AlwaysOn.description(), "AlwaysOnSampler");
+        assert_eq!(
+            CachedSampler::new(Sampler::AlwaysOn, 10).description(),
+            "Cached{max_traces=10,delegate=AlwaysOnSampler}"
+        );
+        assert_eq!(
+            ParentBased::builder(RateLimitingSampler::new(10.0))
+                .build()
+                .description(),
+            "ParentBased{root=RateLimitingSampler{10},remoteParentSampled=AlwaysOnSampler,\
+             remoteParentNotSampled=AlwaysOffSampler,localParentSampled=AlwaysOnSampler,\
+             localParentNotSampled=AlwaysOffSampler}"
+        );
+    }
+
+    fn parent_context(is_remote: bool, is_sampled: bool) -> Context {
+        let trace_flags = if is_sampled { TRACE_FLAG_SAMPLED } else { 0 };
+        let span_context = SpanContext::new(
+            TraceId::from_u128(1),
+            SpanId::from_u64(1),
+            trace_flags,
+            is_remote,
+            TraceState::default(),
+        );
+        Context::current_with_span(TestSpan(span_context))
+    }
+
+    #[test]
+    fn parent_based_honors_parent_sampled_flag() {
+        let cases = vec![
+            ("root", None, None),
+            (
+                "remote_sampled",
+                Some(parent_context(true, true)),
+                Some(true),
+            ),
+            (
+                "remote_not_sampled",
+                Some(parent_context(true, false)),
+                Some(false),
+            ),
+            (
+                "local_sampled",
+                Some(parent_context(false, true)),
+                Some(true),
+            ),
+            (
+                "local_not_sampled",
+                Some(parent_context(false, false)),
+                Some(false),
+            ),
+        ];
+
+        for root in vec![Sampler::AlwaysOn, Sampler::AlwaysOff] {
+            let root_decision = match root {
+                Sampler::AlwaysOn => SamplingDecision::RecordAndSample,
+                _ => SamplingDecision::Drop,
+            };
+            let sampler = Sampler::ParentBased(Box::new(root));
+
+            for (name, cx, parent_sampled) in cases.iter() {
+                let expected = match parent_sampled {
+                    Some(true) => SamplingDecision::RecordAndSample,
+                    Some(false) => SamplingDecision::Drop,
+                    // the root sampler is only consulted without a parent
+                    None => root_decision.clone(),
+                };
+                let decision = sampler
+                    .should_sample(
+                        cx.as_ref(),
+                        TraceId::from_u128(1),
+                        name,
+                        &SpanKind::Internal,
+                        &[],
+                        &[],
+                    )
+                    .decision;
+
+                assert_eq!(decision, expected, "{} with {:?}", name, sampler);
+            }
+        }
+    }
+
+    #[test]
+    fn parent_based_delegates() {
+        // every delegate inverts the default behaviour
+        let sampler = ParentBased::builder(Sampler::AlwaysOff)
+            .with_remote_parent_sampled(Sampler::AlwaysOff)
+            .with_remote_parent_not_sampled(Sampler::AlwaysOn)
+            .with_local_parent_sampled(Sampler::AlwaysOff)
+            .with_local_parent_not_sampled(Sampler::AlwaysOn)
+            .build();
+        let default_sampler = ParentBased::builder(Sampler::AlwaysOn).build();
+
+        let cases = vec![
+            ("root", None),
+            ("remote_sampled", Some(parent_context(true, true))),
+            ("remote_not_sampled", Some(parent_context(true, false))),
+            ("local_sampled", Some(parent_context(false, true))),
+            ("local_not_sampled", Some(parent_context(false, false))),
+        ];
+
+        for (name, cx) in cases {
+            let decision = |sampler: &dyn ShouldSample| {
+                sampler
+                    .should_sample(
+                        cx.as_ref(),
+                        TraceId::from_u128(1),
+                        name,
+                        &SpanKind::Internal,
+                        &[],
+                        &[],
+                    )
+                    .decision
+            };
+            let legacy = Sampler::ParentBased(Box::new(Sampler::AlwaysOn));
+            let expected = decision(&legacy);
+
+            assert_eq!(decision(&default_sampler), expected, "{}", name);
+            assert_ne!(decision(&sampler), expected, "{}", name);
+        }
+    }
+
+    fn consistent_sample(sampler: &ConsistentSampler, trace_state: &str) -> SamplingResult {
+        let span_context = SpanContext::new(
+            TraceId::from_u128(1),
+            SpanId::from_u64(1),
+            TRACE_FLAG_SAMPLED,
+            true,
+            trace_state.parse().unwrap(),
+        );
+        sampler.should_sample(
+            Some(&Context::new().with_remote_span_context(span_context)),
+            TraceId::from_u128(1),
+            "consistent",
+            &SpanKind::Server,
+            &[],
+            &[],
+        )
+    }
+
+    #[test]
+    fn consistent_sampler_uses_incoming_r_value() {
+        let sampler = ConsistentSampler::new(0.25);
+        let cases = vec![
+            ("ot=r:3", SamplingDecision::RecordAndSample, "ot=p:2;r:3"),
+            ("ot=r:2", SamplingDecision::RecordAndSample, "ot=p:2;r:2"),
+            ("ot=r:1", SamplingDecision::Drop, "ot=r:1"),
+            // the p-value of the parent is replaced, other values are kept
+            ("ot=p:0;r:1;x:y", SamplingDecision::Drop, "ot=r:1;x:y"),
+            (
+                "foo=bar,ot=r:5",
+                SamplingDecision::RecordAndSample,
+                "ot=p:2;r:5,foo=bar",
+            ),
+        ];
+
+        for (incoming, decision, outgoing) in cases {
+            // every service using the same probability reaches the same decision
+            for _ in 0..10 {
+                let result = consistent_sample(&sampler, incoming);
+                assert_eq!(result.decision, decision, "{}", incoming);
+                assert_eq!(result.trace_state.header(), outgoing, "{}", incoming);
+            }
+        }
+    }
+
+    #[test]
+    fn consistent_sampler_downstream_decisions() {
+        let upstream = ConsistentSampler::new(0.5);
+        let same = ConsistentSampler::new(0.5);
+        let higher = ConsistentSampler::new(1.0);
+
+        for _ in 0..100 {
+            let root = upstream.should_sample(
+                None,
+                TraceId::from_u128(1),
+                "root",
+                &SpanKind::Server,
+                &[],
+                &[],
+            );
+            let trace_state = root.trace_state.header();
+            assert!(trace_state.contains("r:"), "{}", trace_state);
+
+            assert_eq!(
+                consistent_sample(&same, &trace_state).decision,
+                root.decision
+            );
+            // a higher probability samples a superset of the traces
+            assert_eq!(
+                consistent_sample(&higher, &trace_state).decision,
+                SamplingDecision::RecordAndSample
+            );
+        }
+    }
+
+    #[test]
+    fn consistent_sampler_replaces_invalid_r_values() {
+        let sampler = ConsistentSampler::new(1.0);
+        for incoming in &["ot=r:63", "ot=r:x", "ot=r", "ot=p:3"] {
+            let result = consistent_sample(&sampler, incoming);
+            assert_eq!(result.decision, SamplingDecision::RecordAndSample);
+            let r_value: u8 = result
+                .trace_state
+                .get("ot")
+                .and_then(|ot| ot.strip_prefix("p:0;r:"))
+                .and_then(|r| r.parse().ok())
+                .unwrap_or_else(|| panic!("{:?}", result.trace_state));
+            assert!(r_value <= MAX_R_VALUE);
+        }
+
+        let never = ConsistentSampler::new(0.0);
+        for r in 0..=MAX_R_VALUE {
+            assert_eq!(
+                consistent_sample(&never, &format!("ot=r:{}", r)).decision,
+                SamplingDecision::Drop
+            );
+        }
+    }
+
+    #[test]
+    fn consistent_sampler_probability() {
+        let total = 10_000;
+        for probability in &[0.5, 0.3, 0.1] {
+            let sampler = ConsistentSampler::new(*probability);
+            let sampled = (0..total)
+                .filter(|_| {
+                    sampler
+                        .should_sample(
+                            None,
+                            TraceId::from_u128(1),
+                            "root",
+                            &SpanKind::Server,
+                            &[],
+                            &[],
+                        )
+                        .decision
+                        == SamplingDecision::RecordAndSample
+                })
+                .count();
+            let ratio = sampled as f64 / total as f64;
+            assert!(
+                (ratio - probability).abs() < 0.03,
+                "expected {}, sampled {}",
+                probability,
+                ratio
+            );
+        }
+    }
+
+    /// Samples the first span it sees and drops all others, counting its calls.
+    #[derive(Debug)]
+    struct FirstSpanSampler(Arc<AtomicUsize>);
+
+    impl ShouldSample for FirstSpanSampler {
+        fn should_sample(
+            &self,
+            parent_context: Option<&Context>,
+            _trace_id: TraceId,
+            _name: &str,
+            _span_kind: &SpanKind,
+            _attributes: &[KeyValue],
+            _links: &[Link],
+        ) -> SamplingResult {
+            let decision = if self.0.fetch_add(1, Ordering::SeqCst) == 0 {
+                SamplingDecision::RecordAndSample
+            } else {
+                SamplingDecision::Drop
+            };
+            SamplingResult {
+                decision,
+                attributes: vec![KeyValue::new("sampler", "first")],
+                trace_state: parent_trace_state(parent_context),
+            }
+        }
+    }
+
+    fn cached_sample(
+        sampler: &CachedSampler,
+        parent_context: Option<&Context>,
+        trace_id: u128,
+    ) -> SamplingResult {
+        sampler.should_sample(
+            parent_context,
+            TraceId::from_u128(trace_id),
+            "span",
+            &SpanKind::Internal,
+            &[],
+            &[],
+        )
+    }
+
+    #[test]
+    fn cached_sampler_reuses_root_decisions() {
+        let calls = Arc::new(AtomicUsize::new(0));
+        let sampler = CachedSampler::new(FirstSpanSampler(calls.clone()), 10);
+
+        // the root decision is reused by the local children of the trace
+        let root = cached_sample(&sampler, None, 1);
+        assert_eq!(root.decision, SamplingDecision::RecordAndSample);
+        let child = cached_sample(&sampler, Some(&parent_context(false, true)), 1);
+        assert_eq!(child.decision, SamplingDecision::RecordAndSample);
+        assert_eq!(child.attributes, root.attributes);
+        assert_eq!(calls.load(Ordering::SeqCst), 1);
+
+        // decisions of spans with a local parent are not cached
+        let cx = Context::current_with_span(TestSpan(SpanContext::new(
+            TraceId::from_u128(2),
+            SpanId::from_u64(1),
+            0,
+            false,
+            TraceState::default(),
+        )));
+        cached_sample(&sampler, Some(&cx), 2);
+        cached_sample(&sampler, Some(&cx), 2);
+        assert_eq!(calls.load(Ordering::SeqCst), 3);
+
+        // spans with a remote parent are the root of the trace in this service
+        let cx = Context::current_with_span(TestSpan(SpanContext::new(
+            TraceId::from_u128(3),
+            SpanId::from_u64(1),
+            0,
+            true,
+            TraceState::from_key_value(vec![("foo", "bar")]).unwrap(),
+        )));
+        assert_eq!(
+            cached_sample(&sampler, Some(&cx), 3).decision,
+            SamplingDecision::Drop
+        );
+        let child = cached_sample(&sampler, Some(&cx), 3);
+        assert_eq!(child.decision, SamplingDecision::Drop);
+        assert_eq!(child.trace_state.get("foo"), Some("bar"));
+        assert_eq!(calls.load(Ordering::SeqCst), 4);
+    }
+
+    #[test]
+    fn cached_sampler_evicts_traces() {
+        let sampler = CachedSampler::new(Sampler::AlwaysOn, 2);
+        cached_sample(&sampler, None, 1);
+        cached_sample(&sampler, None, 2);
+        // trace 1 becomes the most recently used, trace 2 is evicted
+        cached_sample(&sampler, None, 1);
+        cached_sample(&sampler, None, 3);
+        let mut cached: Vec<_> = sampler
+            .cache
+            .lock()
+            .unwrap()
+            .entries
+            .keys()
+            .cloned()
+            .collect();
+        cached.sort_by_key(|trace_id| trace_id.to_u128());
+        assert_eq!(cached, vec![TraceId::from_u128(1), TraceId::from_u128(3)]);
+
+        // idle traces are evicted
+        let clock = ManualClock::new(SystemTime::UNIX_EPOCH);
+        let calls = Arc::new(AtomicUsize::new(0));
+        let sampler = CachedSampler::new(FirstSpanSampler(calls.clone()), 2)
+            .with_idle_timeout(Duration::from_secs(10))
+            .with_clock(clock.clone());
+        cached_sample(&sampler, None, 1);
+        clock.advance(Duration::from_secs(5));
+        cached_sample(&sampler, None, 1);
+        assert_eq!(calls.load(Ordering::SeqCst), 1);
+        // idle for 11 seconds since its last use
+        clock.advance(Duration::from_secs(11));
+        assert_eq!(
+            cached_sample(&sampler, None, 1).decision,
+            SamplingDecision::Drop
+        );
+        assert_eq!(calls.load(Ordering::SeqCst), 2);
+
+        // a capacity of zero disables caching
+        let calls = Arc::new(AtomicUsize::new(0));
+        let sampler = CachedSampler::new(FirstSpanSampler(calls.clone()), 0);
+        cached_sample(&sampler, None, 1);
+        cached_sample(&sampler, None, 1);
+        assert_eq!(calls.load(Ordering::SeqCst), 2);
+        let cache = sampler.cache.lock().unwrap();
+        assert!(cache.entries.is_empty());
+        // lookups did not touch the cache either
+        assert_eq!(cache.tick, 0);
+    }
 }
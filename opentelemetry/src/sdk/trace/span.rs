@@ -9,7 +9,7 @@
 //! is possible to change its name, set its `Attributes`, and add `Links` and `Events`.
 //! These cannot be changed after the `Span`'s end time has been set.
 use crate::sdk::trace::SpanLimits;
-use crate::trace::{Event, SpanContext, SpanId, SpanKind, StatusCode};
+use crate::trace::{Event, Link, SpanContext, SpanId, SpanKind, Status};
 use crate::{sdk, trace, KeyValue};
 use std::borrow::Cow;
 use std::sync::Arc;
@@ -42,10 +42,8 @@ pub(crate) struct SpanData {
     pub(crate) events: sdk::trace::EvictedQueue<trace::Event>,
     /// Span Links
     pub(crate) links: sdk::trace::EvictedQueue<trace::Link>,
-    /// Span status code
-    pub(crate) status_code: StatusCode,
-    /// Span status message
-    pub(crate) status_message: Cow<'static, str>,
+    /// Span status
+    pub(crate) status: Status,
 }
 
 impl Span {
@@ -63,6 +61,14 @@ impl Span {
         }
     }
 
+    /// The current time of the clock configured on the provider.
+    fn now(&self) -> SystemTime {
+        self.tracer
+            .provider()
+            .map(|provider| provider.config().clock.now())
+            .unwrap_or_else(crate::time::now)
+    }
+
     /// Operate on a mutable reference to span data
     fn with_data<T, F>(&mut self, f: F) -> Option<T>
     where
@@ -73,6 +79,15 @@ impl Span {
 }
 
 impl crate::trace::Span for Span {
+    /// Records events in the context of a given `Span`, timestamped by the
+    /// configured clock.
+    fn add_event(&mut self, name: String, attributes: Vec<KeyValue>) {
+        if self.is_recording() {
+            let timestamp = self.now();
+            self.add_event_with_timestamp(name, timestamp, attributes)
+        }
+    }
+
     /// Records events at a specific time in the context of a given `Span`.
     ///
     /// Note that the OpenTelemetry project documents certain ["standard event names and
@@ -84,10 +99,17 @@ impl crate::trace::Span for Span {
         timestamp: SystemTime,
         mut attributes: Vec<KeyValue>,
     ) {
-        let event_attributes_limit = self.span_limits.max_attributes_per_event as usize;
+        let span_limits = self.span_limits;
+        let event_attributes_limit = span_limits.max_attributes_per_event as usize;
         self.with_data(|data| {
             let dropped_attributes_count = attributes.len().saturating_sub(event_attributes_limit);
             attributes.truncate(event_attributes_limit);
+            for attribute in attributes.iter_mut() {
+                span_limits.truncate_attribute_value(attribute);
+            }
+
+            let mut name = Cow::from(name);
+            span_limits.truncate_event_name(&mut name);
 
             data.events.push_back(Event::new(
                 name,
@@ -115,31 +137,76 @@ impl crate::trace::Span for Span {
     /// Note that the OpenTelemetry project documents certain ["standard
     /// attributes"](https://github.com/open-telemetry/opentelemetry-specification/tree/v0.5.0/specification/trace/semantic_conventions/README.md)
     /// that have prescribed semantic meanings.
-    fn set_attribute(&mut self, attribute: KeyValue) {
+    ///
+    /// String values, including the elements of string arrays, longer than the
+    /// configured `max_attribute_value_length` are truncated, which is counted in
+    /// the `truncated_count` of the span attributes.
+    fn set_attribute(&mut self, mut attribute: KeyValue) {
+        let span_limits = self.span_limits;
         self.with_data(|data| {
+            if span_limits.truncate_attribute_value(&mut attribute) {
+                data.attributes.record_truncated();
+            }
             data.attributes.insert(attribute);
         });
     }
 
-    /// Sets the status of the `Span`. If used, this will override the default `Span`
-    /// status, which is `Unset`. `message` MUST be ignored when the status is `OK` or `Unset`
-    fn set_status(&mut self, code: StatusCode, message: String) {
+    /// Adds a `Link` to another `Span`, links with an invalid span context are ignored.
+    ///
+    /// The link attributes and the number of links are limited by the configured
+    /// `SpanLimits`, exceeding them increases the dropped counts.
+    fn add_link(&mut self, mut link: Link) {
+        if !link.span_context().is_valid() {
+            return;
+        }
+
+        let span_limits = self.span_limits;
+        let link_attributes_limit = span_limits.max_attributes_per_link as usize;
         self.with_data(|data| {
-            if code == StatusCode::Error {
-                data.status_message = message.into();
+            let dropped_attributes_count =
+                link.attributes.len().saturating_sub(link_attributes_limit);
+            link.attributes.truncate(link_attributes_limit);
+            for attribute in link.attributes.iter_mut() {
+                span_limits.truncate_attribute_value(attribute);
             }
-            data.status_code = code;
+            link.dropped_attributes_count = dropped_attributes_count as u32;
+
+            data.links.push_back(link)
+        });
+    }
+
+    /// Sets the status of the `Span`. If used, this will override the default `Span`
+    /// status, which is `Unset`.
+    ///
+    /// `Unset` never overrides a previously set status, and an `Ok` status is final.
+    fn set_status(&mut self, status: Status) {
+        self.with_data(|data| match (&data.status, &status) {
+            (_, Status::Unset) | (Status::Ok, _) => {}
+            _ => data.status = status,
         });
     }
 
     /// Updates the `Span`'s name.
-    fn update_name(&mut self, new_name: String) {
+    fn update_name<T>(&mut self, new_name: T)
+    where
+        T: Into<Cow<'static, str>>,
+    {
         self.with_data(|data| {
             data.name = new_name.into();
         });
     }
 
+    /// Finishes the span, the end time is read from the configured clock.
+    fn end(&mut self) {
+        if self.is_recording() {
+            let timestamp = self.now();
+            self.end_with_timestamp(timestamp);
+        }
+    }
+
     /// Finishes the span with given timestamp.
+    ///
+    /// Timestamps before the start time of the span are clamped to the start time.
     fn end_with_timestamp(&mut self, timestamp: SystemTime) {
         self.ensure_ended_and_exported(Some(timestamp));
     }
@@ -152,7 +219,11 @@ impl Span {
             if let Some(timestamp) = timestamp {
                 data.end_time = timestamp;
             } else if data.end_time == data.start_time {
-                data.end_time = crate::time::now();
+                data.end_time = self.now();
+            }
+            // Spans can not end before they started
+            if data.end_time < data.start_time {
+                data.end_time = data.start_time;
             }
 
             // Notify each span processor that the span has ended
@@ -206,21 +277,21 @@ fn build_export_data(
         attributes: data.attributes,
         events: data.events,
         links: data.links,
-        status_code: data.status_code,
-        status_message: data.status_message,
+        status: data.status,
         resource,
-        instrumentation_lib: *tracer.instrumentation_library(),
+        instrumentation_lib: tracer.instrumentation_library().clone(),
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::sdk::trace::span_limit::DEFAULT_MAX_LINKS_PER_SPAN;
     use crate::sdk::trace::span_limit::{
         DEFAULT_MAX_ATTRIBUTES_PER_EVENT, DEFAULT_MAX_ATTRIBUTES_PER_LINK,
     };
     use crate::trace::{Link, NoopSpanExporter, TraceId, Tracer};
-    use crate::{core::KeyValue, trace::Span as _, trace::TracerProvider};
+    use crate::{core::KeyValue, trace::Span as _, trace::TracerProvider, Array, Key, Value};
     use std::time::Duration;
 
     fn init() -> (sdk::trace::Tracer, SpanData) {
@@ -239,8 +310,7 @@ mod tests {
             ),
             events: sdk::trace::EvictedQueue::new(config.span_limits.max_events_per_span),
             links: sdk::trace::EvictedQueue::new(config.span_limits.max_links_per_span),
-            status_code: StatusCode::Unset,
-            status_message: "".into(),
+            status: Status::Unset,
         };
         (tracer, data)
     }
@@ -314,10 +384,27 @@ mod tests {
     }
 
     #[test]
-    fn record_exception() {
+    fn builder_events_keep_timestamps() {
+        let provider = sdk::trace::TracerProvider::default();
+        let tracer = provider.get_tracer("opentelemetry-test", None);
+        let enqueued = crate::time::now() - Duration::from_secs(60);
+        let event = trace::Event::new("enqueued", enqueued, Vec::new(), 0);
+
+        let span = tracer
+            .span_builder("process")
+            .with_events(vec![event])
+            .start(&tracer);
+
+        let data = span.data.as_ref().expect("span should be recording");
+        let timestamps: Vec<_> = data.events.iter().map(|event| event.timestamp).collect();
+        assert_eq!(timestamps, vec![enqueued]);
+    }
+
+    #[test]
+    fn record_error() {
         let mut span = create_span();
         let err = std::io::Error::from(std::io::ErrorKind::Other);
-        span.record_exception(&err);
+        span.record_error(&err);
         span.with_data(|data| {
             if let Some(event) = data.events.iter().next() {
                 assert_eq!(event.name, "exception");
@@ -332,11 +419,48 @@ mod tests {
     }
 
     #[test]
-    fn record_exception_with_stacktrace() {
+    fn record_error_with_source() {
+        #[derive(Debug)]
+        struct WrappedError(std::io::Error);
+
+        impl std::fmt::Display for WrappedError {
+            fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                write!(f, "request failed")
+            }
+        }
+
+        impl std::error::Error for WrappedError {
+            fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+                Some(&self.0)
+            }
+        }
+
+        let mut span = create_span();
+        let err = WrappedError(std::io::Error::new(
+            std::io::ErrorKind::Other,
+            "connection reset",
+        ));
+        span.record_error(&err);
+        span.with_data(|data| {
+            let event = data.events.iter().next().expect("no event");
+            assert_eq!(event.name, "exception");
+            assert_eq!(
+                event.attributes,
+                vec![
+                    KeyValue::new("exception.message", "request failed"),
+                    KeyValue::new("exception.stacktrace", "Caused by: connection reset"),
+                ]
+            );
+            assert_eq!(data.status, Status::Unset);
+        });
+    }
+
+    #[test]
+    fn record_error_with_stacktrace() {
         let mut span = create_span();
         let err = std::io::Error::from(std::io::ErrorKind::Other);
         let stacktrace = "stacktrace...".to_string();
-        span.record_exception_with_stacktrace(&err, stacktrace.clone());
+        span.record_error_with_stacktrace(&err, stacktrace.clone());
         span.with_data(|data| {
             if let Some(event) = data.events.iter().next() {
                 assert_eq!(event.name, "exception");
@@ -353,6 +477,38 @@ mod tests {
         });
     }
 
+    #[test]
+    #[allow(deprecated)]
+    fn record_exception_forwards_to_record_error() {
+        let mut span = create_span();
+        let err = std::io::Error::from(std::io::ErrorKind::Other);
+        span.record_exception(&err);
+        span.record_exception_with_stacktrace(&err, "stacktrace...".to_string());
+        span.with_data(|data| {
+            let events = data
+                .events
+                .iter()
+                .map(|event| (event.name.clone(), event.attributes.clone()))
+                .collect::<Vec<_>>();
+            assert_eq!(
+                events,
+                vec![
+                    (
+                        "exception".into(),
+                        vec![KeyValue::new("exception.message", err.to_string())]
+                    ),
+                    (
+                        "exception".into(),
+                        vec![
+                            KeyValue::new("exception.message", err.to_string()),
+                            KeyValue::new("exception.stacktrace", "stacktrace..."),
+                        ]
+                    ),
+                ]
+            );
+        });
+    }
+
     #[test]
     fn set_attribute() {
         let mut span = create_span();
@@ -371,34 +527,48 @@ mod tests {
     fn set_status() {
         {
             let mut span = create_span();
-            let status = StatusCode::Ok;
-            let message = "OK".to_string();
-            span.set_status(status, message);
-            span.with_data(|data| {
-                assert_eq!(data.status_code, status);
-                assert_eq!(data.status_message, "");
-            });
+            let status = Status::Ok;
+            span.set_status(status.clone());
+            span.with_data(|data| assert_eq!(data.status, status));
         }
         {
             let mut span = create_span();
-            let status = StatusCode::Unset;
-            let message = "OK".to_string();
-            span.set_status(status, message);
-            span.with_data(|data| {
-                assert_eq!(data.status_code, status);
-                assert_eq!(data.status_message, "");
-            });
+            let status = Status::Unset;
+            span.set_status(status.clone());
+            span.with_data(|data| assert_eq!(data.status, status));
         }
         {
             let mut span = create_span();
-            let status = StatusCode::Error;
-            let message = "Error".to_string();
-            span.set_status(status, message);
+            let status = Status::error("Error");
+            span.set_status(status.clone());
             span.with_data(|data| {
-                assert_eq!(data.status_code, status);
-                assert_eq!(data.status_message, "Error");
+                assert_eq!(data.status, status);
+                assert_eq!(data.status.description(), "Error");
             });
         }
+        {
+            let mut span = create_span();
+            span.set_status(Status::error("first"));
+            span.set_status(Status::error("second"));
+            span.with_data(|data| assert_eq!(data.status, Status::error("second")));
+        }
+    }
+
+    #[test]
+    fn set_status_unset_does_not_override() {
+        let mut span = create_span();
+        span.set_status(Status::error("Error"));
+        span.set_status(Status::Unset);
+        span.with_data(|data| assert_eq!(data.status, Status::error("Error")));
+    }
+
+    #[test]
+    fn set_status_ok_is_final() {
+        let mut span = create_span();
+        span.set_status(Status::error("Error"));
+        span.set_status(Status::Ok);
+        span.set_status(Status::error("Error after ok"));
+        span.with_data(|data| assert_eq!(data.status, Status::Ok));
     }
 
     #[test]
@@ -411,6 +581,73 @@ mod tests {
         });
     }
 
+    #[test]
+    fn add_link() {
+        let mut span = create_span();
+        let link_context = SpanContext::new(
+            TraceId::from_u128(12),
+            SpanId::from_u64(12),
+            0,
+            false,
+            Default::default(),
+        );
+        let attributes = vec![KeyValue::new("k", "v")];
+        span.add_link(Link::new(link_context.clone(), attributes.clone()));
+        // links to invalid span contexts are ignored
+        span.add_link(Link::new(SpanContext::empty_context(), Vec::new()));
+        span.with_data(|data| {
+            let links: Vec<_> = data.links.iter().collect();
+            assert_eq!(links.len(), 1);
+            assert_eq!(links[0].span_context(), &link_context);
+            assert_eq!(links[0].attributes(), &attributes);
+        });
+    }
+
+    #[test]
+    fn exceed_links_limit_after_start() {
+        let exporter = NoopSpanExporter::new();
+        let provider_builder = sdk::trace::TracerProvider::builder().with_simple_exporter(exporter);
+        let provider = provider_builder.build();
+        let tracer = provider.get_tracer("opentelemetry-test", None);
+        let mut span = tracer.start("test");
+
+        let total = DEFAULT_MAX_LINKS_PER_SPAN + 2;
+        for i in 0..total {
+            let mut link = Link::new(
+                SpanContext::new(
+                    TraceId::from_u128(12),
+                    SpanId::from_u64(u64::from(i) + 1),
+                    0,
+                    false,
+                    Default::default(),
+                ),
+                Vec::new(),
+            );
+            for j in 0..(DEFAULT_MAX_ATTRIBUTES_PER_LINK * 2) {
+                link.attributes
+                    .push(KeyValue::new(format!("key {}", j), j.to_string()));
+            }
+            span.add_link(link);
+        }
+
+        let link_queue = span
+            .data
+            .clone()
+            .expect("span data should not be empty as we already set it before")
+            .links;
+        assert_eq!(link_queue.len(), DEFAULT_MAX_LINKS_PER_SPAN as usize);
+        assert_eq!(link_queue.dropped_count(), 2);
+        let processed_link = link_queue.iter().next().expect("should have links");
+        assert_eq!(
+            processed_link.attributes().len(),
+            DEFAULT_MAX_ATTRIBUTES_PER_LINK as usize
+        );
+        assert_eq!(
+            processed_link.dropped_attributes_count(),
+            DEFAULT_MAX_ATTRIBUTES_PER_LINK
+        );
+    }
+
     #[test]
     fn end() {
         let mut span = create_span();
@@ -453,16 +690,26 @@ mod tests {
             vec![KeyValue::new("k", "v")],
         );
         let err = std::io::Error::from(std::io::ErrorKind::Other);
-        span.record_exception(&err);
-        span.record_exception_with_stacktrace(&err, "stacktrace...".to_string());
+        span.record_error(&err);
+        span.record_error_with_stacktrace(&err, "stacktrace...".to_string());
         span.set_attribute(KeyValue::new("k", "v"));
-        span.set_status(StatusCode::Error, "ERROR".to_string());
+        span.set_status(Status::error("ERROR"));
         span.update_name("new_name".to_string());
+        span.add_link(Link::new(
+            SpanContext::new(
+                TraceId::from_u128(1),
+                SpanId::from_u64(1),
+                0,
+                false,
+                Default::default(),
+            ),
+            Vec::new(),
+        ));
         span.with_data(|data| {
             assert_eq!(data.events, initial.events);
+            assert_eq!(data.links, initial.links);
             assert_eq!(data.attributes, initial.attributes);
-            assert_eq!(data.status_code, initial.status_code);
-            assert_eq!(data.status_message, initial.status_message);
+            assert_eq!(data.status, initial.status);
             assert_eq!(data.name, initial.name);
         });
     }
@@ -512,6 +759,285 @@ mod tests {
         let processed_event_2 = event_vec.get(1).expect("should have at least two events");
         assert_eq!(processed_event_1.attributes.len(), 128);
         assert_eq!(processed_event_2.attributes.len(), 128);
+        assert_eq!(processed_event_1.dropped_attributes_count, 128);
+        assert_eq!(processed_event_2.dropped_attributes_count, 128);
+    }
+
+    #[test]
+    fn event_attributes_keep_insertion_order() {
+        let provider = sdk::trace::TracerProvider::builder()
+            .with_config(sdk::trace::config().with_max_attributes_per_event(2))
+            .build();
+        let tracer = provider.get_tracer("opentelemetry-test", None);
+        let attributes = vec![
+            KeyValue::new("first", 1),
+            KeyValue::new("second", 2),
+            KeyValue::new("third", 3),
+        ];
+
+        let mut span = tracer
+            .span_builder("test")
+            .with_events(vec![Event::new(
+                "builder event",
+                crate::time::now(),
+                attributes.clone(),
+                0,
+            )])
+            .start(&tracer);
+        span.add_event("added event".to_string(), attributes.clone());
+
+        let data = span.data.clone().expect("span data should not be empty");
+        assert_eq!(data.events.len(), 2);
+        for event in data.events.iter() {
+            assert_eq!(event.attributes, attributes[..2].to_vec(), "{}", event.name);
+            assert_eq!(event.dropped_attributes_count, 1, "{}", event.name);
+        }
+    }
+
+    #[test]
+    fn exceed_span_attributes_limit() {
+        let exporter = NoopSpanExporter::new();
+        let provider_builder = sdk::trace::TracerProvider::builder()
+            .with_simple_exporter(exporter)
+            .with_config(sdk::trace::config().with_max_attributes_per_span(2));
+        let provider = provider_builder.build();
+        let tracer = provider.get_tracer("opentelemetry-test", None);
+
+        let mut span = tracer.start("test");
+        span.set_attribute(KeyValue::new("k1", "v1"));
+        span.set_attribute(KeyValue::new("k2", "v2"));
+        span.set_attribute(KeyValue::new("k3", "v3"));
+        // updating a present key does not drop another attribute
+        span.set_attribute(KeyValue::new("k3", "updated"));
+
+        let attributes = span
+            .data
+            .clone()
+            .expect("span data should not be empty as we already set it before")
+            .attributes;
+        assert_eq!(attributes.len(), 2);
+        assert_eq!(attributes.dropped_count(), 1);
+        assert_eq!(
+            attributes.get(&Key::new("k3")),
+            Some(&Value::from("updated"))
+        );
+        assert_eq!(attributes.get(&Key::new("k1")), None);
+    }
+
+    #[test]
+    fn exceed_attribute_value_length_limit() {
+        let provider = sdk::trace::TracerProvider::builder()
+            .with_span_limits(sdk::trace::SpanLimits {
+                max_attribute_value_length: Some(4),
+                ..Default::default()
+            })
+            .build();
+        let tracer = provider.get_tracer("opentelemetry-test", None);
+
+        let mut span = tracer
+            .span_builder("test")
+            .with_attributes(vec![KeyValue::new("builder", "from builder")])
+            .start(&tracer);
+        span.set_attribute(KeyValue::new("long", "too long"));
+        span.set_attribute(KeyValue::new("short", "ok"));
+        span.set_attribute(KeyValue::new("number", 123_456));
+        span.add_event("event".to_string(), vec![KeyValue::new("k", "event value")]);
+
+        let data = span.data.clone().expect("span data should not be empty");
+        assert_eq!(
+            data.attributes.get(&Key::new("builder")),
+            Some(&Value::from("from"))
+        );
+        assert_eq!(
+            data.attributes.get(&Key::new("long")),
+            Some(&Value::from("too "))
+        );
+        assert_eq!(
+            data.attributes.get(&Key::new("short")),
+            Some(&Value::from("ok"))
+        );
+        assert_eq!(
+            data.attributes.get(&Key::new("number")),
+            Some(&Value::from(123_456))
+        );
+        assert_eq!(data.attributes.truncated_count(), 2);
+        let event = data.events.iter().next().expect("event recorded");
+        assert_eq!(event.attributes[0].value, Value::from("even"));
+    }
+
+    #[test]
+    #[cfg(feature = "testing")]
+    fn manual_clock_timestamps() {
+        let start = SystemTime::UNIX_EPOCH + Duration::from_secs(1_000);
+        let clock = sdk::trace::ManualClock::new(start);
+        let (exporter, rx_export, _rx_shutdown) = crate::testing::trace::new_test_exporter();
+        let provider = sdk::trace::TracerProvider::builder()
+            .with_simple_exporter(exporter)
+            .with_clock(clock.clone())
+            .build();
+        let tracer = provider.get_tracer("opentelemetry-test", None);
+
+        let mut span = tracer.start("timed");
+        clock.advance(Duration::from_millis(10));
+        span.add_event("halfway".to_string(), vec![]);
+        clock.advance(Duration::from_millis(15));
+        span.end();
+
+        let exported = rx_export.recv().expect("span should be exported");
+        assert_eq!(exported.start_time, start);
+        assert_eq!(
+            exported
+                .end_time
+                .duration_since(exported.start_time)
+                .unwrap(),
+            Duration::from_millis(25)
+        );
+        let event = exported.events.iter().next().expect("event recorded");
+        assert_eq!(event.timestamp, start + Duration::from_millis(10));
+
+        // spans ended on drop use the clock as well
+        let span = tracer.start("dropped");
+        clock.advance(Duration::from_secs(1));
+        drop(span);
+        let exported = rx_export.recv().expect("span should be exported");
+        assert_eq!(
+            exported
+                .end_time
+                .duration_since(exported.start_time)
+                .unwrap(),
+            Duration::from_secs(1)
+        );
+    }
+
+    #[test]
+    fn truncate_event_names() {
+        for (max_event_name_length, expected) in vec![
+            (None, "a very long event name"),
+            (Some(0), "a very long event name"),
+            (Some(6), "a very"),
+        ] {
+            let provider = sdk::trace::TracerProvider::builder()
+                .with_config(
+                    sdk::trace::config().with_span_limits(sdk::trace::SpanLimits {
+                        max_event_name_length,
+                        ..Default::default()
+                    }),
+                )
+                .build();
+            let tracer = provider.get_tracer("opentelemetry-test", None);
+
+            let mut span = tracer
+                .span_builder("test")
+                .with_events(vec![Event::with_name("a very long event name")])
+                .start(&tracer);
+            span.add_event("a very long event name".to_string(), vec![]);
+
+            let data = span.data.clone().expect("span data should not be empty");
+            let names = data
+                .events
+                .iter()
+                .map(|event| event.name.as_ref())
+                .collect::<Vec<_>>();
+            assert_eq!(
+                names,
+                vec![expected, expected],
+                "{:?}",
+                max_event_name_length
+            );
+        }
+    }
+
+    #[test]
+    fn truncate_string_array_attribute_values() {
+        let provider = sdk::trace::TracerProvider::builder()
+            .with_span_limits(sdk::trace::SpanLimits {
+                max_attribute_value_length: Some(3),
+                ..Default::default()
+            })
+            .build();
+        let tracer = provider.get_tracer("opentelemetry-test", None);
+
+        let mut span = tracer.start("test");
+        span.set_attribute(KeyValue::new(
+            "strings",
+            Value::Array(Array::String(vec![
+                "a".into(),
+                "abcdef".into(),
+                "xyz".into(),
+            ])),
+        ));
+        span.set_attribute(KeyValue::new(
+            "numbers",
+            Value::Array(vec![123_456_i64, 7].into()),
+        ));
+        span.set_attribute(KeyValue::new("short", "ok"));
+
+        let data = span.data.clone().expect("span data should not be empty");
+        assert_eq!(
+            data.attributes.get(&Key::new("strings")),
+            Some(&Value::Array(Array::String(vec![
+                "a".into(),
+                "abc".into(),
+                "xyz".into()
+            ])))
+        );
+        assert_eq!(
+            data.attributes.get(&Key::new("numbers")),
+            Some(&Value::Array(vec![123_456_i64, 7].into()))
+        );
+        assert_eq!(data.attributes.truncated_count(), 1);
+    }
+
+    #[test]
+    fn no_truncation_without_attribute_value_length_limit() {
+        let long_value = "x".repeat(10_000);
+        let provider = sdk::trace::TracerProvider::default();
+        let tracer = provider.get_tracer("test", None);
+        let mut span = tracer.start("test");
+        span.set_attribute(KeyValue::new("long", long_value.clone()));
+        span.set_attribute(KeyValue::new(
+            "strings",
+            Value::Array(Array::String(vec![long_value.clone().into()])),
+        ));
+
+        let data = span.data.clone().expect("span data should not be empty");
+        assert_eq!(
+            data.attributes.get(&Key::new("long")),
+            Some(&Value::from(long_value))
+        );
+        assert_eq!(data.attributes.truncated_count(), 0);
+    }
+
+    #[test]
+    fn exceed_span_events_limit() {
+        let exporter = NoopSpanExporter::new();
+        let provider_builder = sdk::trace::TracerProvider::builder()
+            .with_simple_exporter(exporter)
+            .with_config(sdk::trace::config().with_max_events_per_span(2));
+        let provider = provider_builder.build();
+        let tracer = provider.get_tracer("opentelemetry-test", None);
+
+        let span_builder = tracer.span_builder("test").with_events(vec![
+            Event::with_name("event 1"),
+            Event::with_name("event 2"),
+        ]);
+        let mut span = tracer.build(span_builder);
+        for i in 3..=5 {
+            span.add_event(format!("event {}", i), Vec::new());
+        }
+
+        let event_queue = span
+            .data
+            .clone()
+            .expect("span data should not be empty as we already set it before")
+            .events;
+        assert_eq!(event_queue.len(), 2);
+        assert_eq!(event_queue.dropped_count(), 3);
+        let names: Vec<_> = event_queue
+            .iter()
+            .map(|event| event.name.as_ref())
+            .collect();
+        assert_eq!(names, vec!["event 4", "event 5"]);
     }
 
     #[test]
@@ -545,6 +1071,13 @@ mod tests {
             .links;
         let link_vec: Vec<_> = link_queue.iter().collect();
         let processed_link = link_vec.get(0).expect("should have at least one link");
-        assert_eq!(processed_link.attributes().len(), 128);
+        assert_eq!(
+            processed_link.attributes().len(),
+            DEFAULT_MAX_ATTRIBUTES_PER_LINK as usize
+        );
+        assert_eq!(
+            processed_link.dropped_attributes_count(),
+            DEFAULT_MAX_ATTRIBUTES_PER_LINK
+        );
     }
 }
@@ -1,17 +1,27 @@
-/// # Span limit
-/// Erroneous code can add unintended attributes, events, and links to a span. If these collections
-/// are unbounded, they can quickly exhaust available memory, resulting in crashes that are
-/// difficult to recover from safely.
-///
-/// To protected against those errors. Users can use span limit to configure
-///  - Maximum allowed span attribute count
-///  - Maximum allowed span event count
-///  - Maximum allowed span link count
-///  - Maximum allowed attribute per span event count
-///  - Maximum allowed attribute per span link count
-///
-/// If the limit has been breached. The attributes, events or links will be dropped based on their
-/// index in the collection. The one added to collections later will be dropped first.
+//! # Span limit
+//! Erroneous code can add unintended attributes, events, and links to a span. If these collections
+//! are unbounded, they can quickly exhaust available memory, resulting in crashes that are
+//! difficult to recover from safely.
+//!
+//! To protected against those errors. Users can use span limit to configure
+//!  - Maximum allowed span attribute count
+//!  - Maximum allowed span event count
+//!  - Maximum allowed span link count
+//!  - Maximum allowed attribute per span event count
+//!  - Maximum allowed attribute per span link count
+//!  - Maximum allowed length of string attribute values
+//!  - Maximum allowed length of event names
+//!
+//! If the limit has been breached. The attributes, events or links will be dropped based on their
+//! index in the collection. The one added to collections earlier will be dropped first, and the
+//! number of dropped items is reported on the exported span. String attribute values, including
+//! the elements of string arrays, that are longer than the allowed length are truncated. Only the
+//! truncated values of span attributes are counted, see [`EvictedHashMap::truncated_count`].
+//!
+//! [`EvictedHashMap::truncated_count`]: crate::sdk::trace::EvictedHashMap::truncated_count
+use crate::sdk::env::parse_env;
+use crate::{Array, KeyValue, Value};
+use std::borrow::Cow;
 
 pub(crate) const DEFAULT_MAX_EVENT_PER_SPAN: u32 = 128;
 pub(crate) const DEFAULT_MAX_ATTRIBUTES_PER_SPAN: u32 = 128;
@@ -32,6 +42,12 @@ pub struct SpanLimits {
     pub max_attributes_per_event: u32,
     /// The max attributes that can be added into a `Link`
     pub max_attributes_per_link: u32,
+    /// The max length, in characters, of string attribute values of a `Span`,
+    /// its events and links. String values are not truncated if `None`.
+    pub max_attribute_value_length: Option<u32>,
+    /// The max length, in characters, of the names of `Event`s, longer names
+    /// are truncated. Event names are not truncated if `None` or `Some(0)`.
+    pub max_event_name_length: Option<u32>,
 }
 
 impl Default for SpanLimits {
@@ -42,6 +58,141 @@ impl Default for SpanLimits {
             max_links_per_span: DEFAULT_MAX_LINKS_PER_SPAN,
             max_attributes_per_link: DEFAULT_MAX_ATTRIBUTES_PER_LINK,
             max_attributes_per_event: DEFAULT_MAX_ATTRIBUTES_PER_EVENT,
+            max_attribute_value_length: None,
+            max_event_name_length: None,
         }
     }
 }
+
+impl SpanLimits {
+    /// Create span limits from the `OTEL_SPAN_*_LIMIT`, `OTEL_EVENT_*_LIMIT`
+    /// and `OTEL_LINK_*_LIMIT` environment variables.
+    ///
+    /// Limits that are not set in the environment use their default value,
+    /// malformed values are reported through the global error handler and
+    /// ignored.
+    pub fn from_env() -> Self {
+        let mut limits = SpanLimits::default();
+
+        if let Some(max_attributes_per_span) = parse_env(&["OTEL_SPAN_ATTRIBUTE_COUNT_LIMIT"]) {
+            limits.max_attributes_per_span = max_attributes_per_span;
+        }
+        if let Some(max_events_per_span) = parse_env(&["OTEL_SPAN_EVENT_COUNT_LIMIT"]) {
+            limits.max_events_per_span = max_events_per_span;
+        }
+        if let Some(max_links_per_span) = parse_env(&["OTEL_SPAN_LINK_COUNT_LIMIT"]) {
+            limits.max_links_per_span = max_links_per_span;
+        }
+        if let Some(max_attributes_per_event) = parse_env(&["OTEL_EVENT_ATTRIBUTE_COUNT_LIMIT"]) {
+            limits.max_attributes_per_event = max_attributes_per_event;
+        }
+        if let Some(max_attributes_per_link) = parse_env(&["OTEL_LINK_ATTRIBUTE_COUNT_LIMIT"]) {
+            limits.max_attributes_per_link = max_attributes_per_link;
+        }
+        if let Some(max_attribute_value_length) =
+            parse_env(&["OTEL_SPAN_ATTRIBUTE_VALUE_LENGTH_LIMIT"])
+        {
+            limits.max_attribute_value_length = Some(max_attribute_value_length);
+        }
+
+        limits
+    }
+
+    /// Truncate the string values of `attribute` to `max_attribute_value_length`.
+    ///
+    /// Returns `true` if the value was truncated.
+    pub(crate) fn truncate_attribute_value(&self, attribute: &mut KeyValue) -> bool {
+        let limit = match self.max_attribute_value_length {
+            Some(limit) => limit as usize,
+            None => return false,
+        };
+
+        match &mut attribute.value {
+            Value::String(value) => truncate_str(value, limit),
+            Value::Array(Array::String(values)) => {
+                let mut truncated = false;
+                for value in values.iter_mut() {
+                    truncated |= truncate_str(value, limit);
+                }
+                truncated
+            }
+            _ => false,
+        }
+    }
+
+    /// Truncate an event name to `max_event_name_length`.
+    pub(crate) fn truncate_event_name(&self, name: &mut Cow<'static, str>) {
+        if let Some(limit) = self.max_event_name_length.filter(|limit| *limit > 0) {
+            truncate_str(name, limit as usize);
+        }
+    }
+}
+
+fn truncate_str(value: &mut Cow<'static, str>, limit: usize) -> bool {
+    let end = match value.char_indices().nth(limit) {
+        Some((end, _)) => end,
+        None => return false,
+    };
+
+    match value {
+        Cow::Borrowed(borrowed) => {
+            let borrowed: &'static str = borrowed;
+            *value = Cow::Borrowed(&borrowed[..end]);
+        }
+        Cow::Owned(owned) => owned.truncate(end),
+    }
+
+    true
+}
+
+#[cfg(test)]
+mod tests {
+    use super::SpanLimits;
+    use crate::sdk::env::EnvGuard;
+    use crate::{KeyValue, Value};
+    use std::env;
+
+    #[test]
+    fn test_span_limits_from_env() {
+        let _guard = EnvGuard::new(&[
+            "OTEL_LINK_ATTRIBUTE_COUNT_LIMIT",
+            "OTEL_SPAN_ATTRIBUTE_VALUE_LENGTH_LIMIT",
+            "OTEL_EVENT_ATTRIBUTE_COUNT_LIMIT",
+        ]);
+        env::set_var("OTEL_LINK_ATTRIBUTE_COUNT_LIMIT", "16");
+        env::set_var("OTEL_SPAN_ATTRIBUTE_VALUE_LENGTH_LIMIT", " 1024 ");
+        env::set_var("OTEL_EVENT_ATTRIBUTE_COUNT_LIMIT", "-1");
+        let limits = SpanLimits::from_env();
+
+        assert_eq!(limits.max_attributes_per_link, 16);
+        assert_eq!(limits.max_attribute_value_length, Some(1024));
+        // invalid values fall back to the default
+        assert_eq!(
+            limits.max_attributes_per_event,
+            SpanLimits::default().max_attributes_per_event
+        );
+    }
+
+    #[test]
+    fn test_truncate_attribute_value() {
+        let limits = SpanLimits {
+            max_attribute_value_length: Some(3),
+            ..SpanLimits::default()
+        };
+
+        let mut attribute = KeyValue::new("k", "héllo");
+        assert!(limits.truncate_attribute_value(&mut attribute));
+        assert_eq!(attribute.value, Value::from("hél"));
+
+        let mut attribute = KeyValue::new("k", String::from("abc"));
+        assert!(!limits.truncate_attribute_value(&mut attribute));
+        assert_eq!(attribute.value, Value::from("abc"));
+
+        let mut attribute = KeyValue::new("k", 123_456_789);
+        assert!(!limits.truncate_attribute_value(&mut attribute));
+
+        let mut attribute = KeyValue::new("k", "unlimited");
+        assert!(!SpanLimits::default().truncate_attribute_value(&mut attribute));
+        assert_eq!(attribute.value, Value::from("unlimited"));
+    }
+}
@@ -36,14 +36,35 @@
 
 use crate::global;
 use crate::runtime::Runtime;
+use crate::sdk::env::parse_env;
 use crate::sdk::trace::Span;
 use crate::{
     sdk::export::trace::{ExportResult, SpanData, SpanExporter},
     trace::{TraceError, TraceResult},
     Context,
 };
-use futures::{channel::mpsc, channel::oneshot, executor, future::Either, pin_mut, StreamExt};
-use std::{env, fmt, str::FromStr, sync::Mutex, thread, time::Duration};
+use futures::{
+    channel::mpsc,
+    channel::oneshot,
+    executor,
+    future::{self, BoxFuture, Either},
+    pin_mut,
+    stream::FuturesUnordered,
+    task::ArcWake,
+    Future, FutureExt, StreamExt,
+};
+use rand::Rng;
+use std::{
+    fmt,
+    panic::{self, AssertUnwindSafe},
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc, Mutex,
+    },
+    task::Poll,
+    thread,
+    time::{Duration, Instant},
+};
 
 /// Delay interval between two consecutive exports.
 const OTEL_BSP_SCHEDULE_DELAY: &str = "OTEL_BSP_SCHEDULE_DELAY";
@@ -61,6 +82,8 @@ const OTEL_BSP_MAX_EXPORT_BATCH_SIZE_DEFAULT: usize = 512;
 const OTEL_BSP_EXPORT_TIMEOUT: &str = "OTEL_BSP_EXPORT_TIMEOUT";
 /// Default maximum allowed time to export data.
 const OTEL_BSP_EXPORT_TIMEOUT_DEFAULT: u64 = 30_000;
+/// How often a span blocked by a full queue tries to enter it again.
+const BACKPRESSURE_RETRY_INTERVAL: Duration = Duration::from_millis(1);
 
 /// `SpanProcessor` is an interface which allows hooks for span start and end
 /// method invocations. The span processors are invoked only when is_recording
@@ -69,24 +92,74 @@ pub trait SpanProcessor: Send + Sync + std::fmt::Debug {
     /// `on_start` is called when a `Span` is started.  This method is called
     /// synchronously on the thread that started the span, therefore it should
     /// not block or throw exceptions.
-    fn on_start(&self, span: &Span, cx: &Context);
+    ///
+    /// `cx` is the parent context the span was started with, including any
+    /// baggage it carries, so processors can use it to enrich the span.
+    ///
+    /// The sampling decision is made before `on_start` is called, the span's
+    /// `span_context().is_sampled()` tells sampled spans apart from spans that
+    /// are only recorded.
+    fn on_start(&self, span: &mut Span, cx: &Context);
     /// `on_end` is called after a `Span` is ended (i.e., the end timestamp is
     /// already set). This method is called synchronously within the `Span::end`
     /// API, therefore it should not block or throw an exception.
     fn on_end(&self, span: SpanData);
     /// Force the spans lying in the cache to be exported.
     fn force_flush(&self) -> TraceResult<()>;
+    /// Force the spans lying in the cache to be exported, waiting at most
+    /// `timeout` for the export to finish.
+    ///
+    /// Returns [`TraceError::ExportTimedOut`] if the spans could not be exported
+    /// in time. The default implementation ignores the timeout and calls
+    /// [`force_flush`], which is only suitable for processors that do not block.
+    ///
+    /// [`force_flush`]: SpanProcessor::force_flush
+    fn force_flush_timeout(&self, timeout: Duration) -> TraceResult<()> {
+        let _ = timeout;
+        self.force_flush()
+    }
+    /// Force the spans lying in the cache to be exported without blocking the
+    /// calling thread. The returned future resolves once the export finished.
+    ///
+    /// The default implementation calls [`force_flush`] eagerly and returns a
+    /// ready future, which is only suitable for processors that do not block.
+    ///
+    /// [`force_flush`]: SpanProcessor::force_flush
+    fn force_flush_async(&self) -> BoxFuture<'static, TraceResult<()>> {
+        Box::pin(future::ready(self.force_flush()))
+    }
     /// Shuts down the processor. Called when SDK is shut down. This is an
     /// opportunity for processors to do any cleanup required.
-    fn shutdown(&mut self) -> TraceResult<()>;
+    fn shutdown(&self) -> TraceResult<()>;
 }
 
 /// A [`SpanProcessor`] that exports synchronously when spans are finished.
 ///
+/// Only sampled spans are exported, spans recorded with a
+/// [`SamplingDecision::RecordOnly`] decision are ignored.
+///
+/// [`SamplingDecision::RecordOnly`]: crate::sdk::trace::SamplingDecision::RecordOnly
+///
 /// # Examples
 ///
 /// Note that the simple processor exports synchronously every time a span is
-/// ended. If you find this limiting, consider the batch processor instead.
+/// ended, blocking the thread that ended it until the exporter returns. No
+/// spans are buffered, which makes it a good fit for tests and short-lived
+/// processes like serverless functions. If you find this limiting, consider
+/// the batch processor instead.
+///
+/// Exports taking longer than the export timeout, 30 seconds by default, are
+/// abandoned and reported through the global error handler.
+///
+/// The export is run by blocking the thread ending the span, not by the async
+/// runtime. Exporters sending spans with a client driven by a tokio runtime,
+/// like the [tonic] or [reqwest] clients, can't make progress when spans end
+/// on the thread of a current thread runtime: `on_end` stalls the whole
+/// runtime until the export timeout and every span is dropped. Use a
+/// multi-threaded runtime or the [`BatchSpanProcessor`] with such exporters.
+///
+/// [tonic]: https://github.com/hyperium/tonic
+/// [reqwest]: https://github.com/seanmonstar/reqwest
 ///
 /// ```
 /// use opentelemetry::{trace as apitrace, sdk::trace as sdktrace, global};
@@ -103,49 +176,53 @@ pub trait SpanProcessor: Send + Sync + std::fmt::Debug {
 /// ```
 #[derive(Debug)]
 pub struct SimpleSpanProcessor {
-    sender: crossbeam_channel::Sender<Option<SpanData>>,
-    shutdown: crossbeam_channel::Receiver<()>,
+    exporter: Mutex<Box<dyn SpanExporter>>,
+    export_timeout: Duration,
 }
 
 impl SimpleSpanProcessor {
-    pub(crate) fn new(mut exporter: Box<dyn SpanExporter>) -> Self {
-        let (span_tx, span_rx) = crossbeam_channel::unbounded();
-        let (shutdown_tx, shutdown_rx) = crossbeam_channel::bounded(0);
-
-        let _ = thread::Builder::new()
-            .name("opentelemetry-exporter".to_string())
-            .spawn(move || {
-                while let Ok(Some(span)) = span_rx.recv() {
-                    if let Err(err) = executor::block_on(exporter.export(vec![span])) {
-                        global::handle_error(err);
-                    }
-                }
-
-                exporter.shutdown();
-
-                if let Err(err) = shutdown_tx.send(()) {
-                    global::handle_error(TraceError::from(format!(
-                        "could not send shutdown: {:?}",
-                        err
-                    )));
-                }
-            });
-
+    /// Create a new [`SimpleSpanProcessor`] that exports each finished span
+    /// with the given exporter before `on_end` returns.
+    pub fn new(mut exporter: Box<dyn SpanExporter>) -> Self {
+        let export_timeout = Duration::from_millis(OTEL_BSP_EXPORT_TIMEOUT_DEFAULT);
+        exporter.set_export_timeout(export_timeout);
         SimpleSpanProcessor {
-            sender: span_tx,
-            shutdown: shutdown_rx,
+            exporter: Mutex::new(exporter),
+            export_timeout,
+        }
+    }
+
+    /// Set the maximum duration of a single export.
+    pub fn with_export_timeout(mut self, export_timeout: Duration) -> Self {
+        if let Ok(exporter) = self.exporter.get_mut() {
+            exporter.set_export_timeout(export_timeout);
         }
+        self.export_timeout = export_timeout;
+        self
     }
 }
 
 impl SpanProcessor for SimpleSpanProcessor {
-    fn on_start(&self, _span: &Span, _cx: &Context) {
+    fn on_start(&self, _span: &mut Span, _cx: &Context) {
         // Ignored
     }
 
     fn on_end(&self, span: SpanData) {
-        if let Err(err) = self.sender.send(Some(span)) {
-            global::handle_error(TraceError::from(format!("error processing span {:?}", err)));
+        if !span.span_context.is_sampled() {
+            return;
+        }
+
+        let result = self
+            .exporter
+            .lock()
+            .map_err(|_| TraceError::from("SimpleSpanProcessor mutex poison"))
+            .and_then(|mut exporter| {
+                block_on_timeout(exporter.export(vec![span]), self.export_timeout)
+                    .unwrap_or(Err(TraceError::ExportTimedOut(self.export_timeout)))
+            });
+
+        if let Err(err) = result {
+            global::handle_error(err);
         }
     }
 
@@ -154,16 +231,63 @@ impl SpanProcessor for SimpleSpanProcessor {
         Ok(())
     }
 
-    fn shutdown(&mut self) -> TraceResult<()> {
-        if self.sender.send(None).is_ok() {
-            if let Err(err) = self.shutdown.recv() {
-                global::handle_error(TraceError::from(format!(
-                    "error shutting down span processor: {:?}",
-                    err
-                )))
-            }
+    fn shutdown(&self) -> TraceResult<()> {
+        if let Ok(mut exporter) = self.exporter.lock() {
+            exporter.shutdown();
+            Ok(())
+        } else {
+            Err(TraceError::from(
+                "When shutting down the SimpleSpanProcessor, the exporter's lock has been poisoned",
+            ))
+        }
+    }
+}
+
+/// A [`SpanProcessor`] calling a function with every finished span.
+///
+/// Panics in the function are caught and reported through the global error
+/// handler, so they don't affect other processors.
+pub(crate) struct OnEndProcessor {
+    on_end: Box<dyn Fn(&SpanData) + Send + Sync>,
+}
+
+impl OnEndProcessor {
+    pub(crate) fn new<F>(on_end: F) -> Self
+    where
+        F: Fn(&SpanData) + Send + Sync + 'static,
+    {
+        OnEndProcessor {
+            on_end: Box::new(on_end),
+        }
+    }
+}
+
+impl fmt::Debug for OnEndProcessor {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("OnEndProcessor").finish()
+    }
+}
+
+impl SpanProcessor for OnEndProcessor {
+    fn on_start(&self, _span: &mut Span, _cx: &Context) {
+        // Ignored
+    }
+
+    fn on_end(&self, span: SpanData) {
+        if panic::catch_unwind(AssertUnwindSafe(|| (self.on_end)(&span))).is_err() {
+            global::handle_error(TraceError::from(format!(
+                "on_end callback panicked for span {:?}",
+                span.name
+            )));
         }
+    }
+
+    fn force_flush(&self) -> TraceResult<()> {
+        // Ignored since nothing is buffered.
+        Ok(())
+    }
 
+    fn shutdown(&self) -> TraceResult<()> {
         Ok(())
     }
 }
@@ -171,34 +295,36 @@ impl SpanProcessor for SimpleSpanProcessor {
 /// A [`SpanProcessor`] that asynchronously buffers finished spans and reports
 /// them at a preconfigured interval.
 ///
+/// Only sampled spans are exported, spans recorded with a
+/// [`SamplingDecision::RecordOnly`] decision are ignored.
+///
+/// [`SamplingDecision::RecordOnly`]: crate::sdk::trace::SamplingDecision::RecordOnly
+///
 /// # Examples
 ///
-/// This processor can be configured with an [`executor`] of your choice to
-/// batch and upload spans asynchronously when they end. If you have added a
-/// library like [`tokio`] or [`async-std`], you can pass in their respective
-/// `spawn` and `interval` functions to have batching performed in those
-/// contexts.
+/// This processor can be configured with a [`Runtime`] of your choice to
+/// batch and upload spans asynchronously when they end. Implementations for
+/// [`tokio`] and [`async-std`] are available behind the `rt-tokio`,
+/// `rt-tokio-current-thread` and `rt-async-std` features.
 ///
 /// ```
-/// # #[cfg(feature="tokio")]
+/// # #[cfg(feature="rt-tokio")]
 /// # {
-/// use futures::{stream};
 /// use opentelemetry::{trace as apitrace, sdk::trace as sdktrace, global, runtime};
-/// use std::time::Duration;
 ///
 /// #[tokio::main]
 /// async fn main() {
 ///     // Configure your preferred exporter
 ///     let exporter = apitrace::NoopSpanExporter::new();
 ///
-///     // Then build a batch processor. You can use whichever executor you have available, for
-///     // example if you are using `async-std` instead of `tokio` you can replace the spawn and
-///     // interval functions with `async_std::task::spawn` and `async_std::stream::interval`.
+///     // Then build a batch processor. You can use whichever runtime you have available, for
+///     // example if you are using `async-std` instead of `tokio` you can replace
+///     // `runtime::Tokio` with `runtime::AsyncStd`.
 ///     let batch = sdktrace::BatchSpanProcessor::builder(exporter, runtime::Tokio)
 ///         .with_max_queue_size(4096)
 ///         .build();
 ///
-///     // Then use the `with_batch_exporter` method to have the provider export spans in batches.
+///     // Then use the `with_span_processor` method to have the provider export spans in batches.
 ///     let provider = sdktrace::TracerProvider::builder()
 ///         .with_span_processor(batch)
 ///         .build();
@@ -208,53 +334,84 @@ impl SpanProcessor for SimpleSpanProcessor {
 /// # }
 /// ```
 ///
-/// [`executor`]: https://docs.rs/futures/0.3/futures/executor/index.html
+/// [`Runtime`]: crate::runtime::Runtime
 /// [`tokio`]: https://tokio.rs
 /// [`async-std`]: https://async.rs
 pub struct BatchSpanProcessor {
     message_sender: Mutex<mpsc::Sender<BatchMessage>>,
+    backpressure: BackpressurePolicy,
+    metrics: BatchSpanProcessorMetrics,
 }
 
 impl fmt::Debug for BatchSpanProcessor {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         f.debug_struct("BatchSpanProcessor")
             .field("message_sender", &self.message_sender)
+            .field("backpressure", &self.backpressure)
+            .field("metrics", &self.metrics)
             .finish()
     }
 }
 
 impl SpanProcessor for BatchSpanProcessor {
-    fn on_start(&self, _span: &Span, _cx: &Context) {
+    fn on_start(&self, _span: &mut Span, _cx: &Context) {
         // Ignored
     }
 
     fn on_end(&self, span: SpanData) {
-        let result = self
-            .message_sender
-            .lock()
-            .map_err(|_| TraceError::Other("batch span processor mutex poisoned".into()))
-            .and_then(|mut sender| {
-                sender
-                    .try_send(BatchMessage::ExportSpan(span))
-                    .map_err(|err| TraceError::Other(err.into()))
-            });
+        if !span.span_context.is_sampled() {
+            return;
+        }
 
-        if let Err(err) = result {
-            global::handle_error(err);
+        // counted before sending so the worker never sees a negative queue length
+        self.metrics.0.queue_length.fetch_add(1, Ordering::Relaxed);
+        let result = match self.try_send(BatchMessage::ExportSpan(span)) {
+            Ok(None) => Ok(()),
+            Ok(Some(message)) => self.on_full_queue(message),
+            Err(err) => Err(err),
+        };
+
+        match result {
+            Ok(()) => {
+                self.metrics
+                    .0
+                    .enqueued_spans
+                    .fetch_add(1, Ordering::Relaxed);
+            }
+            Err(err) => {
+                self.metrics.0.queue_length.fetch_sub(1, Ordering::Relaxed);
+                global::handle_error(err);
+            }
         }
     }
 
     fn force_flush(&self) -> TraceResult<()> {
-        let mut sender = self.message_sender.lock().map_err(|_| TraceError::from("When force flushing the BatchSpanProcessor, the message sender's lock has been poisoned"))?;
-        let (res_sender, res_receiver) = oneshot::channel();
-        sender.try_send(BatchMessage::Flush(Some(res_sender)))?;
+        executor::block_on(self.force_flush_async())
+    }
 
-        futures::executor::block_on(res_receiver)
+    fn force_flush_timeout(&self, timeout: Duration) -> TraceResult<()> {
+        let res_receiver = self.request_flush()?;
+
+        block_on_timeout(res_receiver, timeout)
+            .ok_or(TraceError::ExportTimedOut(timeout))?
             .map_err(|err| TraceError::Other(err.into()))
             .and_then(|identity| identity)
     }
 
-    fn shutdown(&mut self) -> TraceResult<()> {
+    fn force_flush_async(&self) -> BoxFuture<'static, TraceResult<()>> {
+        match self.request_flush() {
+            Ok(res_receiver) => res_receiver
+                .map(|result| {
+                    result
+                        .map_err(|err| TraceError::Other(err.into()))
+                        .and_then(|identity| identity)
+                })
+                .boxed(),
+            Err(err) => Box::pin(future::ready(Err(err))),
+        }
+    }
+
+    fn shutdown(&self) -> TraceResult<()> {
         let mut sender = self.message_sender.lock().map_err(|_| TraceError::from("When shutting down the BatchSpanProcessor, the message sender's lock has been poisoned"))?;
         let (res_sender, res_receiver) = oneshot::channel();
         sender.try_send(BatchMessage::Shutdown(res_sender))?;
@@ -273,80 +430,86 @@ enum BatchMessage {
 }
 
 impl BatchSpanProcessor {
+    /// Create a processor exporting batches through any of the given
+    /// exporters, exporting as many batches at once as there are exporters.
     pub(crate) fn new<R>(
-        mut exporter: Box<dyn SpanExporter>,
+        mut exporters: Vec<Box<dyn SpanExporter>>,
         config: BatchConfig,
         runtime: R,
     ) -> Self
     where
         R: Runtime,
     {
+        for exporter in exporters.iter_mut() {
+            exporter.set_export_timeout(config.max_export_timeout);
+        }
         let (message_sender, message_receiver) = mpsc::channel(config.max_queue_size);
+        let backpressure = config.backpressure;
+        let max_export_batch_size = config.max_export_batch_size;
         let ticker = runtime
             .interval(config.scheduled_delay)
             .map(|_| BatchMessage::Flush(None));
-        let timeout_runtime = runtime.clone();
+        let metrics = BatchSpanProcessorMetrics::default();
+        let mut pool = ExportPool {
+            config: Arc::new(config),
+            runtime: runtime.clone(),
+            metrics: metrics.clone(),
+            idle: exporters,
+            in_flight: FuturesUnordered::new(),
+        };
 
         // Spawn worker process via user-defined spawn function.
         runtime.spawn(Box::pin(async move {
             let mut spans = Vec::new();
             let mut messages = Box::pin(futures::stream::select(message_receiver, ticker));
 
-            while let Some(message) = messages.next().await {
+            loop {
+                // keep the exports in flight making progress while waiting for spans
+                let message = if pool.in_flight.is_empty() {
+                    messages.next().await
+                } else {
+                    match future::select(messages.next(), pool.in_flight.next()).await {
+                        Either::Left((message, _)) => message,
+                        Either::Right((finished, _)) => {
+                            if let Some((exporter, result)) = finished {
+                                pool.finished(exporter, result);
+                            }
+                            continue;
+                        }
+                    }
+                };
+
                 match message {
                     // Span has finished, add to buffer of pending spans.
-                    BatchMessage::ExportSpan(span) => {
+                    Some(BatchMessage::ExportSpan(span)) => {
+                        pool.metrics.0.queue_length.fetch_sub(1, Ordering::Relaxed);
                         spans.push(span);
 
-                        if spans.len() == config.max_export_batch_size {
-                            let result = export_with_timeout(
-                                config.max_export_timeout,
-                                exporter.as_mut(),
-                                &timeout_runtime,
-                                spans.split_off(0),
-                            )
-                            .await;
-
-                            if let Err(err) = result {
-                                global::handle_error(err);
-                            }
+                        if spans.len() == max_export_batch_size {
+                            pool.dispatch(spans.split_off(0)).await;
                         }
                     }
-                    // Span batch interval time reached or a force flush has been invoked, export current spans.
-                    BatchMessage::Flush(res_channel) => {
-                        let result = export_with_timeout(
-                            config.max_export_timeout,
-                            exporter.as_mut(),
-                            &timeout_runtime,
-                            spans.split_off(0),
-                        )
-                        .await;
-
-                        if let Some(channel) = res_channel {
-                            if let Err(err) = channel.send(result) {
-                                global::handle_error(TraceError::from(format!(
-                                    "failed to send flush result: {:?}",
-                                    err
-                                )));
-                            }
-                        } else if let Err(err) = result {
+                    // Span batch interval time reached, export current spans.
+                    Some(BatchMessage::Flush(None)) => {
+                        pool.dispatch(spans.split_off(0)).await;
+                    }
+                    // A force flush has been invoked, export current spans and
+                    // wait for all exports to finish.
+                    Some(BatchMessage::Flush(Some(channel))) => {
+                        let result = pool.export(spans.split_off(0)).await;
+
+                        if let Err(err) = channel.send(result) {
                             global::handle_error(TraceError::from(format!(
-                                "failed to export batch {:?}",
+                                "failed to send flush result: {:?}",
                                 err
                             )));
                         }
                     }
-                    // Stream has terminated or processor is shutdown, return to finish execution.
-                    BatchMessage::Shutdown(ch) => {
-                        let result = export_with_timeout(
-                            config.max_export_timeout,
-                            exporter.as_mut(),
-                            &timeout_runtime,
-                            spans.split_off(0),
-                        )
-                        .await;
-
-                        exporter.shutdown();
+                    // Processor is shutdown, return to finish execution.
+                    Some(BatchMessage::Shutdown(ch)) => {
+                        let result = pool.export(spans.split_off(0)).await;
+
+                        pool.shutdown();
 
                         if let Err(err) = ch.send(result) {
                             global::handle_error(TraceError::from(format!(
@@ -357,6 +520,8 @@ impl BatchSpanProcessor {
 
                         break;
                     }
+                    // Stream has terminated, return to finish execution.
+                    None => break,
                 }
             }
         }));
@@ -364,9 +529,83 @@ impl BatchSpanProcessor {
         // Return batch processor with link to worker
         BatchSpanProcessor {
             message_sender: Mutex::new(message_sender),
+            backpressure,
+            metrics,
         }
     }
 
+    /// Ask the worker to export the buffered spans, returning the channel the
+    /// export result will be sent on.
+    fn request_flush(&self) -> TraceResult<oneshot::Receiver<ExportResult>> {
+        let mut sender = self.message_sender.lock().map_err(|_| TraceError::from("When force flushing the BatchSpanProcessor, the message sender's lock has been poisoned"))?;
+        let (res_sender, res_receiver) = oneshot::channel();
+        sender.try_send(BatchMessage::Flush(Some(res_sender)))?;
+
+        Ok(res_receiver)
+    }
+
+    /// Send a message to the worker, handing it back if the queue is full.
+    fn try_send(&self, message: BatchMessage) -> TraceResult<Option<BatchMessage>> {
+        let mut sender = self
+            .message_sender
+            .lock()
+            .map_err(|_| TraceError::Other("batch span processor mutex poisoned".into()))?;
+        match sender.try_send(message) {
+            Ok(()) => Ok(None),
+            Err(err) if err.is_full() => Ok(Some(err.into_inner())),
+            Err(err) => Err(TraceError::Other(err.into())),
+        }
+    }
+
+    /// Apply the backpressure policy to a span that did not fit in the queue.
+    ///
+    /// The sender is only locked while trying to send, so other threads ending
+    /// spans or flushing the processor are not blocked by the wait. Clones of
+    /// the sender are not used as each of them could add one more span to the
+    /// queue.
+    fn on_full_queue(&self, message: BatchMessage) -> TraceResult<()> {
+        if let BackpressurePolicy::Block(bound) = self.backpressure {
+            let deadline = Instant::now() + bound;
+            let mut message = message;
+            loop {
+                let now = Instant::now();
+                if now >= deadline {
+                    break;
+                }
+                thread::sleep((deadline - now).min(BACKPRESSURE_RETRY_INTERVAL));
+
+                message = match self.try_send(message)? {
+                    None => return Ok(()),
+                    Some(message) => message,
+                };
+            }
+        }
+
+        self.metrics.0.dropped_spans.fetch_add(1, Ordering::Relaxed);
+        Err(TraceError::from(
+            "batch span processor queue is full, dropping span",
+        ))
+    }
+
+    /// The total number of spans dropped because the queue was full.
+    pub fn dropped_spans_count(&self) -> usize {
+        self.metrics.dropped_spans()
+    }
+
+    /// The total number of spans dropped because their export failed, either
+    /// with a permanent error or after exhausting the [`RetryPolicy`].
+    pub fn failed_spans_count(&self) -> usize {
+        self.metrics.failed_spans()
+    }
+
+    /// A handle to the queue and export counters of this processor.
+    ///
+    /// The handle stays valid once the processor has been moved into a
+    /// provider, see [`BatchSpanProcessorMetrics`].
+    pub fn metrics(&self) -> BatchSpanProcessorMetrics {
+        self.metrics.clone()
+    }
+
     /// Create a new batch processor builder
     pub fn builder<E, R>(exporter: E, runtime: R) -> BatchSpanProcessorBuilder<E, R>
     where
@@ -374,13 +613,279 @@ impl BatchSpanProcessor {
         R: Runtime,
     {
         BatchSpanProcessorBuilder {
-            exporter,
+            exporters: vec![exporter],
             config: BatchConfig::default(),
             runtime,
         }
     }
 }
 
+/// The exporters of a [`BatchSpanProcessor`] and the batches they are exporting.
+///
+/// Each exporter exports one batch at a time, so there are never more exports
+/// in flight than exporters in the pool.
+struct ExportPool<R> {
+    config: Arc<BatchConfig>,
+    runtime: R,
+    metrics: BatchSpanProcessorMetrics,
+    idle: Vec<Box<dyn SpanExporter>>,
+    in_flight: FuturesUnordered<BoxFuture<'static, (Box<dyn SpanExporter>, ExportResult)>>,
+}
+
+impl<R: Runtime> ExportPool<R> {
+    /// Start exporting a batch without waiting for the export to finish.
+    ///
+    /// If all exporters are busy, waits until one of them is done first.
+    async fn dispatch(&mut self, batch: Vec<SpanData>) {
+        if batch.is_empty() {
+            return;
+        }
+        if self.idle.is_empty() {
+            self.finish_next().await;
+        }
+
+        if let Some(mut exporter) = self.idle.pop() {
+            let config = self.config.clone();
+            let runtime = self.runtime.clone();
+            let metrics = self.metrics.clone();
+            self.in_flight.push(Box::pin(async move {
+                let result =
+                    export_with_retry(&config, exporter.as_mut(), &runtime, batch, &metrics).await;
+                (exporter, result)
+            }));
+        }
+    }
+
+    /// Export a batch once all exports in flight have finished.
+    async fn export(&mut self, batch: Vec<SpanData>) -> ExportResult {
+        while !self.in_flight.is_empty() {
+            self.finish_next().await;
+        }
+
+        match self.idle.first_mut() {
+            Some(exporter) => {
+                export_with_retry(
+                    &self.config,
+                    exporter.as_mut(),
+                    &self.runtime,
+                    batch,
+                    &self.metrics,
+                )
+                .await
+            }
+            None => Ok(()),
+        }
+    }
+
+    async fn finish_next(&mut self) {
+        if let Some((exporter, result)) = self.in_flight.next().await {
+            self.finished(exporter, result);
+        }
+    }
+
+    fn finished(&mut self, exporter: Box<dyn SpanExporter>, result: ExportResult) {
+        self.idle.push(exporter);
+        if let Err(err) = result {
+            global::handle_error(err);
+        }
+    }
+
+    fn shutdown(&mut self) {
+        for exporter in self.idle.iter_mut() {
+            exporter.shutdown();
+        }
+    }
+}
+
+/// Counters describing the queue and exports of a [`BatchSpanProcessor`].
+///
+/// The handle is cheap to clone and can be kept after the processor has been
+/// moved into a provider. Counters are updated atomically without locking, so
+/// reading them has no effect on the processor.
+///
+/// # Examples
+///
+/// ```
+/// # #[cfg(feature="rt-tokio")]
+/// # {
+/// use opentelemetry::{trace as apitrace, sdk::trace as sdktrace, runtime};
+///
+/// #[tokio::main]
+/// async fn main() {
+///     let batch = sdktrace::BatchSpanProcessor::builder(apitrace::NoopSpanExporter::new(), runtime::Tokio)
+///         .build();
+///     let metrics = batch.metrics();
+///
+///     let provider = sdktrace::TracerProvider::builder()
+///         .with_span_processor(batch)
+///         .build();
+///
+///     if metrics.dropped_spans() > 0 {
+///         // consider raising the max queue size
+///     }
+/// }
+/// # }
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct BatchSpanProcessorMetrics(Arc<BatchMetricsInner>);
+
+#[derive(Debug, Default)]
+struct BatchMetricsInner {
+    queue_length: AtomicUsize,
+    enqueued_spans: AtomicUsize,
+    dropped_spans: AtomicUsize,
+    exported_batches: AtomicUsize,
+    failed_exports: AtomicUsize,
+    failed_spans: AtomicUsize,
+}
+
+impl BatchSpanProcessorMetrics {
+    /// The number of spans currently waiting in the queue.
+    pub fn queue_length(&self) -> usize {
+        self.0.queue_length.load(Ordering::Relaxed)
+    }
+
+    /// The total number of spans added to the queue.
+    pub fn enqueued_spans(&self) -> usize {
+        self.0.enqueued_spans.load(Ordering::Relaxed)
+    }
+
+    /// The total number of spans dropped because the queue was full.
+    pub fn dropped_spans(&self) -> usize {
+        self.0.dropped_spans.load(Ordering::Relaxed)
+    }
+
+    /// The total number of non empty batches exported successfully.
+    pub fn exported_batches(&self) -> usize {
+        self.0.exported_batches.load(Ordering::Relaxed)
+    }
+
+    /// The total number of batches whose export failed, either with a
+    /// permanent error or after exhausting the [`RetryPolicy`].
+    pub fn failed_exports(&self) -> usize {
+        self.0.failed_exports.load(Ordering::Relaxed)
+    }
+
+    /// The total number of spans in batches whose export failed.
+    pub fn failed_spans(&self) -> usize {
+        self.0.failed_spans.load(Ordering::Relaxed)
+    }
+
+    /// Report the counters as asynchronous instruments of the given meter.
+    ///
+    /// | Instrument                   | Kind           |
+    /// |------------------------------|----------------|
+    /// | `otel.bsp.queue_length`      | value observer |
+    /// | `otel.bsp.spans.enqueued`    | sum observer   |
+    /// | `otel.bsp.spans.dropped`     | sum observer   |
+    /// | `otel.bsp.batches.exported`  | sum observer   |
+    /// | `otel.bsp.exports.failed`    | sum observer   |
+    #[cfg(feature = "metrics")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "metrics")))]
+    pub fn register(&self, meter: &crate::metrics::Meter) -> crate::metrics::Result<()> {
+        let metrics = self.clone();
+        meter
+            .u64_value_observer("otel.bsp.queue_length", move |result| {
+                result.observe(metrics.queue_length() as u64, &[])
+            })
+            .with_description("The number of spans waiting in the batch span processor queue")
+            .try_init()?;
+
+        let sum_observer = |name: &str, description: &str, counter: fn(&Self) -> usize| {
+            let metrics = self.clone();
+            meter
+                .u64_sum_observer(name, move |result| {
+                    result.observe(counter(&metrics) as u64, &[])
+                })
+                .with_description(description)
+                .try_init()
+                .map(|_| ())
+        };
+        sum_observer(
+            "otel.bsp.spans.enqueued",
+            "The number of spans added to the batch span processor queue",
+            Self::enqueued_spans,
+        )?;
+        sum_observer(
+            "otel.bsp.spans.dropped",
+            "The number of spans dropped because the batch span processor queue was full",
+            Self::dropped_spans,
+        )?;
+        sum_observer(
+            "otel.bsp.batches.exported",
+            "The number of batches exported by the batch span processor",
+            Self::exported_batches,
+        )?;
+        sum_observer(
+            "otel.bsp.exports.failed",
+            "The number of batches the batch span processor failed to export",
+            Self::failed_exports,
+        )?;
+
+        Ok(())
+    }
+}
+
+/// Export a batch, retrying retryable errors according to the retry policy.
+///
+/// All attempts and the backoff between them share the `max_export_timeout`
+/// of the config, no retry is made once it would be exceeded. Batches that
+/// were exported or could not be exported are counted in `metrics`.
+async fn export_with_retry<R, E>(
+    config: &BatchConfig,
+    exporter: &mut E,
+    runtime: &R,
+    mut batch: Vec<SpanData>,
+    metrics: &BatchSpanProcessorMetrics,
+) -> ExportResult
+where
+    R: Runtime,
+    E: SpanExporter + ?Sized,
+{
+    let batch_size = batch.len();
+    let deadline = Instant::now() + config.max_export_timeout;
+    let mut retry = 0;
+    loop {
+        let attempt = if retry < config.retry_policy.max_retries {
+            batch.clone()
+        } else {
+            std::mem::take(&mut batch)
+        };
+
+        let remaining = deadline.saturating_duration_since(Instant::now());
+        match export_with_timeout(remaining, exporter, runtime, attempt).await {
+            Err(err) => {
+                if err.is_retryable() && retry < config.retry_policy.max_retries {
+                    let backoff = config.retry_policy.backoff(retry);
+                    if Instant::now() + backoff < deadline {
+                        runtime.delay(backoff).await;
+                        retry += 1;
+                        continue;
+                    }
+                }
+
+                metrics.0.failed_exports.fetch_add(1, Ordering::Relaxed);
+                metrics
+                    .0
+                    .failed_spans
+                    .fetch_add(batch_size, Ordering::Relaxed);
+                return match err {
+                    TraceError::ExportTimedOut(_) => {
+                        Err(TraceError::ExportTimedOut(config.max_export_timeout))
+                    }
+                    err => Err(err),
+                };
+            }
+            Ok(()) => {
+                if batch_size > 0 {
+                    metrics.0.exported_batches.fetch_add(1, Ordering::Relaxed);
+                }
+                return Ok(());
+            }
+        }
+    }
+}
+
 async fn export_with_timeout<R, E>(
     time_out: Duration,
     exporter: &mut E,
@@ -405,6 +910,37 @@ where
     }
 }
 
+/// Wakes the thread blocked in [`block_on_timeout`].
+struct ThreadWaker(thread::Thread);
+
+impl ArcWake for ThreadWaker {
+    fn wake_by_ref(arc_self: &Arc<Self>) {
+        arc_self.0.unpark();
+    }
+}
+
+/// Run a future to completion on the current thread, giving up after `timeout`.
+///
+/// Unlike the runtime's `delay`, this does not need to be called from within a
+/// runtime context, so it can be used from the synchronous processor API.
+pub(crate) fn block_on_timeout<F: Future>(future: F, timeout: Duration) -> Option<F::Output> {
+    let deadline = Instant::now() + timeout;
+    let waker = futures::task::waker(Arc::new(ThreadWaker(thread::current())));
+    let mut cx = std::task::Context::from_waker(&waker);
+    pin_mut!(future);
+
+    loop {
+        if let Poll::Ready(output) = future.as_mut().poll(&mut cx) {
+            return Some(output);
+        }
+        let now = Instant::now();
+        if now >= deadline {
+            return None;
+        }
+        thread::park_timeout(deadline - now);
+    }
+}
+
 /// Batch span processor configuration
 #[derive(Debug)]
 pub struct BatchConfig {
@@ -422,38 +958,141 @@ pub struct BatchConfig {
     /// is 512.
     max_export_batch_size: usize,
 
-    /// The maximum duration to export a batch of data.
+    /// The maximum duration to export a batch of data, including the retries.
     max_export_timeout: Duration,
+
+    /// What to do with finished spans when the queue is full. The default is
+    /// to drop them.
+    backpressure: BackpressurePolicy,
+
+    /// How exports failing with a retryable error are retried. The default is
+    /// to retry 3 times.
+    retry_policy: RetryPolicy,
+}
+
+/// What the [`BatchSpanProcessor`] does with finished spans when its queue is
+/// full.
+///
+/// Spans that are dropped are counted, see
+/// [`BatchSpanProcessor::dropped_spans_count`].
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum BackpressurePolicy {
+    /// Drop the span immediately.
+    Drop,
+    /// Block the thread ending the span until there is space in the queue, for
+    /// at most the given duration. The span is dropped if the queue is still
+    /// full afterwards.
+    Block(Duration),
+}
+
+// `#[default]` on enum variants needs a newer compiler than the supported minimum
+#[allow(clippy::derivable_impls)]
+impl Default for BackpressurePolicy {
+    fn default() -> Self {
+        BackpressurePolicy::Drop
+    }
+}
+
+/// How the [`BatchSpanProcessor`] retries the export of a batch which failed
+/// with a retryable error, see [`TraceError::is_retryable`].
+///
+/// The delay between two attempts grows exponentially from the initial backoff
+/// up to the max backoff, each delay is randomly reduced by up to half to
+/// spread the retries of different processors. Batches still failing after the
+/// last retry are dropped and counted, see
+/// [`BatchSpanProcessor::failed_spans_count`].
+///
+/// The attempts and the delays between them are bounded by the max export
+/// timeout of the processor, a batch is not retried if the next attempt would
+/// start after it, see [`BatchSpanProcessorBuilder::with_max_timeout`].
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct RetryPolicy {
+    max_retries: usize,
+    initial_backoff: Duration,
+    max_backoff: Duration,
+}
+
+impl RetryPolicy {
+    /// Create a policy retrying at most `max_retries` times.
+    pub fn new(max_retries: usize, initial_backoff: Duration, max_backoff: Duration) -> Self {
+        RetryPolicy {
+            max_retries,
+            initial_backoff,
+            max_backoff,
+        }
+    }
+
+    /// A policy that never retries.
+    pub fn disabled() -> Self {
+        RetryPolicy {
+            max_retries: 0,
+            ..Default::default()
+        }
+    }
+
+    /// The delay before the given retry, starting at 0.
+    fn backoff(&self, retry: usize) -> Duration {
+        let exponential = 1u32
+            .checked_shl(retry as u32)
+            .and_then(|factor| self.initial_backoff.checked_mul(factor))
+            .unwrap_or(self.max_backoff);
+        let backoff = exponential.min(self.max_backoff);
+
+        // wait between half and all of the backoff
+        let nanos = backoff.as_nanos() as u64;
+        Duration::from_nanos(rand::thread_rng().gen_range(nanos / 2..=nanos))
+    }
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        RetryPolicy {
+            max_retries: 3,
+            initial_backoff: Duration::from_millis(100),
+            max_backoff: Duration::from_secs(5),
+        }
+    }
 }
 
 impl Default for BatchConfig {
     fn default() -> Self {
+        BatchConfig::from_env()
+    }
+}
+
+impl BatchConfig {
+    /// Create a batch config from the `OTEL_BSP_*` environment variables.
+    ///
+    /// | Environment variable             | Default |
+    /// |----------------------------------|---------|
+    /// | `OTEL_BSP_MAX_QUEUE_SIZE`        | 2048    |
+    /// | `OTEL_BSP_SCHEDULE_DELAY`        | 5000 ms |
+    /// | `OTEL_BSP_MAX_EXPORT_BATCH_SIZE` | 512     |
+    /// | `OTEL_BSP_EXPORT_TIMEOUT`        | 30000 ms |
+    ///
+    /// Missing variables use the default value. Malformed values are reported
+    /// through the global error handler and also fall back to the default.
+    pub fn from_env() -> Self {
         let mut config = BatchConfig {
             max_queue_size: OTEL_BSP_MAX_QUEUE_SIZE_DEFAULT,
             scheduled_delay: Duration::from_millis(OTEL_BSP_SCHEDULE_DELAY_DEFAULT),
             max_export_batch_size: OTEL_BSP_MAX_EXPORT_BATCH_SIZE_DEFAULT,
             max_export_timeout: Duration::from_millis(OTEL_BSP_EXPORT_TIMEOUT_DEFAULT),
+            backpressure: BackpressurePolicy::default(),
+            retry_policy: RetryPolicy::default(),
         };
 
-        if let Some(max_queue_size) = env::var(OTEL_BSP_MAX_QUEUE_SIZE)
-            .ok()
-            .and_then(|queue_size| usize::from_str(&queue_size).ok())
-        {
+        if let Some(max_queue_size) = parse_env(&[OTEL_BSP_MAX_QUEUE_SIZE]) {
             config.max_queue_size = max_queue_size;
         }
 
-        if let Some(scheduled_delay) = env::var(OTEL_BSP_SCHEDULE_DELAY)
-            .ok()
-            .or_else(|| env::var("OTEL_BSP_SCHEDULE_DELAY_MILLIS").ok())
-            .and_then(|delay| u64::from_str(&delay).ok())
+        if let Some(scheduled_delay) =
+            parse_env(&[OTEL_BSP_SCHEDULE_DELAY, "OTEL_BSP_SCHEDULE_DELAY_MILLIS"])
         {
             config.scheduled_delay = Duration::from_millis(scheduled_delay);
         }
 
-        if let Some(max_export_batch_size) = env::var(OTEL_BSP_MAX_EXPORT_BATCH_SIZE)
-            .ok()
-            .and_then(|batch_size| usize::from_str(&batch_size).ok())
-        {
+        if let Some(max_export_batch_size) = parse_env(&[OTEL_BSP_MAX_EXPORT_BATCH_SIZE]) {
             config.max_export_batch_size = max_export_batch_size;
         }
 
@@ -463,10 +1102,8 @@ impl Default for BatchConfig {
             config.max_export_batch_size = config.max_queue_size;
         }
 
-        if let Some(max_export_timeout) = env::var(OTEL_BSP_EXPORT_TIMEOUT)
-            .ok()
-            .or_else(|| env::var("OTEL_BSP_EXPORT_TIMEOUT_MILLIS").ok())
-            .and_then(|timeout| u64::from_str(&timeout).ok())
+        if let Some(max_export_timeout) =
+            parse_env(&[OTEL_BSP_EXPORT_TIMEOUT, "OTEL_BSP_EXPORT_TIMEOUT_MILLIS"])
         {
             config.max_export_timeout = Duration::from_millis(max_export_timeout);
         }
@@ -479,7 +1116,7 @@ impl Default for BatchConfig {
 ///
 #[derive(Debug)]
 pub struct BatchSpanProcessorBuilder<E, R> {
-    exporter: E,
+    exporters: Vec<E>,
     config: BatchConfig,
     runtime: R,
 }
@@ -506,6 +1143,9 @@ where
     }
 
     /// Set max timeout for exporting.
+    ///
+    /// The timeout bounds the whole export of a batch, including its retries,
+    /// see [`RetryPolicy`].
     pub fn with_max_timeout(self, timeout: Duration) -> Self {
         let mut config = self.config;
         config.max_export_timeout = timeout;
@@ -513,6 +1153,58 @@ where
         BatchSpanProcessorBuilder { config, ..self }
     }
 
+    /// Set what to do with finished spans when the queue is full.
+    pub fn with_backpressure(self, backpressure: BackpressurePolicy) -> Self {
+        let mut config = self.config;
+        config.backpressure = backpressure;
+
+        BatchSpanProcessorBuilder { config, ..self }
+    }
+
+    /// Set how exports failing with a retryable error are retried.
+    pub fn with_retry_policy(self, retry_policy: RetryPolicy) -> Self {
+        let mut config = self.config;
+        config.retry_policy = retry_policy;
+
+        BatchSpanProcessorBuilder { config, ..self }
+    }
+
+    /// Replace the current batch configuration.
+    ///
+    /// The number of concurrent exports is kept, it is set by
+    /// [`BatchSpanProcessorBuilder::with_exporter_factory`].
+    pub fn with_batch_config(self, config: BatchConfig) -> Self {
+        BatchSpanProcessorBuilder { config, ..self }
+    }
+
+    /// Export up to `max_concurrent_exports` batches at the same time, each
+    /// concurrent export uses its own exporter created by `factory`.
+    ///
+    /// Batches are exported independently of each other and may finish out of
+    /// order. This helps throughput to collectors with a high latency per
+    /// request. Values lower than 1 are treated as 1.
+    pub fn with_exporter_factory<F>(self, max_concurrent_exports: usize, factory: F) -> Self
+    where
+        F: Fn() -> E,
+    {
+        let mut exporters = self.exporters;
+        exporters.truncate(1);
+        while exporters.len() < max_concurrent_exports.max(1) {
+            exporters.push(factory());
+        }
+
+        BatchSpanProcessorBuilder { exporters, ..self }
+    }
+
+    /// Replace the current batch configuration with the one read from the
+    /// `OTEL_BSP_*` environment variables, see [`BatchConfig::from_env`].
+    pub fn with_batch_config_from_env(self) -> Self {
+        BatchSpanProcessorBuilder {
+            config: BatchConfig::from_env(),
+            ..self
+        }
+    }
+
     /// Set max export size for batches, should always less than or equals to max queue size.
     ///
     /// If input is larger than max queue size, will lower it to be equal to max queue size
@@ -529,23 +1221,51 @@ where
 
     /// Build a batch processor
     pub fn build(self) -> BatchSpanProcessor {
-        BatchSpanProcessor::new(Box::new(self.exporter), self.config, self.runtime)
+        let exporters = self
+            .exporters
+            .into_iter()
+            .map(|exporter| Box::new(exporter) as Box<dyn SpanExporter>)
+            .collect();
+        BatchSpanProcessor::new(exporters, self.config, self.runtime)
+    }
+}
+
+impl<E, R> BatchSpanProcessorBuilder<E, R>
+where
+    E: SpanExporter + Clone + 'static,
+    R: Runtime,
+{
+    /// Set the maximum number of batches exported at the same time.
+    ///
+    /// Each concurrent export uses its own clone of the exporter, exporters
+    /// which can't be cloned can use
+    /// [`BatchSpanProcessorBuilder::with_exporter_factory`] instead.
+    pub fn with_max_concurrent_exports(self, max_concurrent_exports: usize) -> Self {
+        let exporter = self.exporters.first().cloned();
+        match exporter {
+            Some(exporter) => {
+                self.with_exporter_factory(max_concurrent_exports, || exporter.clone())
+            }
+            None => self,
+        }
     }
 }
 
 #[cfg(all(test, feature = "testing", feature = "trace"))]
 mod tests {
     use super::{
-        BatchSpanProcessor, SimpleSpanProcessor, SpanProcessor, OTEL_BSP_EXPORT_TIMEOUT,
-        OTEL_BSP_MAX_EXPORT_BATCH_SIZE, OTEL_BSP_MAX_QUEUE_SIZE, OTEL_BSP_MAX_QUEUE_SIZE_DEFAULT,
-        OTEL_BSP_SCHEDULE_DELAY, OTEL_BSP_SCHEDULE_DELAY_DEFAULT,
+        BackpressurePolicy, BatchSpanProcessor, RetryPolicy, SimpleSpanProcessor, SpanProcessor,
+        OTEL_BSP_EXPORT_TIMEOUT, OTEL_BSP_MAX_EXPORT_BATCH_SIZE, OTEL_BSP_MAX_QUEUE_SIZE,
+        OTEL_BSP_MAX_QUEUE_SIZE_DEFAULT, OTEL_BSP_SCHEDULE_DELAY, OTEL_BSP_SCHEDULE_DELAY_DEFAULT,
     };
     use crate::runtime;
+    use crate::sdk::env::EnvGuard;
     use crate::sdk::export::trace::{stdout, ExportResult, SpanData, SpanExporter};
     use crate::sdk::trace::BatchConfig;
     use crate::testing::trace::{
         new_test_export_span_data, new_test_exporter, new_tokio_test_exporter,
     };
+    use crate::trace::{SpanContext, TraceError};
     use async_trait::async_trait;
     use futures::Future;
     use std::fmt::Debug;
@@ -554,22 +1274,81 @@ mod tests {
     #[test]
     fn simple_span_processor_on_end_calls_export() {
         let (exporter, rx_export, _rx_shutdown) = new_test_exporter();
-        let mut processor = SimpleSpanProcessor::new(Box::new(exporter));
+        let processor = SimpleSpanProcessor::new(Box::new(exporter));
         processor.on_end(new_test_export_span_data());
         assert!(rx_export.recv().is_ok());
         let _result = processor.shutdown();
     }
 
+    #[test]
+    fn simple_span_processor_exports_before_on_end_returns() {
+        let (exporter, rx_export, _rx_shutdown) = new_test_exporter();
+        let processor = SimpleSpanProcessor::new(Box::new(exporter));
+        processor.on_end(new_test_export_span_data());
+        assert!(rx_export.try_recv().is_ok());
+        assert!(processor.force_flush().is_ok());
+        assert!(rx_export.try_recv().is_err());
+    }
+
+    #[test]
+    fn simple_span_processor_ignores_unsampled_spans() {
+        let (exporter, rx_export, _rx_shutdown) = new_test_exporter();
+        let processor = SimpleSpanProcessor::new(Box::new(exporter));
+        let mut span = new_test_export_span_data();
+        span.span_context = SpanContext::empty_context();
+        processor.on_end(span);
+        assert!(rx_export.try_recv().is_err());
+    }
+
+    /// An exporter whose exports never complete.
+    #[derive(Debug, Default)]
+    struct PendingExporter {
+        timeouts: std::sync::Arc<std::sync::Mutex<Vec<Duration>>>,
+    }
+
+    #[async_trait]
+    impl SpanExporter for PendingExporter {
+        async fn export(&mut self, _batch: Vec<SpanData>) -> ExportResult {
+            futures::future::pending().await
+        }
+
+        fn set_export_timeout(&mut self, timeout: Duration) {
+            self.timeouts.lock().unwrap().push(timeout);
+        }
+    }
+
+    #[test]
+    fn simple_span_processor_export_timeout() {
+        let exporter = PendingExporter::default();
+        let timeouts = exporter.timeouts.clone();
+        let processor = SimpleSpanProcessor::new(Box::new(exporter))
+            .with_export_timeout(Duration::from_millis(10));
+
+        let start = std::time::Instant::now();
+        processor.on_end(new_test_export_span_data());
+        assert!(start.elapsed() < Duration::from_secs(5));
+        assert_eq!(
+            *timeouts.lock().unwrap(),
+            vec![Duration::from_secs(30), Duration::from_millis(10)]
+        );
+    }
+
     #[test]
     fn simple_span_processor_shutdown_calls_shutdown() {
         let (exporter, _rx_export, rx_shutdown) = new_test_exporter();
-        let mut processor = SimpleSpanProcessor::new(Box::new(exporter));
+        let processor = SimpleSpanProcessor::new(Box::new(exporter));
         let _result = processor.shutdown();
         assert!(rx_shutdown.try_recv().is_ok());
     }
 
     #[test]
     fn test_build_batch_span_processor_builder() {
+        let _guard = EnvGuard::new(&[
+            OTEL_BSP_MAX_EXPORT_BATCH_SIZE,
+            OTEL_BSP_EXPORT_TIMEOUT,
+            OTEL_BSP_SCHEDULE_DELAY,
+            OTEL_BSP_MAX_QUEUE_SIZE,
+        ]);
         std::env::set_var(OTEL_BSP_MAX_EXPORT_BATCH_SIZE, "500");
         std::env::set_var(OTEL_BSP_EXPORT_TIMEOUT, "2046");
         std::env::set_var(OTEL_BSP_SCHEDULE_DELAY, "I am not number");
@@ -601,6 +1380,22 @@ mod tests {
 
         assert_eq!(builder.config.max_export_batch_size, 120);
         assert_eq!(builder.config.max_queue_size, 120);
+
+        // manual settings are replaced by the env configuration
+        builder = builder
+            .with_max_queue_size(10)
+            .with_max_timeout(Duration::from_secs(1))
+            .with_batch_config_from_env();
+        assert_eq!(builder.config.max_queue_size, 120);
+        assert_eq!(
+            builder.config.max_export_timeout,
+            Duration::from_millis(2046)
+        );
+
+        // surrounding whitespace is tolerated
+        std::env::set_var(OTEL_BSP_SCHEDULE_DELAY, " 1000 ");
+        let config = BatchConfig::from_env();
+        assert_eq!(config.scheduled_delay, Duration::from_millis(1000));
     }
 
     #[tokio::test]
@@ -610,8 +1405,11 @@ mod tests {
             scheduled_delay: Duration::from_secs(60 * 60 * 24), // set the tick to 24 hours so we know the span must be exported via force_flush
             ..Default::default()
         };
-        let mut processor =
-            BatchSpanProcessor::new(Box::new(exporter), config, runtime::TokioCurrentThread);
+        let processor = BatchSpanProcessor::new(
+            vec![Box::new(exporter)],
+            config,
+            runtime::TokioCurrentThread,
+        );
         let handle = tokio::spawn(async move {
             loop {
                 if let Some(span) = export_receiver.recv().await {
@@ -707,7 +1505,8 @@ mod tests {
             delay_for: Duration::from_millis(if !time_out { 5 } else { 60 }),
             delay_fn: async_std::task::sleep,
         };
-        let mut processor = BatchSpanProcessor::new(Box::new(exporter), config, runtime::AsyncStd);
+        let processor =
+            BatchSpanProcessor::new(vec![Box::new(exporter)], config, runtime::AsyncStd);
         processor.on_end(new_test_export_span_data());
         let flush_res = processor.force_flush();
         if time_out {
@@ -731,8 +1530,11 @@ mod tests {
             delay_for: Duration::from_millis(if !time_out { 5 } else { 60 }),
             delay_fn: tokio::time::sleep,
         };
-        let mut processor =
-            BatchSpanProcessor::new(Box::new(exporter), config, runtime::TokioCurrentThread);
+        let processor = BatchSpanProcessor::new(
+            vec![Box::new(exporter)],
+            config,
+            runtime::TokioCurrentThread,
+        );
         tokio::time::sleep(Duration::from_secs(1)).await; // skip the first
         processor.on_end(new_test_export_span_data());
         let flush_res = processor.force_flush();
@@ -744,4 +1546,590 @@ mod tests {
         let shutdown_res = processor.shutdown();
         assert!(shutdown_res.is_ok());
     }
+
+    #[test]
+    fn test_force_flush_timeout() {
+        let runtime = tokio::runtime::Builder::new_multi_thread()
+            .enable_all()
+            .build()
+            .unwrap();
+        runtime.block_on(async {
+            let config = BatchConfig {
+                max_export_timeout: Duration::from_secs(10),
+                scheduled_delay: Duration::from_secs(60 * 60 * 24), // set the tick to 24 hours so we know the span must be exported via force_flush
+                ..Default::default()
+            };
+            let exporter = BlockingExporter {
+                delay_for: Duration::from_secs(1),
+                delay_fn: tokio::time::sleep,
+            };
+            let processor = BatchSpanProcessor::new(
+                vec![Box::new(exporter)],
+                config,
+                runtime::TokioCurrentThread,
+            );
+            processor.on_end(new_test_export_span_data());
+
+            let timeout = Duration::from_millis(50);
+            let flush_res = processor.force_flush_timeout(timeout);
+            assert!(
+                matches!(flush_res, Err(TraceError::ExportTimedOut(t)) if t == timeout),
+                "expected flush to time out, got {:?}",
+                flush_res
+            );
+
+            // the queue drains once the exporter returns
+            let flush_res = processor.force_flush_timeout(Duration::from_secs(5));
+            assert!(flush_res.is_ok());
+            let shutdown_res = processor.shutdown();
+            assert!(shutdown_res.is_ok());
+        });
+    }
+
+    #[tokio::test]
+    async fn test_force_flush_async() {
+        // The batch processor runs on the same single threaded runtime as the test,
+        // so a blocking flush would never let the worker make progress.
+        let (exporter, mut export_receiver, _shutdown_receiver) = new_tokio_test_exporter();
+        let config = BatchConfig {
+            scheduled_delay: Duration::from_secs(60 * 60 * 24), // set the tick to 24 hours so we know the span must be exported via force_flush
+            ..Default::default()
+        };
+        let processor = BatchSpanProcessor::new(vec![Box::new(exporter)], config, runtime::Tokio);
+        processor.on_end(new_test_export_span_data());
+
+        let flush_res = tokio::time::timeout(Duration::from_secs(5), processor.force_flush_async())
+            .await
+            .expect("timed out in 5 seconds. force_flush_async may block the runtime");
+        assert!(flush_res.is_ok());
+        assert_eq!(
+            export_receiver.try_recv().map(|span| span.span_context),
+            Ok(new_test_export_span_data().span_context)
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "rt-async-std")]
+    fn test_force_flush_async_async_std() {
+        async_std::task::block_on(async {
+            let (exporter, rx_export, _rx_shutdown) = new_test_exporter();
+            let config = BatchConfig {
+                scheduled_delay: Duration::from_secs(60 * 60 * 24), // set the tick to 24 hours so we know the span must be exported via force_flush
+                ..Default::default()
+            };
+            let processor =
+                BatchSpanProcessor::new(vec![Box::new(exporter)], config, runtime::AsyncStd);
+            processor.on_end(new_test_export_span_data());
+
+            assert!(processor.force_flush_async().await.is_ok());
+            assert!(rx_export.try_recv().is_ok());
+        });
+    }
+
+    #[test]
+    #[cfg(feature = "rt-async-std")]
+    fn test_scheduled_export_async_std() {
+        let (exporter, rx_export, _rx_shutdown) = new_test_exporter();
+        let config = BatchConfig {
+            scheduled_delay: Duration::from_millis(10),
+            ..Default::default()
+        };
+        let processor =
+            BatchSpanProcessor::new(vec![Box::new(exporter)], config, runtime::AsyncStd);
+        processor.on_end(new_test_export_span_data());
+
+        // exported by the runtime's interval, without flushing
+        assert!(rx_export.recv_timeout(Duration::from_secs(5)).is_ok());
+    }
+
+    #[derive(Debug, thiserror::Error)]
+    #[error("flaky export error")]
+    struct FlakyError {
+        retryable: bool,
+    }
+
+    impl crate::sdk::export::ExportError for FlakyError {
+        fn exporter_name(&self) -> &'static str {
+            "flaky"
+        }
+
+        fn is_retryable(&self) -> bool {
+            self.retryable
+        }
+    }
+
+    /// Fails the first `failures` exports.
+    #[derive(Debug)]
+    struct FlakyExporter {
+        failures: usize,
+        retryable: bool,
+        attempts: std::sync::Arc<std::sync::atomic::AtomicUsize>,
+    }
+
+    #[async_trait]
+    impl SpanExporter for FlakyExporter {
+        async fn export(&mut self, _batch: Vec<SpanData>) -> ExportResult {
+            let attempt = self
+                .attempts
+                .fetch_add(1, std::sync::atomic::Ordering::SeqCst);
+            if attempt < self.failures {
+                Err(FlakyError {
+                    retryable: self.retryable,
+                }
+                .into())
+            } else {
+                Ok(())
+            }
+        }
+    }
+
+    /// A tokio runtime whose interval never ticks, so spans are only exported
+    /// by flushing and not by the immediate first tick.
+    #[derive(Clone, Debug)]
+    struct UntickedRuntime;
+
+    impl runtime::Runtime for UntickedRuntime {
+        type Interval = futures::stream::Pending<()>;
+        type Delay = tokio::time::Sleep;
+
+        fn interval(&self, _duration: Duration) -> Self::Interval {
+            futures::stream::pending()
+        }
+
+        fn spawn(&self, future: futures::future::BoxFuture<'static, ()>) {
+            runtime::TokioCurrentThread.spawn(future)
+        }
+
+        fn delay(&self, duration: Duration) -> Self::Delay {
+            runtime::TokioCurrentThread.delay(duration)
+        }
+    }
+
+    /// Export one span through a flaky exporter, returning the flush result, the
+    /// number of export attempts and the number of failed spans.
+    fn flaky_export(failures: usize, retryable: bool) -> (bool, usize, usize) {
+        let runtime = tokio::runtime::Builder::new_multi_thread()
+            .enable_all()
+            .build()
+            .unwrap();
+        runtime.block_on(async {
+            let attempts = std::sync::Arc::new(std::sync::atomic::AtomicUsize::new(0));
+            let exporter = FlakyExporter {
+                failures,
+                retryable,
+                attempts: attempts.clone(),
+            };
+            let processor = BatchSpanProcessor::builder(exporter, UntickedRuntime)
+                .with_retry_policy(RetryPolicy::new(
+                    3,
+                    Duration::from_millis(1),
+                    Duration::from_millis(10),
+                ))
+                .build();
+            processor.on_end(new_test_export_span_data());
+
+            let flushed = processor
+                .force_flush_timeout(Duration::from_secs(5))
+                .is_ok();
+            (
+                flushed,
+                attempts.load(std::sync::atomic::Ordering::SeqCst),
+                processor.failed_spans_count(),
+            )
+        })
+    }
+
+    #[test]
+    fn test_retry_retryable_errors() {
+        assert_eq!(flaky_export(2, true), (true, 3, 0));
+    }
+
+    #[test]
+    fn test_retry_exhausted() {
+        assert_eq!(flaky_export(10, true), (false, 4, 1));
+    }
+
+    #[test]
+    fn test_retry_skips_permanent_errors() {
+        assert_eq!(flaky_export(1, false), (false, 1, 1));
+    }
+
+    #[test]
+    fn test_retry_bounded_by_export_timeout() {
+        let runtime = tokio::runtime::Builder::new_multi_thread()
+            .enable_all()
+            .build()
+            .unwrap();
+        runtime.block_on(async {
+            let attempts = std::sync::Arc::new(std::sync::atomic::AtomicUsize::new(0));
+            let exporter = FlakyExporter {
+                failures: usize::MAX,
+                retryable: true,
+                attempts: attempts.clone(),
+            };
+            let processor = BatchSpanProcessor::builder(exporter, UntickedRuntime)
+                .with_max_timeout(Duration::from_millis(50))
+                .with_retry_policy(RetryPolicy::new(
+                    100,
+                    Duration::from_millis(20),
+                    Duration::from_millis(20),
+                ))
+                .build();
+            processor.on_end(new_test_export_span_data());
+
+            let start = std::time::Instant::now();
+            assert!(processor
+                .force_flush_timeout(Duration::from_secs(5))
+                .is_err());
+            assert!(start.elapsed() < Duration::from_secs(1));
+            // each backoff waits at least 10ms
+            let attempts = attempts.load(std::sync::atomic::Ordering::SeqCst);
+            assert!((1..=5).contains(&attempts), "{} attempts", attempts);
+            assert_eq!(processor.failed_spans_count(), 1);
+        })
+    }
+
+    #[test]
+    fn test_retry_backoff() {
+        let policy = RetryPolicy::new(100, Duration::from_millis(100), Duration::from_secs(1));
+        let cases = vec![
+            (0, Duration::from_millis(50), Duration::from_millis(100)),
+            (2, Duration::from_millis(200), Duration::from_millis(400)),
+            (10, Duration::from_millis(500), Duration::from_secs(1)),
+            (99, Duration::from_millis(500), Duration::from_secs(1)),
+        ];
+        for (retry, min, max) in cases {
+            let backoff = policy.backoff(retry);
+            assert!(
+                backoff >= min && backoff <= max,
+                "retry {} waited {:?}",
+                retry,
+                backoff
+            );
+        }
+    }
+
+    /// A runtime keeping the spawned worker without ever polling it, so the
+    /// queue of the batch processor is never drained.
+    #[derive(Clone, Default)]
+    struct ParkedRuntime {
+        tasks: std::sync::Arc<std::sync::Mutex<Vec<futures::future::BoxFuture<'static, ()>>>>,
+    }
+
+    impl runtime::Runtime for ParkedRuntime {
+        type Interval = futures::stream::Pending<()>;
+        type Delay = futures::future::Pending<()>;
+
+        fn interval(&self, _duration: Duration) -> Self::Interval {
+            futures::stream::pending()
+        }
+
+        fn spawn(&self, future: futures::future::BoxFuture<'static, ()>) {
+            self.tasks.lock().unwrap().push(future);
+        }
+
+        fn delay(&self, _duration: Duration) -> Self::Delay {
+            futures::future::pending()
+        }
+    }
+
+    #[test]
+    fn test_backpressure_drop() {
+        let (exporter, _rx_export, _rx_shutdown) = new_test_exporter();
+        let processor = BatchSpanProcessor::builder(exporter, ParkedRuntime::default())
+            .with_max_queue_size(1)
+            .build();
+
+        for _ in 0..5 {
+            processor.on_end(new_test_export_span_data());
+        }
+
+        // the queue holds one span, and one more is parked by the sender
+        assert_eq!(processor.dropped_spans_count(), 3);
+    }
+
+    #[test]
+    fn test_backpressure_block() {
+        let bound = Duration::from_millis(50);
+        let (exporter, _rx_export, _rx_shutdown) = new_test_exporter();
+        let processor = BatchSpanProcessor::builder(exporter, ParkedRuntime::default())
+            .with_max_queue_size(1)
+            .with_backpressure(BackpressurePolicy::Block(bound))
+            .build();
+
+        let start = std::time::Instant::now();
+        for _ in 0..5 {
+            processor.on_end(new_test_export_span_data());
+        }
+
+        // the queue is never drained, so every span that did not fit waited for
+        // the bound before being dropped
+        assert_eq!(processor.dropped_spans_count(), 3);
+        assert!(start.elapsed() >= bound * 3);
+    }
+
+    #[test]
+    fn test_backpressure_block_does_not_hold_lock() {
+        let bound = Duration::from_secs(2);
+        let (exporter, _rx_export, _rx_shutdown) = new_test_exporter();
+        let processor = std::sync::Arc::new(
+            BatchSpanProcessor::builder(exporter, ParkedRuntime::default())
+                .with_max_queue_size(1)
+                .with_backpressure(BackpressurePolicy::Block(bound))
+                .build(),
+        );
+        processor.on_end(new_test_export_span_data());
+        processor.on_end(new_test_export_span_data());
+
+        let blocked = processor.clone();
+        let handle = std::thread::spawn(move || blocked.on_end(new_test_export_span_data()));
+        std::thread::sleep(Duration::from_millis(50));
+
+        // the flush is rejected by the full queue instead of waiting for the
+        // blocked thread to release the sender
+        let start = std::time::Instant::now();
+        assert!(processor
+            .force_flush_timeout(Duration::from_secs(5))
+            .is_err());
+        assert!(start.elapsed() < bound / 2);
+
+        handle.join().unwrap();
+        assert_eq!(processor.dropped_spans_count(), 1);
+    }
+
+    #[test]
+    fn test_batch_export_timeout() {
+        let runtime = tokio::runtime::Builder::new_multi_thread()
+            .enable_all()
+            .build()
+            .unwrap();
+        runtime.block_on(async {
+            let exporter = PendingExporter::default();
+            let timeouts = exporter.timeouts.clone();
+            let processor = BatchSpanProcessor::builder(exporter, runtime::TokioCurrentThread)
+                .with_scheduled_delay(Duration::from_secs(60 * 60 * 24))
+                .with_max_timeout(Duration::from_millis(10))
+                .with_retry_policy(RetryPolicy::disabled())
+                .build();
+            processor.on_end(new_test_export_span_data());
+
+            let flush_res = processor.force_flush_timeout(Duration::from_secs(5));
+            assert!(
+                matches!(flush_res, Err(TraceError::ExportTimedOut(t)) if t == Duration::from_millis(10)),
+                "expected the export to time out, got {:?}",
+                flush_res
+            );
+            assert_eq!(*timeouts.lock().unwrap(), vec![Duration::from_millis(10)]);
+        });
+    }
+
+    #[test]
+    fn test_queue_metrics() {
+        let (exporter, _rx_export, _rx_shutdown) = new_test_exporter();
+        let processor = BatchSpanProcessor::builder(exporter, ParkedRuntime::default())
+            .with_max_queue_size(1)
+            .build();
+        let metrics = processor.metrics();
+
+        for _ in 0..5 {
+            processor.on_end(new_test_export_span_data());
+        }
+
+        // the queue is never drained, so every enqueued span is still waiting
+        assert!(metrics.queue_length() > 0);
+        assert_eq!(metrics.queue_length(), metrics.enqueued_spans());
+        assert_eq!(metrics.enqueued_spans() + metrics.dropped_spans(), 5);
+        assert_eq!(metrics.dropped_spans(), processor.dropped_spans_count());
+    }
+
+    #[test]
+    fn test_export_metrics() {
+        let runtime = tokio::runtime::Builder::new_multi_thread()
+            .enable_all()
+            .build()
+            .unwrap();
+        runtime.block_on(async {
+            let exporter = FlakyExporter {
+                failures: 1,
+                retryable: false,
+                attempts: Default::default(),
+            };
+            let processor = BatchSpanProcessor::builder(exporter, runtime::TokioCurrentThread)
+                .with_scheduled_delay(Duration::from_secs(60 * 60 * 24))
+                .build();
+            let metrics = processor.metrics();
+
+            processor.on_end(new_test_export_span_data());
+            processor.on_end(new_test_export_span_data());
+            assert!(processor
+                .force_flush_timeout(Duration::from_secs(5))
+                .is_err());
+            assert_eq!(metrics.failed_exports(), 1);
+            assert_eq!(metrics.failed_spans(), 2);
+
+            processor.on_end(new_test_export_span_data());
+            assert!(processor
+                .force_flush_timeout(Duration::from_secs(5))
+                .is_ok());
+            // empty batches are not counted
+            assert!(processor
+                .force_flush_timeout(Duration::from_secs(5))
+                .is_ok());
+
+            assert_eq!(metrics.queue_length(), 0);
+            assert_eq!(metrics.enqueued_spans(), 3);
+            assert_eq!(metrics.exported_batches(), 1);
+            assert_eq!(metrics.failed_exports(), 1);
+        });
+    }
+
+    #[test]
+    fn test_register_metrics() {
+        use crate::metrics::MeterProvider;
+        use crate::sdk::export::metrics::{CheckpointSet, ExportKindSelector};
+        use crate::sdk::metrics::{controllers, selectors};
+
+        let (exporter, _rx_export, _rx_shutdown) = new_test_exporter();
+        let processor = BatchSpanProcessor::builder(exporter, ParkedRuntime::default())
+            .with_max_queue_size(1)
+            .build();
+        for _ in 0..3 {
+            processor.on_end(new_test_export_span_data());
+        }
+
+        let mut controller = controllers::pull(
+            Box::new(selectors::simple::Selector::Exact),
+            Box::new(ExportKindSelector::Cumulative),
+        )
+        .with_cache_period(Duration::from_secs(0))
+        .build();
+        let meter = controller.provider().meter("bsp", None);
+        processor.metrics().register(&meter).unwrap();
+        controller.collect().unwrap();
+
+        let mut names = Vec::new();
+        controller
+            .try_for_each(&ExportKindSelector::Cumulative, &mut |record| {
+                names.push(record.descriptor().name().to_string());
+                Ok(())
+            })
+            .unwrap();
+        names.sort();
+        assert_eq!(
+            names,
+            vec![
+                "otel.bsp.batches.exported",
+                "otel.bsp.exports.failed",
+                "otel.bsp.queue_length",
+                "otel.bsp.spans.dropped",
+                "otel.bsp.spans.enqueued",
+            ]
+        );
+    }
+
+    /// An exporter whose clones share a count of the exports in flight.
+    #[derive(Clone, Debug, Default)]
+    struct ConcurrencyExporter {
+        in_flight: std::sync::Arc<std::sync::atomic::AtomicUsize>,
+        max_in_flight: std::sync::Arc<std::sync::atomic::AtomicUsize>,
+        exported: std::sync::Arc<std::sync::atomic::AtomicUsize>,
+    }
+
+    #[async_trait]
+    impl SpanExporter for ConcurrencyExporter {
+        async fn export(&mut self, batch: Vec<SpanData>) -> ExportResult {
+            use std::sync::atomic::Ordering;
+
+            let in_flight = self.in_flight.fetch_add(1, Ordering::SeqCst) + 1;
+            self.max_in_flight.fetch_max(in_flight, Ordering::SeqCst);
+            tokio::time::sleep(Duration::from_millis(100)).await;
+            self.exported.fetch_add(batch.len(), Ordering::SeqCst);
+            self.in_flight.fetch_sub(1, Ordering::SeqCst);
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn test_max_concurrent_exports() {
+        use std::sync::atomic::Ordering;
+
+        let runtime = tokio::runtime::Builder::new_multi_thread()
+            .enable_all()
+            .build()
+            .unwrap();
+        runtime.block_on(async {
+            for max_concurrent_exports in vec![0, 1, 3] {
+                let exporter = ConcurrencyExporter::default();
+                let processor =
+                    BatchSpanProcessor::builder(exporter.clone(), runtime::TokioCurrentThread)
+                        .with_scheduled_delay(Duration::from_secs(60 * 60 * 24))
+                        .with_max_export_batch_size(1)
+                        .with_max_concurrent_exports(max_concurrent_exports)
+                        .build();
+
+                for _ in 0..6 {
+                    processor.on_end(new_test_export_span_data());
+                }
+                assert!(processor
+                    .force_flush_timeout(Duration::from_secs(5))
+                    .is_ok());
+
+                // the flush waits for all batches in flight
+                assert_eq!(exporter.exported.load(Ordering::SeqCst), 6);
+                assert_eq!(
+                    exporter.max_in_flight.load(Ordering::SeqCst),
+                    max_concurrent_exports.max(1)
+                );
+                assert_eq!(processor.metrics().exported_batches(), 6);
+            }
+        });
+    }
+
+    /// An exporter that can't be cloned.
+    #[derive(Debug)]
+    struct UniqueExporter(ConcurrencyExporter);
+
+    #[async_trait]
+    impl SpanExporter for UniqueExporter {
+        async fn export(&mut self, batch: Vec<SpanData>) -> ExportResult {
+            self.0.export(batch).await
+        }
+    }
+
+    #[test]
+    fn test_exporter_factory() {
+        use std::sync::atomic::Ordering;
+
+        let runtime = tokio::runtime::Builder::new_multi_thread()
+            .enable_all()
+            .build()
+            .unwrap();
+        runtime.block_on(async {
+            // replacing the config keeps the concurrent exports
+            for config in vec![None, Some(BatchConfig::default())] {
+                let exporter = ConcurrencyExporter::default();
+                let mut builder = BatchSpanProcessor::builder(
+                    UniqueExporter(exporter.clone()),
+                    runtime::TokioCurrentThread,
+                )
+                .with_exporter_factory(3, || UniqueExporter(exporter.clone()));
+                if let Some(config) = config {
+                    builder = builder.with_batch_config(config);
+                }
+                let processor = builder
+                    .with_scheduled_delay(Duration::from_secs(60 * 60 * 24))
+                    .with_max_export_batch_size(1)
+                    .build();
+
+                for _ in 0..6 {
+                    processor.on_end(new_test_export_span_data());
+                }
+                assert!(processor
+                    .force_flush_timeout(Duration::from_secs(5))
+                    .is_ok());
+                assert_eq!(exporter.exported.load(Ordering::SeqCst), 6);
+                assert_eq!(exporter.max_in_flight.load(Ordering::SeqCst), 3);
+            }
+        });
+    }
 }
@@ -17,8 +17,8 @@ use crate::sdk::{
     InstrumentationLibrary,
 };
 use crate::trace::{
-    Link, SpanBuilder, SpanContext, SpanId, SpanKind, StatusCode, TraceContextExt, TraceId,
-    TraceState, TRACE_FLAG_SAMPLED,
+    Link, SpanBuilder, SpanContext, SpanId, SpanKind, TraceContextExt, TraceId, TraceState,
+    TRACE_FLAG_SAMPLED,
 };
 use crate::{Context, KeyValue};
 use std::borrow::Cow;
@@ -56,8 +56,14 @@ impl Tracer {
     }
 
     /// TracerProvider associated with this tracer.
+    ///
+    /// Returns `None` once the provider was dropped or shut down, spans of the
+    /// tracer are dropped from then on.
     pub fn provider(&self) -> Option<TracerProvider> {
-        self.provider.upgrade().map(TracerProvider::new)
+        self.provider
+            .upgrade()
+            .map(TracerProvider::new)
+            .filter(|provider| !provider.is_shutdown())
     }
 
     /// instrumentation library information of this tracer.
@@ -76,7 +82,7 @@ impl Tracer {
         attributes: &[KeyValue],
         links: &[Link],
         config: &Config,
-    ) -> Option<(u8, Vec<KeyValue>, TraceState)> {
+    ) -> (Option<(u8, Vec<KeyValue>)>, TraceState) {
         let sampling_result = config.sampler.should_sample(
             Some(parent_cx),
             trace_id,
@@ -93,19 +99,23 @@ impl Tracer {
         &self,
         sampling_result: SamplingResult,
         parent_cx: &Context,
-    ) -> Option<(u8, Vec<KeyValue>, TraceState)> {
+    ) -> (Option<(u8, Vec<KeyValue>)>, TraceState) {
         match sampling_result {
             SamplingResult {
                 decision: SamplingDecision::Drop,
+                trace_state,
                 ..
-            } => None,
+            } => (None, trace_state),
             SamplingResult {
                 decision: SamplingDecision::RecordOnly,
                 attributes,
                 trace_state,
             } => {
                 let trace_flags = parent_cx.span().span_context().trace_flags();
-                Some((trace_flags & !TRACE_FLAG_SAMPLED, attributes, trace_state))
+                (
+                    Some((trace_flags & !TRACE_FLAG_SAMPLED, attributes)),
+                    trace_state,
+                )
             }
             SamplingResult {
                 decision: SamplingDecision::RecordAndSample,
@@ -113,7 +123,10 @@ impl Tracer {
                 trace_state,
             } => {
                 let trace_flags = parent_cx.span().span_context().trace_flags();
-                Some((trace_flags | TRACE_FLAG_SAMPLED, attributes, trace_state))
+                (
+                    Some((trace_flags | TRACE_FLAG_SAMPLED, attributes)),
+                    trace_state,
+                )
             }
         }
     }
@@ -179,16 +192,17 @@ impl crate::trace::Tracer for Tracer {
         let provider = provider.unwrap();
         let config = provider.config();
         let span_limits = config.span_limits;
-        let span_id = builder
-            .span_id
-            .take()
+        let span_context = builder.span_context.take();
+        let span_id = span_context
+            .as_ref()
+            .map(|span_context| span_context.span_id())
+            .or_else(|| builder.span_id.take())
             .unwrap_or_else(|| config.id_generator.new_span_id());
 
         let span_kind = builder.span_kind.take().unwrap_or(SpanKind::Internal);
         let mut attribute_options = builder.attributes.take().unwrap_or_else(Vec::new);
         let mut link_options = builder.links.take();
         let mut flags = 0;
-        let mut span_trace_state = Default::default();
 
         let parent_span = if builder.parent_context.has_active_span() {
             Some(builder.parent_context.span())
@@ -209,22 +223,34 @@ impl crate::trace::Tracer for Tracer {
                     sc.trace_flags(),
                 )
             })
-            .unwrap_or((
-                true,
-                builder
-                    .trace_id
-                    .unwrap_or_else(|| config.id_generator.new_trace_id()),
-                SpanId::invalid(),
-                false,
-                0,
-            ));
+            .unwrap_or_else(|| {
+                (
+                    true,
+                    builder
+                        .trace_id
+                        .unwrap_or_else(|| config.id_generator.new_trace_id()),
+                    SpanId::invalid(),
+                    false,
+                    0,
+                )
+            });
+        let trace_id = span_context
+            .as_ref()
+            .map(|span_context| span_context.trace_id())
+            .unwrap_or(trace_id);
 
-        // There are 3 paths for sampling.
+        // There are 4 paths for sampling.
         //
+        // * The span context is given, in which case its trace flags are used
         // * Sampling has occurred elsewhere and is already stored in the builder
         // * There is no parent or a remote parent, in which case make decision now
         // * There is a local parent, in which case defer to the parent's decision
-        let sampling_decision = if let Some(sampling_result) = builder.sampling_result.take() {
+        let (sampling_decision, span_trace_state) = if let Some(span_context) = span_context {
+            (
+                Some((span_context.trace_flags(), Vec::new())),
+                span_context.trace_state().clone(),
+            )
+        } else if let Some(sampling_result) = builder.sampling_result.take() {
             self.process_sampling_result(sampling_result, &builder.parent_context)
         } else if no_parent || remote_parent {
             self.make_sampling_decision(
@@ -238,15 +264,16 @@ impl crate::trace::Tracer for Tracer {
             )
         } else {
             // has parent that is local: use parent if sampled, or don't record.
-            parent_span
-                .filter(|span| span.span_context().is_sampled())
-                .map(|span| {
-                    (
-                        parent_trace_flags,
-                        Vec::new(),
-                        span.span_context().trace_state().clone(),
-                    )
-                })
+            // the parent trace state is propagated whether or not the span is recorded.
+            let parent_span_context = parent_span.as_ref().map(|span| span.span_context());
+            (
+                parent_span_context
+                    .filter(|span_context| span_context.is_sampled())
+                    .map(|_| (parent_trace_flags, Vec::new())),
+                parent_span_context
+                    .map(|span_context| span_context.trace_state().clone())
+                    .unwrap_or_default(),
+            )
         };
 
         // Build optional inner context, `None` if not recording.
@@ -256,17 +283,18 @@ impl crate::trace::Tracer for Tracer {
             start_time,
             end_time,
             events,
-            status_code,
-            status_message,
+            status,
             ..
         } = builder;
-        let inner = sampling_decision.map(|(trace_flags, mut extra_attrs, trace_state)| {
+        let inner = sampling_decision.map(|(trace_flags, mut extra_attrs)| {
             flags = trace_flags;
-            span_trace_state = trace_state;
             attribute_options.append(&mut extra_attrs);
             let mut attributes =
                 EvictedHashMap::new(span_limits.max_attributes_per_span, attribute_options.len());
-            for attribute in attribute_options {
+            for mut attribute in attribute_options {
+                if span_limits.truncate_attribute_value(&mut attribute) {
+                    attributes.record_truncated();
+                }
                 attributes.insert(attribute);
             }
             let mut links = EvictedQueue::new(span_limits.max_links_per_span);
@@ -277,10 +305,13 @@ impl crate::trace::Tracer for Tracer {
                         link.attributes.len().saturating_sub(link_attributes_limit);
                     link.attributes.truncate(link_attributes_limit);
                     link.dropped_attributes_count = dropped_attributes_count as u32;
+                    for attribute in link.attributes.iter_mut() {
+                        span_limits.truncate_attribute_value(attribute);
+                    }
                 }
                 links.append_vec(link_options);
             }
-            let start_time = start_time.unwrap_or_else(crate::time::now);
+            let start_time = start_time.unwrap_or_else(|| config.clock.now());
             let end_time = end_time.unwrap_or(start_time);
             let mut events_queue = EvictedQueue::new(span_limits.max_events_per_span);
             if let Some(mut events) = events {
@@ -292,11 +323,13 @@ impl crate::trace::Tracer for Tracer {
                         .saturating_sub(event_attributes_limit);
                     event.attributes.truncate(event_attributes_limit);
                     event.dropped_attributes_count = dropped_attributes_count as u32;
+                    for attribute in event.attributes.iter_mut() {
+                        span_limits.truncate_attribute_value(attribute);
+                    }
+                    span_limits.truncate_event_name(&mut event.name);
                 }
                 events_queue.append_vec(&mut events);
             }
-            let status_code = status_code.unwrap_or(StatusCode::Unset);
-            let status_message = status_message.unwrap_or(Cow::Borrowed(""));
 
             SpanData {
                 parent_span_id,
@@ -307,17 +340,20 @@ impl crate::trace::Tracer for Tracer {
                 attributes,
                 events: events_queue,
                 links,
-                status_code,
-                status_message,
+                status,
             }
         });
 
         let span_context = SpanContext::new(trace_id, span_id, flags, false, span_trace_state);
-        let span = Span::new(span_context, inner, self.clone(), span_limits);
-
-        // Call `on_start` for all processors
-        for processor in provider.span_processors() {
-            processor.on_start(&span, &parent_context)
+        let is_recording = inner.is_some();
+        let mut span = Span::new(span_context, inner, self.clone(), span_limits);
+
+        // Call `on_start` for all processors, the sampling decision is final at
+        // this point so processors can rely on `is_sampled` and `is_recording`.
+        if is_recording {
+            for processor in provider.span_processors() {
+                processor.on_start(&mut span, &parent_context)
+            }
         }
 
         span
@@ -327,20 +363,30 @@ impl crate::trace::Tracer for Tracer {
 #[cfg(all(test, feature = "testing", feature = "trace"))]
 mod tests {
     use crate::{
+        baggage::BaggageExt,
+        propagation::TextMapPropagator,
         sdk::{
             self,
-            trace::{Config, Sampler, SamplingDecision, SamplingResult, ShouldSample},
+            export::trace::SpanData,
+            trace::{
+                Config, Sampler, SamplingDecision, SamplingResult, ShouldSample, SpanProcessor,
+            },
         },
-        testing::trace::TestSpan,
+        testing::trace::{new_test_exporter, TestSpan},
         trace::{
             Link, Span, SpanBuilder, SpanContext, SpanId, SpanKind, TraceContextExt, TraceId,
-            TraceState, Tracer, TracerProvider, TRACE_FLAG_NOT_SAMPLED, TRACE_FLAG_SAMPLED,
+            TraceState, Tracer, TracerProvider, TRACE_FLAG_DEBUG, TRACE_FLAG_NOT_SAMPLED,
+            TRACE_FLAG_SAMPLED,
         },
         Context, KeyValue,
     };
+    use std::collections::HashMap;
+    use std::time::{Duration, SystemTime};
 
     #[derive(Debug)]
-    struct TestSampler {}
+    struct TestSampler {
+        decision: SamplingDecision,
+    }
 
     impl ShouldSample for TestSampler {
         fn should_sample(
@@ -359,9 +405,9 @@ mod tests {
                 .trace_state()
                 .clone();
             SamplingResult {
-                decision: SamplingDecision::RecordAndSample,
+                decision: self.decision.clone(),
                 attributes: Vec::new(),
-                trace_state: trace_state.insert("foo".into(), "notbar".into()).unwrap(),
+                trace_state: trace_state.insert("foo", "notbar").unwrap(),
             }
         }
     }
@@ -369,7 +415,9 @@ mod tests {
     #[test]
     fn allow_sampler_to_change_trace_state() {
         // Setup
-        let sampler = TestSampler {};
+        let sampler = TestSampler {
+            decision: SamplingDecision::RecordAndSample,
+        };
         let config = Config::default().with_sampler(sampler);
         let tracer_provider = sdk::trace::TracerProvider::builder()
             .with_config(config)
@@ -394,6 +442,73 @@ mod tests {
         assert_eq!(expected.get("foo"), Some("notbar"))
     }
 
+    #[test]
+    fn dropped_spans_keep_sampler_trace_state() {
+        let sampler = TestSampler {
+            decision: SamplingDecision::Drop,
+        };
+        let config = Config::default().with_sampler(sampler);
+        let tracer_provider = sdk::trace::TracerProvider::builder()
+            .with_config(config)
+            .build();
+        let tracer = tracer_provider.get_tracer("test", None);
+        let trace_state = TraceState::from_key_value(vec![("foo", "bar")]).unwrap();
+        let parent_cx = Context::new().with_remote_span_context(SpanContext::new(
+            TraceId::from_u128(128),
+            SpanId::from_u64(64),
+            TRACE_FLAG_SAMPLED,
+            true,
+            trace_state,
+        ));
+
+        let span = tracer.start_with_context("dropped", parent_cx);
+        assert!(!span.is_recording());
+        assert_eq!(span.span_context().trace_state().get("foo"), Some("notbar"));
+
+        // local children of the dropped span propagate its trace state
+        let child = tracer.start_with_context("child", Context::new().with_span(span));
+        assert!(!child.is_recording());
+        assert_eq!(
+            child.span_context().trace_state().get("foo"),
+            Some("notbar")
+        );
+    }
+
+    #[test]
+    fn record_only_spans_are_recorded_but_not_exported() {
+        let (exporter, rx_export, _rx_shutdown) = new_test_exporter();
+        let observed = std::sync::Arc::new(std::sync::Mutex::new(Vec::new()));
+        let tracer_provider = sdk::trace::TracerProvider::builder()
+            .with_config(Config::default().with_sampler(TestSampler {
+                decision: SamplingDecision::RecordOnly,
+            }))
+            .with_on_end({
+                let observed = observed.clone();
+                move |span| observed.lock().unwrap().push(span.attributes.len())
+            })
+            .with_simple_exporter(exporter)
+            .build();
+        let tracer = tracer_provider.get_tracer("test", None);
+
+        let mut span = tracer.start("record_only");
+        assert!(span.is_recording());
+        assert!(!span.span_context().is_sampled());
+        span.set_attribute(KeyValue::new("k", "v"));
+        span.end();
+
+        // the span was recorded and seen by processors, but not exported
+        assert_eq!(*observed.lock().unwrap(), vec![1]);
+        assert!(rx_export.try_recv().is_err());
+
+        let tracer_provider = sdk::trace::TracerProvider::builder()
+            .with_config(Config::default().with_sampler(TestSampler {
+                decision: SamplingDecision::Drop,
+            }))
+            .build();
+        let span = tracer_provider.get_tracer("test", None).start("dropped");
+        assert!(!span.is_recording());
+    }
+
     #[test]
     fn drop_parent_based_children() {
         let sampler = Sampler::ParentBased(Box::new(Sampler::AlwaysOn));
@@ -409,6 +524,37 @@ mod tests {
         assert!(!span.span_context().is_sampled());
     }
 
+    #[test]
+    fn parent_based_children_of_remote_parents() {
+        let config =
+            Config::default().with_sampler(Sampler::ParentBased(Box::new(Sampler::AlwaysOff)));
+        let tracer_provider = sdk::trace::TracerProvider::builder()
+            .with_config(config)
+            .build();
+        let tracer = tracer_provider.get_tracer("test", None);
+
+        for (trace_flags, sampled) in
+            vec![(TRACE_FLAG_SAMPLED, true), (TRACE_FLAG_NOT_SAMPLED, false)]
+        {
+            let parent_cx = Context::new().with_remote_span_context(SpanContext::new(
+                TraceId::from_u128(128),
+                SpanId::from_u64(64),
+                trace_flags,
+                true,
+                TraceState::default(),
+            ));
+            let span = tracer.start_with_context("child", parent_cx);
+            assert_eq!(span.is_recording(), sampled);
+            assert_eq!(span.span_context().is_sampled(), sampled);
+        }
+
+        // without a parent the root sampler decides
+        assert!(!tracer
+            .start_with_context("root", Context::new())
+            .span_context()
+            .is_sampled());
+    }
+
     #[test]
     fn uses_current_context_for_builders_if_unset() {
         let sampler = Sampler::ParentBased(Box::new(Sampler::AlwaysOn));
@@ -435,4 +581,380 @@ mod tests {
 
         assert!(!span.span_context().is_sampled());
     }
+
+    #[derive(Debug)]
+    struct BaggageSpanProcessor;
+
+    impl SpanProcessor for BaggageSpanProcessor {
+        fn on_start(&self, span: &mut sdk::trace::Span, cx: &Context) {
+            if let Some(user_id) = cx.baggage().get("user.id") {
+                span.set_attribute(KeyValue::new("user.id", user_id.clone()));
+            }
+        }
+
+        fn on_end(&self, _span: SpanData) {}
+
+        fn force_flush(&self) -> crate::trace::TraceResult<()> {
+            Ok(())
+        }
+
+        fn shutdown(&self) -> crate::trace::TraceResult<()> {
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn on_start_receives_parent_context_baggage() {
+        let (exporter, rx_export, _rx_shutdown) = new_test_exporter();
+        let tracer_provider = sdk::trace::TracerProvider::builder()
+            .with_span_processor(BaggageSpanProcessor)
+            .with_simple_exporter(exporter)
+            .build();
+        let tracer = tracer_provider.get_tracer("test", None);
+
+        // explicitly passed context
+        let cx = Context::new().with_baggage(vec![KeyValue::new("user.id", "42")]);
+        tracer.start_with_context("explicit", cx).end();
+
+        // context attached to the current thread
+        let _attached =
+            Context::current_with_baggage(vec![KeyValue::new("user.id", "43")]).attach();
+        tracer.start("current").end();
+
+        for expected in &["42", "43"] {
+            let exported = rx_export.recv().expect("span should be exported");
+            assert_eq!(
+                exported.attributes.get(&"user.id".into()),
+                Some(&(*expected).into())
+            );
+        }
+    }
+
+    #[derive(Debug)]
+    struct SampledTagSpanProcessor;
+
+    impl SpanProcessor for SampledTagSpanProcessor {
+        fn on_start(&self, span: &mut sdk::trace::Span, _cx: &Context) {
+            assert!(span.is_recording());
+            let sampled = span.span_context().is_sampled();
+            span.set_attribute(KeyValue::new("sampled", sampled));
+        }
+
+        fn on_end(&self, _span: SpanData) {}
+
+        fn force_flush(&self) -> crate::trace::TraceResult<()> {
+            Ok(())
+        }
+
+        fn shutdown(&self) -> crate::trace::TraceResult<()> {
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn on_start_sees_sampling_decision() {
+        for (decision, expected) in vec![
+            (SamplingDecision::RecordAndSample, Some(true)),
+            (SamplingDecision::RecordOnly, Some(false)),
+            (SamplingDecision::Drop, None),
+        ] {
+            let tagged = std::sync::Arc::new(std::sync::Mutex::new(Vec::new()));
+            let tracer_provider = sdk::trace::TracerProvider::builder()
+                .with_config(Config::default().with_sampler(TestSampler { decision }))
+                .with_span_processor(SampledTagSpanProcessor)
+                .with_on_end({
+                    let tagged = tagged.clone();
+                    move |span| {
+                        tagged
+                            .lock()
+                            .unwrap()
+                            .push(span.attributes.get(&"sampled".into()).cloned())
+                    }
+                })
+                .build();
+            tracer_provider.get_tracer("test", None).start("span").end();
+
+            let tagged = tagged.lock().unwrap();
+            match expected {
+                Some(sampled) => assert_eq!(*tagged, vec![Some(sampled.into())]),
+                // processors are not called for spans that are not recorded
+                None => assert!(tagged.is_empty()),
+            }
+        }
+    }
+
+    #[test]
+    fn exporters_receive_updated_name() {
+        let (exporter, rx_export, _rx_shutdown) = new_test_exporter();
+        let tracer_provider = sdk::trace::TracerProvider::builder()
+            .with_simple_exporter(exporter)
+            .build();
+        let tracer = tracer_provider.get_tracer("test", None);
+
+        let mut span = tracer.start("HTTP GET");
+        span.update_name("GET /users/:id");
+        span.end();
+
+        // spans in a context are renamed through the span reference
+        let cx = Context::new().with_span(tracer.start("HTTP POST"));
+        cx.span().update_name(format!("POST {}", "/users"));
+        cx.span().end();
+
+        for expected in &["GET /users/:id", "POST /users"] {
+            let exported = rx_export.recv().expect("span should be exported");
+            assert_eq!(exported.name, *expected);
+        }
+    }
+
+    #[test]
+    fn explicit_start_and_end_time() {
+        let (exporter, rx_export, _rx_shutdown) = new_test_exporter();
+        let tracer_provider = sdk::trace::TracerProvider::builder()
+            .with_simple_exporter(exporter)
+            .build();
+        let tracer = tracer_provider.get_tracer("test", None);
+        let start_time = SystemTime::UNIX_EPOCH + Duration::from_secs(10);
+        let end_time = start_time + Duration::from_secs(5);
+
+        // end time set on the builder is used when the span is dropped
+        drop(
+            tracer
+                .span_builder("builder")
+                .with_start_time(start_time)
+                .with_end_time(end_time)
+                .start(&tracer),
+        );
+        let exported = rx_export.recv().expect("span should be exported");
+        assert_eq!(exported.start_time, start_time);
+        assert_eq!(exported.end_time, end_time);
+
+        // end time before the start time is clamped to the start time
+        tracer
+            .span_builder("clamped")
+            .with_start_time(start_time)
+            .start(&tracer)
+            .end_with_timestamp(start_time - Duration::from_secs(1));
+        let exported = rx_export.recv().expect("span should be exported");
+        assert_eq!(exported.end_time, start_time);
+    }
+
+    #[test]
+    fn span_kind_defaults_to_internal() {
+        let (exporter, rx_export, _rx_shutdown) = new_test_exporter();
+        let tracer_provider = sdk::trace::TracerProvider::builder()
+            .with_simple_exporter(exporter)
+            .build();
+        let tracer = tracer_provider.get_tracer("test", None);
+
+        drop(tracer.start("default"));
+        let exported = rx_export.recv().expect("span should be exported");
+        assert_eq!(exported.span_kind, SpanKind::Internal);
+
+        for kind in vec![
+            SpanKind::Client,
+            SpanKind::Server,
+            SpanKind::Producer,
+            SpanKind::Consumer,
+        ] {
+            drop(
+                tracer
+                    .span_builder("with kind")
+                    .with_kind(kind.clone())
+                    .start(&tracer),
+            );
+            let exported = rx_export.recv().expect("span should be exported");
+            assert_eq!(exported.span_kind, kind);
+        }
+    }
+
+    #[test]
+    fn extracted_parent_is_remote_and_children_are_local() {
+        let propagator = sdk::propagation::TraceContextPropagator::new();
+        let mut headers = HashMap::new();
+        headers.insert(
+            "traceparent".to_string(),
+            "00-4bf92f3577b34da6a3ce929d0e0e4736-00f067aa0ba902b7-01".to_string(),
+        );
+        let parent_cx = propagator.extract(&headers);
+        assert!(parent_cx.span().span_context().is_remote());
+
+        let tracer_provider = sdk::trace::TracerProvider::default();
+        let tracer = tracer_provider.get_tracer("test", None);
+        let child = tracer.start_with_context("child", parent_cx);
+        assert!(child.span_context().is_valid());
+        assert!(!child.span_context().is_remote());
+
+        let grandchild = tracer.start_with_context("grandchild", Context::current_with_span(child));
+        assert!(!grandchild.span_context().is_remote());
+    }
+
+    #[test]
+    fn sampling_decision_sets_injected_sampled_flag() {
+        let propagator = sdk::propagation::TraceContextPropagator::new();
+        let mut headers = HashMap::new();
+        headers.insert(
+            "traceparent".to_string(),
+            "00-4bf92f3577b34da6a3ce929d0e0e4736-00f067aa0ba902b7-01".to_string(),
+        );
+        let parent_cx = propagator.extract(&headers);
+
+        for (decision, sampled) in vec![
+            (SamplingDecision::Drop, false),
+            (SamplingDecision::RecordOnly, false),
+            (SamplingDecision::RecordAndSample, true),
+        ] {
+            let tracer_provider = sdk::trace::TracerProvider::builder()
+                .with_config(Config::default().with_sampler(TestSampler { decision }))
+                .build();
+            let tracer = tracer_provider.get_tracer("test", None);
+            let span = tracer.start_with_context("span", parent_cx.clone());
+            assert_eq!(span.span_context().is_sampled(), sampled);
+
+            let mut injected = HashMap::new();
+            propagator.inject_context(&Context::current_with_span(span), &mut injected);
+            let traceparent = injected.get("traceparent").expect("traceparent injected");
+            let expected_flags = if sampled { "-01" } else { "-00" };
+            assert!(traceparent.ends_with(expected_flags), "{}", traceparent);
+        }
+    }
+
+    #[test]
+    fn start_with_explicit_parent_context() {
+        let (exporter, rx_export, _rx_shutdown) = new_test_exporter();
+        let tracer_provider = sdk::trace::TracerProvider::builder()
+            .with_simple_exporter(exporter)
+            .build();
+        let tracer = tracer_provider.get_tracer("test", None);
+
+        let parent_cx = Context::new().with_span(TestSpan(SpanContext::new(
+            TraceId::from_u128(128),
+            SpanId::from_u64(64),
+            TRACE_FLAG_SAMPLED,
+            false,
+            TraceState::default(),
+        )));
+        // another span is current when the children are started
+        let _guard = Context::current_with_span(tracer.start("current")).attach();
+
+        drop(tracer.start_with_context("child", parent_cx.clone()));
+        drop(
+            tracer
+                .span_builder("builder_child")
+                .start_with_context(&tracer, &parent_cx),
+        );
+
+        for name in &["child", "builder_child"] {
+            let exported = rx_export.recv().expect("span should be exported");
+            assert_eq!(exported.name, *name);
+            assert_eq!(exported.span_context.trace_id(), TraceId::from_u128(128));
+            assert_eq!(exported.parent_span_id, SpanId::from_u64(64));
+        }
+    }
+
+    #[test]
+    fn span_builder_with_span_context() {
+        let (exporter, rx_export, _rx_shutdown) = new_test_exporter();
+        let tracer_provider = sdk::trace::TracerProvider::builder()
+            .with_simple_exporter(exporter)
+            .with_config(Config::default().with_sampler(Sampler::AlwaysOff))
+            .build();
+        let tracer = tracer_provider.get_tracer("test", None);
+
+        let parent_cx = Context::new().with_span(TestSpan(SpanContext::new(
+            TraceId::from_u128(128),
+            SpanId::from_u64(64),
+            TRACE_FLAG_SAMPLED,
+            true,
+            TraceState::default(),
+        )));
+        let trace_state = TraceState::from_key_value(vec![("foo", "bar")]).unwrap();
+        let span_context = SpanContext::new(
+            TraceId::from_u128(128),
+            SpanId::from_u64(32),
+            TRACE_FLAG_SAMPLED | TRACE_FLAG_DEBUG,
+            true,
+            trace_state.clone(),
+        );
+
+        // the sampler is not consulted for the given span context
+        drop(
+            tracer
+                .span_builder("imported")
+                .with_span_context(span_context)
+                .start_with_context(&tracer, &parent_cx),
+        );
+
+        let exported = rx_export.recv().expect("span should be exported");
+        assert_eq!(
+            exported.span_context,
+            SpanContext::new(
+                TraceId::from_u128(128),
+                SpanId::from_u64(32),
+                TRACE_FLAG_SAMPLED | TRACE_FLAG_DEBUG,
+                false,
+                trace_state,
+            )
+        );
+        assert_eq!(exported.parent_span_id, SpanId::from_u64(64));
+    }
+
+    #[test]
+    fn in_span_ends_span() {
+        let (exporter, rx_export, _rx_shutdown) = new_test_exporter();
+        let tracer_provider = sdk::trace::TracerProvider::builder()
+            .with_simple_exporter(exporter)
+            .build();
+        let tracer = tracer_provider.get_tracer("test", None);
+
+        // the span is ended on return even if the context outlives the closure
+        let cx = tracer.in_span("returned", |cx| cx);
+        assert_eq!(
+            rx_export
+                .recv_timeout(Duration::from_secs(5))
+                .expect("span should be exported")
+                .name,
+            "returned"
+        );
+        assert!(!cx.span().is_recording());
+
+        // and ended while unwinding if the closure panics
+        let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+            tracer.in_span("panicked", |_cx| panic!("closure panicked"))
+        }));
+        assert!(result.is_err());
+        assert_eq!(
+            rx_export
+                .recv_timeout(Duration::from_secs(5))
+                .expect("span should be exported")
+                .name,
+            "panicked"
+        );
+        assert!(!Context::current().has_active_span());
+    }
+
+    #[test]
+    fn sampler_receives_builder_attributes() {
+        let tracer_provider = sdk::trace::TracerProvider::builder()
+            .with_config(
+                Config::default().with_sampler(sdk::trace::AttributeBasedSampler::new(
+                    "http.target",
+                    vec!["/health".into()],
+                    Sampler::AlwaysOn,
+                )),
+            )
+            .build();
+        let tracer = tracer_provider.get_tracer("test", None);
+
+        let health_check = tracer
+            .span_builder("GET")
+            .with_attributes(vec![KeyValue::new("http.target", "/health")])
+            .start(&tracer);
+        assert!(!health_check.is_recording());
+
+        let request = tracer
+            .span_builder("GET")
+            .with_attributes(vec![KeyValue::new("http.target", "/users")])
+            .start(&tracer);
+        assert!(request.is_recording());
+    }
 }
@@ -7,12 +7,16 @@ use crate::{
         trace::{Config, EvictedHashMap, EvictedQueue},
         InstrumentationLibrary,
     },
-    trace::{Span, SpanContext, SpanId, SpanKind, StatusCode},
+    trace::{
+        Link, Span, SpanContext, SpanId, SpanKind, Status, TraceId, TraceState, TRACE_FLAG_SAMPLED,
+    },
     KeyValue,
 };
 use async_trait::async_trait;
+use std::borrow::Cow;
 use std::fmt::{Display, Formatter};
 use std::sync::mpsc::{channel, Receiver, Sender};
+use std::sync::{Arc, Mutex};
 
 #[derive(Debug)]
 pub struct TestSpan(pub SpanContext);
@@ -32,15 +36,26 @@ impl Span for TestSpan {
         false
     }
     fn set_attribute(&mut self, _attribute: KeyValue) {}
-    fn set_status(&mut self, _code: StatusCode, _message: String) {}
-    fn update_name(&mut self, _new_name: String) {}
+    fn add_link(&mut self, _link: Link) {}
+    fn set_status(&mut self, _status: Status) {}
+    fn update_name<T>(&mut self, _new_name: T)
+    where
+        T: Into<Cow<'static, str>>,
+    {
+    }
     fn end_with_timestamp(&mut self, _timestamp: std::time::SystemTime) {}
 }
 
 pub fn new_test_export_span_data() -> SpanData {
     let config = Config::default();
     SpanData {
-        span_context: SpanContext::empty_context(),
+        span_context: SpanContext::new(
+            TraceId::from_u128(1),
+            SpanId::from_u64(1),
+            TRACE_FLAG_SAMPLED,
+            false,
+            TraceState::default(),
+        ),
         parent_span_id: SpanId::from_u64(0),
         span_kind: SpanKind::Internal,
         name: "opentelemetry".into(),
@@ -49,8 +64,7 @@ pub fn new_test_export_span_data() -> SpanData {
         attributes: EvictedHashMap::new(config.span_limits.max_attributes_per_span, 0),
         events: EvictedQueue::new(config.span_limits.max_events_per_span),
         links: EvictedQueue::new(config.span_limits.max_links_per_span),
-        status_code: StatusCode::Unset,
-        status_message: "".into(),
+        status: Status::Unset,
         resource: config.resource,
         instrumentation_lib: InstrumentationLibrary::default(),
     }
@@ -88,6 +102,65 @@ pub fn new_test_exporter() -> (TestSpanExporter, Receiver<SpanData>, Receiver<()
     (exporter, rx_export, rx_shutdown)
 }
 
+/// A [`SpanExporter`] that keeps exported spans in memory so tests can assert
+/// on them.
+///
+/// Clones share the same storage, so keep a clone around after handing the
+/// exporter to a span processor. Pair it with the [`SimpleSpanProcessor`] to
+/// have spans available as soon as they end.
+///
+/// # Examples
+///
+/// ```
+/// use opentelemetry::sdk::trace::TracerProvider;
+/// use opentelemetry::testing::trace::InMemorySpanExporter;
+/// use opentelemetry::trace::{Span, Tracer, TracerProvider as _};
+///
+/// let exporter = InMemorySpanExporter::default();
+/// let provider = TracerProvider::builder()
+///     .with_simple_exporter(exporter.clone())
+///     .build();
+///
+/// provider.get_tracer("test", None).start("my-span").end();
+///
+/// let spans = exporter.get_finished_spans();
+/// assert_eq!(spans.len(), 1);
+/// assert_eq!(spans[0].name, "my-span");
+/// ```
+///
+/// [`SimpleSpanProcessor`]: crate::sdk::trace::SimpleSpanProcessor
+#[derive(Clone, Debug, Default)]
+pub struct InMemorySpanExporter {
+    spans: Arc<Mutex<Vec<SpanData>>>,
+}
+
+impl InMemorySpanExporter {
+    /// Returns the spans exported so far, in the order they were exported.
+    pub fn get_finished_spans(&self) -> Vec<SpanData> {
+        self.spans
+            .lock()
+            .map(|spans| spans.clone())
+            .unwrap_or_default()
+    }
+
+    /// Removes all spans exported so far.
+    pub fn reset(&self) {
+        if let Ok(mut spans) = self.spans.lock() {
+            spans.clear();
+        }
+    }
+}
+
+#[async_trait]
+impl SpanExporter for InMemorySpanExporter {
+    async fn export(&mut self, batch: Vec<SpanData>) -> ExportResult {
+        self.spans
+            .lock()
+            .map(|mut spans| spans.extend(batch))
+            .map_err(|err| TestExportError(err.to_string()).into())
+    }
+}
+
 #[derive(Debug)]
 pub struct TokioSpanExporter {
     tx_export: tokio::sync::mpsc::UnboundedSender<SpanData>,
@@ -152,3 +225,34 @@ impl<T> From<std::sync::mpsc::SendError<T>> for TestExportError {
         TestExportError(err.to_string())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::{new_test_export_span_data, InMemorySpanExporter};
+    use crate::sdk::export::trace::SpanExporter;
+    use futures::executor::block_on;
+
+    #[test]
+    fn in_memory_exporter_keeps_spans_in_order() {
+        let exporter = InMemorySpanExporter::default();
+        let mut handle = exporter.clone();
+
+        let span = |name: &'static str| {
+            let mut span = new_test_export_span_data();
+            span.name = name.into();
+            span
+        };
+        block_on(handle.export(vec![span("first"), span("second")])).unwrap();
+        block_on(handle.export(vec![span("third")])).unwrap();
+
+        let names = exporter
+            .get_finished_spans()
+            .into_iter()
+            .map(|span| span.name)
+            .collect::<Vec<_>>();
+        assert_eq!(names, vec!["first", "second", "third"]);
+
+        exporter.reset();
+        assert!(exporter.get_finished_spans().is_empty());
+    }
+}
@@ -1,5 +1,6 @@
 //! Context extensions for tracing
 use crate::{global, trace::SpanContext, Context, ContextGuard, KeyValue};
+use std::borrow::Cow;
 use std::error::Error;
 use std::sync::Mutex;
 
@@ -19,11 +20,11 @@ struct SynchronizedSpan {
     /// Immutable span context
     span_context: SpanContext,
     /// Mutable span inner that requires synchronization
-    inner: Option<Box<Mutex<dyn crate::trace::Span + Send + Sync>>>,
+    inner: Option<Box<Mutex<dyn global::GenericSpan + Send + Sync>>>,
 }
 
 impl SpanRef<'_> {
-    fn with_inner_mut<F: FnOnce(&mut dyn crate::trace::Span)>(&self, f: F) {
+    fn with_inner_mut<F: FnOnce(&mut dyn global::GenericSpan)>(&self, f: F) {
         if let Some(ref inner) = self.0.inner {
             match inner.lock() {
                 Ok(mut locked) => f(&mut *locked),
@@ -40,13 +41,25 @@ impl SpanRef<'_> {
     }
 
     /// Convenience method to record an exception/error as an `Event`
+    pub fn record_error(&self, err: &dyn Error) {
+        self.with_inner_mut(|inner| inner.record_error(err))
+    }
+
+    /// Convenience method to record a exception/error as an `Event` with custom stacktrace
+    pub fn record_error_with_stacktrace(&self, err: &dyn Error, stacktrace: String) {
+        self.with_inner_mut(|inner| inner.record_error_with_stacktrace(err, stacktrace))
+    }
+
+    /// Convenience method to record an exception/error as an `Event`
+    #[deprecated(since = "0.15.0", note = "use `record_error` instead")]
     pub fn record_exception(&self, err: &dyn Error) {
-        self.with_inner_mut(|inner| inner.record_exception(err))
+        self.record_error(err)
     }
 
     /// Convenience method to record a exception/error as an `Event` with custom stacktrace
+    #[deprecated(since = "0.15.0", note = "use `record_error_with_stacktrace` instead")]
     pub fn record_exception_with_stacktrace(&self, err: &dyn Error, stacktrace: String) {
-        self.with_inner_mut(|inner| inner.record_exception_with_stacktrace(err, stacktrace))
+        self.record_error_with_stacktrace(err, stacktrace)
     }
 
     /// An API to record events at a specific time in the context of a given `Span`.
@@ -83,16 +96,24 @@ impl SpanRef<'_> {
         self.with_inner_mut(move |inner| inner.set_attribute(attribute))
     }
 
+    /// Adds a `Link` to another `Span` after this `Span` was started.
+    pub fn add_link(&self, link: super::Link) {
+        self.with_inner_mut(move |inner| inner.add_link(link))
+    }
+
     /// Sets the status of the `Span`. If used, this will override the default `Span`
-    /// status, which is `Unset`. `message` MUST be ignored when the status is `OK` or `Unset`
-    pub fn set_status(&self, code: super::StatusCode, message: String) {
-        self.with_inner_mut(move |inner| inner.set_status(code, message))
+    /// status, which is `Unset`.
+    pub fn set_status(&self, status: super::Status) {
+        self.with_inner_mut(move |inner| inner.set_status(status))
     }
 
     /// Updates the `Span`'s name. After this update, any sampling behavior based on the
     /// name will depend on the implementation.
-    pub fn update_name(&self, new_name: String) {
-        self.with_inner_mut(move |inner| inner.update_name(new_name))
+    pub fn update_name<T>(&self, new_name: T)
+    where
+        T: Into<Cow<'static, str>>,
+    {
+        self.with_inner_mut(move |inner| inner.update_name_boxed(new_name.into()))
     }
 
     /// Finishes the `Span`.
@@ -140,6 +161,36 @@ pub trait TraceContextExt {
     /// ```
     fn span(&self) -> SpanRef<'_>;
 
+    /// Returns a reference to this context's span context, or an invalid span
+    /// context if no span has been set.
+    ///
+    /// This allows inspecting an extracted remote context without attaching it
+    /// or starting a span.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use opentelemetry::{
+    ///     propagation::TextMapPropagator,
+    ///     sdk::propagation::TraceContextPropagator,
+    ///     trace::{SpanId, TraceContextExt, TraceId},
+    /// };
+    /// use std::collections::HashMap;
+    ///
+    /// let mut headers = HashMap::new();
+    /// headers.insert(
+    ///     "traceparent".to_string(),
+    ///     "00-4bf92f3577b34da6a3ce929d0e0e4736-00f067aa0ba902b7-01".to_string(),
+    /// );
+    ///
+    /// let cx = TraceContextPropagator::new().extract(&headers);
+    /// let span_context = cx.span_context();
+    /// assert_eq!(span_context.trace_id(), TraceId::from_u128(0x4bf9_2f35_77b3_4da6_a3ce_929d_0e0e_4736));
+    /// assert_eq!(span_context.span_id(), SpanId::from_u64(0x00f0_67aa_0ba9_02b7));
+    /// assert!(span_context.is_remote());
+    /// ```
+    fn span_context(&self) -> &SpanContext;
+
     /// Used to see if a span has been marked as active
     ///
     /// This is useful for building tracers.
@@ -174,6 +225,12 @@ impl TraceContextExt for Context {
         }
     }
 
+    fn span_context(&self) -> &SpanContext {
+        self.get::<SynchronizedSpan>()
+            .map(|span| &span.span_context)
+            .unwrap_or(&NOOP_SPAN.span_context)
+    }
+
     fn has_active_span(&self) -> bool {
         self.get::<SynchronizedSpan>().is_some()
     }
@@ -251,3 +308,69 @@ where
 {
     f(Context::current().span())
 }
+
+#[cfg(all(test, feature = "testing"))]
+mod tests {
+    use super::*;
+    use crate::sdk;
+    use crate::testing::trace::new_test_exporter;
+    use crate::trace::{Tracer, TracerProvider};
+
+    #[test]
+    fn get_active_span_without_active_span() {
+        get_active_span(|span| {
+            assert!(!span.is_recording());
+            assert_eq!(span.span_context(), &SpanContext::empty_context());
+            // mutations of the noop span are ignored
+            span.set_attribute(KeyValue::new("ignored", true));
+        });
+    }
+
+    #[test]
+    fn get_active_span_mutates_active_span() {
+        let (exporter, rx_export, _rx_shutdown) = new_test_exporter();
+        let provider = sdk::trace::TracerProvider::builder()
+            .with_simple_exporter(exporter)
+            .build();
+        let tracer = provider.get_tracer("test", None);
+
+        let guard = mark_span_as_active(tracer.start("active"));
+        get_active_span(|span| {
+            assert!(span.is_recording());
+            span.set_attribute(KeyValue::new("http.method", "GET"));
+        });
+        drop(guard);
+
+        let exported = rx_export.recv().expect("span should be exported");
+        assert_eq!(exported.name, "active");
+        assert_eq!(
+            exported.attributes.get(&"http.method".into()),
+            Some(&"GET".into())
+        );
+    }
+
+    #[test]
+    fn active_span_is_shared_across_threads() {
+        let (exporter, rx_export, _rx_shutdown) = new_test_exporter();
+        let provider = sdk::trace::TracerProvider::builder()
+            .with_simple_exporter(exporter)
+            .build();
+        let tracer = provider.get_tracer("test", None);
+
+        let _guard = mark_span_as_active(tracer.start("parent"));
+        let cx = Context::current();
+        std::thread::spawn(move || {
+            let _guard = cx.attach();
+            get_active_span(|span| span.set_attribute(KeyValue::new("worker", true)));
+        })
+        .join()
+        .unwrap();
+
+        Context::current().span().end();
+        let exported = rx_export.recv().expect("span should be exported");
+        assert_eq!(
+            exported.attributes.get(&"worker".into()),
+            Some(&true.into())
+        );
+    }
+}
@@ -93,7 +93,9 @@ pub trait FutureExt: Sized {
     /// wrapper.
     ///
     /// When the wrapped type is a future, stream, or sink, the attached context
-    /// will be set as current while it is being polled.
+    /// will be set as current while it is being polled. The context is attached
+    /// on every poll, so it follows the future across `.await` points and
+    /// executors, whichever thread it is polled on.
     ///
     /// [`Context`]: crate::Context
     fn with_context(self, otel_cx: OpenTelemetryContext) -> WithContext<Self> {
@@ -115,3 +117,66 @@ pub trait FutureExt: Sized {
         self.with_context(otel_cx)
     }
 }
+
+#[cfg(all(test, feature = "testing"))]
+mod tests {
+    use super::*;
+    use crate::testing::trace::TestSpan;
+    use crate::trace::{SpanContext, SpanId, TraceContextExt, TraceId, TraceState};
+    use std::future::Future;
+
+    /// Yields once so the futures joined with it get interleaved.
+    #[derive(Default)]
+    struct YieldNow(bool);
+
+    impl Future for YieldNow {
+        type Output = ();
+
+        fn poll(mut self: Pin<&mut Self>, task_cx: &mut TaskContext<'_>) -> Poll<()> {
+            if self.0 {
+                Poll::Ready(())
+            } else {
+                self.0 = true;
+                task_cx.waker().wake_by_ref();
+                Poll::Pending
+            }
+        }
+    }
+
+    fn context_with_span(span_id: u64) -> OpenTelemetryContext {
+        OpenTelemetryContext::new().with_span(TestSpan(SpanContext::new(
+            TraceId::from_u128(1),
+            SpanId::from_u64(span_id),
+            0,
+            false,
+            TraceState::default(),
+        )))
+    }
+
+    async fn observed_span_ids() -> Vec<SpanId> {
+        let mut span_ids = Vec::new();
+        for _ in 0..3 {
+            span_ids.push(
+                OpenTelemetryContext::current()
+                    .span()
+                    .span_context()
+                    .span_id(),
+            );
+            YieldNow::default().await;
+        }
+        span_ids
+    }
+
+    #[test]
+    fn interleaved_futures_observe_their_own_context() {
+        let first = observed_span_ids().with_context(context_with_span(1));
+        let second = observed_span_ids().with_context(context_with_span(2));
+
+        let (first, second) = futures::executor::block_on(futures::future::join(first, second));
+        assert_eq!(first, vec![SpanId::from_u64(1); 3]);
+        assert_eq!(second, vec![SpanId::from_u64(2); 3]);
+
+        // the contexts are detached once the futures are not polled
+        assert!(!OpenTelemetryContext::current().has_active_span());
+    }
+}
@@ -131,11 +131,11 @@ pub use self::{
     id_generator::IdGenerator,
     link::Link,
     noop::{NoopSpan, NoopSpanExporter, NoopTracer, NoopTracerProvider},
-    provider::TracerProvider,
-    span::{Span, SpanKind, StatusCode},
+    provider::{TracerConfig, TracerProvider},
+    span::{Span, SpanKind, Status},
     span_context::{
-        SpanContext, SpanId, TraceId, TraceState, TRACE_FLAG_DEBUG, TRACE_FLAG_DEFERRED,
-        TRACE_FLAG_NOT_SAMPLED, TRACE_FLAG_SAMPLED,
+        SpanContext, SpanId, SpanIdError, TraceId, TraceIdError, TraceState, TraceStateError,
+        TRACE_FLAG_DEBUG, TRACE_FLAG_DEFERRED, TRACE_FLAG_NOT_SAMPLED, TRACE_FLAG_SAMPLED,
     },
     tracer::{SpanBuilder, Tracer},
 };
@@ -162,6 +162,20 @@ pub enum TraceError {
     Other(#[from] Box<dyn std::error::Error + Send + Sync + 'static>),
 }
 
+impl TraceError {
+    /// Whether the failed operation may succeed if it is retried.
+    ///
+    /// Timeouts are retryable, export failures are retryable if the exporter
+    /// says so, see [`ExportError::is_retryable`].
+    pub fn is_retryable(&self) -> bool {
+        match self {
+            TraceError::ExportFailed(err) => err.is_retryable(),
+            TraceError::ExportTimedOut(_) => true,
+            TraceError::Other(_) => false,
+        }
+    }
+}
+
 impl<T> From<T> for TraceError
 where
     T: ExportError,
@@ -98,13 +98,21 @@ impl trace::Span for NoopSpan {
         // Ignored
     }
 
+    /// Ignores links
+    fn add_link(&mut self, _link: trace::Link) {
+        // Ignored
+    }
+
     /// Ignores status
-    fn set_status(&mut self, _code: trace::StatusCode, _message: String) {
+    fn set_status(&mut self, _status: trace::Status) {
         // Ignored
     }
 
     /// Ignores name updates
-    fn update_name(&mut self, _new_name: String) {
+    fn update_name<T>(&mut self, _new_name: T)
+    where
+        T: Into<std::borrow::Cow<'static, str>>,
+    {
         // Ignored
     }
 
@@ -20,6 +20,7 @@
 //! Implementations might require the user to specify configuration properties at
 //! `TracerProvider` creation time, or rely on external configurations.
 use crate::trace::{TraceResult, Tracer};
+use crate::KeyValue;
 use std::fmt;
 
 /// An interface to create `Tracer` instances.
@@ -31,6 +32,97 @@ pub trait TracerProvider: fmt::Debug + 'static {
     /// If the name is an empty string then provider uses default name.
     fn get_tracer(&self, name: &'static str, version: Option<&'static str>) -> Self::Tracer;
 
+    /// Creates a named tracer instance of `Self::Tracer` that also records the
+    /// [schema url] of the telemetry it emits.
+    ///
+    /// Providers that do not track schema urls fall back to [`get_tracer`].
+    ///
+    /// [schema url]: https://github.com/open-telemetry/opentelemetry-specification/blob/main/specification/schemas/overview.md#schema-url
+    /// [`get_tracer`]: TracerProvider::get_tracer
+    fn versioned_tracer(
+        &self,
+        name: &'static str,
+        version: Option<&'static str>,
+        _schema_url: Option<&'static str>,
+    ) -> Self::Tracer {
+        self.get_tracer(name, version)
+    }
+
+    /// Creates a tracer instance of `Self::Tracer` described by the given
+    /// [`TracerConfig`].
+    ///
+    /// Providers that do not track scope attributes fall back to
+    /// [`versioned_tracer`].
+    ///
+    /// [`versioned_tracer`]: TracerProvider::versioned_tracer
+    fn tracer_with_config(&self, config: TracerConfig) -> Self::Tracer {
+        self.versioned_tracer(config.name, config.version, config.schema_url)
+    }
+
     /// Force flush all remaining spans in span processors and return results.
     fn force_flush(&self) -> Vec<TraceResult<()>>;
 }
+
+/// Describes the instrumentation scope of the `Tracer` created by
+/// [`TracerProvider::tracer_with_config`].
+///
+/// # Examples
+///
+/// ```
+/// use opentelemetry::{global, trace::{TracerConfig, TracerProvider}, KeyValue};
+///
+/// let config = TracerConfig::new("my-integration")
+///     .with_version(env!("CARGO_PKG_VERSION"))
+///     .with_attributes(vec![KeyValue::new("feature", "async")]);
+/// let tracer = global::tracer_provider().tracer_with_config(config);
+/// ```
+#[derive(Clone, Debug, Default, PartialEq)]
+#[non_exhaustive]
+pub struct TracerConfig {
+    /// The name of the instrumentation library.
+    pub name: &'static str,
+    /// The version of the instrumentation library.
+    pub version: Option<&'static str>,
+    /// The [schema url] of the telemetry emitted by the tracer.
+    ///
+    /// Exporters without a place for it in their protocol drop it, e.g. the
+    /// OTLP exporter with its current protocol version.
+    ///
+    /// [schema url]: https://github.com/open-telemetry/opentelemetry-specification/blob/main/specification/schemas/overview.md#schema-url
+    pub schema_url: Option<&'static str>,
+    /// Attributes of the instrumentation scope.
+    pub attributes: Vec<KeyValue>,
+}
+
+impl TracerConfig {
+    /// Create a config for the instrumentation library with the given name.
+    pub fn new(name: &'static str) -> Self {
+        TracerConfig {
+            name,
+            ..Default::default()
+        }
+    }
+
+    /// Assign the version of the instrumentation library.
+    pub fn with_version(self, version: &'static str) -> Self {
+        TracerConfig {
+            version: Some(version),
+            ..self
+        }
+    }
+
+    /// Assign the schema url of the telemetry emitted by the tracer.
+    pub fn with_schema_url(self, schema_url: &'static str) -> Self {
+        TracerConfig {
+            schema_url: Some(schema_url),
+            ..self
+        }
+    }
+
+    /// Assign the attributes of the instrumentation scope.
+    ///
+    /// Tracers that only differ by their attributes are distinct scopes.
+    pub fn with_attributes(self, attributes: Vec<KeyValue>) -> Self {
+        TracerConfig { attributes, ..self }
+    }
+}
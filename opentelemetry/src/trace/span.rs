@@ -15,9 +15,13 @@
 //! Vendors may implement the `Span` interface to effect vendor-specific logic. However, alternative
 //! implementations MUST NOT allow callers to create Spans directly. All `Span`s MUST be created
 //! via a Tracer.
-use crate::{trace::SpanContext, KeyValue};
+use crate::{
+    trace::{Link, SpanContext},
+    KeyValue,
+};
 #[cfg(feature = "serialize")]
 use serde::{Deserialize, Serialize};
+use std::borrow::Cow;
 use std::error::Error;
 use std::fmt;
 use std::time::SystemTime;
@@ -46,22 +50,35 @@ pub trait Span: fmt::Debug {
     ///
     /// The semantic conventions for Errors are described in ["Semantic Conventions for Exceptions"](https://github.com/open-telemetry/opentelemetry-specification/blob/master/specification/trace/semantic_conventions/exceptions.md)
     ///
-    /// For now we will not set `exception.stacktrace` attribute since the `Error::backtrace`
-    /// method is still in nightly. Users can provide a stacktrace by using the
-    /// `record_exception_with_stacktrace` method.
+    /// The `exception.message` attribute is set to the `fmt::Display` output of the error. If the
+    /// error has a source, the `exception.stacktrace` attribute lists the chain of sources, one
+    /// `Caused by` line per source. As the `Error::backtrace` method is still in nightly, users can
+    /// provide an actual stacktrace by using the `record_error_with_stacktrace` method.
     ///
-    /// Users can custom the exception message by overriding the `fmt::Display` trait's `fmt` method
-    /// for the error.
-    fn record_exception(&mut self, err: &dyn Error) {
-        let attributes = vec![KeyValue::new("exception.message", err.to_string())];
+    /// The `exception.type` attribute is not set as the concrete type of the error can not be
+    /// determined from a trait object.
+    ///
+    /// Recording an error does not change the status of the `Span`.
+    fn record_error(&mut self, err: &dyn Error) {
+        let mut attributes = vec![KeyValue::new("exception.message", err.to_string())];
+
+        let mut source = err.source();
+        if source.is_some() {
+            let mut causes = Vec::new();
+            while let Some(cause) = source {
+                causes.push(format!("Caused by: {}", cause));
+                source = cause.source();
+            }
+            attributes.push(KeyValue::new("exception.stacktrace", causes.join("\n")));
+        }
 
         self.add_event("exception".to_string(), attributes);
     }
 
     /// Convenience method to record a exception/error as an `Event` with custom stacktrace
     ///
-    /// See `Span:record_exception` method for more details.
-    fn record_exception_with_stacktrace(&mut self, err: &dyn Error, stacktrace: String) {
+    /// See `Span:record_error` method for more details.
+    fn record_error_with_stacktrace(&mut self, err: &dyn Error, stacktrace: String) {
         let attributes = vec![
             KeyValue::new("exception.message", err.to_string()),
             KeyValue::new("exception.stacktrace", stacktrace),
@@ -70,6 +87,18 @@ pub trait Span: fmt::Debug {
         self.add_event("exception".to_string(), attributes);
     }
 
+    /// Convenience method to record an exception/error as an `Event`
+    #[deprecated(since = "0.15.0", note = "use `record_error` instead")]
+    fn record_exception(&mut self, err: &dyn Error) {
+        self.record_error(err)
+    }
+
+    /// Convenience method to record a exception/error as an `Event` with custom stacktrace
+    #[deprecated(since = "0.15.0", note = "use `record_error_with_stacktrace` instead")]
+    fn record_exception_with_stacktrace(&mut self, err: &dyn Error, stacktrace: String) {
+        self.record_error_with_stacktrace(err, stacktrace)
+    }
+
     /// An API to record events at a specific time in the context of a given `Span`.
     ///
     /// Events SHOULD preserve the order in which they're set. This will typically match
@@ -122,12 +151,22 @@ pub trait Span: fmt::Debug {
     /// that have prescribed semantic meanings.
     fn set_attribute(&mut self, attribute: KeyValue);
 
+    /// Adds a `Link` to another `Span` after this `Span` was started.
+    ///
+    /// Links added this way are handled exactly like the links passed to the
+    /// [`SpanBuilder`] when the `Span` was created, which are preferred as
+    /// samplers can only consider links known at creation time.
+    ///
+    /// [`SpanBuilder`]: crate::trace::SpanBuilder
+    fn add_link(&mut self, link: Link);
+
     /// Sets the status of the `Span`. If used, this will override the default `Span`
-    /// status, which is `Unset`. `message` MUST be ignored when the status is `OK` or `Unset`
+    /// status, which is `Unset`.
     ///
-    /// Only the value of the last call will be recorded, and implementations are free
-    /// to ignore previous calls.
-    fn set_status(&mut self, code: StatusCode, message: String);
+    /// Setting the status to `Unset` is ignored, and once the status is set to `Ok`
+    /// it is final and further calls are ignored. Otherwise only the value of the
+    /// last call will be recorded.
+    fn set_status(&mut self, status: Status);
 
     /// Updates the `Span`'s name. After this update, any sampling behavior based on the
     /// name will depend on the implementation.
@@ -141,7 +180,13 @@ pub trait Span: fmt::Debug {
     /// regular property. It emphasizes that this operation signifies a
     /// major change for a `Span` and may lead to re-calculation of sampling or
     /// filtering decisions made previously depending on the implementation.
-    fn update_name(&mut self, new_name: String);
+    ///
+    /// Exporters receive the updated name, which makes it possible to name spans
+    /// after information only known once they started, like a matched route.
+    fn update_name<T>(&mut self, new_name: T)
+    where
+        T: Into<Cow<'static, str>>,
+        Self: Sized;
 
     /// Finishes the `Span`.
     ///
@@ -237,27 +282,66 @@ impl fmt::Display for SpanKind {
     }
 }
 
-/// The `StatusCode` interface represents the status of a finished `Span`.
-/// It's composed of a canonical code in conjunction with an optional
-/// descriptive message.
+/// The `Status` interface represents the status of a finished `Span`.
+///
+/// It's either unset, an error with a descriptive message, or ok.
 #[cfg_attr(feature = "serialize", derive(Deserialize, Serialize))]
-#[derive(Clone, Debug, PartialEq, Copy)]
-pub enum StatusCode {
+#[derive(Clone, Debug, PartialEq)]
+pub enum Status {
     /// The default status.
-    Unset = 0,
-    /// OK is returned on success.
-    Ok = 1,
+    Unset,
     /// The operation contains an error.
-    Error = 2,
+    Error {
+        /// The description of the error
+        description: Cow<'static, str>,
+    },
+    /// The operation has been validated by an application developer or operator to
+    /// have completed successfully.
+    Ok,
 }
 
-impl StatusCode {
+impl Status {
+    /// Create a new error status with the given description.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use opentelemetry::trace::Status;
+    ///
+    /// // record error with `str` description
+    /// let error_status = Status::error("something wrong");
+    ///
+    /// // or with `String` description
+    /// let error_status = Status::error(format!("too many foos: {}", 42));
+    /// ```
+    pub fn error(description: impl Into<Cow<'static, str>>) -> Self {
+        Status::Error {
+            description: description.into(),
+        }
+    }
+
     /// Return a static str that represent the status code
     pub fn as_str(&self) -> &'static str {
         match self {
-            StatusCode::Unset => "",
-            StatusCode::Ok => "OK",
-            StatusCode::Error => "ERROR",
+            Status::Unset => "",
+            Status::Ok => "OK",
+            Status::Error { .. } => "ERROR",
+        }
+    }
+
+    /// The description of the status, which is empty unless the status is an error.
+    pub fn description(&self) -> &str {
+        match self {
+            Status::Error { description } => description.as_ref(),
+            _ => "",
         }
     }
 }
+
+// `#[default]` on enum variants needs a newer compiler than the supported minimum
+#[allow(clippy::derivable_impls)]
+impl Default for Status {
+    fn default() -> Self {
+        Status::Unset
+    }
+}
@@ -10,10 +10,12 @@
 //! The spec can be viewed here: https://github.com/open-telemetry/opentelemetry-specification/blob/master/specification/api-tracing.md#SpanContext
 //!
 //! [w3c TraceContext specification]: https://www.w3.org/TR/trace-context/
+use crate::trace::{TraceError, TraceResult};
 #[cfg(feature = "serialize")]
 use serde::{Deserialize, Serialize};
 use std::collections::VecDeque;
 use std::str::FromStr;
+use thiserror::Error;
 
 /// A SpanContext with TRACE_FLAG_NOT_SAMPLED means the span is not sampled.
 pub const TRACE_FLAG_NOT_SAMPLED: u8 = 0x00;
@@ -34,9 +36,17 @@ pub const TRACE_FLAG_DEBUG: u8 = 0x04;
 pub struct TraceId(u128);
 
 impl TraceId {
+    /// The invalid (zero-valued) TraceId.
+    pub const INVALID: TraceId = TraceId(0);
+
     /// Construct a new invalid (zero-valued) TraceId
     pub fn invalid() -> Self {
-        TraceId(0)
+        TraceId::INVALID
+    }
+
+    /// Returns true if the TraceId is valid, i.e. not all zeros.
+    pub fn is_valid(self) -> bool {
+        self != TraceId::INVALID
     }
 
     /// Convert from u128 to TraceId
@@ -59,9 +69,33 @@ impl TraceId {
         self.0.to_be_bytes()
     }
 
-    /// Construct a new TraceId from Hexadecimal String
-    pub fn from_hex(hex: &str) -> Self {
-        TraceId(u128::from_str_radix(hex, 16).unwrap_or(0))
+    /// Construct a new TraceId from a 32 character hexadecimal string.
+    ///
+    /// Returns an error if the string has the wrong length or contains a
+    /// character which is not a hex digit.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use opentelemetry::trace::{TraceId, TraceIdError};
+    ///
+    /// let trace_id = TraceId::from_hex("4bf92f3577b34da6a3ce929d0e0e4736");
+    /// assert_eq!(trace_id.unwrap().to_u128(), 0x4bf9_2f35_77b3_4da6_a3ce_929d_0e0e_4736);
+    ///
+    /// let invalid = TraceId::from_hex("4bf92f35");
+    /// assert!(matches!(invalid, Err(TraceIdError::InvalidLength(8))));
+    /// ```
+    pub fn from_hex(hex: &str) -> Result<Self, TraceIdError> {
+        if hex.len() != 32 {
+            return Err(TraceIdError::InvalidLength(hex.len()));
+        }
+        if let Some(c) = first_non_hex_digit(hex) {
+            return Err(TraceIdError::InvalidDigit(c));
+        }
+
+        u128::from_str_radix(hex, 16)
+            .map(TraceId)
+            .map_err(|_| TraceIdError::InvalidLength(hex.len()))
     }
 
     /// Construct a new TraceId from Big-Endian byte array
@@ -77,9 +111,17 @@ impl TraceId {
 pub struct SpanId(u64);
 
 impl SpanId {
+    /// The invalid (zero-valued) SpanId.
+    pub const INVALID: SpanId = SpanId(0);
+
     /// Construct a new invalid (zero-valued) SpanId
     pub fn invalid() -> Self {
-        SpanId(0)
+        SpanId::INVALID
+    }
+
+    /// Returns true if the SpanId is valid, i.e. not all zeros.
+    pub fn is_valid(self) -> bool {
+        self != SpanId::INVALID
     }
 
     /// Convert from u64 to SpanId
@@ -102,9 +144,21 @@ impl SpanId {
         self.0.to_be_bytes()
     }
 
-    /// Construct a new SpanId from Hexadecimal String
-    pub fn from_hex(hex: &str) -> Self {
-        SpanId(u64::from_str_radix(hex, 16).unwrap_or(0))
+    /// Construct a new SpanId from a 16 character hexadecimal string.
+    ///
+    /// Returns an error if the string has the wrong length or contains a
+    /// character which is not a hex digit.
+    pub fn from_hex(hex: &str) -> Result<Self, SpanIdError> {
+        if hex.len() != 16 {
+            return Err(SpanIdError::InvalidLength(hex.len()));
+        }
+        if let Some(c) = first_non_hex_digit(hex) {
+            return Err(SpanIdError::InvalidDigit(c));
+        }
+
+        u64::from_str_radix(hex, 16)
+            .map(SpanId)
+            .map_err(|_| SpanIdError::InvalidLength(hex.len()))
     }
 
     /// Construct a new SpanId from Big-Endian byte array
@@ -113,6 +167,49 @@ impl SpanId {
     }
 }
 
+/// Returns the first character of `hex` which is not a hex digit.
+fn first_non_hex_digit(hex: &str) -> Option<char> {
+    hex.chars().find(|c| !c.is_ascii_hexdigit())
+}
+
+/// Error returned when parsing a `TraceId` from a hex string.
+#[derive(Error, Debug, Clone, PartialEq)]
+#[non_exhaustive]
+pub enum TraceIdError {
+    /// The string does not have the 32 characters of a trace id.
+    #[error("trace id must be 32 hex characters long, got {0}")]
+    InvalidLength(usize),
+
+    /// The string contains a character which is not a hex digit.
+    #[error("trace id contains the non-hex character {0:?}")]
+    InvalidDigit(char),
+}
+
+impl From<TraceIdError> for TraceError {
+    fn from(err: TraceIdError) -> Self {
+        TraceError::Other(Box::new(err))
+    }
+}
+
+/// Error returned when parsing a `SpanId` from a hex string.
+#[derive(Error, Debug, Clone, PartialEq)]
+#[non_exhaustive]
+pub enum SpanIdError {
+    /// The string does not have the 16 characters of a span id.
+    #[error("span id must be 16 hex characters long, got {0}")]
+    InvalidLength(usize),
+
+    /// The string contains a character which is not a hex digit.
+    #[error("span id contains the non-hex character {0:?}")]
+    InvalidDigit(char),
+}
+
+impl From<SpanIdError> for TraceError {
+    fn from(err: SpanIdError) -> Self {
+        TraceError::Other(Box::new(err))
+    }
+}
+
 /// TraceState carries system-specific configuration data, represented as a list
 /// of key-value pairs. TraceState allows multiple tracing systems to
 /// participate in the same trace.
@@ -170,19 +267,22 @@ impl TraceState {
 
     /// Creates a new `TraceState` from the given key-value collection.
     ///
+    /// Returns an error if any of the keys or values are invalid per the [W3 Spec]['spec'].
+    ///
     /// # Examples
     ///
     /// ```
-    /// use opentelemetry::trace::TraceState;
+    /// use opentelemetry::trace::{TraceResult, TraceState};
     ///
     /// let kvs = vec![("foo", "bar"), ("apple", "banana")];
-    /// let trace_state: Result<TraceState, ()> = TraceState::from_key_value(kvs);
+    /// let trace_state: TraceResult<TraceState> = TraceState::from_key_value(kvs);
     ///
     /// assert!(trace_state.is_ok());
     /// assert_eq!(trace_state.unwrap().header(), String::from("foo=bar,apple=banana"))
     /// ```
-    #[allow(clippy::all)]
-    pub fn from_key_value<T, K, V>(trace_state: T) -> Result<Self, ()>
+    ///
+    /// ['spec']: https://www.w3.org/TR/trace-context/#list
+    pub fn from_key_value<T, K, V>(trace_state: T) -> TraceResult<Self>
     where
         T: IntoIterator<Item = (K, V)>,
         K: ToString,
@@ -192,14 +292,16 @@ impl TraceState {
             .into_iter()
             .map(|(key, value)| {
                 let (key, value) = (key.to_string(), value.to_string());
-                if !TraceState::valid_key(key.as_str()) || !TraceState::valid_value(value.as_str())
-                {
-                    return Err(());
+                if !TraceState::valid_key(key.as_str()) {
+                    return Err(TraceStateError::InvalidKey(key));
+                }
+                if !TraceState::valid_value(value.as_str()) {
+                    return Err(TraceStateError::InvalidValue(value));
                 }
 
                 Ok((key, value))
             })
-            .collect::<Result<VecDeque<_>, ()>>()?;
+            .collect::<Result<VecDeque<_>, TraceStateError>>()?;
 
         if ordered_data.is_empty() {
             Ok(TraceState(None))
@@ -226,11 +328,29 @@ impl TraceState {
     /// invalid per the [W3 Spec]['spec'] an `Err` is returned, else a new `TraceState` with the
     /// updated key/value is returned.
     ///
+    /// The inserted entry is always moved to the front of the list.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use opentelemetry::trace::TraceState;
+    ///
+    /// let trace_state = TraceState::from_key_value(vec![("foo", "bar")]).unwrap();
+    /// let updated = trace_state.insert("apple", "banana").unwrap();
+    ///
+    /// assert_eq!(updated.header(), "apple=banana,foo=bar");
+    /// assert!(updated.insert("Invalid Key", "value").is_err());
+    /// ```
+    ///
     /// ['spec']: https://www.w3.org/TR/trace-context/#list
-    #[allow(clippy::all)]
-    pub fn insert(&self, key: String, value: String) -> Result<TraceState, ()> {
-        if !TraceState::valid_key(key.as_str()) || !TraceState::valid_value(value.as_str()) {
-            return Err(());
+    pub fn insert<K, V>(&self, key: K, value: V) -> TraceResult<TraceState>
+    where
+        K: Into<String>,
+        V: Into<String>,
+    {
+        let (key, value) = (key.into(), value.into());
+        if !TraceState::valid_value(value.as_str()) {
+            return Err(TraceStateError::InvalidValue(value).into());
         }
 
         let mut trace_state = self.delete(key.clone())?;
@@ -242,23 +362,24 @@ impl TraceState {
     }
 
     /// Removes the given key-value pair from the `TraceState`. If the key is invalid per the
-    /// [W3 Spec]['spec'] or the key does not exist an `Err` is returned. Else, a new `TraceState`
-    /// with the removed entry is returned.
+    /// [W3 Spec]['spec'] an `Err` is returned. Else, a new `TraceState` without the given key is
+    /// returned, which is equal to the current one if the key does not exist.
     ///
     /// ['spec']: https://www.w3.org/TR/trace-context/#list
-    #[allow(clippy::all)]
-    pub fn delete(&self, key: String) -> Result<TraceState, ()> {
+    pub fn delete<K: Into<String>>(&self, key: K) -> TraceResult<TraceState> {
+        let key = key.into();
         if !TraceState::valid_key(key.as_str()) {
-            return Err(());
+            return Err(TraceStateError::InvalidKey(key).into());
         }
 
         let mut owned = self.clone();
-        let kvs = owned.0.as_mut().ok_or(())?;
-
-        if let Some(index) = kvs.iter().position(|x| *x.0 == *key) {
-            kvs.remove(index);
-        } else {
-            return Err(());
+        if let Some(kvs) = owned.0.as_mut() {
+            if let Some(index) = kvs.iter().position(|x| *x.0 == *key) {
+                kvs.remove(index);
+            }
+            if kvs.is_empty() {
+                owned.0 = None;
+            }
         }
 
         Ok(owned)
@@ -285,7 +406,7 @@ impl TraceState {
 }
 
 impl FromStr for TraceState {
-    type Err = ();
+    type Err = TraceError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         let list_members: Vec<&str> = s.split_terminator(',').collect();
@@ -293,7 +414,7 @@ impl FromStr for TraceState {
 
         for list_member in list_members {
             match list_member.find('=') {
-                None => return Err(()),
+                None => return Err(TraceStateError::InvalidList(list_member.to_string()).into()),
                 Some(separator_index) => {
                     let (key, value) = list_member.split_at(separator_index);
                     key_value_pairs
@@ -306,6 +427,35 @@ impl FromStr for TraceState {
     }
 }
 
+/// Error returned by `TraceState` operations.
+#[derive(Error, Debug)]
+#[non_exhaustive]
+pub enum TraceStateError {
+    /// The key is invalid per the [W3 Spec]['spec'].
+    ///
+    /// ['spec']: https://www.w3.org/TR/trace-context/#key
+    #[error("{0} is not a valid key in TraceState, see https://www.w3.org/TR/trace-context/#key for more details")]
+    InvalidKey(String),
+
+    /// The value is invalid per the [W3 Spec]['spec'].
+    ///
+    /// ['spec']: https://www.w3.org/TR/trace-context/#value
+    #[error("{0} is not a valid value in TraceState, see https://www.w3.org/TR/trace-context/#value for more details")]
+    InvalidValue(String),
+
+    /// The list member is not a `key=value` pair per the [W3 Spec]['spec'].
+    ///
+    /// ['spec']: https://www.w3.org/TR/trace-context/#list
+    #[error("{0} is not a valid list member in TraceState, see https://www.w3.org/TR/trace-context/#list for more details")]
+    InvalidList(String),
+}
+
+impl From<TraceStateError> for TraceError {
+    fn from(err: TraceStateError) -> Self {
+        TraceError::Other(Box::new(err))
+    }
+}
+
 /// Immutable portion of a `Span` which can be serialized and propagated.
 #[cfg_attr(feature = "serialize", derive(Deserialize, Serialize))]
 #[derive(Clone, Debug, PartialEq)]
@@ -365,10 +515,13 @@ impl SpanContext {
     /// Returns a bool flag which is true if the `SpanContext` has a valid (non-zero) `trace_id`
     /// and a valid (non-zero) `span_id`.
     pub fn is_valid(&self) -> bool {
-        self.trace_id.0 != 0 && self.span_id.0 != 0
+        self.trace_id.is_valid() && self.span_id.is_valid()
     }
 
     /// Returns true if the `SpanContext` was propagated from a remote parent.
+    ///
+    /// Propagators set this on the contexts they extract, span contexts of
+    /// spans created in this process are never remote.
     pub fn is_remote(&self) -> bool {
         self.is_remote
     }
@@ -431,22 +584,75 @@ mod tests {
             assert_eq!(test_case.0.to_hex(), test_case.1);
             assert_eq!(test_case.0.to_byte_array(), test_case.2);
 
-            assert_eq!(test_case.0, TraceId::from_hex(test_case.1));
+            assert_eq!(Ok(test_case.0), TraceId::from_hex(test_case.1));
             assert_eq!(test_case.0, TraceId::from_byte_array(test_case.2));
         }
     }
 
+    #[test]
+    fn test_id_from_hex_errors() {
+        #[rustfmt::skip]
+        let trace_id_cases = vec![
+            ("", TraceIdError::InvalidLength(0)),
+            ("4bf92f3577b34da6a3ce929d0e0e473", TraceIdError::InvalidLength(31)),
+            ("4bf92f3577b34da6a3ce929d0e0e47360", TraceIdError::InvalidLength(33)),
+            ("4bf92f3577b34da6a3ce929d0e0e473g", TraceIdError::InvalidDigit('g')),
+            ("+bf92f3577b34da6a3ce929d0e0e4736", TraceIdError::InvalidDigit('+')),
+        ];
+        for (hex, expected) in trace_id_cases {
+            assert_eq!(TraceId::from_hex(hex), Err(expected), "{}", hex);
+        }
+
+        #[rustfmt::skip]
+        let span_id_cases = vec![
+            ("", SpanIdError::InvalidLength(0)),
+            ("00f067aa0ba902b", SpanIdError::InvalidLength(15)),
+            ("00f067aa0ba902b70", SpanIdError::InvalidLength(17)),
+            ("00f067aa-ba902b7", SpanIdError::InvalidDigit('-')),
+        ];
+        for (hex, expected) in span_id_cases {
+            assert_eq!(SpanId::from_hex(hex), Err(expected), "{}", hex);
+        }
+    }
+
     #[test]
     fn test_span_id() {
         for test_case in span_id_test_data() {
             assert_eq!(test_case.0.to_hex(), test_case.1);
             assert_eq!(test_case.0.to_byte_array(), test_case.2);
 
-            assert_eq!(test_case.0, SpanId::from_hex(test_case.1));
+            assert_eq!(Ok(test_case.0), SpanId::from_hex(test_case.1));
             assert_eq!(test_case.0, SpanId::from_byte_array(test_case.2));
         }
     }
 
+    #[test]
+    fn test_invalid_ids() {
+        assert_eq!(
+            TraceId::INVALID.to_hex(),
+            "00000000000000000000000000000000"
+        );
+        assert_eq!(
+            TraceId::from_hex("00000000000000000000000000000000"),
+            Ok(TraceId::INVALID)
+        );
+        assert_eq!(SpanId::INVALID.to_hex(), "0000000000000000");
+        assert_eq!(SpanId::from_hex("0000000000000000"), Ok(SpanId::INVALID));
+
+        assert!(!TraceId::INVALID.is_valid());
+        assert!(!SpanId::INVALID.is_valid());
+        assert!(TraceId::from_u128(1).is_valid());
+        assert!(SpanId::from_u64(1).is_valid());
+
+        let context = |trace_id, span_id| {
+            SpanContext::new(trace_id, span_id, 0, false, TraceState::default())
+        };
+        assert!(!SpanContext::empty_context().is_valid());
+        assert!(!context(TraceId::INVALID, SpanId::from_u64(1)).is_valid());
+        assert!(!context(TraceId::from_u128(1), SpanId::INVALID).is_valid());
+        assert!(context(TraceId::from_u128(1), SpanId::from_u64(1)).is_valid());
+    }
+
     #[test]
     fn test_trace_state() {
         for test_case in trace_state_test_data() {
@@ -454,7 +660,7 @@ mod tests {
 
             let new_key = format!("{}-{}", test_case.0.get(test_case.2).unwrap(), "test");
 
-            let updated_trace_state = test_case.0.insert(test_case.2.into(), new_key.clone());
+            let updated_trace_state = test_case.0.insert(test_case.2, new_key.clone());
             assert!(updated_trace_state.is_ok());
             let updated_trace_state = updated_trace_state.unwrap();
 
@@ -465,7 +671,7 @@ mod tests {
             assert!(index.is_some());
             assert_eq!(index.unwrap(), 0);
 
-            let deleted_trace_state = updated_trace_state.delete(test_case.2.to_string());
+            let deleted_trace_state = updated_trace_state.delete(test_case.2);
             assert!(deleted_trace_state.is_ok());
 
             let deleted_trace_state = deleted_trace_state.unwrap();
@@ -473,4 +679,33 @@ mod tests {
             assert!(deleted_trace_state.get(test_case.2).is_none());
         }
     }
+
+    #[test]
+    fn test_trace_state_insert_new_key() {
+        let trace_state = TraceState::default().insert("foo", "bar").unwrap();
+        assert_eq!(trace_state.header(), "foo=bar");
+
+        let trace_state = trace_state.insert("apple", "banana").unwrap();
+        assert_eq!(trace_state.header(), "apple=banana,foo=bar");
+        assert_eq!(trace_state.get("foo"), Some("bar"));
+    }
+
+    #[test]
+    fn test_trace_state_invalid_entries() {
+        let trace_state = TraceState::from_key_value(vec![("foo", "bar")]).unwrap();
+
+        assert!(trace_state.insert("Foo", "bar").is_err());
+        assert!(trace_state.insert("foo", "bar,baz").is_err());
+        assert!(trace_state.delete("@foo").is_err());
+        assert!(TraceState::from_key_value(vec![("foo", "a=b")]).is_err());
+        assert!("foo=bar,invalid".parse::<TraceState>().is_err());
+    }
+
+    #[test]
+    fn test_trace_state_delete() {
+        let trace_state = TraceState::from_key_value(vec![("foo", "bar")]).unwrap();
+
+        assert_eq!(trace_state.delete("apple").unwrap(), trace_state);
+        assert_eq!(trace_state.delete("foo").unwrap(), TraceState::default());
+    }
 }
@@ -1,6 +1,6 @@
 use crate::{
     sdk,
-    trace::{Event, Link, Span, SpanId, SpanKind, StatusCode, TraceContextExt, TraceId},
+    trace::{Event, Link, Span, SpanContext, SpanId, SpanKind, Status, TraceContextExt, TraceId},
     Context, KeyValue,
 };
 use std::borrow::Cow;
@@ -217,6 +217,26 @@ pub trait Tracer: fmt::Debug + 'static {
     /// created in another process. Each propagators' deserialization must set
     /// `is_remote` to true on a parent `SpanContext` so `Span` creation knows if the
     /// parent is remote.
+    ///
+    /// The parent and the sampling decision are taken from `context`, not from
+    /// the current context, which makes this the right choice for spans created
+    /// in spawned tasks that hold their parent context explicitly.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use opentelemetry::{global, trace::{TraceContextExt, Tracer}, Context};
+    ///
+    /// let tracer = global::tracer("example");
+    /// let parent_cx = Context::current_with_span(tracer.start("parent"));
+    ///
+    /// std::thread::spawn(move || {
+    ///     // the current context of this thread does not contain the parent
+    ///     let _child = tracer.start_with_context("child", parent_cx);
+    /// })
+    /// .join()
+    /// .unwrap();
+    /// ```
     fn start_with_context<T>(&self, name: T, context: Context) -> Self::Span
     where
         T: Into<Cow<'static, str>>;
@@ -236,7 +256,7 @@ pub trait Tracer: fmt::Debug + 'static {
     ///
     /// This method starts a new span and sets it as the active span for the given
     /// function. It then executes the body. It closes the span before returning the
-    /// execution result.
+    /// execution result, or while unwinding if the function panics.
     ///
     /// # Examples
     ///
@@ -266,15 +286,16 @@ pub trait Tracer: fmt::Debug + 'static {
         let span = self.start(name);
         let cx = Context::current_with_span(span);
         let _guard = cx.clone().attach();
+        let _end = SpanEndGuard(cx.clone());
         f(cx)
     }
 
     /// Start a new span and execute the given closure with reference to the span's
     /// context.
     ///
-    /// This method starts a new span and sets it as the active span for the given
-    /// function. It then executes the body. It closes the span before returning the
-    /// execution result.
+    /// This method sets the given span as the active span for the given function.
+    /// It then executes the body. It closes the span before returning the execution
+    /// result, or while unwinding if the function panics.
     ///
     /// # Examples
     ///
@@ -306,10 +327,21 @@ pub trait Tracer: fmt::Debug + 'static {
     {
         let cx = Context::current_with_span(span);
         let _guard = cx.clone().attach();
+        let _end = SpanEndGuard(cx.clone());
         f(cx)
     }
 }
 
+/// Ends the span of the context when dropped, so spans passed to closures are
+/// ended when the closure returns or unwinds, even if the context is kept alive.
+struct SpanEndGuard(Context);
+
+impl Drop for SpanEndGuard {
+    fn drop(&mut self) {
+        self.0.span().end();
+    }
+}
+
 /// `SpanBuilder` allows span attributes to be configured before the span
 /// has started.
 ///
@@ -342,6 +374,14 @@ pub struct SpanBuilder {
     pub trace_id: Option<TraceId>,
     /// Span id, useful for integrations with external tracing systems.
     pub span_id: Option<SpanId>,
+    /// The span's own context, useful to reconstruct spans imported from
+    /// external tracing systems.
+    ///
+    /// Its trace id, span id, trace flags and trace state are used as they
+    /// are instead of being generated or sampled, the parent span id is still
+    /// taken from the parent context. The span is always recorded and it is
+    /// exported if the trace flags are sampled.
+    pub span_context: Option<SpanContext>,
     /// Span kind
     pub span_kind: Option<SpanKind>,
     /// Span name
@@ -356,10 +396,8 @@ pub struct SpanBuilder {
     pub events: Option<Vec<Event>>,
     /// Span Links
     pub links: Option<Vec<Link>>,
-    /// Span status code
-    pub status_code: Option<StatusCode>,
-    /// Span status message
-    pub status_message: Option<Cow<'static, str>>,
+    /// Span status
+    pub status: Status,
     /// Sampling result
     pub sampling_result: Option<sdk::trace::SamplingResult>,
 }
@@ -380,6 +418,7 @@ impl SpanBuilder {
             parent_context,
             trace_id: None,
             span_id: None,
+            span_context: None,
             span_kind: None,
             name: name.into(),
             start_time: None,
@@ -387,8 +426,7 @@ impl SpanBuilder {
             attributes: None,
             events: None,
             links: None,
-            status_code: None,
-            status_message: None,
+            status: Status::Unset,
             sampling_result: None,
         }
     }
@@ -417,6 +455,14 @@ impl SpanBuilder {
         }
     }
 
+    /// Assign the span's own context, see [`SpanBuilder::span_context`].
+    pub fn with_span_context(self, span_context: SpanContext) -> Self {
+        SpanBuilder {
+            span_context: Some(span_context),
+            ..self
+        }
+    }
+
     /// Assign span kind
     pub fn with_kind(self, span_kind: SpanKind) -> Self {
         SpanBuilder {
@@ -450,6 +496,9 @@ impl SpanBuilder {
     }
 
     /// Assign events
+    ///
+    /// The events keep their own timestamps, so they can describe things which
+    /// happened before the span was started.
     pub fn with_events(self, events: Vec<Event>) -> Self {
         SpanBuilder {
             events: Some(events),
@@ -466,20 +515,9 @@ impl SpanBuilder {
         }
     }
 
-    /// Assign status code
-    pub fn with_status_code(self, code: StatusCode) -> Self {
-        SpanBuilder {
-            status_code: Some(code),
-            ..self
-        }
-    }
-
-    /// Assign status message
-    pub fn with_status_message<T: Into<Cow<'static, str>>>(self, message: T) -> Self {
-        SpanBuilder {
-            status_message: Some(message.into()),
-            ..self
-        }
+    /// Assign status
+    pub fn with_status(self, status: Status) -> Self {
+        SpanBuilder { status, ..self }
     }
 
     /// Assign sampling result
@@ -494,4 +532,13 @@ impl SpanBuilder {
     pub fn start<T: Tracer>(self, tracer: &T) -> T::Span {
         tracer.build(self)
     }
+
+    /// Builds a span with the given tracer from this configuration, using
+    /// `parent_cx` instead of the parent context of the builder as parent.
+    ///
+    /// This is useful in async code, where the current context of the thread
+    /// polling a task is not necessarily the context the task was created in.
+    pub fn start_with_context<T: Tracer>(self, tracer: &T, parent_cx: &Context) -> T::Span {
+        tracer.build(self.with_parent_context(parent_cx.clone()))
+    }
 }
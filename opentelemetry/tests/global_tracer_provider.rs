@@ -0,0 +1,58 @@
+//! Runs in its own process so the global tracer provider is not shared with other tests.
+use opentelemetry::{
+    global,
+    runtime::Tokio,
+    sdk::{export::trace::stdout, trace::TracerProvider},
+    trace::Tracer,
+};
+use std::{
+    io::{self, Write},
+    sync::{Arc, Mutex},
+};
+
+#[derive(Clone, Debug, Default)]
+struct AssertWriter {
+    buf: Arc<Mutex<Vec<u8>>>,
+}
+
+impl AssertWriter {
+    fn len(&self) -> usize {
+        self.buf.lock().unwrap().len()
+    }
+}
+
+impl Write for AssertWriter {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.buf.lock().unwrap().write(buf)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn flush_before_swapping_tracer_provider() {
+    let writer_a = AssertWriter::default();
+    let provider_a = TracerProvider::builder()
+        .with_batch_exporter(stdout::Exporter::new(writer_a.clone(), false), Tokio)
+        .build();
+    let _ = global::set_tracer_provider(provider_a);
+
+    global::tracer("provider a").in_span("span a", |_cx| {});
+    global::force_flush_tracer_provider();
+    assert!(writer_a.len() > 0, "span should be exported by provider a");
+
+    let writer_b = AssertWriter::default();
+    let provider_b = TracerProvider::builder()
+        .with_simple_exporter(stdout::Exporter::new(writer_b.clone(), false))
+        .build();
+    let _previous = global::set_tracer_provider(provider_b);
+
+    let exported_by_a = writer_a.len();
+    global::tracer("provider b").in_span("span b", |_cx| {});
+    assert_eq!(writer_a.len(), exported_by_a);
+    assert!(writer_b.len() > 0, "span should be exported by provider b");
+
+    global::shutdown_tracer_provider();
+}